@@ -0,0 +1,51 @@
+use arglex::lex;
+use criterion::black_box;
+use criterion::criterion_group;
+use criterion::criterion_main;
+use criterion::BatchSize;
+use criterion::Criterion;
+
+/// What a shell hands `touch *` in a big directory: a few options, then lots of file names.
+fn many_files(count: usize) -> Vec<String> {
+    let mut args = vec![
+        "-c".to_owned(),
+        "--date=2000-01-01T00:00:00Z".to_owned(),
+        "-h".to_owned(),
+    ];
+    args.extend((0..count).map(|i| format!("some-directory/file-{:05}.txt", i)));
+    args
+}
+
+/// Every argument an option, with and without attached values.
+fn many_options(count: usize) -> Vec<String> {
+    (0..count)
+        .map(|i| match i % 4 {
+            0 => "-v".to_owned(),
+            1 => format!("-n{}", i),
+            2 => "--verbose".to_owned(),
+            _ => format!("--lines={}", i),
+        })
+        .collect()
+}
+
+fn bench_lex(c: &mut Criterion) {
+    let files = many_files(50_000);
+    c.bench_function("lex 50k file names", |b| {
+        b.iter_batched(
+            || files.clone(),
+            |args| black_box(lex(args)),
+            BatchSize::LargeInput,
+        )
+    });
+    let options = many_options(50_000);
+    c.bench_function("lex 50k options", |b| {
+        b.iter_batched(
+            || options.clone(),
+            |args| black_box(lex(args)),
+            BatchSize::LargeInput,
+        )
+    });
+}
+
+criterion_group!(benches, bench_lex);
+criterion_main!(benches);
@@ -0,0 +1,129 @@
+use std::env;
+use std::fs;
+use std::io;
+use std::path::Path;
+use std::path::PathBuf;
+
+/// How cp, mv and ln back up a destination they're about to replace, as set by
+/// `--backup=CONTROL` or `VERSION_CONTROL`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Control {
+    /// Don't make backups.
+    Off,
+    /// `dest~`.
+    Simple,
+    /// `dest.~N~`, one more than the highest existing N.
+    Numbered,
+    /// Numbered if `dest` already has numbered backups, simple otherwise.
+    Existing,
+}
+
+impl Control {
+    /// Parses a CONTROL value, accepting the same names and aliases as GNU.
+    pub fn parse(control: &str) -> Result<Self, String> {
+        match control {
+            "none" | "off" => Ok(Control::Off),
+            "simple" | "never" => Ok(Control::Simple),
+            "numbered" | "t" => Ok(Control::Numbered),
+            "existing" | "nil" => Ok(Control::Existing),
+            _ => Err(format!("invalid backup type '{}'", control)),
+        }
+    }
+
+    /// What `-b` or a bare `--backup` means: `VERSION_CONTROL` if set, otherwise `existing`.
+    pub fn from_env() -> Result<Self, String> {
+        match env::var("VERSION_CONTROL") {
+            Ok(control) if !control.is_empty() => Control::parse(&control),
+            _ => Ok(Control::Existing),
+        }
+    }
+}
+
+/// The numbers of `path`'s existing `path.~N~` backups.
+fn backup_numbers(path: &Path) -> io::Result<Vec<u64>> {
+    let name = match path.file_name().and_then(|name| name.to_str()) {
+        Some(name) => format!("{}.~", name),
+        None => return Ok(vec![]),
+    };
+    let dir = match path.parent() {
+        Some(parent) if !parent.as_os_str().is_empty() => parent,
+        _ => Path::new("."),
+    };
+    let mut numbers = vec![];
+    for entry in fs::read_dir(dir)? {
+        let entry = entry?.file_name();
+        let number: Option<u64> = entry
+            .to_str()
+            .and_then(|entry| entry.strip_prefix(&name))
+            .and_then(|rest| rest.strip_suffix('~'))
+            .and_then(|number| number.parse().ok());
+        numbers.extend(number);
+    }
+    Ok(numbers)
+}
+
+/// The name to back `path` up to under `control`, or `None` for `Control::Off`.
+pub fn backup_name(path: &Path, control: Control) -> io::Result<Option<PathBuf>> {
+    let numbered = match control {
+        Control::Off => return Ok(None),
+        Control::Simple => None,
+        Control::Numbered | Control::Existing => {
+            let numbers = backup_numbers(path)?;
+            match numbers.into_iter().max() {
+                Some(highest) => Some(highest + 1),
+                None if control == Control::Numbered => Some(1),
+                None => None,
+            }
+        }
+    };
+    let mut name = path.as_os_str().to_owned();
+    match numbered {
+        Some(number) => name.push(format!(".~{}~", number)),
+        None => name.push("~"),
+    }
+    Ok(Some(PathBuf::from(name)))
+}
+
+/// Renames `path` out of the way before it gets replaced. Returns the backup's name, or `None`
+/// if no backup was wanted or there was nothing at `path` to back up.
+pub fn backup(path: &Path, control: Control) -> io::Result<Option<PathBuf>> {
+    if control == Control::Off || path.symlink_metadata().is_err() {
+        return Ok(None);
+    }
+    let name = backup_name(path, control)?;
+    if let Some(name) = &name {
+        fs::rename(path, name)?;
+    }
+    Ok(name)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn controls() {
+        assert_eq!(Control::parse("t"), Ok(Control::Numbered));
+        assert_eq!(Control::parse("never"), Ok(Control::Simple));
+        assert_eq!(Control::parse("nil"), Ok(Control::Existing));
+        assert_eq!(Control::parse("off"), Ok(Control::Off));
+        assert!(Control::parse("sometimes").is_err());
+    }
+
+    #[test]
+    fn names() {
+        let path = Path::new("/no/such/dir/file");
+        assert_eq!(backup_name(path, Control::Off).unwrap(), None);
+        assert_eq!(
+            backup_name(Path::new("file"), Control::Simple).unwrap(),
+            Some(PathBuf::from("file~"))
+        );
+        assert!(backup_name(path, Control::Numbered).is_err());
+    }
+
+    #[test]
+    fn nothing_to_back_up() {
+        let path = Path::new("/no/such/file");
+        assert_eq!(backup(path, Control::Simple).unwrap(), None);
+    }
+}
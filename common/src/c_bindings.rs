@@ -0,0 +1,26 @@
+#![allow(clippy::unreadable_literal)]
+include!(concat!(env!("OUT_DIR"), "/bindings.rs"));
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn constants_match_libc() {
+        assert_eq!(AT_FDCWD, libc::AT_FDCWD);
+        assert_eq!(
+            AT_SYMLINK_NOFOLLOW as libc::c_int,
+            libc::AT_SYMLINK_NOFOLLOW
+        );
+        assert_eq!(UTIME_OMIT as libc::c_long, libc::UTIME_OMIT);
+    }
+
+    #[test]
+    #[allow(clippy::assertions_on_constants)]
+    fn constants_are_sane() {
+        assert!(AT_FDCWD < 0, "AT_FDCWD must not collide with a real fd");
+        assert_ne!(AT_SYMLINK_NOFOLLOW, 0);
+        // UTIME_OMIT has to sit outside the valid nanosecond range to be distinguishable
+        assert!(UTIME_OMIT as i64 >= 1_000_000_000);
+    }
+}
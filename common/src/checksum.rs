@@ -0,0 +1,251 @@
+use crate::report_error;
+use std::fmt::Write;
+use std::fs::File;
+use std::io;
+use std::io::BufRead;
+use std::io::BufReader;
+use std::io::ErrorKind;
+use std::io::Read;
+
+/// What md5sum, sha256sum and friends differ in.
+pub struct Algorithm {
+    /// Prefix for messages, e.g. `md5sum`.
+    pub tool: &'static str,
+    /// The name used by --tag lines, e.g. `MD5`.
+    pub tag: &'static str,
+    /// Hex digits in a digest.
+    pub digest_length: usize,
+    /// Hashes everything `input` yields into lowercase hex.
+    pub digest: fn(&mut dyn Read) -> io::Result<String>,
+}
+
+/// Feeds `input` to `update` in buffered chunks until end of file.
+pub fn read_chunks(input: &mut dyn Read, update: &mut dyn FnMut(&[u8])) -> io::Result<()> {
+    let mut buffer = [0; 8192];
+    loop {
+        match input.read(&mut buffer) {
+            Ok(0) => return Ok(()),
+            Ok(n) => update(&buffer[..n]),
+            Err(e) if e.kind() == ErrorKind::Interrupted => {}
+            Err(e) => return Err(e),
+        }
+    }
+}
+
+/// Lowercase hex for a finished digest.
+pub fn hex(bytes: &[u8]) -> String {
+    let mut hex = String::with_capacity(bytes.len() * 2);
+    for byte in bytes {
+        let _ = write!(hex, "{:02x}", byte);
+    }
+    hex
+}
+
+/// Digests `file`, with `-` meaning standard input.
+pub fn digest_file(algorithm: &Algorithm, file: &str) -> io::Result<String> {
+    if file == "-" {
+        let stdin = io::stdin();
+        let mut input = stdin.lock();
+        (algorithm.digest)(&mut input)
+    } else {
+        File::open(file).and_then(|mut f| (algorithm.digest)(&mut f))
+    }
+}
+
+/// One line of a checksum file: the expected digest and the file it belongs to.
+#[derive(Debug, PartialEq)]
+pub struct Entry<'a> {
+    pub digest: &'a str,
+    pub name: &'a str,
+}
+
+/// Parses `HASH  NAME`, `HASH *NAME` or the BSD-style `TAG (NAME) = HASH` written by --tag.
+pub fn parse_line<'a>(line: &'a str, tag: &str, digest_length: usize) -> Option<Entry<'a>> {
+    let is_digest = |digest: &str| {
+        digest.len() == digest_length && digest.chars().all(|c| c.is_ascii_hexdigit())
+    };
+    if let Some(rest) = line
+        .strip_prefix(tag)
+        .and_then(|rest| rest.strip_prefix(" ("))
+    {
+        let i = rest.rfind(") = ")?;
+        let digest = &rest[i + 4..];
+        return if is_digest(digest) {
+            Some(Entry {
+                digest,
+                name: &rest[..i],
+            })
+        } else {
+            None
+        };
+    }
+    let digest = line.get(..digest_length)?;
+    let name = line[digest_length..]
+        .strip_prefix("  ")
+        .or_else(|| line[digest_length..].strip_prefix(" *"))?;
+    if is_digest(digest) && !name.is_empty() {
+        Some(Entry { digest, name })
+    } else {
+        None
+    }
+}
+
+/// Tallies of a `-c` run, reported as warnings once every line has been checked.
+#[derive(Default)]
+pub struct Summary {
+    pub checked: usize,
+    pub mismatched: usize,
+    pub unreadable: usize,
+    pub malformed: usize,
+}
+
+fn plural(count: usize, one: &str, many: &str) -> String {
+    format!("{} {}", count, if count == 1 { one } else { many })
+}
+
+impl Summary {
+    pub fn ok(&self) -> bool {
+        self.checked > 0 && self.mismatched == 0 && self.unreadable == 0
+    }
+
+    pub fn warnings(&self) -> Vec<String> {
+        let mut warnings = vec![];
+        if self.malformed > 0 {
+            warnings.push(format!(
+                "WARNING: {} improperly formatted",
+                plural(self.malformed, "line is", "lines are")
+            ));
+        }
+        if self.unreadable > 0 {
+            warnings.push(format!(
+                "WARNING: {} could not be read",
+                plural(self.unreadable, "listed file", "listed files")
+            ));
+        }
+        if self.mismatched > 0 {
+            warnings.push(format!(
+                "WARNING: {} did NOT match",
+                plural(self.mismatched, "computed checksum", "computed checksums")
+            ));
+        }
+        warnings
+    }
+}
+
+/// Verifies every line of `list`, printing `NAME: OK` or `NAME: FAILED` for each.
+pub fn check(algorithm: &Algorithm, list: &mut dyn BufRead) -> io::Result<Summary> {
+    let mut summary = Summary::default();
+    for line in list.lines() {
+        let line = line?;
+        let entry = match parse_line(&line, algorithm.tag, algorithm.digest_length) {
+            Some(entry) => entry,
+            None => {
+                summary.malformed += 1;
+                continue;
+            }
+        };
+        summary.checked += 1;
+        match digest_file(algorithm, entry.name) {
+            Ok(actual) if actual.eq_ignore_ascii_case(entry.digest) => {
+                println!("{}: OK", entry.name)
+            }
+            Ok(_) => {
+                println!("{}: FAILED", entry.name);
+                summary.mismatched += 1;
+            }
+            Err(e) => {
+                report_error(algorithm.tool, format!("{}: {}", entry.name, e));
+                println!("{}: FAILED open or read", entry.name);
+                summary.unreadable += 1;
+            }
+        }
+    }
+    Ok(summary)
+}
+
+/// Runs `-c` on the checksum file `file` and reports the outcome. Returns false if anything
+/// failed to verify.
+pub fn verify(algorithm: &Algorithm, file: &str) -> bool {
+    let summary = if file == "-" {
+        check(algorithm, &mut BufReader::new(io::stdin()))
+    } else {
+        File::open(file).and_then(|f| check(algorithm, &mut BufReader::new(f)))
+    };
+    match summary {
+        Ok(summary) if summary.checked == 0 => {
+            report_error(
+                algorithm.tool,
+                format!(
+                    "{}: no properly formatted {} checksum lines found",
+                    file, algorithm.tag
+                ),
+            );
+            false
+        }
+        Ok(summary) => {
+            for warning in summary.warnings() {
+                report_error(algorithm.tool, warning);
+            }
+            summary.ok()
+        }
+        Err(e) => {
+            report_error(algorithm.tool, format!("{}: {}", file, e));
+            false
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DIGEST: &str = "d41d8cd98f00b204e9800998ecf8427e";
+
+    fn parse(line: &str) -> Option<Entry<'_>> {
+        parse_line(line, "MD5", 32)
+    }
+
+    #[test]
+    fn line_formats() {
+        let entry = |name| {
+            Some(Entry {
+                digest: DIGEST,
+                name,
+            })
+        };
+        assert_eq!(parse(&format!("{}  a b", DIGEST)), entry("a b"));
+        assert_eq!(parse(&format!("{} *bin", DIGEST)), entry("bin"));
+        assert_eq!(
+            parse(&format!("MD5 (x) = y) = {}", DIGEST)),
+            entry("x) = y")
+        );
+        assert_eq!(parse(&format!("{} a", DIGEST)), None);
+        assert_eq!(parse(&format!("{}  ", DIGEST)), None);
+        assert_eq!(parse("d41d8  a"), None);
+        assert_eq!(parse(&format!("SHA1 (a) = {}", DIGEST)), None);
+    }
+
+    #[test]
+    fn hex_digits() {
+        assert_eq!(hex(&[0x00, 0x0f, 0xa0, 0xff]), "000fa0ff");
+    }
+
+    #[test]
+    fn warnings() {
+        let summary = Summary {
+            checked: 3,
+            mismatched: 2,
+            unreadable: 1,
+            malformed: 1,
+        };
+        assert!(!summary.ok());
+        assert_eq!(
+            summary.warnings(),
+            vec![
+                "WARNING: 1 line is improperly formatted",
+                "WARNING: 1 listed file could not be read",
+                "WARNING: 2 computed checksums did NOT match",
+            ]
+        );
+    }
+}
@@ -0,0 +1,409 @@
+use crate::backup::backup;
+use crate::backup::Control;
+use crate::mode::umask;
+use crate::paths::same_file;
+use crate::prompt::confirm;
+use crate::report_error;
+use crate::signals::Partial;
+use crate::times::copy_times;
+use crate::walk::Ancestors;
+use std::fs;
+use std::fs::DirBuilder;
+use std::fs::File;
+use std::fs::Metadata;
+use std::fs::OpenOptions;
+use std::io;
+use std::io::BufReader;
+use std::io::BufWriter;
+use std::io::ErrorKind;
+use std::io::Write;
+use std::os::unix::fs::lchown;
+use std::os::unix::fs::symlink;
+use std::os::unix::fs::DirBuilderExt;
+use std::os::unix::fs::MetadataExt;
+use std::os::unix::fs::OpenOptionsExt;
+use std::os::unix::fs::PermissionsExt;
+use std::os::unix::io::AsRawFd;
+use std::path::Path;
+
+/// Which attributes of the source a copy keeps, as chosen with `--preserve=ATTR_LIST`.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Preserve {
+    pub mode: bool,
+    pub ownership: bool,
+    pub timestamps: bool,
+}
+
+impl Preserve {
+    /// Everything `-p` keeps.
+    pub fn all() -> Self {
+        Preserve {
+            mode: true,
+            ownership: true,
+            timestamps: true,
+        }
+    }
+
+    /// Parses a comma-separated list of `mode`, `ownership`, `timestamps` and `all`.
+    pub fn parse(list: &str) -> Result<Self, String> {
+        let mut preserve = Preserve::default();
+        for attribute in list.split(',') {
+            match attribute {
+                "mode" => preserve.mode = true,
+                "ownership" => preserve.ownership = true,
+                "timestamps" => preserve.timestamps = true,
+                "all" => preserve = Preserve::all(),
+                _ => {
+                    return Err(format!(
+                        "invalid attribute '{}' for '--preserve'",
+                        attribute
+                    ))
+                }
+            }
+        }
+        Ok(preserve)
+    }
+}
+
+/// Whether a copy shares the source's data blocks, on file systems that can (such as btrfs
+/// and xfs), instead of copying them. Chosen with `--reflink[=WHEN]`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Reflink {
+    /// Share the blocks if the file system can, and copy them otherwise.
+    Auto,
+    /// Share the blocks, or fail.
+    Always,
+    Never,
+}
+
+impl Reflink {
+    pub fn parse(when: &str) -> Result<Self, String> {
+        match when {
+            "auto" => Ok(Reflink::Auto),
+            "always" => Ok(Reflink::Always),
+            "never" => Ok(Reflink::Never),
+            _ => Err(format!("invalid argument '{}' for '--reflink'", when)),
+        }
+    }
+}
+
+/// How `copy` behaves, shared by cp and by mv's cross-device fallback.
+pub struct CopyOptions {
+    /// Prefix for per-file error messages, e.g. `cp`.
+    pub tool: &'static str,
+    pub backup: Control,
+    pub force: bool,
+    /// Ask before overwriting an existing destination.
+    pub interactive: bool,
+    pub no_clobber: bool,
+    /// With `recursive`, don't copy the contents of directories on other file systems.
+    pub one_file_system: bool,
+    pub preserve: Preserve,
+    pub recursive: bool,
+    pub reflink: Reflink,
+    pub verbose: bool,
+}
+
+/// Prints a per-file error. Returns false so callers can `return report(..)` as their result.
+fn report(options: &CopyOptions, message: String) -> bool {
+    report_error(options.tool, message);
+    false
+}
+
+/// Applies `--preserve` to a finished copy: the source's owner, then its permission bits (so
+/// that chown can't clear setuid again), then its timestamps. A symlink has no mode of its own,
+/// so its mode is left alone.
+fn preserve(metadata: &Metadata, dest: &Path, is_symlink: bool, options: &CopyOptions) -> bool {
+    let preserve = options.preserve;
+    if preserve.ownership {
+        match lchown(dest, Some(metadata.uid()), Some(metadata.gid())) {
+            // Only root may give files away, so like GNU cp this isn't worth failing over.
+            Err(e) if e.kind() == ErrorKind::PermissionDenied => {}
+            Err(e) => {
+                return report(
+                    options,
+                    format!(
+                        "failed to preserve ownership for '{}': {}",
+                        dest.display(),
+                        e
+                    ),
+                )
+            }
+            Ok(()) => {}
+        }
+    }
+    if preserve.mode && !is_symlink {
+        let permissions = fs::Permissions::from_mode(metadata.mode() & 0o7777);
+        if let Err(e) = fs::set_permissions(dest, permissions) {
+            return report(
+                options,
+                format!("preserving permissions for '{}': {}", dest.display(), e),
+            );
+        }
+    }
+    if preserve.timestamps {
+        if let Err(e) = copy_times(metadata, dest, is_symlink) {
+            return report(
+                options,
+                format!("preserving times for '{}': {}", dest.display(), e),
+            );
+        }
+    }
+    true
+}
+
+fn open_dest(dest: &Path, mode: u32) -> io::Result<File> {
+    OpenOptions::new()
+        .write(true)
+        .create(true)
+        .truncate(true)
+        .mode(mode)
+        .open(dest)
+}
+
+/// Makes `output` share all of `input`'s data blocks, with the FICLONE ioctl.
+#[cfg(target_os = "linux")]
+fn clone_file(input: &File, output: &File) -> io::Result<()> {
+    // _IOW(0x94, 9, int); the libc crate doesn't define it yet.
+    const FICLONE: u64 = 0x4004_9409;
+    if unsafe { libc::ioctl(output.as_raw_fd(), FICLONE as _, input.as_raw_fd()) } == -1 {
+        return Err(io::Error::last_os_error());
+    }
+    Ok(())
+}
+
+#[cfg(not(target_os = "linux"))]
+fn clone_file(_input: &File, _output: &File) -> io::Result<()> {
+    Err(io::Error::from_raw_os_error(libc::EOPNOTSUPP))
+}
+
+fn copy_file(
+    src: &Path,
+    dest: &Path,
+    metadata: &Metadata,
+    options: &CopyOptions,
+) -> io::Result<()> {
+    let input = File::open(src)?;
+    let mode = metadata.mode() & 0o777;
+    let output = match open_dest(dest, mode) {
+        Err(_) if options.force && dest.symlink_metadata().is_ok() => {
+            fs::remove_file(dest)?;
+            open_dest(dest, mode)?
+        }
+        opened => opened?,
+    };
+    let _partial = Partial::register(dest);
+    if options.reflink != Reflink::Never {
+        match clone_file(&input, &output) {
+            Ok(()) => return Ok(()),
+            Err(e) if options.reflink == Reflink::Always => {
+                return Err(io::Error::new(e.kind(), format!("failed to clone: {}", e)))
+            }
+            // Not a file system that can share blocks, or not between these two files.
+            Err(_) => {}
+        }
+    }
+    let mut input = BufReader::new(input);
+    let mut output = BufWriter::new(output);
+    io::copy(&mut input, &mut output)?;
+    output.flush()
+}
+
+/// Copies everything in the directory `src` into `dest`, which already exists.
+fn copy_entries(
+    src: &Path,
+    dest: &Path,
+    metadata: &Metadata,
+    options: &CopyOptions,
+    ancestors: &mut Ancestors,
+) -> bool {
+    if !ancestors.enter(options.tool, src, metadata) {
+        return false;
+    }
+    let mut ok = true;
+    match fs::read_dir(src) {
+        Ok(entries) => {
+            for entry in entries {
+                match entry {
+                    Ok(entry) => {
+                        let dest = dest.join(entry.file_name());
+                        ok &= copy_within(&entry.path(), &dest, options, ancestors);
+                    }
+                    Err(e) => {
+                        ok = report(options, format!("cannot access '{}': {}", src.display(), e))
+                    }
+                }
+            }
+        }
+        Err(e) => ok = report(options, format!("cannot access '{}': {}", src.display(), e)),
+    }
+    ancestors.leave();
+    ok
+}
+
+fn copy_dir(
+    src: &Path,
+    dest: &Path,
+    metadata: &Metadata,
+    options: &CopyOptions,
+    ancestors: &mut Ancestors,
+) -> bool {
+    // The mode a new directory ends up with, set once its contents are in: until then it has
+    // to stay writable, even if the source directory isn't.
+    let mut final_mode = None;
+    match dest.symlink_metadata() {
+        Ok(existing) if !existing.is_dir() => {
+            return report(
+                options,
+                format!(
+                    "cannot overwrite non-directory '{}' with directory '{}'",
+                    dest.display(),
+                    src.display()
+                ),
+            );
+        }
+        Ok(_) => {}
+        Err(_) => {
+            let mode = metadata.mode() & 0o7777;
+            let created = DirBuilder::new().mode(mode | 0o700).create(dest);
+            if let Err(e) = created {
+                return report(
+                    options,
+                    format!("cannot create directory '{}': {}", dest.display(), e),
+                );
+            }
+            if options.verbose {
+                println!("'{}' -> '{}'", src.display(), dest.display());
+            }
+            final_mode = Some(mode & !umask());
+        }
+    }
+
+    // Like GNU cp -x, a directory on another file system is copied as an empty mount point.
+    let mut ok = if options.one_file_system && !ancestors.same_file_system(metadata) {
+        true
+    } else {
+        copy_entries(src, dest, metadata, options, ancestors)
+    };
+    if let Some(mode) = final_mode {
+        if let Err(e) = fs::set_permissions(dest, fs::Permissions::from_mode(mode)) {
+            ok = report(
+                options,
+                format!("setting permissions for '{}': {}", dest.display(), e),
+            );
+        }
+    }
+    ok & preserve(metadata, dest, false, options)
+}
+
+/// Copies `src` to exactly `dest`. Returns false if anything failed; the failure has already
+/// been reported.
+pub fn copy(src: &Path, dest: &Path, options: &CopyOptions) -> bool {
+    copy_within(src, dest, options, &mut Ancestors::new())
+}
+
+/// `copy`, inside the directories a recursive copy has already entered.
+fn copy_within(src: &Path, dest: &Path, options: &CopyOptions, ancestors: &mut Ancestors) -> bool {
+    // -r copies symlinks as they are, so only look through them for plain file copies.
+    let metadata = if options.recursive {
+        src.symlink_metadata()
+    } else {
+        src.metadata()
+    };
+    let metadata = match metadata {
+        Ok(metadata) => metadata,
+        Err(e) => return report(options, format!("cannot stat '{}': {}", src.display(), e)),
+    };
+    if metadata.is_dir() {
+        if !options.recursive {
+            return report(
+                options,
+                format!("-r not specified; omitting directory '{}'", src.display()),
+            );
+        }
+        return copy_dir(src, dest, &metadata, options, ancestors);
+    }
+
+    if let Ok(existing) = dest.metadata() {
+        if options.no_clobber {
+            return true;
+        }
+        if same_file(&metadata, &existing) {
+            return report(
+                options,
+                format!(
+                    "'{}' and '{}' are the same file",
+                    src.display(),
+                    dest.display()
+                ),
+            );
+        }
+        let question = format!("{}: overwrite '{}'", options.tool, dest.display());
+        if options.interactive && !confirm(&question).unwrap_or(false) {
+            return true;
+        }
+    }
+
+    let backed_up = match backup(dest, options.backup) {
+        Ok(backed_up) => backed_up,
+        Err(e) => {
+            return report(
+                options,
+                format!("cannot back up '{}': {}", dest.display(), e),
+            )
+        }
+    };
+    let is_symlink = metadata.file_type().is_symlink();
+    let copied = if is_symlink {
+        if options.no_clobber && dest.symlink_metadata().is_ok() {
+            return true;
+        }
+        fs::read_link(src).and_then(|target| {
+            if dest.symlink_metadata().is_ok() {
+                fs::remove_file(dest)?;
+            }
+            symlink(target, dest)
+        })
+    } else {
+        copy_file(src, dest, &metadata, options)
+    };
+    if let Err(e) = copied {
+        return report(
+            options,
+            format!(
+                "cannot copy '{}' to '{}': {}",
+                src.display(),
+                dest.display(),
+                e
+            ),
+        );
+    }
+    if options.verbose {
+        match backed_up {
+            Some(backed_up) => println!(
+                "'{}' -> '{}' (backup: '{}')",
+                src.display(),
+                dest.display(),
+                backed_up.display()
+            ),
+            None => println!("'{}' -> '{}'", src.display(), dest.display()),
+        }
+    }
+    preserve(&metadata, dest, is_symlink, options)
+}
+
+/// Whether copying directory `src` to `dest` would copy it into itself.
+pub fn into_itself(src: &Path, dest: &Path) -> bool {
+    let src = match src.canonicalize() {
+        Ok(src) => src,
+        Err(_) => return false,
+    };
+    let parent = match dest.parent() {
+        Some(parent) if !parent.as_os_str().is_empty() => parent,
+        _ => Path::new("."),
+    };
+    match (parent.canonicalize(), dest.file_name()) {
+        (Ok(parent), Some(name)) => parent.join(name).starts_with(&src),
+        _ => false,
+    }
+}
@@ -0,0 +1,187 @@
+use std::fs;
+use std::path::Path;
+
+/// Whether `pattern` has a `*`, `?` or `[` that isn't escaped with a backslash.
+pub fn has_wildcards(pattern: &str) -> bool {
+    let mut chars = pattern.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' => {
+                chars.next();
+            }
+            '*' | '?' | '[' => return true,
+            _ => {}
+        }
+    }
+    false
+}
+
+/// Drops the backslashes escaping characters in a pattern with no wildcards.
+fn unescape(component: &str) -> String {
+    let mut unescaped = String::with_capacity(component.len());
+    let mut chars = component.chars();
+    while let Some(c) = chars.next() {
+        unescaped.extend(if c == '\\' { chars.next() } else { Some(c) });
+    }
+    unescaped
+}
+
+/// Matches a `[...]` class starting just after the `[` against `c`. Returns whether it
+/// matched and what follows the class, or `None` if the class is never closed.
+fn match_class(pattern: &[char], c: char) -> Option<(bool, &[char])> {
+    let (negated, mut rest) = match pattern.first() {
+        Some('!') | Some('^') => (true, &pattern[1..]),
+        _ => (false, pattern),
+    };
+    let mut matched = false;
+    let mut first = true;
+    loop {
+        let low = match rest.first() {
+            None => return None,
+            Some(']') if !first => return Some((matched != negated, &rest[1..])),
+            Some('\\') if rest.len() > 1 => {
+                rest = &rest[1..];
+                rest[0]
+            }
+            Some(&low) => low,
+        };
+        first = false;
+        rest = &rest[1..];
+        if rest.len() > 1 && rest[0] == '-' && rest[1] != ']' {
+            matched |= low <= c && c <= rest[1];
+            rest = &rest[2..];
+        } else {
+            matched |= low == c;
+        }
+    }
+}
+
+fn matches_chars(pattern: &[char], name: &[char]) -> bool {
+    match pattern.first() {
+        None => name.is_empty(),
+        Some('*') => (0..=name.len()).any(|skip| matches_chars(&pattern[1..], &name[skip..])),
+        Some('?') => !name.is_empty() && matches_chars(&pattern[1..], &name[1..]),
+        Some('[') if !name.is_empty() => match match_class(&pattern[1..], name[0]) {
+            Some((matched, rest)) => matched && matches_chars(rest, &name[1..]),
+            // An unclosed bracket is just a bracket.
+            None => name[0] == '[' && matches_chars(&pattern[1..], &name[1..]),
+        },
+        Some('\\') if pattern.len() > 1 => {
+            name.first() == Some(&pattern[1]) && matches_chars(&pattern[2..], &name[1..])
+        }
+        Some(&c) => name.first() == Some(&c) && matches_chars(&pattern[1..], &name[1..]),
+    }
+}
+
+/// Whether the file name `name` matches a single-component shell `pattern`. As in the shell,
+/// a leading `.` is only matched by a pattern that starts with one.
+pub fn matches(pattern: &str, name: &str) -> bool {
+    if name.starts_with('.') && !pattern.starts_with('.') {
+        return false;
+    }
+    let pattern: Vec<char> = pattern.chars().collect();
+    let name: Vec<char> = name.chars().collect();
+    matches_chars(&pattern, &name)
+}
+
+/// The paths matching the shell pattern `pattern`, sorted. Like the shell, a pattern with no
+/// wildcards just has its backslashes removed, and one with no matches is returned unchanged.
+pub fn expand(pattern: &str) -> Vec<String> {
+    if !has_wildcards(pattern) {
+        return vec![unescape(pattern)];
+    }
+    let (mut paths, rest) = match pattern.strip_prefix('/') {
+        Some(rest) => (vec!["/".to_owned()], rest),
+        None => (vec![String::new()], pattern),
+    };
+    for component in rest.split('/') {
+        if component.is_empty() {
+            continue;
+        }
+        let mut next = vec![];
+        for path in &paths {
+            if !has_wildcards(component) {
+                let joined = path.clone() + &unescape(component);
+                if Path::new(&joined).symlink_metadata().is_ok() {
+                    next.push(joined + "/");
+                }
+                continue;
+            }
+            let dir = if path.is_empty() { "." } else { path.as_str() };
+            let mut names: Vec<String> = match fs::read_dir(dir) {
+                Ok(entries) => entries
+                    .filter_map(|entry| entry.ok()?.file_name().into_string().ok())
+                    .filter(|name| matches(component, name))
+                    .collect(),
+                Err(_) => continue,
+            };
+            names.sort();
+            next.extend(names.into_iter().map(|name| format!("{}{}/", path, name)));
+        }
+        paths = next;
+    }
+    if paths.is_empty() {
+        return vec![pattern.to_owned()];
+    }
+    // Every path picked up a trailing slash on the way; keep it only if the pattern has one.
+    if !pattern.ends_with('/') {
+        for path in &mut paths {
+            if path.len() > 1 {
+                path.pop();
+            }
+        }
+    }
+    paths
+}
+
+/// Expands every argument in `args` with `expand`, for tools run without a shell to do it.
+pub fn expand_all(args: Vec<String>) -> Vec<String> {
+    args.iter().flat_map(|arg| expand(arg)).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn wildcards() {
+        assert!(has_wildcards("*.txt"));
+        assert!(has_wildcards("a[bc]"));
+        assert!(!has_wildcards("plain"));
+        assert!(!has_wildcards("not\\*here"));
+    }
+
+    #[test]
+    fn matching() {
+        assert!(matches("*.txt", "notes.txt"));
+        assert!(!matches("*.txt", "notes.txt.bak"));
+        assert!(matches("?at", "cat"));
+        assert!(!matches("?at", "at"));
+        assert!(matches("[a-c]x", "bx"));
+        assert!(!matches("[!a-c]x", "bx"));
+        assert!(matches("[]]", "]"));
+        assert!(matches("a[", "a["));
+        assert!(matches("\\*", "*"));
+        assert!(!matches("\\*", "x"));
+    }
+
+    #[test]
+    fn hidden_files_need_a_leading_dot() {
+        assert!(!matches("*", ".profile"));
+        assert!(matches(".*", ".profile"));
+    }
+
+    #[test]
+    fn unmatched_patterns_are_kept() {
+        assert_eq!(expand("/no/such/dir/*.txt"), vec!["/no/such/dir/*.txt"]);
+        assert_eq!(expand("plain"), vec!["plain"]);
+        assert_eq!(expand("\\*.txt"), vec!["*.txt"]);
+    }
+
+    #[test]
+    fn expands_against_the_filesystem() {
+        let dev = expand("/dev/nul?");
+        assert_eq!(dev, vec!["/dev/null"]);
+        assert_eq!(expand("/de[v]/"), vec!["/dev/"]);
+    }
+}
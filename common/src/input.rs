@@ -0,0 +1,113 @@
+use std::ffi::OsStr;
+use std::ffi::OsString;
+use std::fs::File;
+use std::io;
+use std::io::BufRead;
+use std::io::BufReader;
+use std::os::unix::ffi::OsStringExt;
+
+/// Opens the file `name` for buffered reading, or standard input if `name` is `-`.
+pub fn open_input(name: impl AsRef<OsStr>) -> io::Result<Box<dyn BufRead>> {
+    let name = name.as_ref();
+    if name == "-" {
+        Ok(Box::new(io::stdin().lock()))
+    } else {
+        Ok(Box::new(BufReader::new(File::open(name)?)))
+    }
+}
+
+/// An iterator over the lines of a reader, created by [`lines`].
+pub struct Lines<R> {
+    input: R,
+}
+
+impl<R: BufRead> Iterator for Lines<R> {
+    type Item = io::Result<Vec<u8>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let mut line = vec![];
+        match self.input.read_until(b'\n', &mut line) {
+            Ok(0) => None,
+            Ok(_) => Some(Ok(line)),
+            Err(e) => Some(Err(e)),
+        }
+    }
+}
+
+/// The lines of `input`, each keeping its `\n`. Only the last line can be missing one.
+///
+/// Unlike `BufRead::lines`, the lines are bytes, so input that isn't UTF-8 passes through.
+pub fn lines<R: BufRead>(input: R) -> Lines<R> {
+    Lines { input }
+}
+
+/// Reads a list of file names separated by `separator`, as for touch's `--files-from` or wc's
+/// `--files0-from`. Empty names are kept, so that callers can skip or reject them, and names are
+/// raw bytes, so they don't need to be valid UTF-8.
+pub fn read_names(input: &mut dyn BufRead, separator: u8) -> io::Result<Vec<OsString>> {
+    let mut names = vec![];
+    let mut name = vec![];
+    loop {
+        name.clear();
+        if input.read_until(separator, &mut name)? == 0 {
+            return Ok(names);
+        }
+        if name.last() == Some(&separator) {
+            name.pop();
+        }
+        names.push(OsString::from_vec(name.clone()));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::env;
+    use std::fs;
+    use std::process;
+
+    #[test]
+    fn keeps_terminators() {
+        let read: Vec<Vec<u8>> = lines(&b"a\n\nb\xff"[..]).map(Result::unwrap).collect();
+        assert_eq!(
+            read,
+            vec![b"a\n".to_vec(), b"\n".to_vec(), b"b\xff".to_vec()]
+        );
+        assert_eq!(lines(&b""[..]).count(), 0);
+    }
+
+    #[test]
+    fn opens_files() {
+        let path = env::temp_dir().join(format!("coreutils-common-input-{}", process::id()));
+        fs::write(&path, "one\ntwo\n").unwrap();
+        let input = open_input(path.to_str().unwrap());
+        fs::remove_file(&path).unwrap();
+        let read: Vec<Vec<u8>> = lines(input.unwrap()).map(Result::unwrap).collect();
+        assert_eq!(read, vec![b"one\n".to_vec(), b"two\n".to_vec()]);
+    }
+
+    #[test]
+    fn dash_is_stdin() {
+        assert!(open_input("-").is_ok());
+    }
+
+    #[test]
+    fn names() {
+        let read = read_names(&mut &b"a\0\0b\xff\0c"[..], b'\0').unwrap();
+        let expected: Vec<OsString> = vec![
+            "a".into(),
+            "".into(),
+            OsString::from_vec(b"b\xff".to_vec()),
+            "c".into(),
+        ];
+        assert_eq!(read, expected);
+    }
+
+    #[test]
+    fn missing_file() {
+        let error = open_input("/nonexistent/coreutils-common-input")
+            .err()
+            .unwrap();
+        assert_eq!(error.kind(), io::ErrorKind::NotFound);
+    }
+}
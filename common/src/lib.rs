@@ -0,0 +1,194 @@
+pub mod backup;
+mod c_bindings;
+pub mod checksum;
+pub mod copy;
+pub mod glob;
+pub mod input;
+pub mod mode;
+pub mod numparse;
+pub mod output;
+pub mod paths;
+pub mod prompt;
+pub mod ring;
+pub mod signals;
+pub mod tabs;
+pub mod times;
+pub mod timestamp;
+pub mod units;
+pub mod users;
+pub mod walk;
+
+use std::env;
+use std::fmt::Debug;
+use std::fmt::Display;
+use std::process::exit;
+
+/// An error a utility can report before exiting.
+///
+/// The `Debug` output is what gets shown to the user, matching how the tools already format
+/// their errors (e.g. `touch: message`).
+pub trait CoreError: Debug {
+    fn exit_code(&self) -> i32 {
+        1
+    }
+}
+
+/// Print `msg` and exit with `code`. Successful exits print to stdout, anything else to stderr.
+pub fn print_and_exit(code: i32, msg: impl Display) -> ! {
+    if code == 0 {
+        println!("{}", msg);
+    } else {
+        eprintln_error(msg);
+    }
+    exit(code);
+}
+
+/// Whether diagnostics get color: only when stderr is a terminal and `NO_COLOR` is unset or
+/// empty, so piped output (and every test) sees the plain text.
+fn color_errors() -> bool {
+    let no_color = env::var_os("NO_COLOR").is_some_and(|value| !value.is_empty());
+    !no_color && unsafe { libc::isatty(libc::STDERR_FILENO) } == 1
+}
+
+fn decorate_error(msg: impl Display, color: bool) -> String {
+    if color {
+        format!("\x1b[1;31merror:\x1b[0m {}", msg)
+    } else {
+        msg.to_string()
+    }
+}
+
+/// Print the diagnostic `msg` to stderr, with a red `error:` prefix on a terminal.
+pub fn eprintln_error(msg: impl Display) {
+    eprintln!("{}", decorate_error(msg, color_errors()));
+}
+
+/// Print a diagnostic from `tool` that doesn't end the program, such as one file failing, as
+/// `tool: msg`. Like every other error it gets the `error:` prefix on a terminal.
+pub fn report_error(tool: &str, msg: impl Display) {
+    eprintln_error(format!("{}: {}", tool, msg));
+}
+
+/// Print `msg` to stderr and exit with status 1.
+pub fn die(msg: impl Display) -> ! {
+    print_and_exit(1, msg);
+}
+
+/// Report `err` and exit with its exit code.
+pub fn exit_with(err: &impl CoreError) -> ! {
+    print_and_exit(err.exit_code(), format!("{:?}", err));
+}
+
+/// Let a closed pipe kill the process with SIGPIPE, like other Unix tools, instead of every
+/// write failing with `BrokenPipe`. Tools that stream unbounded output (`seq`, `yes`) call this
+/// first thing so `seq 1 1000000 | head` ends quietly.
+pub fn restore_sigpipe() {
+    unsafe {
+        libc::signal(libc::SIGPIPE, libc::SIG_DFL);
+    }
+}
+
+/// Keep running when the terminal sends SIGINT, for `tee -i` at the end of an interactive
+/// pipeline.
+pub fn ignore_sigint() {
+    unsafe {
+        libc::signal(libc::SIGINT, libc::SIG_IGN);
+    }
+}
+
+/// The `--version` line for `tool`, e.g. `touch version 0.1.0`.
+///
+/// This is a macro so that the version is that of the package the utility is built in, not of
+/// this crate.
+#[macro_export]
+macro_rules! version_string {
+    ($tool:expr) => {
+        format!("{} version {}", $tool, env!("CARGO_PKG_VERSION"))
+    };
+}
+
+pub fn needs_arg_message(arg: impl Display) -> String {
+    format!("{} needs an argument. See --help for more details.", arg)
+}
+
+pub fn unexpected_value_message(arg: impl Display) -> String {
+    format!(
+        "{} doesn't allow an argument. See --help for more details.",
+        arg
+    )
+}
+
+pub fn unknown_arg_message(arg: impl Display) -> String {
+    format!(
+        "unknown argument {}\nSee --help for a list of valid arguments.",
+        arg
+    )
+}
+
+pub fn needs_arg(arg: impl Display) -> ! {
+    die(needs_arg_message(arg));
+}
+
+pub fn unknown_arg(arg: impl Display) -> ! {
+    die(unknown_arg_message(arg));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fmt;
+
+    struct Plain;
+
+    impl Debug for Plain {
+        fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+            write!(f, "plain")
+        }
+    }
+
+    impl CoreError for Plain {}
+
+    #[derive(Debug)]
+    struct Usage;
+
+    impl CoreError for Usage {
+        fn exit_code(&self) -> i32 {
+            2
+        }
+    }
+
+    #[test]
+    fn exit_code_defaults_to_one() {
+        assert_eq!(Plain.exit_code(), 1);
+    }
+
+    #[test]
+    fn exit_code_can_be_overridden() {
+        assert_eq!(Usage.exit_code(), 2);
+    }
+
+    #[test]
+    fn errors_are_plain_without_color() {
+        assert_eq!(decorate_error("touch: oops", false), "touch: oops");
+        assert_eq!(
+            decorate_error("touch: oops", true),
+            "\x1b[1;31merror:\x1b[0m touch: oops"
+        );
+    }
+
+    #[test]
+    fn needs_arg_names_the_option() {
+        assert_eq!(
+            needs_arg_message("-d"),
+            "-d needs an argument. See --help for more details."
+        );
+    }
+
+    #[test]
+    fn unknown_arg_names_the_option() {
+        assert_eq!(
+            unknown_arg_message("--bogus"),
+            "unknown argument --bogus\nSee --help for a list of valid arguments."
+        );
+    }
+}
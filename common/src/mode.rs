@@ -0,0 +1,268 @@
+use std::fs::Metadata;
+use std::os::unix::fs::FileTypeExt;
+use std::os::unix::fs::MetadataExt;
+
+/// The nine `rwx` characters of `mode`, with setuid, setgid and sticky shown as `s`/`S` and
+/// `t`/`T` in the execute positions, e.g. `rwsr-xr--`.
+pub fn permissions_string(mode: u32) -> String {
+    let mut string = String::with_capacity(9);
+    for &(shift, special, set_exec, set_only) in &[
+        (6, 0o4000, 's', 'S'),
+        (3, 0o2000, 's', 'S'),
+        (0, 0o1000, 't', 'T'),
+    ] {
+        let bits = (mode >> shift) & 0o7;
+        string.push(if bits & 0o4 != 0 { 'r' } else { '-' });
+        string.push(if bits & 0o2 != 0 { 'w' } else { '-' });
+        string.push(match (bits & 0o1 != 0, mode & special != 0) {
+            (true, true) => set_exec,
+            (false, true) => set_only,
+            (true, false) => 'x',
+            (false, false) => '-',
+        });
+    }
+    string
+}
+
+/// The `ls -l` style permission string, e.g. `-rwxr-xr-x`.
+pub fn mode_string(metadata: &Metadata) -> String {
+    let file_type = metadata.file_type();
+    let kind = if file_type.is_dir() {
+        'd'
+    } else if file_type.is_symlink() {
+        'l'
+    } else if file_type.is_block_device() {
+        'b'
+    } else if file_type.is_char_device() {
+        'c'
+    } else if file_type.is_fifo() {
+        'p'
+    } else if file_type.is_socket() {
+        's'
+    } else {
+        '-'
+    };
+    format!("{}{}", kind, permissions_string(metadata.mode()))
+}
+
+/// The process umask. There is no way to read it without setting it, so it is put back at once.
+pub fn umask() -> u32 {
+    unsafe {
+        let mask = libc::umask(0);
+        libc::umask(mask);
+        mask as u32
+    }
+}
+
+/// Bits each `who` letter controls; `o` also owns the sticky bit.
+const USER: u32 = 0o4700;
+const GROUP: u32 = 0o2070;
+const OTHER: u32 = 0o1007;
+const ALL: u32 = USER | GROUP | OTHER;
+
+#[derive(Debug, PartialEq)]
+enum Perms {
+    /// Some of `rwxXst`, with `X` kept apart because it depends on the file.
+    Bits { bits: u32, conditional_exec: bool },
+    /// The current permissions of `u`, `g` or `o`, given as the shift of that class.
+    Copy(u32),
+}
+
+#[derive(Debug, PartialEq)]
+struct Action {
+    op: char,
+    perms: Perms,
+}
+
+#[derive(Debug, PartialEq)]
+pub struct Clause {
+    /// The classes named before the operator, or `None` for "all, minus the umask".
+    who: Option<u32>,
+    actions: Vec<Action>,
+}
+
+/// A parsed MODE operand: either absolute octal bits or a list of symbolic clauses.
+#[derive(Debug, PartialEq)]
+pub enum Mode {
+    Octal(u32),
+    Symbolic(Vec<Clause>),
+}
+
+fn parse_clause(clause: &str) -> Option<Clause> {
+    let mut chars = clause.chars().peekable();
+    let mut who = None;
+    while let Some(&c) = chars.peek() {
+        let class = match c {
+            'u' => USER,
+            'g' => GROUP,
+            'o' => OTHER,
+            'a' => ALL,
+            _ => break,
+        };
+        who = Some(who.unwrap_or(0) | class);
+        chars.next();
+    }
+
+    let mut actions = vec![];
+    while let Some(op) = chars.next() {
+        if !"+-=".contains(op) {
+            return None;
+        }
+        let perms = match chars.peek() {
+            Some(&c) if "ugo".contains(c) => {
+                chars.next();
+                Perms::Copy(match c {
+                    'u' => 6,
+                    'g' => 3,
+                    _ => 0,
+                })
+            }
+            _ => {
+                let mut bits = 0;
+                let mut conditional_exec = false;
+                while let Some(&c) = chars.peek() {
+                    match c {
+                        'r' => bits |= 0o444,
+                        'w' => bits |= 0o222,
+                        'x' => bits |= 0o111,
+                        'X' => conditional_exec = true,
+                        's' => bits |= 0o6000,
+                        't' => bits |= 0o1000,
+                        _ => break,
+                    }
+                    chars.next();
+                }
+                Perms::Bits {
+                    bits,
+                    conditional_exec,
+                }
+            }
+        };
+        actions.push(Action { op, perms });
+    }
+    if actions.is_empty() {
+        return None;
+    }
+    Some(Clause { who, actions })
+}
+
+impl Mode {
+    /// Parses `755` style octal or `u+x,go-w` style symbolic modes.
+    pub fn parse(mode: &str) -> Result<Mode, String> {
+        let invalid = || format!("invalid mode: '{}'", mode);
+        if !mode.is_empty() && mode.chars().all(|c| c.is_digit(8)) {
+            return match u32::from_str_radix(mode, 8) {
+                Ok(bits) if bits <= 0o7777 => Ok(Mode::Octal(bits)),
+                _ => Err(invalid()),
+            };
+        }
+        mode.split(',')
+            .map(parse_clause)
+            .collect::<Option<Vec<Clause>>>()
+            .map(Mode::Symbolic)
+            .ok_or_else(invalid)
+    }
+
+    /// The permission bits a file with `current` bits ends up with. `umask` limits clauses that
+    /// don't say whose bits they change, as in `chmod +x`.
+    pub fn apply(&self, current: u32, is_dir: bool, umask: u32) -> u32 {
+        let clauses = match self {
+            Mode::Octal(bits) => return *bits,
+            Mode::Symbolic(clauses) => clauses,
+        };
+        let mut mode = current & 0o7777;
+        for clause in clauses {
+            let (affected, mask) = match clause.who {
+                Some(who) => (who, who),
+                None => (ALL, ALL & !umask),
+            };
+            for action in &clause.actions {
+                let bits = match action.perms {
+                    Perms::Bits {
+                        bits,
+                        conditional_exec,
+                    } => {
+                        let exec = conditional_exec && (is_dir || mode & 0o111 != 0);
+                        bits | if exec { 0o111 } else { 0 }
+                    }
+                    Perms::Copy(shift) => ((mode >> shift) & 0o7) * 0o111,
+                } & mask;
+                match action.op {
+                    '+' => mode |= bits,
+                    '-' => mode &= !bits,
+                    _ => mode = (mode & !affected) | bits,
+                }
+            }
+        }
+        mode
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::Path;
+
+    #[test]
+    fn permission_strings() {
+        assert_eq!(permissions_string(0o644), "rw-r--r--");
+        assert_eq!(permissions_string(0o100755), "rwxr-xr-x");
+        assert_eq!(permissions_string(0o4754), "rwsr-xr--");
+        assert_eq!(permissions_string(0o2640), "rw-r-S---");
+        assert_eq!(permissions_string(0o1777), "rwxrwxrwt");
+        assert_eq!(permissions_string(0o1666), "rw-rw-rwT");
+    }
+
+    #[test]
+    fn mode_strings_start_with_the_file_type() {
+        let dir = std::env::temp_dir().symlink_metadata().unwrap();
+        assert!(mode_string(&dir).starts_with('d'));
+        let null = Path::new("/dev/null").metadata().unwrap();
+        assert_eq!(mode_string(&null), "crw-rw-rw-");
+    }
+
+    fn apply(mode: &str, current: u32) -> u32 {
+        Mode::parse(mode).unwrap().apply(current, false, 0o022)
+    }
+
+    #[test]
+    fn octal() {
+        assert_eq!(apply("755", 0o600), 0o755);
+        assert_eq!(apply("4711", 0o600), 0o4711);
+        assert!(Mode::parse("8").is_err());
+        assert!(Mode::parse("17777").is_err());
+    }
+
+    #[test]
+    fn symbolic() {
+        assert_eq!(apply("u+x", 0o644), 0o744);
+        assert_eq!(apply("go-w", 0o666), 0o644);
+        assert_eq!(apply("a=r", 0o755), 0o444);
+        assert_eq!(apply("u=rw,g=r,o=", 0o777), 0o640);
+        assert_eq!(apply("g=u", 0o750), 0o770);
+        assert_eq!(apply("u+s,+t", 0o755), 0o5755);
+        assert_eq!(apply("o-rwx+w", 0o777), 0o772);
+    }
+
+    #[test]
+    fn unspecified_who_respects_umask() {
+        assert_eq!(apply("+w", 0o444), 0o644);
+        assert_eq!(apply("=rx", 0o777), 0o555);
+        assert_eq!(apply("a+w", 0o444), 0o666);
+    }
+
+    #[test]
+    fn conditional_exec() {
+        let mode = Mode::parse("a+X").unwrap();
+        assert_eq!(mode.apply(0o644, false, 0), 0o644);
+        assert_eq!(mode.apply(0o744, false, 0), 0o755);
+        assert_eq!(mode.apply(0o644, true, 0), 0o755);
+    }
+
+    #[test]
+    fn invalid() {
+        for mode in &["", "u", "u+z", "x+r", "u+r,", "+-q"] {
+            assert!(Mode::parse(mode).is_err(), "{}", mode);
+        }
+    }
+}
@@ -0,0 +1,110 @@
+use std::convert::TryFrom;
+
+/// The multiplier for a size suffix: `b` is 512, `K`/`KiB` through `E`/`EiB` are powers of
+/// 1024, and `KB` through `EB` are powers of 1000. A lowercase `k` is accepted like `K`.
+fn multiplier(suffix: &str) -> Option<u64> {
+    if suffix.is_empty() {
+        return Some(1);
+    }
+    if suffix == "b" {
+        return Some(512);
+    }
+    let mut chars = suffix.chars();
+    let power = match chars.next()? {
+        'K' | 'k' => 1,
+        'M' => 2,
+        'G' => 3,
+        'T' => 4,
+        'P' => 5,
+        'E' => 6,
+        _ => return None,
+    };
+    let base: u64 = match chars.as_str() {
+        "" | "iB" => 1024,
+        "B" => 1000,
+        _ => return None,
+    };
+    Some(base.pow(power))
+}
+
+fn invalid(text: &str) -> String {
+    format!("invalid number: '{}'", text)
+}
+
+/// Parses a plain count: decimal digits only, with no sign, suffix or locale-specific
+/// grouping.
+pub fn parse_count(text: &str) -> Result<u64, String> {
+    if text.is_empty() || !text.bytes().all(|b| b.is_ascii_digit()) {
+        return Err(invalid(text));
+    }
+    text.parse()
+        .map_err(|_| format!("number too large: '{}'", text))
+}
+
+/// Parses a count with an optional size suffix (see `multiplier`), such as `10K` or `1M`. The
+/// number may have a fractional part, as in `1.5G`, as long as the result is a whole number.
+pub fn parse_size(text: &str) -> Result<u64, String> {
+    let end = text
+        .find(|c: char| !c.is_ascii_digit() && c != '.')
+        .unwrap_or(text.len());
+    let (number, suffix) = text.split_at(end);
+    let multiplier = multiplier(suffix).ok_or_else(|| invalid(text))?;
+    let (whole, fraction) = number.split_once('.').unwrap_or((number, ""));
+    if whole.is_empty() || !fraction.bytes().all(|b| b.is_ascii_digit()) {
+        return Err(invalid(text));
+    }
+    let too_large = || format!("number too large: '{}'", text);
+    // Work in u128 so a long fraction can't overflow before it's scaled back down.
+    let scale = 10u128
+        .checked_pow(fraction.len() as u32)
+        .ok_or_else(too_large)?;
+    let digits: u128 = format!("{}{}", whole, fraction)
+        .parse()
+        .map_err(|_| too_large())?;
+    let scaled = digits
+        .checked_mul(u128::from(multiplier))
+        .ok_or_else(too_large)?;
+    if scaled % scale != 0 {
+        return Err(invalid(text));
+    }
+    u64::try_from(scaled / scale).map_err(|_| too_large())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn counts() {
+        assert_eq!(parse_count("0"), Ok(0));
+        assert_eq!(parse_count("42"), Ok(42));
+        assert!(parse_count("").is_err());
+        assert!(parse_count("+4").is_err());
+        assert!(parse_count("-4").is_err());
+        assert!(parse_count("1,000").is_err());
+        assert!(parse_count("4K").is_err());
+        assert_eq!(
+            parse_count("99999999999999999999"),
+            Err("number too large: '99999999999999999999'".to_owned())
+        );
+    }
+
+    #[test]
+    fn sizes() {
+        assert_eq!(parse_size("10"), Ok(10));
+        assert_eq!(parse_size("10K"), Ok(10 * 1024));
+        assert_eq!(parse_size("10KiB"), Ok(10 * 1024));
+        assert_eq!(parse_size("10KB"), Ok(10_000));
+        assert_eq!(parse_size("1M"), Ok(1 << 20));
+        assert_eq!(parse_size("1.5G"), Ok(3 << 29));
+        assert_eq!(parse_size("2b"), Ok(1024));
+        assert_eq!(parse_size("16E"), Err("number too large: '16E'".to_owned()));
+    }
+
+    #[test]
+    fn invalid_sizes() {
+        for text in ["", "K", "1.5", "1.2.3K", "1X", "1Kb", ".5K", "-1K", "1 K"] {
+            assert_eq!(parse_size(text), Err(invalid(text)), "{}", text);
+        }
+    }
+}
@@ -0,0 +1,39 @@
+/// What ends each record a tool prints: a newline, or NUL when asked for with `-z` (or env's
+/// `-0`) so that names containing newlines can be told apart.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Terminator {
+    Newline,
+    Nul,
+}
+
+impl Terminator {
+    pub fn new(nul: bool) -> Self {
+        if nul {
+            Terminator::Nul
+        } else {
+            Terminator::Newline
+        }
+    }
+
+    pub fn as_byte(self) -> u8 {
+        match self {
+            Terminator::Newline => b'\n',
+            Terminator::Nul => b'\0',
+        }
+    }
+
+    pub fn as_char(self) -> char {
+        char::from(self.as_byte())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn terminators() {
+        assert_eq!(Terminator::new(false).as_byte(), b'\n');
+        assert_eq!(Terminator::new(true).as_char(), '\0');
+    }
+}
@@ -0,0 +1,168 @@
+use std::collections::VecDeque;
+use std::env;
+use std::ffi::OsString;
+use std::fs;
+use std::fs::Metadata;
+use std::io;
+use std::os::unix::fs::MetadataExt;
+use std::path::Component;
+use std::path::Path;
+use std::path::PathBuf;
+
+/// How many symlinks `canonicalize` follows before giving up, as Linux does for path lookup.
+const MAX_SYMLINKS: usize = 40;
+
+/// Which components of a path `canonicalize` requires to exist.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Missing {
+    /// Every component must exist (`realpath -e`, `readlink -e`).
+    Forbidden,
+    /// Everything but the final component must exist (`readlink -f`, plain `realpath`).
+    LastAllowed,
+    /// Nothing needs to exist or be a directory (`realpath -m`, `ln -r`).
+    Allowed,
+}
+
+fn tail(path: &Path) -> VecDeque<OsString> {
+    path.components()
+        .map(|component| component.as_os_str().to_owned())
+        .collect()
+}
+
+/// The absolute form of `path` with every symlink resolved and no `.` or `..` components.
+///
+/// Symlinks are expanded before any `..` that follows them is applied, so `link/..` is the
+/// parent of the link's target, not the directory holding the link. Components that are
+/// allowed to be missing are appended as written.
+pub fn canonicalize(path: &Path, missing: Missing) -> io::Result<PathBuf> {
+    let mut resolved = if path.is_absolute() {
+        PathBuf::from("/")
+    } else {
+        env::current_dir()?
+    };
+    let mut rest = tail(path);
+    let mut followed = 0;
+    while let Some(name) = rest.pop_front() {
+        match Path::new(&name).components().next() {
+            Some(Component::RootDir) => resolved = PathBuf::from("/"),
+            Some(Component::ParentDir) => {
+                resolved.pop();
+            }
+            Some(Component::Normal(_)) => {
+                let candidate = resolved.join(&name);
+                match fs::symlink_metadata(&candidate) {
+                    Ok(metadata) if metadata.file_type().is_symlink() => {
+                        followed += 1;
+                        if followed > MAX_SYMLINKS {
+                            return Err(io::Error::from_raw_os_error(libc::ELOOP));
+                        }
+                        let target = fs::read_link(&candidate)?;
+                        for component in tail(&target).into_iter().rev() {
+                            rest.push_front(component);
+                        }
+                    }
+                    Ok(_) => resolved = candidate,
+                    Err(e) => {
+                        let absent = e.kind() == io::ErrorKind::NotFound
+                            || e.raw_os_error() == Some(libc::ENOTDIR);
+                        let allowed = match missing {
+                            Missing::Forbidden => false,
+                            Missing::LastAllowed => rest.is_empty(),
+                            Missing::Allowed => true,
+                        };
+                        if !(absent && allowed) {
+                            return Err(e);
+                        }
+                        resolved = candidate;
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+    Ok(resolved)
+}
+
+/// The path that leads from directory `from` to `to`; both must be canonical.
+pub fn relative_path(from: &Path, to: &Path) -> PathBuf {
+    let from: Vec<Component> = from.components().collect();
+    let to: Vec<Component> = to.components().collect();
+    let common = from.iter().zip(&to).take_while(|(a, b)| a == b).count();
+    let mut relative = PathBuf::new();
+    for _ in common..from.len() {
+        relative.push("..");
+    }
+    for component in &to[common..] {
+        relative.push(component);
+    }
+    if relative.as_os_str().is_empty() {
+        relative.push(".");
+    }
+    relative
+}
+
+/// Whether two files' metadata describe the same file, by device and inode.
+pub fn same_file(a: &Metadata, b: &Metadata) -> bool {
+    a.dev() == b.dev() && a.ino() == b.ino()
+}
+
+/// `path` without its trailing slashes. A path made only of slashes is left as `/`.
+pub fn strip_trailing_slashes(path: &str) -> &str {
+    let stripped = path.trim_end_matches('/');
+    if stripped.is_empty() && !path.is_empty() {
+        "/"
+    } else {
+        stripped
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::os::unix::fs::symlink;
+    use std::process;
+
+    #[test]
+    fn relative_paths() {
+        let rel = |from, to| relative_path(Path::new(from), Path::new(to));
+        assert_eq!(rel("/a/b", "/a/b/c"), Path::new("c"));
+        assert_eq!(rel("/a/b", "/a/c"), Path::new("../c"));
+        assert_eq!(rel("/a/b/c", "/d"), Path::new("../../../d"));
+        assert_eq!(rel("/", "/a"), Path::new("a"));
+        assert_eq!(rel("/a", "/a"), Path::new("."));
+    }
+
+    #[test]
+    fn trailing_slashes() {
+        assert_eq!(strip_trailing_slashes("dir///"), "dir");
+        assert_eq!(strip_trailing_slashes("a/b"), "a/b");
+        assert_eq!(strip_trailing_slashes("//"), "/");
+        assert_eq!(strip_trailing_slashes(""), "");
+    }
+
+    #[test]
+    fn missing_components() {
+        let dir = env::temp_dir()
+            .canonicalize()
+            .unwrap()
+            .join(format!("coreutils-common-paths-{}", process::id()));
+        fs::create_dir_all(dir.join("a/b")).unwrap();
+        symlink("a/b", dir.join("link")).unwrap();
+        symlink("loop", dir.join("loop")).unwrap();
+
+        let canon = |path: &str, missing| canonicalize(&dir.join(path), missing);
+        assert_eq!(canon("link/..", Missing::Forbidden).unwrap(), dir.join("a"));
+        assert_eq!(
+            canon("a/./b/../x", Missing::LastAllowed).unwrap(),
+            dir.join("a/x")
+        );
+        assert!(canon("x/y", Missing::LastAllowed).is_err());
+        assert_eq!(canon("x/../y", Missing::Allowed).unwrap(), dir.join("y"));
+        assert_eq!(
+            canon("loop", Missing::Allowed).unwrap_err().raw_os_error(),
+            Some(libc::ELOOP)
+        );
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
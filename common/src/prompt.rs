@@ -0,0 +1,41 @@
+use std::io;
+use std::io::BufRead;
+use std::io::Write;
+
+/// Whether `answer` means yes: `y` or `yes` in any case. The answer isn't localised.
+pub fn is_yes(answer: &str) -> bool {
+    let answer = answer.trim();
+    answer.eq_ignore_ascii_case("y") || answer.eq_ignore_ascii_case("yes")
+}
+
+/// Reads one line of `input` as the answer to a prompt. Running out of input is a no.
+pub fn read_answer(input: &mut impl BufRead) -> io::Result<bool> {
+    let mut answer = String::new();
+    input.read_line(&mut answer)?;
+    Ok(is_yes(&answer))
+}
+
+/// Asks `question` on stderr and reads the answer from stdin. The question should name the
+/// tool, e.g. `rm: remove 'file'`; the `? ` is added here.
+pub fn confirm(question: &str) -> io::Result<bool> {
+    eprint!("{}? ", question);
+    io::stderr().flush()?;
+    read_answer(&mut io::stdin().lock())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Cursor;
+
+    #[test]
+    fn answers() {
+        let mut input = Cursor::new("y\nn\nYES\nyep\n");
+        assert!(read_answer(&mut input).unwrap());
+        assert!(!read_answer(&mut input).unwrap());
+        assert!(read_answer(&mut input).unwrap());
+        assert!(!read_answer(&mut input).unwrap());
+        // End of input.
+        assert!(!read_answer(&mut input).unwrap());
+    }
+}
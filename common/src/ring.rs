@@ -0,0 +1,90 @@
+use std::collections::vec_deque;
+use std::collections::VecDeque;
+use std::convert::TryFrom;
+
+/// The last `capacity` items of a stream, for `tail -n K` and `head -n -K`. Memory stays
+/// bounded by the capacity however long the stream is.
+pub struct RingBuffer<T> {
+    items: VecDeque<T>,
+    capacity: usize,
+}
+
+impl<T> RingBuffer<T> {
+    /// A buffer holding at most `capacity` items. A count too big for memory is clamped, since
+    /// the buffer only ever grows as far as the input does.
+    pub fn new(capacity: u64) -> Self {
+        RingBuffer {
+            items: VecDeque::new(),
+            capacity: usize::try_from(capacity).unwrap_or(usize::MAX),
+        }
+    }
+
+    /// Adds `item`, returning whichever item falls out the other end: the oldest one once the
+    /// buffer is full, or `item` itself if the capacity is zero.
+    pub fn push(&mut self, item: T) -> Option<T> {
+        if self.capacity == 0 {
+            return Some(item);
+        }
+        let evicted = if self.items.len() == self.capacity {
+            self.items.pop_front()
+        } else {
+            None
+        };
+        self.items.push_back(item);
+        evicted
+    }
+
+    pub fn len(&self) -> usize {
+        self.items.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+}
+
+impl<T> IntoIterator for RingBuffer<T> {
+    type Item = T;
+    type IntoIter = vec_deque::IntoIter<T>;
+
+    /// The held items, oldest first.
+    fn into_iter(self) -> Self::IntoIter {
+        self.items.into_iter()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn keeps_the_last_items_of_a_long_stream() {
+        let mut ring = RingBuffer::new(3);
+        let mut evicted = vec![];
+        for line in 0..10_000 {
+            evicted.extend(ring.push(format!("line {}", line)));
+            assert!(ring.len() <= 3);
+        }
+        assert_eq!(evicted.len(), 9_997);
+        assert_eq!(evicted[0], "line 0");
+        assert_eq!(evicted[9_996], "line 9996");
+        let kept: Vec<String> = ring.into_iter().collect();
+        assert_eq!(kept, vec!["line 9997", "line 9998", "line 9999"]);
+    }
+
+    #[test]
+    fn short_stream_is_kept_whole() {
+        let mut ring = RingBuffer::new(5);
+        assert!(ring.is_empty());
+        assert_eq!(ring.push(1), None);
+        assert_eq!(ring.push(2), None);
+        assert_eq!(ring.into_iter().collect::<Vec<_>>(), vec![1, 2]);
+    }
+
+    #[test]
+    fn zero_capacity_passes_everything_through() {
+        let mut ring = RingBuffer::new(0);
+        assert_eq!(ring.push("a"), Some("a"));
+        assert!(ring.is_empty());
+    }
+}
@@ -0,0 +1,97 @@
+use libc::c_char;
+use libc::c_int;
+use std::ffi::CString;
+use std::mem;
+use std::os::unix::ffi::OsStrExt;
+use std::path::Path;
+use std::ptr;
+use std::sync::atomic::AtomicPtr;
+use std::sync::atomic::Ordering;
+
+/// The file being written right now, as a C string, or null. The signal handler can only
+/// afford an atomic load, so the path is kept ready to pass to `unlink`.
+static PARTIAL: AtomicPtr<c_char> = AtomicPtr::new(ptr::null_mut());
+
+extern "C" fn remove_partial(signal: c_int) {
+    let path = PARTIAL.load(Ordering::SeqCst);
+    unsafe {
+        if !path.is_null() {
+            libc::unlink(path);
+        }
+        // Die from the signal as if there was no handler, so the exit status still says so.
+        libc::signal(signal, libc::SIG_DFL);
+        libc::raise(signal);
+    }
+}
+
+/// Make SIGINT and SIGTERM remove the file registered with [`Partial::register`] before the
+/// process dies, so an interrupted copy doesn't leave a truncated file behind. Signals that
+/// were ignored when the tool started (e.g. under `nohup`) stay ignored.
+pub fn remove_partial_on_interrupt() {
+    for &signal in &[libc::SIGINT, libc::SIGTERM] {
+        let handler = remove_partial as extern "C" fn(c_int) as libc::sighandler_t;
+        unsafe {
+            if libc::signal(signal, handler) == libc::SIG_IGN {
+                libc::signal(signal, libc::SIG_IGN);
+            }
+        }
+    }
+}
+
+/// A file that is incomplete until this is dropped. Only one can be registered at a time.
+pub struct Partial {
+    _private: (),
+}
+
+impl Partial {
+    /// Registers `path` for removal if the process is interrupted before the guard is dropped.
+    pub fn register(path: &Path) -> Partial {
+        // A path with a NUL couldn't have been opened, so there is nothing to clean up.
+        let raw = CString::new(path.as_os_str().as_bytes())
+            .map(CString::into_raw)
+            .unwrap_or(ptr::null_mut());
+        replace(raw);
+        Partial { _private: () }
+    }
+}
+
+impl Drop for Partial {
+    fn drop(&mut self) {
+        replace(ptr::null_mut());
+    }
+}
+
+/// Registers `raw` in place of the current path, which is then freed. The handled signals are
+/// blocked around the swap, so no handler can still be using the old path once it is freed;
+/// the tools that use this are single-threaded, so no handler runs on another thread.
+fn replace(raw: *mut c_char) {
+    let old = unsafe {
+        let mut handled = mem::zeroed();
+        let mut previous = mem::zeroed();
+        libc::sigemptyset(&mut handled);
+        libc::sigaddset(&mut handled, libc::SIGINT);
+        libc::sigaddset(&mut handled, libc::SIGTERM);
+        libc::sigprocmask(libc::SIG_BLOCK, &handled, &mut previous);
+        let old = PARTIAL.swap(raw, Ordering::SeqCst);
+        libc::sigprocmask(libc::SIG_SETMASK, &previous, ptr::null_mut());
+        old
+    };
+    if !old.is_null() {
+        drop(unsafe { CString::from_raw(old) });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::ffi::CStr;
+
+    #[test]
+    fn registers_until_dropped() {
+        let partial = Partial::register(Path::new("/tmp/partial"));
+        let registered = unsafe { CStr::from_ptr(PARTIAL.load(Ordering::SeqCst)) };
+        assert_eq!(registered.to_bytes(), b"/tmp/partial");
+        drop(partial);
+        assert!(PARTIAL.load(Ordering::SeqCst).is_null());
+    }
+}
@@ -0,0 +1,89 @@
+/// Where tab stops fall, from a `-t` argument.
+#[derive(Debug, PartialEq)]
+pub enum TabStops {
+    /// A stop every N columns.
+    Every(usize),
+    /// Stops at these ascending columns, counted from zero.
+    List(Vec<usize>),
+}
+
+impl TabStops {
+    /// Parses `N`, or a list of columns separated by commas or blanks.
+    pub fn parse(tabs: &str) -> Result<TabStops, String> {
+        let mut stops = vec![];
+        for stop in tabs.split([',', ' ']).filter(|s| !s.is_empty()) {
+            let stop: usize = stop
+                .parse()
+                .map_err(|_| format!("tab size contains invalid character(s): '{}'", stop))?;
+            if stop == 0 {
+                return Err("tab size cannot be 0".to_string());
+            }
+            if stops.last().is_some_and(|&last| stop <= last) {
+                return Err("tab sizes must be ascending".to_string());
+            }
+            stops.push(stop);
+        }
+        match stops.len() {
+            0 => Err(format!("invalid tab size: '{}'", tabs)),
+            1 => Ok(TabStops::Every(stops[0])),
+            _ => Ok(TabStops::List(stops)),
+        }
+    }
+
+    /// The column a tab at `column` moves to. Past the last listed stop, a tab is a single
+    /// space, as in GNU expand.
+    pub fn next_stop(&self, column: usize) -> usize {
+        match self {
+            TabStops::Every(size) => column + size - column % size,
+            TabStops::List(stops) => match stops.iter().find(|&&stop| stop > column) {
+                Some(&stop) => stop,
+                None => column + 1,
+            },
+        }
+    }
+
+    /// Whether `column` is exactly on a stop.
+    pub fn is_stop(&self, column: usize) -> bool {
+        match self {
+            TabStops::Every(size) => column.is_multiple_of(*size),
+            TabStops::List(stops) => stops.contains(&column),
+        }
+    }
+}
+
+impl Default for TabStops {
+    fn default() -> Self {
+        TabStops::Every(8)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parsing() {
+        assert_eq!(TabStops::parse("4"), Ok(TabStops::Every(4)));
+        assert_eq!(TabStops::parse("2,5 9"), Ok(TabStops::List(vec![2, 5, 9])));
+        assert!(TabStops::parse("0").is_err());
+        assert!(TabStops::parse("4,2").is_err());
+        assert!(TabStops::parse("x").is_err());
+        assert!(TabStops::parse(",").is_err());
+    }
+
+    #[test]
+    fn stops() {
+        let every = TabStops::Every(4);
+        assert_eq!(every.next_stop(0), 4);
+        assert_eq!(every.next_stop(3), 4);
+        assert_eq!(every.next_stop(4), 8);
+        let list = TabStops::List(vec![2, 5]);
+        assert_eq!(list.next_stop(0), 2);
+        assert_eq!(list.next_stop(2), 5);
+        assert_eq!(list.next_stop(7), 8);
+        assert!(every.is_stop(8));
+        assert!(!every.is_stop(9));
+        assert!(list.is_stop(5));
+        assert!(!list.is_stop(4));
+    }
+}
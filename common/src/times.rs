@@ -0,0 +1,121 @@
+use crate::c_bindings::AT_FDCWD;
+use crate::c_bindings::AT_SYMLINK_NOFOLLOW;
+use crate::c_bindings::UTIME_OMIT;
+use libc::timespec;
+use std::ffi::CString;
+use std::fs::Metadata;
+use std::io;
+use std::io::ErrorKind;
+use std::os::unix::ffi::OsStrExt;
+use std::os::unix::fs::MetadataExt;
+use std::path::Path;
+use syscall::syscall;
+
+/// The `tv_nsec` value telling `utimensat` to leave a timestamp untouched.
+///
+/// bindgen may type the constant as `u32`, `i32` or `i64` depending on the target; `i64::from`
+/// only exists for lossless conversions, so the bit pattern can't be truncated or sign-extended.
+pub fn omit_nsec() -> i64 {
+    i64::from(UTIME_OMIT)
+}
+
+/// Set the access and modification times of `path` with `utimensat`, leaving either one alone
+/// when it is `None`. With `no_dereference`, a symlink's own times change rather than its
+/// target's.
+pub fn set_times(
+    path: &Path,
+    accessed: Option<timespec>,
+    modified: Option<timespec>,
+    no_dereference: bool,
+) -> io::Result<()> {
+    let c_path = CString::new(path.as_os_str().as_bytes())
+        .map_err(|_| io::Error::new(ErrorKind::InvalidInput, "file name contains NUL"))?
+        .into_bytes_with_nul();
+    let omitted = timespec {
+        tv_sec: 0,
+        tv_nsec: omit_nsec(),
+    };
+    let times = [accessed.unwrap_or(omitted), modified.unwrap_or(omitted)];
+    let flag = if no_dereference {
+        AT_SYMLINK_NOFOLLOW
+    } else {
+        0
+    };
+    let ret = unsafe { syscall!(UTIMENSAT, AT_FDCWD, c_path.as_ptr(), times.as_ptr(), flag) };
+    // A raw syscall doesn't set errno; failures come back as -errno instead.
+    let ret = ret as isize;
+    if ret < 0 {
+        return Err(io::Error::from_raw_os_error(-ret as i32));
+    }
+    Ok(())
+}
+
+/// Give `path` the access and modification times recorded in `metadata`, as `cp -p` does.
+pub fn copy_times(metadata: &Metadata, path: &Path, no_dereference: bool) -> io::Result<()> {
+    let accessed = timespec {
+        tv_sec: metadata.atime(),
+        tv_nsec: metadata.atime_nsec(),
+    };
+    let modified = timespec {
+        tv_sec: metadata.mtime(),
+        tv_nsec: metadata.mtime_nsec(),
+    };
+    set_times(path, Some(accessed), Some(modified), no_dereference)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::env;
+    use std::fs;
+    use std::process;
+
+    #[test]
+    #[allow(clippy::unnecessary_cast)] // c_long is only i64 on 64-bit targets
+    fn omit_nsec_matches_libc() {
+        assert_eq!(omit_nsec(), libc::UTIME_OMIT as i64);
+    }
+
+    #[test]
+    fn omit_nsec_survives_timespec() {
+        let time = timespec {
+            tv_sec: 0,
+            tv_nsec: omit_nsec(),
+        };
+        assert_eq!(time.tv_nsec, libc::UTIME_OMIT);
+    }
+
+    #[test]
+    fn sets_and_omits_times() {
+        let path = env::temp_dir().join(format!("coreutils-common-times-{}", process::id()));
+        fs::write(&path, "").unwrap();
+        let time = |tv_sec| timespec {
+            tv_sec,
+            tv_nsec: 500,
+        };
+        set_times(&path, Some(time(1_000_000)), Some(time(2_000_000)), false).unwrap();
+        set_times(&path, None, Some(time(3_000_000)), false).unwrap();
+        let metadata = fs::metadata(&path).unwrap();
+        fs::remove_file(&path).unwrap();
+        assert_eq!(metadata.atime(), 1_000_000);
+        assert_eq!(metadata.mtime(), 3_000_000);
+        assert_eq!(metadata.mtime_nsec(), 500);
+    }
+
+    #[test]
+    fn reports_missing_files() {
+        let now = timespec {
+            tv_sec: 0,
+            tv_nsec: libc::UTIME_NOW,
+        };
+        let path = Path::new("/nonexistent/coreutils-common-times");
+        let error = set_times(path, Some(now), None, false).unwrap_err();
+        assert_eq!(error.kind(), ErrorKind::NotFound);
+    }
+
+    #[test]
+    fn rejects_nul_in_path() {
+        let error = set_times(Path::new("a\0b"), None, None, false).unwrap_err();
+        assert_eq!(error.kind(), ErrorKind::InvalidInput);
+    }
+}
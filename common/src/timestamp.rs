@@ -0,0 +1,234 @@
+use chrono::format::strftime::StrftimeItems;
+use chrono::format::Item;
+use chrono::offset::TimeZone;
+use chrono::DateTime;
+use chrono::Datelike;
+use chrono::Local;
+use chrono::NaiveDate;
+use chrono::Utc;
+
+/// Parses a `-d` date: an RFC 3339 date, or `@SECONDS` since the epoch.
+pub fn parse_date(date: &str) -> Result<DateTime<Local>, String> {
+    if let Some(seconds) = date.strip_prefix('@') {
+        let seconds: i64 = seconds
+            .parse()
+            .map_err(|_| format!("invalid number of seconds: {}", seconds))?;
+        return Ok(Utc.timestamp(seconds, 0).with_timezone(&Local));
+    }
+    DateTime::parse_from_rfc3339(date)
+        .map(|time| time.with_timezone(&Local))
+        .map_err(|e| format!("error parsing {} as an RFC 3339 date: {}", date, e))
+}
+
+/// Parses a `[[CC]YY]MMDDhhmm[.ss]` stamp. The fields are read as local time, or as UTC if
+/// `utc` is set.
+pub fn parse_timestamp(timestamp: &str, utc: bool) -> Result<DateTime<Local>, String> {
+    let chars: Vec<char> = timestamp.chars().collect();
+    // the "has_" flags are checking for optional parts of the timestamp string.
+    // Not much validation is done here until we try to parse integers.
+    let has_seconds = chars.contains(&'.');
+    let expected_len = 8 + if has_seconds { 3 } else { 0 };
+    if timestamp.len() < expected_len {
+        return Err("timestamp is too short".into());
+    }
+    let has_century = chars.len() == expected_len + 4;
+    let has_year = has_century || chars.len() == expected_len + 2;
+    // Take slices for the significant parts of the timestamp to clean up later code.
+    // "Shift" the input by using a `rest` slice.
+    let (raw_century, rest) = if has_century {
+        (&timestamp[0..2], &timestamp[2..])
+    } else {
+        ("", timestamp)
+    };
+    let (raw_year, rest) = if has_year {
+        (&rest[0..2], &rest[2..])
+    } else {
+        ("", rest)
+    };
+    // No more shifting here, it's unnecessary
+    let raw_month = &rest[0..2];
+    let raw_day = &rest[2..4];
+    let raw_hours = &rest[4..6];
+    let raw_minutes = &rest[6..8];
+    let raw_seconds = if has_seconds { &rest[9..] } else { "" };
+
+    // Missing fields will be substituted with the current date
+    let today = if utc {
+        Utc::today().naive_utc()
+    } else {
+        Local::today().naive_local()
+    };
+
+    // Try and parse the fields now
+    let century: i32 = if has_century {
+        raw_century.parse::<i32>().map_err(|_| "invalid century")? * 100
+    } else {
+        today.year() / 100 * 100
+    };
+    let year: i32 = if has_year {
+        raw_year.parse().map_err(|_| "invalid year")?
+    } else {
+        today.year() % 100
+    };
+
+    let month: u32 = raw_month.parse().map_err(|_| "invalid month")?;
+    let day: u32 = raw_day.parse().map_err(|_| "invalid day")?;
+    let hours: u32 = raw_hours.parse().map_err(|_| "invalid hour")?;
+    let minutes: u32 = raw_minutes.parse().map_err(|_| "invalid minute")?;
+    let seconds: u32 = if has_seconds {
+        raw_seconds.parse::<u32>().map_err(|_| "invalid second")?
+    } else {
+        0
+    };
+
+    // Done! Construct a DateTime (and also check none of the numbers were OOB)
+    if let Some(date) = NaiveDate::from_ymd_opt(century + year, month, day)
+        .and_then(|d| d.and_hms_opt(hours, minutes, seconds))
+    {
+        if utc {
+            Ok(Utc.from_utc_datetime(&date).with_timezone(&Local))
+        } else {
+            Ok(Local.from_local_datetime(&date).unwrap())
+        }
+    } else {
+        Err("invalid date".into())
+    }
+}
+
+/// Times further in the past than this (about six months, in seconds) aren't recent.
+const SIX_MONTHS: i64 = 15_778_476;
+
+/// How ls, stat and du show a timestamp, as chosen with `--time-style`.
+#[derive(Clone, Debug, PartialEq)]
+pub enum TimeStyle {
+    /// `2000-01-02 03:04:05.000000000 +0000`
+    FullIso,
+    /// `2000-01-02 03:04`
+    LongIso,
+    /// `01-02 03:04` when recent, else `2000-01-02`
+    Iso,
+    /// `Jan  2 03:04` when recent, else `Jan  2  2000`, as ls shows by default
+    Locale,
+    /// A strftime format from `+FORMAT`. Like GNU ls, a newline separates a format for old
+    /// times from one for recent times.
+    Format(String),
+}
+
+impl TimeStyle {
+    /// Parses `full-iso`, `long-iso`, `iso`, `locale` or `+FORMAT`.
+    pub fn parse(style: &str) -> Result<TimeStyle, String> {
+        match style {
+            "full-iso" => Ok(TimeStyle::FullIso),
+            "long-iso" => Ok(TimeStyle::LongIso),
+            "iso" => Ok(TimeStyle::Iso),
+            "locale" => Ok(TimeStyle::Locale),
+            _ => match style.strip_prefix('+') {
+                // chrono can't print a format it fails to parse, so check it now.
+                Some(format)
+                    if !StrftimeItems::new(format).any(|item| matches!(item, Item::Error)) =>
+                {
+                    Ok(TimeStyle::Format(format.to_string()))
+                }
+                Some(format) => Err(format!("invalid time format '{}'", format)),
+                None => Err(format!("invalid argument '{}' for 'time style'", style)),
+            },
+        }
+    }
+
+    /// Formats a time given as seconds and nanoseconds since the epoch, in the local time zone.
+    pub fn format(&self, seconds: i64, nanoseconds: u32) -> String {
+        self.format_at(seconds, nanoseconds, Local::now().timestamp())
+    }
+
+    fn format_at(&self, seconds: i64, nanoseconds: u32, now: i64) -> String {
+        // Like ls, a time is recent if it's in the last six months; future times aren't.
+        let recent = seconds <= now && now - seconds < SIX_MONTHS;
+        let format = match self {
+            TimeStyle::FullIso => "%Y-%m-%d %H:%M:%S%.9f %z",
+            TimeStyle::LongIso => "%Y-%m-%d %H:%M",
+            TimeStyle::Iso if recent => "%m-%d %H:%M",
+            TimeStyle::Iso => "%Y-%m-%d ",
+            TimeStyle::Locale if recent => "%b %e %H:%M",
+            TimeStyle::Locale => "%b %e  %Y",
+            TimeStyle::Format(format) => match format.split_once('\n') {
+                Some((_, recent_format)) if recent => recent_format,
+                Some((old_format, _)) => old_format,
+                None => format,
+            },
+        };
+        match Local.timestamp_opt(seconds, nanoseconds).single() {
+            Some(time) => time.format(format).to_string(),
+            None => "?".to_string(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn utc_timestamp() {
+        let time = parse_timestamp("200001020304.05", true).unwrap();
+        assert_eq!(time.timestamp(), 946_782_245);
+    }
+
+    #[test]
+    fn utc_timestamp_with_century() {
+        let time = parse_timestamp("199912312359", true).unwrap();
+        assert_eq!(time.timestamp(), 946_684_740);
+    }
+
+    #[test]
+    fn local_timestamp_is_offset_from_utc() {
+        let local = parse_timestamp("200006150000", false).unwrap();
+        let utc = parse_timestamp("200006150000", true).unwrap();
+        let offset = i64::from(local.offset().local_minus_utc());
+        assert_eq!(utc.timestamp() - local.timestamp(), offset);
+    }
+
+    #[test]
+    fn dates() {
+        assert_eq!(
+            parse_date("2000-01-02T03:04:05Z").unwrap().timestamp(),
+            946_782_245
+        );
+        assert_eq!(parse_date("@946782245").unwrap().timestamp(), 946_782_245);
+        assert!(parse_date("@soon").is_err());
+        assert!(parse_date("yesterday").is_err());
+    }
+
+    #[test]
+    fn time_styles() {
+        assert_eq!(TimeStyle::parse("long-iso").unwrap(), TimeStyle::LongIso);
+        assert_eq!(
+            TimeStyle::parse("+%Y").unwrap(),
+            TimeStyle::Format("%Y".to_string())
+        );
+        assert!(TimeStyle::parse("+%Q").is_err());
+        assert!(TimeStyle::parse("short").is_err());
+    }
+
+    #[test]
+    fn recent_times_use_the_recent_format() {
+        let now = 946_782_245;
+        let style = TimeStyle::Format("old\nrecent".to_string());
+        assert_eq!(style.format_at(now - 60, 0, now), "recent");
+        assert_eq!(style.format_at(now - SIX_MONTHS, 0, now), "old");
+        assert_eq!(style.format_at(now + 60, 0, now), "old");
+        assert_eq!(
+            TimeStyle::Format("%Y".to_string()).format_at(now, 0, now),
+            "2000"
+        );
+        let long_iso = TimeStyle::LongIso.format_at(now, 0, now);
+        assert_eq!(long_iso.len(), "2000-01-02 03:04".len());
+        assert_eq!(
+            TimeStyle::Iso.format_at(now, 0, now).len(),
+            "01-02 03:04".len()
+        );
+        assert_eq!(
+            TimeStyle::Iso.format_at(0, 0, now).len(),
+            "1970-01-01 ".len()
+        );
+    }
+}
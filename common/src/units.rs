@@ -0,0 +1,60 @@
+/// `bytes` scaled to the largest power of 1024 (`binary`) or 1000 that keeps it under that
+/// base, rounded up, with one decimal place below 10: `512`, `4.0K`, `12K`, `1.5M`. Powers
+/// of 1000 are written `k`, `M`, `G`... as GNU's `--si` does.
+pub fn human_readable(bytes: u64, binary: bool) -> String {
+    let (base, units) = if binary {
+        (1024.0, ['K', 'M', 'G', 'T', 'P', 'E'])
+    } else {
+        (1000.0, ['k', 'M', 'G', 'T', 'P', 'E'])
+    };
+    if (bytes as f64) < base {
+        return bytes.to_string();
+    }
+    let mut value = bytes as f64 / base;
+    let mut unit = 0;
+    while value >= base && unit < units.len() - 1 {
+        value /= base;
+        unit += 1;
+    }
+    if value < 10.0 {
+        let tenths = (value * 10.0).ceil() / 10.0;
+        if tenths < 10.0 {
+            return format!("{:.1}{}", tenths, units[unit]);
+        }
+    }
+    // Rounding up can reach the base itself, which reads as the next unit.
+    let whole = value.ceil();
+    if whole >= base && unit < units.len() - 1 {
+        return format!("1.0{}", units[unit + 1]);
+    }
+    format!("{}{}", whole, units[unit])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn binary_sizes() {
+        assert_eq!(human_readable(0, true), "0");
+        assert_eq!(human_readable(512, true), "512");
+        assert_eq!(human_readable(1023, true), "1023");
+        assert_eq!(human_readable(1024, true), "1.0K");
+        assert_eq!(human_readable(1536, true), "1.5K");
+        assert_eq!(human_readable(4097, true), "4.1K");
+        assert_eq!(human_readable(10 * 1024 - 1, true), "10K");
+        assert_eq!(human_readable(12 * 1024, true), "12K");
+        assert_eq!(human_readable((1 << 20) - 1, true), "1.0M");
+        assert_eq!(human_readable(3 << 29, true), "1.5G");
+    }
+
+    #[test]
+    fn decimal_sizes() {
+        assert_eq!(human_readable(999, false), "999");
+        assert_eq!(human_readable(1000, false), "1.0k");
+        assert_eq!(human_readable(1023, false), "1.1k");
+        assert_eq!(human_readable(1024, false), "1.1k");
+        assert_eq!(human_readable(1536, false), "1.6k");
+        assert_eq!(human_readable(2_500_000, false), "2.5M");
+    }
+}
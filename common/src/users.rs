@@ -0,0 +1,139 @@
+use std::ffi::CStr;
+use std::ffi::CString;
+use std::io;
+use std::mem::MaybeUninit;
+use std::os::raw::c_char;
+use std::os::raw::c_int;
+
+/// Buffer size to start with when `sysconf` has no suggestion.
+const DEFAULT_BUFFER: usize = 1024;
+
+/// Calls a reentrant `getpw*_r`/`getgr*_r` lookup, growing the scratch buffer on `ERANGE`, and
+/// picks the wanted fields out of the entry it found.
+fn lookup<T, R>(
+    buffer_hint: c_int,
+    call: impl Fn(*mut T, *mut c_char, usize, *mut *mut T) -> c_int,
+    fields: impl Fn(&T) -> R,
+) -> Option<R> {
+    let hint = unsafe { libc::sysconf(buffer_hint) };
+    let mut size = if hint > 0 {
+        hint as usize
+    } else {
+        DEFAULT_BUFFER
+    };
+    loop {
+        let mut entry = MaybeUninit::<T>::uninit();
+        let mut buffer = vec![0 as c_char; size];
+        let mut result = std::ptr::null_mut();
+        let status = call(entry.as_mut_ptr(), buffer.as_mut_ptr(), size, &mut result);
+        if status == libc::ERANGE {
+            size *= 2;
+            continue;
+        }
+        if status != 0 || result.is_null() {
+            return None;
+        }
+        let entry = unsafe { entry.assume_init() };
+        return Some(fields(&entry));
+    }
+}
+
+fn name(field: *const c_char) -> String {
+    unsafe { CStr::from_ptr(field) }
+        .to_string_lossy()
+        .into_owned()
+}
+
+/// The login name of `uid`, or `None` if the password database has no entry for it.
+pub fn user_name(uid: u32) -> Option<String> {
+    lookup(
+        libc::_SC_GETPW_R_SIZE_MAX,
+        |entry, buffer, size, result| unsafe { libc::getpwuid_r(uid, entry, buffer, size, result) },
+        |entry: &libc::passwd| name(entry.pw_name),
+    )
+}
+
+/// The name of group `gid`, or `None` if the group database has no entry for it.
+pub fn group_name(gid: u32) -> Option<String> {
+    lookup(
+        libc::_SC_GETGR_R_SIZE_MAX,
+        |entry, buffer, size, result| unsafe { libc::getgrgid_r(gid, entry, buffer, size, result) },
+        |entry: &libc::group| name(entry.gr_name),
+    )
+}
+
+/// The uid of user `name` and the gid of their login group.
+pub fn user_ids(name: &str) -> Option<(u32, u32)> {
+    let name = CString::new(name).ok()?;
+    lookup(
+        libc::_SC_GETPW_R_SIZE_MAX,
+        |entry, buffer, size, result| unsafe {
+            libc::getpwnam_r(name.as_ptr(), entry, buffer, size, result)
+        },
+        |entry: &libc::passwd| (entry.pw_uid, entry.pw_gid),
+    )
+}
+
+/// The gid of group `name`.
+pub fn group_id(name: &str) -> Option<u32> {
+    let name = CString::new(name).ok()?;
+    lookup(
+        libc::_SC_GETGR_R_SIZE_MAX,
+        |entry, buffer, size, result| unsafe {
+            libc::getgrnam_r(name.as_ptr(), entry, buffer, size, result)
+        },
+        |entry: &libc::group| entry.gr_gid,
+    )
+}
+
+/// The supplementary group ids of the calling process.
+pub fn groups() -> io::Result<Vec<u32>> {
+    loop {
+        let count = unsafe { libc::getgroups(0, std::ptr::null_mut()) };
+        if count < 0 {
+            return Err(io::Error::last_os_error());
+        }
+        let mut groups = vec![0; count as usize];
+        let filled = unsafe { libc::getgroups(count, groups.as_mut_ptr()) };
+        if filled >= 0 {
+            groups.truncate(filled as usize);
+            return Ok(groups);
+        }
+        // The group list grew between the two calls; ask for its size again.
+        let error = io::Error::last_os_error();
+        if error.raw_os_error() != Some(libc::EINVAL) {
+            return Err(error);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn root_has_a_name() {
+        assert_eq!(user_name(0).as_deref(), Some("root"));
+        assert!(group_name(0).is_some());
+    }
+
+    #[test]
+    fn root_by_name() {
+        assert_eq!(user_ids("root"), Some((0, 0)));
+        assert_eq!(group_id(&group_name(0).unwrap()), Some(0));
+        assert_eq!(user_ids("no such user"), None);
+        assert_eq!(group_id("no\0group"), None);
+    }
+
+    #[test]
+    fn group_list() {
+        let count = unsafe { libc::getgroups(0, std::ptr::null_mut()) };
+        assert_eq!(groups().unwrap().len(), count as usize);
+    }
+
+    #[test]
+    fn unknown_ids() {
+        assert_eq!(user_name(u32::MAX - 1), None);
+        assert_eq!(group_name(u32::MAX - 1), None);
+    }
+}
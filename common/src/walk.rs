@@ -0,0 +1,95 @@
+use crate::report_error;
+use std::fs::Metadata;
+use std::os::unix::fs::MetadataExt;
+use std::path::Path;
+
+/// The directories a recursive walk is inside, by device and inode, so it never enters one of
+/// them again. Only a walk that follows symlinks (or crosses a bind mount of an ancestor) can
+/// meet one of its ancestors below itself, and descending into it would never end.
+#[derive(Default)]
+pub struct Ancestors {
+    dirs: Vec<(u64, u64)>,
+}
+
+impl Ancestors {
+    pub fn new() -> Self {
+        Ancestors::default()
+    }
+
+    /// Records that the walk is entering the directory at `path`. If that directory is already
+    /// being walked, warns that `tool` is not following it and returns false; the caller should
+    /// skip it, and must not call `leave`.
+    pub fn enter(&mut self, tool: &str, path: &Path, metadata: &Metadata) -> bool {
+        let id = (metadata.dev(), metadata.ino());
+        if self.dirs.contains(&id) {
+            report_error(
+                tool,
+                format!(
+                    "warning: not following '{}': directory cycle",
+                    path.display()
+                ),
+            );
+            return false;
+        }
+        self.dirs.push(id);
+        true
+    }
+
+    /// Records that the walk is done with the directory it entered last.
+    pub fn leave(&mut self) {
+        self.dirs.pop();
+    }
+
+    /// Whether `metadata` is on the file system of the first directory the walk entered, for
+    /// `-x`/`--one-file-system`. Before entering anything, everything is.
+    pub fn same_file_system(&self, metadata: &Metadata) -> bool {
+        match self.dirs.first() {
+            Some(&(dev, _)) => dev == metadata.dev(),
+            None => true,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::env;
+    use std::fs;
+    use std::os::unix::fs::symlink;
+    use std::process;
+
+    #[test]
+    fn refuses_to_reenter_an_ancestor() {
+        let dir = env::temp_dir().join(format!("coreutils-common-walk-{}", process::id()));
+        fs::create_dir_all(dir.join("sub")).unwrap();
+        symlink("..", dir.join("sub/up")).unwrap();
+        let mut ancestors = Ancestors::new();
+        assert!(ancestors.enter("test", &dir, &dir.metadata().unwrap()));
+        let sub = dir.join("sub");
+        assert!(ancestors.enter("test", &sub, &sub.metadata().unwrap()));
+        let up = sub.join("up");
+        assert!(!ancestors.enter("test", &up, &up.metadata().unwrap()));
+        // Leaving both frees the directory to be entered again by a later walk.
+        ancestors.leave();
+        ancestors.leave();
+        assert!(ancestors.enter("test", &up, &up.metadata().unwrap()));
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn compares_devices_with_the_first_directory() {
+        let root = Path::new("/").metadata().unwrap();
+        let mut ancestors = Ancestors::new();
+        assert!(ancestors.same_file_system(&root));
+        assert!(ancestors.enter("test", Path::new("/"), &root));
+        let etc = Path::new("/etc").metadata().unwrap();
+        assert_eq!(ancestors.same_file_system(&etc), etc.dev() == root.dev());
+        // /proc is its own file system wherever it's mounted.
+        if let Ok(proc) = Path::new("/proc").metadata() {
+            assert_eq!(ancestors.same_file_system(&proc), proc.dev() == root.dev());
+            if proc.dev() != root.dev() {
+                assert!(!ancestors.same_file_system(&proc));
+            }
+        }
+    }
+}
@@ -19,49 +19,126 @@ impl Display for Arg {
     }
 }
 
-fn arg_of(raw_arg: String, delimited: &mut bool) -> (Arg, Option<String>) {
+/// Splits one raw argument into an `Arg` and any value attached to it (`--name=value`,
+/// `-xvalue`). The option name reuses `raw_arg`'s buffer, so only attached values allocate.
+fn arg_of(mut raw_arg: String, delimited: &mut bool) -> (Arg, Option<String>) {
     if *delimited {
         (Arg::Positional(raw_arg), None)
     } else if raw_arg.starts_with("--") {
         if raw_arg.len() == 2 {
             *delimited = true;
-            (Arg::Positional(raw_arg), None)
-        } else if let Some(i) = raw_arg.find('=') {
-            (
-                Arg::Long(raw_arg[2..i].to_string()),
-                Some(raw_arg[i + 1..].to_string()),
-            )
-        } else {
-            (Arg::Long(raw_arg[2..].to_string()), None)
+            return (Arg::Positional(raw_arg), None);
         }
-    } else if raw_arg.starts_with('-') {
-        if raw_arg.len() == 1 {
-            (Arg::Positional(raw_arg), None)
+        let value = match raw_arg[2..].find('=').filter(|&i| i > 0) {
+            Some(i) => {
+                let value = raw_arg.split_off(2 + i + 1);
+                raw_arg.truncate(2 + i);
+                Some(value)
+            }
+            None => None,
+        };
+        raw_arg.drain(..2);
+        (Arg::Long(raw_arg), value)
+    } else if let Some(name) = raw_arg
+        .strip_prefix('-')
+        .and_then(|short| short.chars().next())
+    {
+        let name_end = 1 + name.len_utf8();
+        let rest = if raw_arg.len() > name_end {
+            Some(raw_arg.split_off(name_end))
         } else {
-            (
-                Arg::Short(raw_arg[1..2].to_string()),
-                if raw_arg.len() > 2 {
-                    Some(raw_arg[2..].to_string())
-                } else {
-                    None
-                },
-            )
-        }
+            None
+        };
+        raw_arg.remove(0);
+        (Arg::Short(raw_arg), rest)
     } else {
         (Arg::Positional(raw_arg), None)
     }
 }
 
-pub fn lex(raw_args: Vec<String>) -> Vec<Arg> {
-    let mut args: Vec<Arg> = vec![];
+fn is_negative_number(raw_arg: &str) -> bool {
+    raw_arg.strip_prefix('-').is_some_and(|number| {
+        number.chars().any(|c| c.is_ascii_digit())
+            && number.chars().all(|c| c.is_ascii_digit() || c == '.')
+    })
+}
+
+/// Splits the rest of a short option cluster, after its first option, into one `Short` per
+/// letter. The first letter in `with_values` takes whatever follows it as its value.
+fn push_cluster(args: &mut Vec<Arg>, cluster: &str, with_values: &str) {
+    for (i, name) in cluster.char_indices() {
+        args.push(Arg::Short(name.to_string()));
+        let value = &cluster[i + name.len_utf8()..];
+        if with_values.contains(name) {
+            if !value.is_empty() {
+                args.push(Arg::Positional(value.to_owned()));
+            }
+            return;
+        }
+    }
+}
+
+fn lex_args(raw_args: Vec<String>, negative_numbers: bool, clusters: Option<&str>) -> Vec<Arg> {
+    let mut args: Vec<Arg> = Vec::with_capacity(raw_args.len());
     let mut delimited = false;
     for raw_arg in raw_args {
+        if negative_numbers && !delimited && is_negative_number(&raw_arg) {
+            args.push(Arg::Positional(raw_arg));
+            continue;
+        }
         let (arg, rest) = arg_of(raw_arg, &mut delimited);
+        let cluster = match (&arg, clusters) {
+            (Arg::Short(name), Some(with_values)) if !with_values.contains(name.as_str()) => {
+                with_values
+            }
+            _ => {
+                args.push(arg);
+                args.extend(rest.map(Arg::Positional));
+                continue;
+            }
+        };
         args.push(arg);
         if let Some(rest) = rest {
-            args.push(Arg::Positional(rest));
+            push_cluster(&mut args, &rest, cluster);
         }
     }
 
     args
 }
+
+pub fn lex(raw_args: Vec<String>) -> Vec<Arg> {
+    lex_args(raw_args, false, None)
+}
+
+/// Like `lex`, but arguments that look like negative numbers (`-5`, `-2.5`) are kept as
+/// positionals instead of being read as short options, for tools that take them as values.
+pub fn lex_with_negatives(raw_args: Vec<String>) -> Vec<Arg> {
+    lex_args(raw_args, true, None)
+}
+
+/// Like `lex`, but a cluster of short options such as `-rf` is split into one `Short` per
+/// letter. The options named in `with_values` take a value, so the rest of a cluster after one
+/// of them is its value: `-qn5` is `-q`, `-n` and `5`.
+pub fn lex_clustered(raw_args: Vec<String>, with_values: &str) -> Vec<Arg> {
+    lex_args(raw_args, false, Some(with_values))
+}
+
+/// `lex_clustered`, keeping negative numbers as positionals like `lex_with_negatives`.
+pub fn lex_clustered_with_negatives(raw_args: Vec<String>, with_values: &str) -> Vec<Arg> {
+    lex_args(raw_args, true, Some(with_values))
+}
+
+/// Lexes one raw argument with `lex_clustered`, for tools that lex each argument as they reach
+/// it so that they can tell `--opt=VALUE` apart from `--opt FILE`. Returns the options (or the
+/// positional) it holds, and the value attached to the last option, if there is one.
+pub fn lex_one(raw_arg: String, with_values: &str) -> (Vec<Arg>, Option<String>) {
+    let mut lexed = lex_clustered(vec![raw_arg], with_values);
+    let attached = match lexed.last() {
+        Some(Arg::Positional(_)) if lexed.len() > 1 => match lexed.pop() {
+            Some(Arg::Positional(value)) => Some(value),
+            _ => None,
+        },
+        _ => None,
+    };
+    (lexed, attached)
+}
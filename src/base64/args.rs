@@ -0,0 +1,119 @@
+use crate::Base64Error;
+use arglex::lex;
+use arglex::Arg;
+use coreutils_common::print_and_exit;
+use coreutils_common::unknown_arg_message;
+use coreutils_common::version_string;
+use std::slice::Iter;
+
+const HELP: &str = "
+Usage: base64 [OPTION]... [FILE]
+Base64 encode or decode FILE, or standard input, to standard output.
+
+With no FILE, or when FILE is -, read standard input.
+
+Options:
+  -d, --decode              Decode data
+  -i, --ignore-garbage      When decoding, ignore non-alphabet characters
+  -w, --wrap <COLS>         Wrap encoded lines after COLS characters (default 76).
+                            Use 0 to disable line wrapping
+  --version                 Output version information and exit
+  --help                    Display this help and exit
+";
+
+fn print_help() -> ! {
+    print_and_exit(0, HELP);
+}
+
+fn print_version() -> ! {
+    print_and_exit(0, version_string!("base64"));
+}
+
+pub struct Args {
+    pub decode: bool,
+    pub ignore_garbage: bool,
+    pub wrap: usize,
+    pub file: String,
+}
+
+impl Args {
+    fn new() -> Self {
+        Args {
+            decode: false,
+            ignore_garbage: false,
+            wrap: 76,
+            file: "-".to_owned(),
+        }
+    }
+}
+
+fn get_arg_to(args: &mut Iter<Arg>, name: &Arg) -> Result<String, Base64Error> {
+    match args.next() {
+        Some(Arg::Positional(value)) => Ok(value.clone()),
+        _ => Err(format!("{} needs an argument", name).into()),
+    }
+}
+
+fn parse_wrap(cols: &str) -> Result<usize, Base64Error> {
+    cols.parse()
+        .map_err(|_| format!("invalid wrap size: '{}'", cols).into())
+}
+
+pub fn parse(args: Vec<String>) -> Result<Args, Base64Error> {
+    let args = lex(args);
+
+    let mut args = args.iter();
+    let mut arg_struct = Args::new();
+    let mut files = vec![];
+    while let Some(arg) = args.next() {
+        match arg {
+            Arg::Positional(positional) => match positional.as_str() {
+                "--" => continue,
+                _ => files.push(positional.clone()),
+            },
+            Arg::Short(short) => match short.as_str() {
+                "d" => arg_struct.decode = true,
+                "i" => arg_struct.ignore_garbage = true,
+                "w" => arg_struct.wrap = parse_wrap(&get_arg_to(&mut args, arg)?)?,
+                _ => return Err(unknown_arg_message(arg).into()),
+            },
+            Arg::Long(long) => match long.as_str() {
+                "decode" => arg_struct.decode = true,
+                "ignore-garbage" => arg_struct.ignore_garbage = true,
+                "wrap" => arg_struct.wrap = parse_wrap(&get_arg_to(&mut args, arg)?)?,
+                "version" => print_version(),
+                "help" => print_help(),
+                _ => return Err(unknown_arg_message(arg).into()),
+            },
+        };
+    }
+    match files.len() {
+        0 => {}
+        1 => arg_struct.file = files.remove(0),
+        _ => return Err(format!("extra operand '{}'", files[1]).into()),
+    }
+    Ok(arg_struct)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse_strs(args: &[&str]) -> Result<Args, Base64Error> {
+        parse(args.iter().map(|s| s.to_string()).collect())
+    }
+
+    #[test]
+    fn wrap() {
+        assert_eq!(parse_strs(&[]).unwrap().wrap, 76);
+        assert_eq!(parse_strs(&["-w", "0"]).unwrap().wrap, 0);
+        assert_eq!(parse_strs(&["--wrap=10"]).unwrap().wrap, 10);
+        assert!(parse_strs(&["-w", "-1"]).is_err());
+    }
+
+    #[test]
+    fn one_file() {
+        assert_eq!(parse_strs(&["-d", "f"]).unwrap().file, "f");
+        assert!(parse_strs(&["a", "b"]).is_err());
+    }
+}
@@ -0,0 +1,217 @@
+const ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+/// Encodes a stream in pieces, wrapping the output every `wrap` characters (never if 0).
+pub struct Encoder {
+    wrap: usize,
+    column: usize,
+    pending: Vec<u8>,
+}
+
+impl Encoder {
+    pub fn new(wrap: usize) -> Self {
+        Encoder {
+            wrap,
+            column: 0,
+            pending: Vec::with_capacity(3),
+        }
+    }
+
+    fn emit(&mut self, c: u8, out: &mut Vec<u8>) {
+        if self.wrap != 0 && self.column == self.wrap {
+            out.push(b'\n');
+            self.column = 0;
+        }
+        out.push(c);
+        self.column += 1;
+    }
+
+    fn encode_group(&mut self, group: &[u8], out: &mut Vec<u8>) {
+        let bits = group.iter().enumerate().fold(0u32, |bits, (i, &byte)| {
+            bits | u32::from(byte) << (16 - 8 * i)
+        });
+        for i in 0..4 {
+            let c = if i <= group.len() {
+                ALPHABET[(bits >> (18 - 6 * i) & 0x3f) as usize]
+            } else {
+                b'='
+            };
+            self.emit(c, out);
+        }
+    }
+
+    pub fn feed(&mut self, mut data: &[u8], out: &mut Vec<u8>) {
+        if !self.pending.is_empty() {
+            let needed = (3 - self.pending.len()).min(data.len());
+            self.pending.extend_from_slice(&data[..needed]);
+            data = &data[needed..];
+            if self.pending.len() < 3 {
+                return;
+            }
+            let group = std::mem::take(&mut self.pending);
+            self.encode_group(&group, out);
+        }
+        let mut groups = data.chunks_exact(3);
+        for group in &mut groups {
+            self.encode_group(group, out);
+        }
+        self.pending.extend_from_slice(groups.remainder());
+    }
+
+    /// Pads out the last group and ends the final line.
+    pub fn finish(mut self, out: &mut Vec<u8>) {
+        if !self.pending.is_empty() {
+            let group = std::mem::take(&mut self.pending);
+            self.encode_group(&group, out);
+        }
+        if self.wrap != 0 && self.column > 0 {
+            out.push(b'\n');
+        }
+    }
+}
+
+fn value(c: u8) -> Option<u8> {
+    match c {
+        b'A'..=b'Z' => Some(c - b'A'),
+        b'a'..=b'z' => Some(c - b'a' + 26),
+        b'0'..=b'9' => Some(c - b'0' + 52),
+        b'+' => Some(62),
+        b'/' => Some(63),
+        _ => None,
+    }
+}
+
+/// Input `Decoder` can't make sense of.
+#[derive(Debug, PartialEq)]
+pub struct InvalidInput;
+
+/// Decodes a stream in pieces. Newlines are always skipped; other characters outside the
+/// alphabet are errors unless garbage is ignored.
+pub struct Decoder {
+    ignore_garbage: bool,
+    quad: [u8; 4],
+    length: usize,
+    padding: usize,
+}
+
+impl Decoder {
+    pub fn new(ignore_garbage: bool) -> Self {
+        Decoder {
+            ignore_garbage,
+            quad: [0; 4],
+            length: 0,
+            padding: 0,
+        }
+    }
+
+    fn flush(&mut self, out: &mut Vec<u8>) {
+        let bits = self.quad[..self.length]
+            .iter()
+            .enumerate()
+            .fold(0u32, |bits, (i, &v)| bits | u32::from(v) << (18 - 6 * i));
+        for i in 0..self.length - 1 {
+            out.push((bits >> (16 - 8 * i)) as u8);
+        }
+    }
+
+    pub fn feed(&mut self, data: &[u8], out: &mut Vec<u8>) -> Result<(), InvalidInput> {
+        for &c in data {
+            if c == b'=' {
+                // Padding only completes a group of two or three characters.
+                if self.length < 2 || self.length + self.padding == 4 {
+                    return Err(InvalidInput);
+                }
+                self.padding += 1;
+                if self.length + self.padding == 4 {
+                    self.flush(out);
+                }
+                continue;
+            }
+            let v = match value(c) {
+                Some(v) => v,
+                None if c == b'\n' || self.ignore_garbage => continue,
+                None => return Err(InvalidInput),
+            };
+            if self.padding > 0 {
+                if self.length + self.padding < 4 {
+                    return Err(InvalidInput);
+                }
+                self.length = 0;
+                self.padding = 0;
+            }
+            self.quad[self.length] = v;
+            self.length += 1;
+            if self.length == 4 {
+                self.flush(out);
+                self.length = 0;
+            }
+        }
+        Ok(())
+    }
+
+    /// Fails if the input stopped partway through a group.
+    pub fn finish(self) -> Result<(), InvalidInput> {
+        if self.length == 0 || self.length + self.padding == 4 {
+            Ok(())
+        } else {
+            Err(InvalidInput)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn encode(data: &[u8], wrap: usize) -> String {
+        let mut out = vec![];
+        let mut encoder = Encoder::new(wrap);
+        for piece in data.chunks(2) {
+            encoder.feed(piece, &mut out);
+        }
+        encoder.finish(&mut out);
+        String::from_utf8(out).unwrap()
+    }
+
+    fn decode(data: &str, ignore_garbage: bool) -> Result<Vec<u8>, InvalidInput> {
+        let mut out = vec![];
+        let mut decoder = Decoder::new(ignore_garbage);
+        for piece in data.as_bytes().chunks(3) {
+            decoder.feed(piece, &mut out)?;
+        }
+        decoder.finish()?;
+        Ok(out)
+    }
+
+    #[test]
+    fn padding() {
+        assert_eq!(encode(b"", 76), "");
+        assert_eq!(encode(b"f", 0), "Zg==");
+        assert_eq!(encode(b"fo", 0), "Zm8=");
+        assert_eq!(encode(b"foo", 0), "Zm9v");
+        assert_eq!(encode(b"foobar", 76), "Zm9vYmFy\n");
+    }
+
+    #[test]
+    fn wrapping() {
+        assert_eq!(encode(b"foobar", 4), "Zm9v\nYmFy\n");
+        assert_eq!(encode(b"foobar", 5), "Zm9vY\nmFy\n");
+    }
+
+    #[test]
+    fn round_trip() {
+        let data: Vec<u8> = (0..=255).collect();
+        assert_eq!(decode(&encode(&data, 10), false).unwrap(), data);
+    }
+
+    #[test]
+    fn decoding() {
+        assert_eq!(decode("Zm8=", false).unwrap(), b"fo");
+        assert_eq!(decode("Zg==Zm8=\n", false).unwrap(), b"ffo");
+        assert_eq!(decode("Zm9v\nYmFy\n", false).unwrap(), b"foobar");
+        assert_eq!(decode("Zm9v*YmFy", false), Err(InvalidInput));
+        assert_eq!(decode("Zm9v*Ym Fy", true).unwrap(), b"foobar");
+        assert_eq!(decode("Zm9", false), Err(InvalidInput));
+        assert_eq!(decode("Z===", false), Err(InvalidInput));
+        assert_eq!(decode("Zg=Zm8=", false), Err(InvalidInput));
+    }
+}
@@ -0,0 +1,117 @@
+mod args;
+mod codec;
+
+use codec::Decoder;
+use codec::Encoder;
+use codec::InvalidInput;
+use coreutils_common::exit_with;
+use coreutils_common::CoreError;
+use std::env;
+use std::fmt;
+use std::fmt::Debug;
+use std::fs::File;
+use std::io;
+use std::io::ErrorKind;
+use std::io::Read;
+use std::io::Write;
+
+pub struct Base64Error {
+    message: String,
+}
+
+impl Debug for Base64Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "base64: {}", self.message)
+    }
+}
+
+impl CoreError for Base64Error {}
+
+impl<T> From<T> for Base64Error
+where
+    T: ToString,
+{
+    fn from(error: T) -> Self {
+        Base64Error {
+            message: error.to_string(),
+        }
+    }
+}
+
+enum Codec {
+    Encode(Encoder),
+    Decode(Decoder),
+}
+
+impl Codec {
+    fn feed(&mut self, data: &[u8], out: &mut Vec<u8>) -> Result<(), InvalidInput> {
+        match self {
+            Codec::Encode(encoder) => {
+                encoder.feed(data, out);
+                Ok(())
+            }
+            Codec::Decode(decoder) => decoder.feed(data, out),
+        }
+    }
+
+    fn finish(self, out: &mut Vec<u8>) -> Result<(), InvalidInput> {
+        match self {
+            Codec::Encode(encoder) => {
+                encoder.finish(out);
+                Ok(())
+            }
+            Codec::Decode(decoder) => decoder.finish(),
+        }
+    }
+}
+
+/// Streams `input` through `codec`. Whatever was converted before invalid input is still
+/// written out.
+fn pipe(input: &mut dyn Read, out: &mut dyn Write, mut codec: Codec) -> Result<(), Base64Error> {
+    let mut buffer = [0; 8192];
+    let mut converted = Vec::with_capacity(buffer.len() * 2);
+    loop {
+        converted.clear();
+        let result = match input.read(&mut buffer) {
+            Ok(0) => {
+                let result = codec.finish(&mut converted);
+                out.write_all(&converted)?;
+                return result.map_err(|_| "invalid input".into());
+            }
+            Ok(n) => codec.feed(&buffer[..n], &mut converted),
+            Err(e) if e.kind() == ErrorKind::Interrupted => continue,
+            Err(e) => return Err(e.into()),
+        };
+        out.write_all(&converted)?;
+        if result.is_err() {
+            return Err("invalid input".into());
+        }
+    }
+}
+
+fn main() {
+    if let Err(e) = run() {
+        exit_with(&e);
+    }
+}
+
+fn run() -> Result<(), Base64Error> {
+    let args = args::parse(env::args().skip(1).collect())?;
+    let stdin = io::stdin();
+    let mut input: Box<dyn Read> = if args.file == "-" {
+        Box::new(stdin.lock())
+    } else {
+        Box::new(File::open(&args.file).map_err(|e| format!("{}: {}", args.file, e))?)
+    };
+    let stdout = io::stdout();
+    let mut out = io::BufWriter::new(stdout.lock());
+
+    let codec = if args.decode {
+        Codec::Decode(Decoder::new(args.ignore_garbage))
+    } else {
+        Codec::Encode(Encoder::new(args.wrap))
+    };
+    let result = pipe(&mut input, &mut out, codec);
+    out.flush()?;
+    result
+}
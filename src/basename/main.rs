@@ -0,0 +1,159 @@
+use arglex::lex;
+use arglex::Arg;
+use coreutils_common::die;
+use coreutils_common::output::Terminator;
+use coreutils_common::paths::strip_trailing_slashes;
+use coreutils_common::print_and_exit;
+use coreutils_common::unknown_arg_message;
+use coreutils_common::version_string;
+use std::env;
+use std::io;
+use std::io::Write;
+use std::slice::Iter;
+
+const HELP: &str = "
+Usage: basename NAME [SUFFIX]
+  or:  basename OPTION... NAME...
+Print NAME with any leading directory components removed.
+If specified, also remove a trailing SUFFIX, unless it is all that's left of NAME.
+
+Options:
+  -a, --multiple            Support multiple arguments and treat each as a NAME
+  -s, --suffix <SUFFIX>     Remove a trailing SUFFIX; implies -a
+  -z, --zero                End each output line with NUL, not newline
+  --version                 Output version information and exit
+  --help                    Display this help and exit
+";
+
+struct Args {
+    multiple: bool,
+    suffix: Option<String>,
+    terminator: Terminator,
+    names: Vec<String>,
+}
+
+fn fail(message: impl AsRef<str>) -> ! {
+    die(format!("basename: {}", message.as_ref()));
+}
+
+fn get_arg_to(args: &mut Iter<Arg>, name: &Arg) -> String {
+    match args.next() {
+        Some(Arg::Positional(value)) => value.clone(),
+        _ => fail(format!("{} needs an argument", name)),
+    }
+}
+
+fn parse(args: Vec<String>) -> Args {
+    let args = lex(args);
+
+    let mut args = args.iter();
+    let mut arg_struct = Args {
+        multiple: false,
+        suffix: None,
+        terminator: Terminator::Newline,
+        names: vec![],
+    };
+    let mut delimited = false;
+    while let Some(arg) = args.next() {
+        if delimited {
+            arg_struct.names.push(arg.to_string());
+            continue;
+        }
+        match arg {
+            Arg::Positional(positional) => match positional.as_str() {
+                "--" => delimited = true,
+                _ => arg_struct.names.push(positional.clone()),
+            },
+            Arg::Short(short) => match short.as_str() {
+                "a" => arg_struct.multiple = true,
+                "s" => arg_struct.suffix = Some(get_arg_to(&mut args, arg)),
+                "z" => arg_struct.terminator = Terminator::Nul,
+                _ => fail(unknown_arg_message(arg)),
+            },
+            Arg::Long(long) => match long.as_str() {
+                "multiple" => arg_struct.multiple = true,
+                "suffix" => arg_struct.suffix = Some(get_arg_to(&mut args, arg)),
+                "zero" => arg_struct.terminator = Terminator::Nul,
+                "version" => print_and_exit(0, version_string!("basename")),
+                "help" => print_and_exit(0, HELP),
+                _ => fail(unknown_arg_message(arg)),
+            },
+        }
+    }
+    if arg_struct.suffix.is_some() {
+        arg_struct.multiple = true;
+    }
+    arg_struct
+}
+
+/// The last component of `name` after trailing slashes are removed, minus `suffix` unless the
+/// suffix is the whole component.
+fn basename(name: &str, suffix: Option<&str>) -> String {
+    let trimmed = strip_trailing_slashes(name);
+    if trimmed == "/" {
+        return trimmed.to_owned();
+    }
+    let base = match trimmed.rfind('/') {
+        Some(i) => &trimmed[i + 1..],
+        None => trimmed,
+    };
+    match suffix {
+        Some(suffix) if !suffix.is_empty() && base != suffix => {
+            base.strip_suffix(suffix).unwrap_or(base).to_owned()
+        }
+        _ => base.to_owned(),
+    }
+}
+
+fn main() {
+    let args = parse(env::args().skip(1).collect());
+    let (names, suffix) = if args.multiple {
+        (&args.names[..], args.suffix.as_deref())
+    } else {
+        match args.names.as_slice() {
+            [] => fail("missing operand"),
+            [_] => (&args.names[..1], None),
+            [_, suffix] => (&args.names[..1], Some(suffix.as_str())),
+            [_, _, extra, ..] => fail(format!("extra operand {}", extra)),
+        }
+    };
+    if names.is_empty() {
+        fail("missing operand");
+    }
+    let mut output = String::new();
+    for name in names {
+        output.push_str(&basename(name, suffix));
+        output.push(args.terminator.as_char());
+    }
+    if let Err(e) = io::stdout().write_all(output.as_bytes()) {
+        fail(format!("write error: {}", e));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn strips_directories() {
+        assert_eq!(basename("/usr/bin/sort", None), "sort");
+        assert_eq!(basename("sort", None), "sort");
+    }
+
+    #[test]
+    fn strips_trailing_slashes() {
+        assert_eq!(basename("/usr/bin/", None), "bin");
+        assert_eq!(basename("usr//", None), "usr");
+        assert_eq!(basename("/", None), "/");
+        assert_eq!(basename("///", None), "/");
+        assert_eq!(basename("", None), "");
+    }
+
+    #[test]
+    fn strips_suffix_unless_whole_name() {
+        assert_eq!(basename("include/stdio.h", Some(".h")), "stdio");
+        assert_eq!(basename("/tmp/.h", Some(".h")), ".h");
+        assert_eq!(basename("a.txt/", Some(".txt")), "a");
+        assert_eq!(basename("a.txt", Some(".c")), "a.txt");
+    }
+}
@@ -0,0 +1,117 @@
+use crate::CalError;
+use arglex::lex;
+use arglex::Arg;
+use coreutils_common::print_and_exit;
+use coreutils_common::unknown_arg_message;
+use coreutils_common::version_string;
+
+const HELP: &str = "
+Usage: cal [OPTION]... [[MONTH] YEAR]
+Display a calendar of the current month, of MONTH of YEAR, or of all of YEAR.
+
+Options:
+  -m, --monday              Start weeks on Monday instead of Sunday
+  -y, --year                Display the whole year
+  --version                 Output version information and exit
+  --help                    Display this help and exit
+
+The Gregorian calendar is used for every year from 1 to 9999.
+";
+
+fn print_help() -> ! {
+    print_and_exit(0, HELP);
+}
+
+fn print_version() -> ! {
+    print_and_exit(0, version_string!("cal"));
+}
+
+pub struct Args {
+    pub month: Option<u32>,
+    pub year: Option<i32>,
+    pub whole_year: bool,
+    pub monday_first: bool,
+}
+
+fn parse_month(month: &str) -> Result<u32, CalError> {
+    match month.parse() {
+        Ok(month) if (1..=12).contains(&month) => Ok(month),
+        _ => Err(format!("illegal month value: use 1-12, not '{}'", month).into()),
+    }
+}
+
+fn parse_year(year: &str) -> Result<i32, CalError> {
+    match year.parse() {
+        Ok(year) if (1..=9999).contains(&year) => Ok(year),
+        _ => Err(format!("illegal year value: use 1-9999, not '{}'", year).into()),
+    }
+}
+
+pub fn parse(args: Vec<String>) -> Result<Args, CalError> {
+    let mut whole_year = false;
+    let mut monday_first = false;
+    let mut operands = vec![];
+    for arg in &lex(args) {
+        match arg {
+            Arg::Positional(positional) => match positional.as_str() {
+                "--" => continue,
+                _ => operands.push(positional.clone()),
+            },
+            Arg::Short(short) => match short.as_str() {
+                "m" => monday_first = true,
+                "y" => whole_year = true,
+                _ => return Err(unknown_arg_message(arg).into()),
+            },
+            Arg::Long(long) => match long.as_str() {
+                "monday" => monday_first = true,
+                "year" => whole_year = true,
+                "version" => print_version(),
+                "help" => print_help(),
+                _ => return Err(unknown_arg_message(arg).into()),
+            },
+        };
+    }
+    let (month, year) = match operands.as_slice() {
+        [] => (None, None),
+        [year] => (None, Some(parse_year(year)?)),
+        [month, year] => (Some(parse_month(month)?), Some(parse_year(year)?)),
+        [_, _, extra, ..] => return Err(format!("extra operand '{}'", extra).into()),
+    };
+    if whole_year && month.is_some() {
+        return Err("-y cannot be used with a MONTH".into());
+    }
+    Ok(Args {
+        // A lone YEAR means the whole of it.
+        whole_year: whole_year || (year.is_some() && month.is_none()),
+        month,
+        year,
+        monday_first,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse_strs(args: &[&str]) -> Result<Args, CalError> {
+        parse(args.iter().map(|s| s.to_string()).collect())
+    }
+
+    #[test]
+    fn operands() {
+        let args = parse_strs(&["2", "2000"]).unwrap();
+        assert_eq!(
+            (args.month, args.year, args.whole_year),
+            (Some(2), Some(2000), false)
+        );
+        let args = parse_strs(&["1999"]).unwrap();
+        assert_eq!(
+            (args.month, args.year, args.whole_year),
+            (None, Some(1999), true)
+        );
+        assert!(parse_strs(&["13", "2000"]).is_err());
+        assert!(parse_strs(&["0"]).is_err());
+        assert!(parse_strs(&["-y", "1", "2000"]).is_err());
+        assert!(parse_strs(&["1", "2", "3"]).is_err());
+    }
+}
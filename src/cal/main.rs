@@ -0,0 +1,159 @@
+mod args;
+
+use chrono::Datelike;
+use chrono::Local;
+use chrono::NaiveDate;
+use coreutils_common::exit_with;
+use coreutils_common::CoreError;
+use std::env;
+use std::fmt;
+use std::fmt::Debug;
+
+/// Each month is laid out in a block this many columns wide.
+const WIDTH: usize = 20;
+/// Enough week rows for any month, so blocks printed side by side line up.
+const WEEKS: usize = 6;
+const MONTH_NAMES: [&str; 12] = [
+    "January",
+    "February",
+    "March",
+    "April",
+    "May",
+    "June",
+    "July",
+    "August",
+    "September",
+    "October",
+    "November",
+    "December",
+];
+
+pub struct CalError {
+    message: String,
+}
+
+impl Debug for CalError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "cal: {}", self.message)
+    }
+}
+
+impl CoreError for CalError {}
+
+impl<T> From<T> for CalError
+where
+    T: ToString,
+{
+    fn from(error: T) -> Self {
+        CalError {
+            message: error.to_string(),
+        }
+    }
+}
+
+fn center(text: &str, width: usize) -> String {
+    let left = width.saturating_sub(text.len()) / 2;
+    format!(
+        "{:left$}{:<right$}",
+        "",
+        text,
+        left = left,
+        right = width - left
+    )
+}
+
+fn days_in_month(year: i32, month: u32) -> u32 {
+    let next = if month == 12 {
+        NaiveDate::from_ymd(year + 1, 1, 1)
+    } else {
+        NaiveDate::from_ymd(year, month + 1, 1)
+    };
+    next.pred().day()
+}
+
+/// The title, weekday header and week rows of one month, each padded to `WIDTH`.
+fn month_block(year: i32, month: u32, title: &str, monday_first: bool) -> Vec<String> {
+    let header = if monday_first {
+        "Mo Tu We Th Fr Sa Su"
+    } else {
+        "Su Mo Tu We Th Fr Sa"
+    };
+    let first = NaiveDate::from_ymd(year, month, 1).weekday();
+    let offset = if monday_first {
+        first.num_days_from_monday()
+    } else {
+        first.num_days_from_sunday()
+    } as usize;
+
+    let mut cells = vec!["  ".to_owned(); offset];
+    cells.extend((1..=days_in_month(year, month)).map(|day| format!("{:>2}", day)));
+    let mut block = vec![center(title, WIDTH), header.to_owned()];
+    block.extend(
+        cells
+            .chunks(7)
+            .map(|week| format!("{:<width$}", week.join(" "), width = WIDTH)),
+    );
+    block
+}
+
+fn print_month(year: i32, month: u32, monday_first: bool) {
+    let title = format!("{} {}", MONTH_NAMES[month as usize - 1], year);
+    for line in month_block(year, month, &title, monday_first) {
+        println!("{}", line.trim_end());
+    }
+}
+
+fn print_year(year: i32, monday_first: bool) {
+    let row_width = WIDTH * 3 + 2 * 2;
+    println!("{}", center(&year.to_string(), row_width).trim_end());
+    for quarter in 0..4 {
+        println!();
+        let blocks: Vec<Vec<String>> = (1..=3)
+            .map(|i| {
+                let month = quarter * 3 + i;
+                let title = MONTH_NAMES[month as usize - 1];
+                let mut block = month_block(year, month, title, monday_first);
+                block.resize(WEEKS + 2, " ".repeat(WIDTH));
+                block
+            })
+            .collect();
+        for line in 0..WEEKS + 2 {
+            let row: Vec<&str> = blocks.iter().map(|block| block[line].as_str()).collect();
+            println!("{}", row.join("  ").trim_end());
+        }
+    }
+}
+
+fn main() {
+    if let Err(e) = run() {
+        exit_with(&e);
+    }
+}
+
+fn run() -> Result<(), CalError> {
+    let args = args::parse(env::args().skip(1).collect())?;
+    let today = Local::today();
+    let year = args.year.unwrap_or_else(|| today.year());
+    if args.whole_year {
+        print_year(year, args.monday_first);
+    } else {
+        print_month(
+            year,
+            args.month.unwrap_or_else(|| today.month()),
+            args.monday_first,
+        );
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn month_lengths() {
+        assert_eq!(days_in_month(2000, 2), 29);
+        assert_eq!(days_in_month(1900, 2), 28);
+        assert_eq!(days_in_month(1999, 12), 31);
+    }
+}
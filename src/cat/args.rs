@@ -0,0 +1,112 @@
+use crate::CatError;
+use arglex::lex;
+use arglex::Arg;
+use coreutils_common::print_and_exit;
+use coreutils_common::unknown_arg_message;
+use coreutils_common::version_string;
+
+const HELP: &str = "
+Usage: cat [option]... [FILE]...
+Concatenate FILE(s) to standard output.
+
+With no FILE, or when FILE is -, read standard input.
+
+Options:
+  -A, --show-all            Equivalent to -vET
+  -b, --number-nonblank     Number nonempty output lines, overrides -n
+  -e                        Equivalent to -vE
+  -E, --show-ends           Display $ at the end of each line
+  -n, --number              Number all output lines
+  -s, --squeeze-blank       Suppress repeated empty output lines
+  -t                        Equivalent to -vT
+  -T, --show-tabs           Display TAB characters as ^I
+  -v, --show-nonprinting    Use ^ and M- notation, except for LFD and TAB
+  --version                 Output version information and exit
+  --help                    Display this help and exit
+";
+
+fn print_help() -> ! {
+    print_and_exit(0, HELP);
+}
+
+fn print_version() -> ! {
+    print_and_exit(0, version_string!("cat"));
+}
+
+pub struct Args {
+    pub number: bool,
+    pub number_nonblank: bool,
+    pub show_ends: bool,
+    pub show_nonprinting: bool,
+    pub show_tabs: bool,
+    pub squeeze_blank: bool,
+    pub files: Vec<String>,
+}
+
+impl Args {
+    fn new() -> Self {
+        Args {
+            number: false,
+            number_nonblank: false,
+            show_ends: false,
+            show_nonprinting: false,
+            show_tabs: false,
+            squeeze_blank: false,
+            files: vec![],
+        }
+    }
+}
+
+pub fn parse(args: Vec<String>) -> Result<Args, CatError> {
+    let mut arg_struct = Args::new();
+    for arg in &lex(args) {
+        match arg {
+            Arg::Positional(positional) => match positional.as_str() {
+                "--" => continue,
+                _ => arg_struct.files.push(positional.clone()),
+            },
+            Arg::Short(short) => match short.as_str() {
+                "A" => {
+                    arg_struct.show_nonprinting = true;
+                    arg_struct.show_ends = true;
+                    arg_struct.show_tabs = true;
+                }
+                "b" => arg_struct.number_nonblank = true,
+                "e" => {
+                    arg_struct.show_nonprinting = true;
+                    arg_struct.show_ends = true;
+                }
+                "E" => arg_struct.show_ends = true,
+                "n" => arg_struct.number = true,
+                "s" => arg_struct.squeeze_blank = true,
+                "t" => {
+                    arg_struct.show_nonprinting = true;
+                    arg_struct.show_tabs = true;
+                }
+                "T" => arg_struct.show_tabs = true,
+                "v" => arg_struct.show_nonprinting = true,
+                _ => return Err(unknown_arg_message(arg).into()),
+            },
+            Arg::Long(long) => match long.as_str() {
+                "show-all" => {
+                    arg_struct.show_nonprinting = true;
+                    arg_struct.show_ends = true;
+                    arg_struct.show_tabs = true;
+                }
+                "number-nonblank" => arg_struct.number_nonblank = true,
+                "show-ends" => arg_struct.show_ends = true,
+                "number" => arg_struct.number = true,
+                "squeeze-blank" => arg_struct.squeeze_blank = true,
+                "show-tabs" => arg_struct.show_tabs = true,
+                "show-nonprinting" => arg_struct.show_nonprinting = true,
+                "version" => print_version(),
+                "help" => print_help(),
+                _ => return Err(unknown_arg_message(arg).into()),
+            },
+        };
+    }
+    if arg_struct.files.is_empty() {
+        arg_struct.files.push("-".to_owned());
+    }
+    Ok(arg_struct)
+}
@@ -0,0 +1,166 @@
+mod args;
+
+use args::Args;
+use coreutils_common::exit_with;
+use coreutils_common::input::lines;
+use coreutils_common::input::open_input;
+use coreutils_common::report_error;
+use coreutils_common::CoreError;
+use std::env;
+use std::fmt;
+use std::fmt::Debug;
+use std::io;
+use std::io::BufRead;
+use std::io::BufWriter;
+use std::io::Write;
+use std::process::exit;
+
+pub struct CatError {
+    message: String,
+}
+
+impl Debug for CatError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "cat: {}", self.message)
+    }
+}
+
+impl CoreError for CatError {}
+
+impl<T> From<T> for CatError
+where
+    T: ToString,
+{
+    fn from(error: T) -> Self {
+        CatError {
+            message: error.to_string(),
+        }
+    }
+}
+
+/// Writes `content` with tabs as `^I` if `show_tabs`, and other control characters in `^X`
+/// and `M-` notation if `show_nonprinting`.
+fn write_visible(
+    content: &[u8],
+    show_nonprinting: bool,
+    show_tabs: bool,
+    out: &mut dyn Write,
+) -> io::Result<()> {
+    if !show_nonprinting && !show_tabs {
+        return out.write_all(content);
+    }
+    let mut visible = Vec::with_capacity(content.len());
+    for &byte in content {
+        if byte == b'\t' {
+            if show_tabs {
+                visible.extend_from_slice(b"^I");
+            } else {
+                visible.push(byte);
+            }
+            continue;
+        }
+        if !show_nonprinting {
+            visible.push(byte);
+            continue;
+        }
+        let low = if byte >= 128 {
+            visible.extend_from_slice(b"M-");
+            byte - 128
+        } else {
+            byte
+        };
+        match low {
+            0..=31 => visible.extend_from_slice(&[b'^', low + 64]),
+            127 => visible.extend_from_slice(b"^?"),
+            _ => visible.push(low),
+        }
+    }
+    out.write_all(&visible)
+}
+
+/// Output state that carries over from one file to the next, like GNU cat's line numbers.
+struct Cat<'a> {
+    args: &'a Args,
+    line_number: usize,
+    last_was_blank: bool,
+}
+
+impl<'a> Cat<'a> {
+    fn new(args: &'a Args) -> Self {
+        Cat {
+            args,
+            line_number: 1,
+            last_was_blank: false,
+        }
+    }
+
+    fn copy(&mut self, input: &mut dyn BufRead, out: &mut dyn Write) -> io::Result<()> {
+        for line in lines(input) {
+            let line = line?;
+            let blank = line == b"\n";
+            if self.args.squeeze_blank && blank && self.last_was_blank {
+                continue;
+            }
+            self.last_was_blank = blank;
+
+            let numbered = if self.args.number_nonblank {
+                !blank
+            } else {
+                self.args.number
+            };
+            if numbered {
+                write!(out, "{:>6}\t", self.line_number)?;
+                self.line_number += 1;
+            }
+
+            let (content, newline) = match line.split_last() {
+                Some((b'\n', content)) => (content, true),
+                _ => (&line[..], false),
+            };
+            write_visible(
+                content,
+                self.args.show_nonprinting,
+                self.args.show_tabs,
+                out,
+            )?;
+            if newline {
+                if self.args.show_ends {
+                    out.write_all(b"$")?;
+                }
+                out.write_all(b"\n")?;
+            }
+        }
+        Ok(())
+    }
+}
+
+fn main() {
+    if let Err(e) = run() {
+        exit_with(&e);
+    }
+}
+
+fn run() -> Result<(), CatError> {
+    let args = args::parse(env::args().skip(1).collect())?;
+    let stdout = io::stdout();
+    let mut out = BufWriter::new(stdout.lock());
+    let mut cat = Cat::new(&args);
+    let mut failed = false;
+    for file in &args.files {
+        let result = match open_input(file) {
+            Ok(mut input) => cat.copy(&mut input, &mut out),
+            Err(e) => {
+                out.flush()?;
+                report_error("cat", format!("{}: {}", file, e));
+                failed = true;
+                continue;
+            }
+        };
+        result.map_err(|e| format!("{}: {}", file, e))?;
+    }
+    out.flush()?;
+    if failed {
+        exit(1);
+    }
+    Ok(())
+}
@@ -0,0 +1,120 @@
+use crate::ChmodError;
+use arglex::lex;
+use arglex::Arg;
+use coreutils_common::mode::Mode;
+use coreutils_common::print_and_exit;
+use coreutils_common::unknown_arg_message;
+use coreutils_common::version_string;
+
+const HELP: &str = "
+Usage: chmod [OPTION]... MODE[,MODE]... FILE...
+  or:  chmod [OPTION]... OCTAL-MODE FILE...
+Change the mode of each FILE to MODE.
+
+Options:
+  -R, --recursive           Change files and directories recursively
+  -v, --verbose             Output a diagnostic for every file processed
+  --version                 Output version information and exit
+  --help                    Display this help and exit
+
+Each MODE is of the form '[ugoa]*([-+=]([rwxXst]*|[ugo]))+' or an octal number.
+";
+
+fn print_help() -> ! {
+    print_and_exit(0, HELP);
+}
+
+fn print_version() -> ! {
+    print_and_exit(0, version_string!("chmod"));
+}
+
+pub struct Args {
+    pub recursive: bool,
+    pub verbose: bool,
+    pub mode: Mode,
+    pub files: Vec<String>,
+}
+
+/// Modes such as `-w` or `-x,g+r` look like options, so they are recognized before lexing.
+fn is_negative_mode(raw_arg: &str) -> bool {
+    let mut chars = raw_arg.chars();
+    chars.next() == Some('-')
+        && chars.next().is_some_and(|c| "rwxXst".contains(c))
+        && chars.all(|c| "rwxXstugoa+-=,".contains(c))
+}
+
+pub fn parse(args: Vec<String>) -> Result<Args, ChmodError> {
+    let mut recursive = false;
+    let mut verbose = false;
+    let mut operands = vec![];
+    let mut delimited = false;
+    for raw_arg in args {
+        if delimited || (operands.is_empty() && is_negative_mode(&raw_arg)) {
+            operands.push(raw_arg);
+            continue;
+        }
+        for arg in &lex(vec![raw_arg]) {
+            match arg {
+                Arg::Positional(positional) => match positional.as_str() {
+                    "--" => delimited = true,
+                    _ => operands.push(positional.clone()),
+                },
+                Arg::Short(short) => match short.as_str() {
+                    "R" => recursive = true,
+                    "v" => verbose = true,
+                    _ => return Err(unknown_arg_message(arg).into()),
+                },
+                Arg::Long(long) => match long.as_str() {
+                    "recursive" => recursive = true,
+                    "verbose" => verbose = true,
+                    "version" => print_version(),
+                    "help" => print_help(),
+                    _ => return Err(unknown_arg_message(arg).into()),
+                },
+            };
+        }
+    }
+
+    let mut operands = operands.into_iter();
+    let mode = match operands.next() {
+        Some(mode) => mode,
+        None => return Err("missing operand".into()),
+    };
+    let files: Vec<String> = operands.collect();
+    if files.is_empty() {
+        return Err(format!("missing operand after '{}'", mode).into());
+    }
+    Ok(Args {
+        recursive,
+        verbose,
+        mode: Mode::parse(&mode)?,
+        files,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse_strs(args: &[&str]) -> Result<Args, ChmodError> {
+        parse(args.iter().map(|s| s.to_string()).collect())
+    }
+
+    #[test]
+    fn negative_modes() {
+        let args = parse_strs(&["-v", "-w", "f"]).unwrap();
+        assert!(args.verbose);
+        assert_eq!(args.mode, Mode::parse("-w").unwrap());
+        assert_eq!(args.files, vec!["f"]);
+        let args = parse_strs(&["u+x", "--", "-w"]).unwrap();
+        assert_eq!(args.files, vec!["-w"]);
+    }
+
+    #[test]
+    fn operands() {
+        assert!(parse_strs(&[]).is_err());
+        assert!(parse_strs(&["755"]).is_err());
+        assert!(parse_strs(&["bogus", "f"]).is_err());
+        assert!(parse_strs(&["-R", "755", "d"]).unwrap().recursive);
+    }
+}
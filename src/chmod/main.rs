@@ -0,0 +1,132 @@
+mod args;
+
+use args::Args;
+use coreutils_common::exit_with;
+use coreutils_common::mode::permissions_string;
+use coreutils_common::mode::umask;
+use coreutils_common::report_error;
+use coreutils_common::CoreError;
+use std::env;
+use std::fmt;
+use std::fmt::Debug;
+use std::fs;
+use std::fs::Permissions;
+use std::os::unix::fs::PermissionsExt;
+use std::path::Path;
+use std::process::exit;
+
+pub struct ChmodError {
+    message: String,
+}
+
+impl Debug for ChmodError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "chmod: {}", self.message)
+    }
+}
+
+impl CoreError for ChmodError {}
+
+impl<T> From<T> for ChmodError
+where
+    T: ToString,
+{
+    fn from(error: T) -> Self {
+        ChmodError {
+            message: error.to_string(),
+        }
+    }
+}
+
+fn describe(mode: u32) -> String {
+    format!("{:04o} ({})", mode, permissions_string(mode))
+}
+
+/// Changes the mode of `path`, then of everything below it with -R. Symlinks met during the
+/// walk are skipped, since chmod would change their targets instead.
+fn change(path: &Path, args: &Args, umask: u32, top_level: bool) -> bool {
+    let metadata = if top_level {
+        fs::metadata(path)
+    } else {
+        fs::symlink_metadata(path)
+    };
+    let metadata = match metadata {
+        Ok(metadata) => metadata,
+        Err(e) => {
+            report_error(
+                "chmod",
+                format!("cannot access '{}': {}", path.display(), e),
+            );
+            return false;
+        }
+    };
+    if metadata.file_type().is_symlink() {
+        return true;
+    }
+
+    let mut ok = true;
+    let old = metadata.permissions().mode() & 0o7777;
+    let new = args.mode.apply(old, metadata.is_dir(), umask);
+    if let Err(e) = fs::set_permissions(path, Permissions::from_mode(new)) {
+        report_error(
+            "chmod",
+            format!("changing permissions of '{}': {}", path.display(), e),
+        );
+        ok = false;
+    } else if args.verbose && old == new {
+        println!("mode of '{}' retained as {}", path.display(), describe(new));
+    } else if args.verbose {
+        println!(
+            "mode of '{}' changed from {} to {}",
+            path.display(),
+            describe(old),
+            describe(new)
+        );
+    }
+
+    if args.recursive && metadata.is_dir() {
+        let entries = match fs::read_dir(path) {
+            Ok(entries) => entries,
+            Err(e) => {
+                report_error(
+                    "chmod",
+                    format!("cannot read directory '{}': {}", path.display(), e),
+                );
+                return false;
+            }
+        };
+        for entry in entries {
+            match entry {
+                Ok(entry) => ok &= change(&entry.path(), args, umask, false),
+                Err(e) => {
+                    report_error(
+                        "chmod",
+                        format!("cannot read directory '{}': {}", path.display(), e),
+                    );
+                    ok = false;
+                }
+            }
+        }
+    }
+    ok
+}
+
+fn main() {
+    if let Err(e) = run() {
+        exit_with(&e);
+    }
+}
+
+fn run() -> Result<(), ChmodError> {
+    let args = args::parse(env::args().skip(1).collect())?;
+    let umask = umask();
+
+    let mut failed = false;
+    for file in &args.files {
+        failed |= !change(Path::new(file), &args, umask, true);
+    }
+    if failed {
+        exit(1);
+    }
+    Ok(())
+}
@@ -0,0 +1,165 @@
+use crate::ChownError;
+use arglex::lex;
+use arglex::Arg;
+use coreutils_common::print_and_exit;
+use coreutils_common::unknown_arg_message;
+use coreutils_common::users::group_id;
+use coreutils_common::users::user_ids;
+use coreutils_common::version_string;
+
+const HELP: &str = "
+Usage: chown [OPTION]... [OWNER][:[GROUP]] FILE...
+Change the owner and/or group of each FILE to OWNER and/or GROUP.
+
+Options:
+  -h, --no-dereference      Affect symbolic links instead of any referenced file
+  -R, --recursive           Operate on files and directories recursively
+  --version                 Output version information and exit
+  --help                    Display this help and exit
+
+Owner is unchanged if missing. Group is unchanged if missing, but changed
+to the login group if implied by a ':' following a symbolic OWNER.
+OWNER and GROUP may be numeric as well as symbolic.
+";
+
+fn print_help() -> ! {
+    print_and_exit(0, HELP);
+}
+
+fn print_version() -> ! {
+    print_and_exit(0, version_string!("chown"));
+}
+
+pub struct Args {
+    pub no_dereference: bool,
+    pub recursive: bool,
+    pub owner: Option<u32>,
+    pub group: Option<u32>,
+    pub files: Vec<String>,
+}
+
+/// Resolves the `OWNER[:[GROUP]]` operand to numeric ids. Names are tried before numbers, as
+/// GNU does.
+///
+/// This runs once, before any file is touched, so a recursive walk never repeats the
+/// passwd or group lookups.
+pub fn resolve_owner(spec: &str) -> Result<(Option<u32>, Option<u32>), ChownError> {
+    let (user, group) = match spec.find(':') {
+        Some(i) => (&spec[..i], Some(&spec[i + 1..])),
+        None => (spec, None),
+    };
+    let mut login_group = None;
+    let owner = if user.is_empty() {
+        None
+    } else if let Some((uid, gid)) = user_ids(user) {
+        login_group = Some(gid);
+        Some(uid)
+    } else {
+        Some(
+            user.parse()
+                .map_err(|_| format!("invalid user: '{}'", spec))?,
+        )
+    };
+    let group = match group {
+        None => None,
+        Some("") if user.is_empty() => None,
+        Some("") => match login_group {
+            Some(gid) => Some(gid),
+            None => return Err(format!("invalid spec: '{}'", spec).into()),
+        },
+        Some(group) => match group_id(group) {
+            Some(gid) => Some(gid),
+            None => Some(
+                group
+                    .parse()
+                    .map_err(|_| format!("invalid group: '{}'", spec))?,
+            ),
+        },
+    };
+    Ok((owner, group))
+}
+
+pub fn parse(args: Vec<String>) -> Result<Args, ChownError> {
+    let mut no_dereference = false;
+    let mut recursive = false;
+    let mut operands = vec![];
+    for arg in &lex(args) {
+        match arg {
+            Arg::Positional(positional) => match positional.as_str() {
+                "--" => continue,
+                _ => operands.push(positional.clone()),
+            },
+            Arg::Short(short) => match short.as_str() {
+                "h" => no_dereference = true,
+                "R" => recursive = true,
+                _ => return Err(unknown_arg_message(arg).into()),
+            },
+            Arg::Long(long) => match long.as_str() {
+                "no-dereference" => no_dereference = true,
+                "recursive" => recursive = true,
+                "version" => print_version(),
+                "help" => print_help(),
+                _ => return Err(unknown_arg_message(arg).into()),
+            },
+        };
+    }
+
+    let mut operands = operands.into_iter();
+    let spec = match operands.next() {
+        Some(spec) => spec,
+        None => return Err("missing operand".into()),
+    };
+    let files: Vec<String> = operands.collect();
+    if files.is_empty() {
+        return Err(format!("missing operand after '{}'", spec).into());
+    }
+    let (owner, group) = resolve_owner(&spec)?;
+    Ok(Args {
+        no_dereference,
+        recursive,
+        owner,
+        group,
+        files,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use coreutils_common::users::user_name;
+
+    #[test]
+    fn specs() {
+        assert_eq!(resolve_owner("root").unwrap(), (Some(0), None));
+        assert_eq!(resolve_owner("root:").unwrap(), (Some(0), Some(0)));
+        assert_eq!(resolve_owner("12:34").unwrap(), (Some(12), Some(34)));
+        assert_eq!(resolve_owner(":34").unwrap(), (None, Some(34)));
+        assert_eq!(resolve_owner("12").unwrap(), (Some(12), None));
+        assert_eq!(resolve_owner(":").unwrap(), (None, None));
+    }
+
+    #[test]
+    fn invalid_specs() {
+        assert_eq!(
+            format!("{:?}", resolve_owner("no such user").unwrap_err()),
+            "chown: invalid user: 'no such user'"
+        );
+        assert_eq!(
+            format!("{:?}", resolve_owner("0:nogroup!").unwrap_err()),
+            "chown: invalid group: '0:nogroup!'"
+        );
+        assert!(resolve_owner("12:").is_err());
+    }
+
+    #[test]
+    fn current_user_by_name() {
+        let uid = unsafe { libc::geteuid() };
+        let name = user_name(uid).unwrap();
+        let (_, login_gid) = user_ids(&name).unwrap();
+        assert_eq!(resolve_owner(&name).unwrap(), (Some(uid), None));
+        assert_eq!(
+            resolve_owner(&format!("{}:", name)).unwrap(),
+            (Some(uid), Some(login_gid))
+        );
+    }
+}
@@ -0,0 +1,111 @@
+mod args;
+
+use args::Args;
+use coreutils_common::exit_with;
+use coreutils_common::report_error;
+use coreutils_common::CoreError;
+use std::env;
+use std::fmt;
+use std::fmt::Debug;
+use std::fs;
+use std::os::unix::fs::chown;
+use std::os::unix::fs::lchown;
+use std::path::Path;
+use std::process::exit;
+
+pub struct ChownError {
+    message: String,
+}
+
+impl Debug for ChownError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "chown: {}", self.message)
+    }
+}
+
+impl CoreError for ChownError {}
+
+impl<T> From<T> for ChownError
+where
+    T: ToString,
+{
+    fn from(error: T) -> Self {
+        ChownError {
+            message: error.to_string(),
+        }
+    }
+}
+
+/// Changes the ownership of `path`, then of everything below it with -R. Symlinks met during
+/// the walk are never followed; only command-line symlinks are dereferenced, unless -h.
+fn change(path: &Path, args: &Args, top_level: bool) -> bool {
+    let metadata = match fs::symlink_metadata(path) {
+        Ok(metadata) => metadata,
+        Err(e) => {
+            report_error(
+                "chown",
+                format!("cannot access '{}': {}", path.display(), e),
+            );
+            return false;
+        }
+    };
+    let is_symlink = metadata.file_type().is_symlink();
+    let result = if is_symlink && (args.no_dereference || !top_level) {
+        lchown(path, args.owner, args.group)
+    } else {
+        chown(path, args.owner, args.group)
+    };
+    let mut ok = true;
+    if let Err(e) = result {
+        report_error(
+            "chown",
+            format!("changing ownership of '{}': {}", path.display(), e),
+        );
+        ok = false;
+    }
+
+    if args.recursive && metadata.is_dir() {
+        let entries = match fs::read_dir(path) {
+            Ok(entries) => entries,
+            Err(e) => {
+                report_error(
+                    "chown",
+                    format!("cannot read directory '{}': {}", path.display(), e),
+                );
+                return false;
+            }
+        };
+        for entry in entries {
+            match entry {
+                Ok(entry) => ok &= change(&entry.path(), args, false),
+                Err(e) => {
+                    report_error(
+                        "chown",
+                        format!("cannot read directory '{}': {}", path.display(), e),
+                    );
+                    ok = false;
+                }
+            }
+        }
+    }
+    ok
+}
+
+fn main() {
+    if let Err(e) = run() {
+        exit_with(&e);
+    }
+}
+
+fn run() -> Result<(), ChownError> {
+    let args = args::parse(env::args().skip(1).collect())?;
+
+    let mut failed = false;
+    for file in &args.files {
+        failed |= !change(Path::new(file), &args, true);
+    }
+    if failed {
+        exit(1);
+    }
+    Ok(())
+}
@@ -0,0 +1,120 @@
+use crate::CommError;
+use arglex::lex;
+use arglex::Arg;
+use coreutils_common::print_and_exit;
+use coreutils_common::unknown_arg_message;
+use coreutils_common::version_string;
+
+const HELP: &str = "
+Usage: comm [OPTION]... FILE1 FILE2
+Compare sorted files FILE1 and FILE2 line by line.
+
+When FILE1 or FILE2 (not both) is -, read standard input.
+
+With no options, produce three-column output. Column one contains
+lines unique to FILE1, column two contains lines unique to FILE2,
+and column three contains lines common to both files.
+
+Options:
+  -1                        Suppress column 1 (lines unique to FILE1)
+  -2                        Suppress column 2 (lines unique to FILE2)
+  -3                        Suppress column 3 (lines that appear in both files)
+  --version                 Output version information and exit
+  --help                    Display this help and exit
+";
+
+fn print_help() -> ! {
+    print_and_exit(0, HELP);
+}
+
+fn print_version() -> ! {
+    print_and_exit(0, version_string!("comm"));
+}
+
+pub struct Args {
+    /// Which of the three columns are printed.
+    pub show: [bool; 3],
+    pub file1: String,
+    pub file2: String,
+}
+
+/// Whether `raw_arg` is a group of column flags such as `-12`, which would otherwise lex as
+/// `-1` followed by an operand `2`.
+fn is_column_flags(raw_arg: &str) -> bool {
+    raw_arg.len() > 1
+        && raw_arg.starts_with('-')
+        && raw_arg[1..].chars().all(|c| ('1'..='3').contains(&c))
+}
+
+pub fn parse(args: Vec<String>) -> Result<Args, CommError> {
+    let mut show = [true; 3];
+    let mut files = vec![];
+    let mut delimited = false;
+    for raw_arg in args {
+        if delimited {
+            files.push(raw_arg);
+            continue;
+        }
+        if is_column_flags(&raw_arg) {
+            for column in raw_arg[1..].bytes() {
+                show[(column - b'1') as usize] = false;
+            }
+            continue;
+        }
+        for arg in &lex(vec![raw_arg]) {
+            match arg {
+                Arg::Positional(positional) => match positional.as_str() {
+                    "--" => delimited = true,
+                    _ => files.push(positional.clone()),
+                },
+                Arg::Long(long) => match long.as_str() {
+                    "version" => print_version(),
+                    "help" => print_help(),
+                    _ => return Err(unknown_arg_message(arg).into()),
+                },
+                _ => return Err(unknown_arg_message(arg).into()),
+            };
+        }
+    }
+    match files.len() {
+        0 => Err("missing operand".into()),
+        1 => Err(format!("missing operand after '{}'", files[0]).into()),
+        2 => {
+            let file2 = files.pop().unwrap();
+            let file1 = files.pop().unwrap();
+            Ok(Args { show, file1, file2 })
+        }
+        _ => Err(format!("extra operand '{}'", files[2]).into()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse_strs(args: &[&str]) -> Result<Args, CommError> {
+        parse(args.iter().map(|s| s.to_string()).collect())
+    }
+
+    #[test]
+    fn columns() {
+        assert_eq!(parse_strs(&["a", "b"]).unwrap().show, [true; 3]);
+        assert_eq!(
+            parse_strs(&["-1", "-3", "a", "b"]).unwrap().show,
+            [false, true, false]
+        );
+        assert_eq!(
+            parse_strs(&["-12", "a", "b"]).unwrap().show,
+            [false, false, true]
+        );
+        assert!(parse_strs(&["-4", "a", "b"]).is_err());
+    }
+
+    #[test]
+    fn operands() {
+        assert!(parse_strs(&["a"]).is_err());
+        assert!(parse_strs(&["a", "b", "c"]).is_err());
+        let args = parse_strs(&["--", "-1", "-"]).unwrap();
+        assert_eq!((args.file1.as_str(), args.file2.as_str()), ("-1", "-"));
+    }
+}
@@ -0,0 +1,127 @@
+mod args;
+
+use args::Args;
+use coreutils_common::exit_with;
+use coreutils_common::CoreError;
+use std::cmp::Ordering;
+use std::env;
+use std::fmt;
+use std::fmt::Debug;
+use std::fs::File;
+use std::io;
+use std::io::BufRead;
+use std::io::BufReader;
+use std::io::BufWriter;
+use std::io::Write;
+
+pub struct CommError {
+    message: String,
+}
+
+impl Debug for CommError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "comm: {}", self.message)
+    }
+}
+
+impl CoreError for CommError {}
+
+impl<T> From<T> for CommError
+where
+    T: ToString,
+{
+    fn from(error: T) -> Self {
+        CommError {
+            message: error.to_string(),
+        }
+    }
+}
+
+/// The next line of `input` without its newline, or `None` at end of file.
+fn next_line(input: &mut dyn BufRead) -> io::Result<Option<Vec<u8>>> {
+    let mut line = vec![];
+    if input.read_until(b'\n', &mut line)? == 0 {
+        return Ok(None);
+    }
+    if line.last() == Some(&b'\n') {
+        line.pop();
+    }
+    Ok(Some(line))
+}
+
+/// Writes `line` in `column` (0-based), indented by a tab for each shown column before it.
+fn emit(line: &[u8], column: usize, args: &Args, out: &mut dyn Write) -> io::Result<()> {
+    if !args.show[column] {
+        return Ok(());
+    }
+    for _ in args.show[..column].iter().filter(|&&shown| shown) {
+        out.write_all(b"\t")?;
+    }
+    out.write_all(line)?;
+    out.write_all(b"\n")
+}
+
+/// Walks both sorted inputs in step, comparing lines byte by byte.
+fn comm(
+    input1: &mut dyn BufRead,
+    input2: &mut dyn BufRead,
+    args: &Args,
+    out: &mut dyn Write,
+) -> io::Result<()> {
+    let mut line1 = next_line(input1)?;
+    let mut line2 = next_line(input2)?;
+    loop {
+        let order = match (&line1, &line2) {
+            (None, None) => return Ok(()),
+            (Some(_), None) => Ordering::Less,
+            (None, Some(_)) => Ordering::Greater,
+            (Some(a), Some(b)) => a.cmp(b),
+        };
+        match order {
+            Ordering::Less => {
+                emit(line1.as_ref().unwrap(), 0, args, out)?;
+                line1 = next_line(input1)?;
+            }
+            Ordering::Greater => {
+                emit(line2.as_ref().unwrap(), 1, args, out)?;
+                line2 = next_line(input2)?;
+            }
+            Ordering::Equal => {
+                emit(line1.as_ref().unwrap(), 2, args, out)?;
+                line1 = next_line(input1)?;
+                line2 = next_line(input2)?;
+            }
+        }
+    }
+}
+
+fn open(file: &str) -> Result<Box<dyn BufRead>, CommError> {
+    if file == "-" {
+        Ok(Box::new(BufReader::new(io::stdin())))
+    } else {
+        match File::open(file) {
+            Ok(f) => Ok(Box::new(BufReader::new(f))),
+            Err(e) => Err(format!("{}: {}", file, e).into()),
+        }
+    }
+}
+
+fn main() {
+    if let Err(e) = run() {
+        exit_with(&e);
+    }
+}
+
+fn run() -> Result<(), CommError> {
+    let args = args::parse(env::args().skip(1).collect())?;
+    if args.file1 == "-" && args.file2 == "-" {
+        return Err("both files cannot be standard input".into());
+    }
+    let mut input1 = open(&args.file1)?;
+    let mut input2 = open(&args.file2)?;
+    let stdout = io::stdout();
+    let mut out = BufWriter::new(stdout.lock());
+    comm(&mut input1, &mut input2, &args, &mut out)?;
+    out.flush()?;
+    Ok(())
+}
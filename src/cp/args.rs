@@ -0,0 +1,288 @@
+use crate::CpError;
+use arglex::lex_one;
+use arglex::Arg;
+use coreutils_common::backup::Control;
+use coreutils_common::copy::Preserve;
+use coreutils_common::copy::Reflink;
+use coreutils_common::mode::Mode;
+use coreutils_common::paths::strip_trailing_slashes;
+use coreutils_common::print_and_exit;
+use coreutils_common::unexpected_value_message;
+use coreutils_common::unknown_arg_message;
+use coreutils_common::version_string;
+
+const HELP: &str = "
+Usage: cp [OPTION]... SOURCE DEST
+  or:  cp [OPTION]... SOURCE... DIRECTORY
+Copy SOURCE to DEST, or multiple SOURCE(s) to DIRECTORY.
+
+Options:
+  --backup[=CONTROL]        Make a backup of each existing destination file
+  -b                        Like --backup but does not accept an argument
+  -D                        Create all leading components of DEST, as install -D
+  -f, --force               If an existing destination file cannot be opened,
+                            remove it and try again
+  -i, --interactive         Prompt before overwrite
+  -m, --mode <MODE>         Set the mode of each copy (octal or symbolic, as in
+                            chmod) after copying it
+  -n, --no-clobber          Do not overwrite an existing file
+  -p                        Same as --preserve=mode,ownership,timestamps
+  --preserve[=ATTR_LIST]    Preserve the given attributes (default:
+                            mode,ownership,timestamps); ATTR_LIST may also be all
+  --parents                 Use each full source path under DIRECTORY, creating
+                            the directories leading up to it
+  -r, -R, --recursive       Copy directories recursively, copying symlinks as symlinks
+  --reflink[=WHEN]          Share the source's data blocks instead of copying
+                            them, on file systems that support it: WHEN is
+                            auto (the default, falling back to copying),
+                            always (the default for a bare --reflink) or never
+  --strip-trailing-slashes  Remove any trailing slashes from each SOURCE
+  -v, --verbose             Explain what is being done
+  -x, --one-file-system     With -r, don't copy the contents of directories on
+                            other file systems
+  --version                 Output version information and exit
+  --help                    Display this help and exit
+
+A trailing slash on a SOURCE that's a symlink to a directory means the directory
+itself, as it does to the kernel; --strip-trailing-slashes makes it the symlink.
+
+The backup CONTROL is one of none (or off), numbered (or t), existing (or nil)
+and simple (or never). A bare --backup or -b uses VERSION_CONTROL, else existing.
+";
+
+fn print_help() -> ! {
+    print_and_exit(0, HELP);
+}
+
+fn print_version() -> ! {
+    print_and_exit(0, version_string!("cp"));
+}
+
+pub struct Args {
+    pub backup: Control,
+    pub create_leading: bool,
+    pub force: bool,
+    pub interactive: bool,
+    pub mode: Option<Mode>,
+    pub no_clobber: bool,
+    pub one_file_system: bool,
+    pub parents: bool,
+    pub preserve: Preserve,
+    pub recursive: bool,
+    pub reflink: Reflink,
+    pub strip_trailing_slashes: bool,
+    pub verbose: bool,
+    pub sources: Vec<String>,
+    pub dest: String,
+}
+
+impl Args {
+    fn new() -> Self {
+        Args {
+            backup: Control::Off,
+            create_leading: false,
+            force: false,
+            interactive: false,
+            mode: None,
+            no_clobber: false,
+            one_file_system: false,
+            parents: false,
+            preserve: Preserve::default(),
+            recursive: false,
+            reflink: Reflink::Auto,
+            strip_trailing_slashes: false,
+            verbose: false,
+            sources: vec![],
+            dest: String::new(),
+        }
+    }
+}
+
+/// The value attached to an option with `=`, or else the next argument.
+fn value_of(
+    attached: Option<String>,
+    raw_args: &mut impl Iterator<Item = String>,
+    name: &Arg,
+) -> Result<String, CpError> {
+    attached
+        .or_else(|| raw_args.next())
+        .ok_or_else(|| format!("{} needs an argument", name).into())
+}
+
+pub fn parse(args: Vec<String>) -> Result<Args, CpError> {
+    let mut arg_struct = Args::new();
+    let mut raw_args = args.into_iter();
+    let mut delimited = false;
+    // Each argument is lexed on its own, so that `--backup` can tell `--backup=CONTROL` apart
+    // from `--backup FILE`.
+    while let Some(raw_arg) = raw_args.next() {
+        if delimited {
+            arg_struct.sources.push(raw_arg);
+            continue;
+        }
+        let (lexed, mut attached) = lex_one(raw_arg, "m");
+        for arg in &lexed {
+            match arg {
+                Arg::Positional(positional) => match positional.as_str() {
+                    "--" => delimited = true,
+                    _ => arg_struct.sources.push(positional.clone()),
+                },
+                Arg::Short(short) => match short.as_str() {
+                    "b" => arg_struct.backup = Control::from_env()?,
+                    "D" => arg_struct.create_leading = true,
+                    "f" => arg_struct.force = true,
+                    "i" => arg_struct.interactive = true,
+                    "m" => {
+                        let mode = value_of(attached.take(), &mut raw_args, arg)?;
+                        arg_struct.mode = Some(Mode::parse(&mode)?);
+                    }
+                    "n" => arg_struct.no_clobber = true,
+                    "p" => arg_struct.preserve = Preserve::all(),
+                    "r" | "R" => arg_struct.recursive = true,
+                    "v" => arg_struct.verbose = true,
+                    "x" => arg_struct.one_file_system = true,
+                    _ => return Err(unknown_arg_message(arg).into()),
+                },
+                Arg::Long(long) => match long.as_str() {
+                    "backup" => {
+                        arg_struct.backup = match attached.take() {
+                            Some(control) => Control::parse(&control)?,
+                            None => Control::from_env()?,
+                        };
+                    }
+                    "force" => arg_struct.force = true,
+                    "interactive" => arg_struct.interactive = true,
+                    "mode" => {
+                        let mode = value_of(attached.take(), &mut raw_args, arg)?;
+                        arg_struct.mode = Some(Mode::parse(&mode)?);
+                    }
+                    "no-clobber" => arg_struct.no_clobber = true,
+                    "parents" => arg_struct.parents = true,
+                    "one-file-system" => arg_struct.one_file_system = true,
+                    "preserve" => {
+                        arg_struct.preserve = match attached.take() {
+                            Some(list) => Preserve::parse(&list)?,
+                            None => Preserve::all(),
+                        };
+                    }
+                    "recursive" => arg_struct.recursive = true,
+                    "reflink" => {
+                        arg_struct.reflink = match attached.take() {
+                            Some(when) => Reflink::parse(&when)?,
+                            None => Reflink::Always,
+                        };
+                    }
+                    "strip-trailing-slashes" => arg_struct.strip_trailing_slashes = true,
+                    "verbose" => arg_struct.verbose = true,
+                    "version" => print_version(),
+                    "help" => print_help(),
+                    _ => return Err(unknown_arg_message(arg).into()),
+                },
+            };
+        }
+        if let (Some(_), Some(arg)) = (attached, lexed.last()) {
+            return Err(unexpected_value_message(arg).into());
+        }
+    }
+    match arg_struct.sources.len() {
+        0 => return Err("missing file operand".into()),
+        1 => {
+            return Err(format!(
+                "missing destination file operand after '{}'",
+                arg_struct.sources[0]
+            )
+            .into())
+        }
+        _ => arg_struct.dest = arg_struct.sources.pop().unwrap(),
+    }
+    if arg_struct.strip_trailing_slashes {
+        for source in &mut arg_struct.sources {
+            let stripped = strip_trailing_slashes(source).len();
+            source.truncate(stripped);
+        }
+    }
+    Ok(arg_struct)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse_strs(args: &[&str]) -> Result<Args, CpError> {
+        parse(args.iter().map(|s| s.to_string()).collect())
+    }
+
+    #[test]
+    fn last_operand_is_the_destination() {
+        let args = parse_strs(&["-r", "a", "b", "dir"]).unwrap();
+        assert_eq!(args.sources, vec!["a", "b"]);
+        assert_eq!(args.dest, "dir");
+    }
+
+    #[test]
+    fn clustered_short_options() {
+        let args = parse_strs(&["-rv", "a", "b"]).unwrap();
+        assert!(args.recursive && args.verbose);
+        assert_eq!(args.sources, vec!["a"]);
+        let args = parse_strs(&["-pr", "a", "b"]).unwrap();
+        assert!(args.recursive && args.preserve.mode);
+        assert_eq!(args.sources, vec!["a"]);
+        let args = parse_strs(&["-vm644", "a", "b"]).unwrap();
+        assert!(args.verbose && args.mode.is_some());
+        assert_eq!(args.sources, vec!["a"]);
+        assert!(parse_strs(&["--force=yes", "a", "b"]).is_err());
+    }
+
+    #[test]
+    fn backup_control() {
+        let args = parse_strs(&["--backup=t", "a", "b"]).unwrap();
+        assert_eq!(args.backup, Control::Numbered);
+        let args = parse_strs(&["--backup", "a", "b"]).unwrap();
+        assert_ne!(args.backup, Control::Off);
+        assert_eq!(args.sources, vec!["a"]);
+        assert_eq!(parse_strs(&["a", "b"]).unwrap().backup, Control::Off);
+    }
+
+    #[test]
+    fn preserve_lists() {
+        let args = parse_strs(&["--preserve=timestamps", "a", "b"]).unwrap();
+        assert!(args.preserve.timestamps && !args.preserve.mode && !args.preserve.ownership);
+        let args = parse_strs(&["--preserve=mode,ownership", "a", "b"]).unwrap();
+        assert!(args.preserve.mode && args.preserve.ownership && !args.preserve.timestamps);
+        assert_eq!(
+            parse_strs(&["-p", "a", "b"]).unwrap().preserve,
+            Preserve::all()
+        );
+        let args = parse_strs(&["--preserve", "a", "b"]).unwrap();
+        assert_eq!(args.preserve, Preserve::all());
+        assert_eq!(args.sources, vec!["a"]);
+        assert!(parse_strs(&["--preserve=links", "a", "b"]).is_err());
+    }
+
+    #[test]
+    fn reflinks() {
+        assert_eq!(parse_strs(&["a", "b"]).unwrap().reflink, Reflink::Auto);
+        let args = parse_strs(&["--reflink", "a", "b"]).unwrap();
+        assert_eq!(args.reflink, Reflink::Always);
+        assert_eq!(args.sources, vec!["a"]);
+        let args = parse_strs(&["--reflink=never", "a", "b"]).unwrap();
+        assert_eq!(args.reflink, Reflink::Never);
+        assert!(parse_strs(&["--reflink=sometimes", "a", "b"]).is_err());
+    }
+
+    #[test]
+    fn modes() {
+        let args = parse_strs(&["-m", "600", "a", "b"]).unwrap();
+        assert_eq!(args.mode, Some(Mode::Octal(0o600)));
+        assert_eq!(args.sources, vec!["a"]);
+        let args = parse_strs(&["--mode=u+x", "a", "b"]).unwrap();
+        assert_eq!(args.mode, Mode::parse("u+x").ok());
+        assert!(parse_strs(&["-m", "u+q", "a", "b"]).is_err());
+    }
+
+    #[test]
+    fn needs_two_operands() {
+        assert!(parse_strs(&[]).is_err());
+        assert!(parse_strs(&["a"]).is_err());
+    }
+}
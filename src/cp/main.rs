@@ -0,0 +1,181 @@
+mod args;
+
+use args::Args;
+use coreutils_common::copy::copy;
+use coreutils_common::copy::into_itself;
+use coreutils_common::copy::CopyOptions;
+use coreutils_common::exit_with;
+use coreutils_common::mode::umask;
+use coreutils_common::mode::Mode;
+use coreutils_common::report_error;
+use coreutils_common::signals::remove_partial_on_interrupt;
+use coreutils_common::CoreError;
+use std::env;
+use std::fmt;
+use std::fmt::Debug;
+use std::fs;
+use std::fs::Permissions;
+use std::io;
+use std::os::unix::fs::PermissionsExt;
+use std::path::Component;
+use std::path::Path;
+use std::path::PathBuf;
+use std::process::exit;
+
+pub struct CpError {
+    message: String,
+}
+
+impl Debug for CpError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "cp: {}", self.message)
+    }
+}
+
+impl CoreError for CpError {}
+
+impl<T> From<T> for CpError
+where
+    T: ToString,
+{
+    fn from(error: T) -> Self {
+        CpError {
+            message: error.to_string(),
+        }
+    }
+}
+
+impl Args {
+    fn copy_options(&self) -> CopyOptions {
+        CopyOptions {
+            tool: "cp",
+            backup: self.backup,
+            force: self.force,
+            interactive: self.interactive,
+            no_clobber: self.no_clobber,
+            one_file_system: self.one_file_system,
+            preserve: self.preserve,
+            recursive: self.recursive,
+            reflink: self.reflink,
+            verbose: self.verbose,
+        }
+    }
+}
+
+/// `path` without its leading `/`, for --parents to recreate under the destination.
+fn relative(path: &Path) -> PathBuf {
+    path.components()
+        .filter(|component| !matches!(component, Component::RootDir))
+        .collect()
+}
+
+/// Creates whatever -D needs to exist before copying: the directory holding DEST, or DEST
+/// itself when several sources are copied into it.
+fn create_leading(dest: &Path, source_count: usize) -> io::Result<()> {
+    let dir = if source_count > 1 {
+        Some(dest)
+    } else {
+        dest.parent()
+            .filter(|parent| !parent.as_os_str().is_empty())
+    };
+    match dir {
+        Some(dir) => fs::create_dir_all(dir),
+        None => Ok(()),
+    }
+}
+
+/// Applies `-m` to a finished copy, as chmod would.
+fn set_mode(target: &Path, mode: &Mode, umask: u32) -> io::Result<()> {
+    let metadata = fs::metadata(target)?;
+    let new = mode.apply(metadata.permissions().mode(), metadata.is_dir(), umask);
+    fs::set_permissions(target, Permissions::from_mode(new))
+}
+
+fn main() {
+    if let Err(e) = run() {
+        exit_with(&e);
+    }
+}
+
+fn run() -> Result<(), CpError> {
+    let args = args::parse(env::args().skip(1).collect())?;
+    remove_partial_on_interrupt();
+    let options = args.copy_options();
+    let dest = Path::new(&args.dest);
+    if args.create_leading {
+        if let Err(e) = create_leading(dest, args.sources.len()) {
+            return Err(format!("cannot create directories for '{}': {}", args.dest, e).into());
+        }
+    }
+    let into_dir = dest.is_dir();
+    if args.parents && !into_dir {
+        return Err(format!(
+            "with --parents, the destination must be a directory: '{}'",
+            args.dest
+        )
+        .into());
+    }
+    if args.sources.len() > 1 && !into_dir {
+        return Err(format!("target '{}' is not a directory", args.dest).into());
+    }
+
+    let umask = umask();
+    let mut failed = false;
+    for source in &args.sources {
+        let src = Path::new(source);
+        let target: PathBuf = if args.parents {
+            let target = dest.join(relative(src));
+            if let Some(parent) = target.parent() {
+                if let Err(e) = fs::create_dir_all(parent) {
+                    report_error(
+                        "cp",
+                        format!("cannot create directory '{}': {}", parent.display(), e),
+                    );
+                    failed = true;
+                    continue;
+                }
+            }
+            target
+        } else if into_dir {
+            match src.file_name() {
+                Some(name) => dest.join(name),
+                None => {
+                    report_error("cp", format!("cannot copy '{}': invalid file name", source));
+                    failed = true;
+                    continue;
+                }
+            }
+        } else {
+            dest.to_path_buf()
+        };
+        if args.recursive && src.is_dir() && into_itself(src, &target) {
+            report_error(
+                "cp",
+                format!(
+                    "cannot copy a directory, '{}', into itself, '{}'",
+                    source,
+                    target.display()
+                ),
+            );
+            failed = true;
+            continue;
+        }
+        if !copy(src, &target, &options) {
+            failed = true;
+            continue;
+        }
+        if let Some(mode) = &args.mode {
+            if let Err(e) = set_mode(&target, mode, umask) {
+                report_error(
+                    "cp",
+                    format!("setting permissions for '{}': {}", target.display(), e),
+                );
+                failed = true;
+            }
+        }
+    }
+    if failed {
+        exit(1);
+    }
+    Ok(())
+}
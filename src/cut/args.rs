@@ -0,0 +1,201 @@
+use crate::ranges::RangeSet;
+use crate::CutError;
+use arglex::lex;
+use arglex::Arg;
+use coreutils_common::print_and_exit;
+use coreutils_common::unknown_arg_message;
+use coreutils_common::version_string;
+use std::slice::Iter;
+
+const HELP: &str = "
+Usage: cut OPTION... [FILE]...
+Print selected parts of lines from each FILE to standard output.
+
+With no FILE, or when FILE is -, read standard input.
+
+Options:
+  -b, --bytes <LIST>        Select only these bytes
+  -c, --characters <LIST>   Select only these characters
+  -d, --delimiter <DELIM>   Use DELIM instead of TAB for field delimiter
+  -f, --fields <LIST>       Select only these fields; also print any line
+                            that contains no delimiter character, unless
+                            the -s option is specified
+  --complement              Complement the set of selected bytes, characters
+                            or fields
+  -s, --only-delimited      Do not print lines not containing delimiters
+  --output-delimiter <STR>  Join the selected fields with STR instead of the
+                            input delimiter; with -b or -c, put STR between
+                            the selected ranges
+  --version                 Output version information and exit
+  --help                    Display this help and exit
+
+Use one, and only one of -b, -c or -f. Each LIST is made up of one range, or
+many ranges separated by commas. Each range is one of N, N-, N-M or -M.
+";
+
+fn print_help() -> ! {
+    print_and_exit(0, HELP);
+}
+
+fn print_version() -> ! {
+    print_and_exit(0, version_string!("cut"));
+}
+
+/// What the LIST counts.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Mode {
+    Bytes,
+    Characters,
+    Fields,
+}
+
+pub struct Args {
+    pub mode: Mode,
+    pub list: RangeSet,
+    pub delimiter: u8,
+    pub complement: bool,
+    pub only_delimited: bool,
+    pub output_delimiter: Option<Vec<u8>>,
+    pub files: Vec<String>,
+}
+
+fn get_arg_to(args: &mut Iter<Arg>, name: &Arg) -> Result<String, CutError> {
+    match args.next() {
+        Some(Arg::Positional(value)) => Ok(value.clone()),
+        _ => Err(format!("{} needs an argument", name).into()),
+    }
+}
+
+fn set_list(
+    selected: &mut Option<(Mode, RangeSet)>,
+    mode: Mode,
+    list: &str,
+) -> Result<(), CutError> {
+    if selected.is_some() {
+        return Err("only one type of list may be specified".into());
+    }
+    *selected = Some((mode, RangeSet::parse(list)?));
+    Ok(())
+}
+
+fn parse_delimiter(delimiter: &str) -> Result<u8, CutError> {
+    match delimiter.as_bytes() {
+        [byte] => Ok(*byte),
+        _ => Err("the delimiter must be a single character".into()),
+    }
+}
+
+pub fn parse(args: Vec<String>) -> Result<Args, CutError> {
+    let args = lex(args);
+
+    let mut args = args.iter();
+    let mut selected = None;
+    let mut delimiter = None;
+    let mut complement = false;
+    let mut only_delimited = false;
+    let mut output_delimiter = None;
+    let mut files = vec![];
+    while let Some(arg) = args.next() {
+        match arg {
+            Arg::Positional(positional) => match positional.as_str() {
+                "--" => continue,
+                _ => files.push(positional.clone()),
+            },
+            Arg::Short(short) => match short.as_str() {
+                "b" => set_list(&mut selected, Mode::Bytes, &get_arg_to(&mut args, arg)?)?,
+                "c" => set_list(
+                    &mut selected,
+                    Mode::Characters,
+                    &get_arg_to(&mut args, arg)?,
+                )?,
+                "d" => delimiter = Some(parse_delimiter(&get_arg_to(&mut args, arg)?)?),
+                "f" => set_list(&mut selected, Mode::Fields, &get_arg_to(&mut args, arg)?)?,
+                "s" => only_delimited = true,
+                _ => return Err(unknown_arg_message(arg).into()),
+            },
+            Arg::Long(long) => match long.as_str() {
+                "bytes" => set_list(&mut selected, Mode::Bytes, &get_arg_to(&mut args, arg)?)?,
+                "characters" => set_list(
+                    &mut selected,
+                    Mode::Characters,
+                    &get_arg_to(&mut args, arg)?,
+                )?,
+                "delimiter" => delimiter = Some(parse_delimiter(&get_arg_to(&mut args, arg)?)?),
+                "fields" => set_list(&mut selected, Mode::Fields, &get_arg_to(&mut args, arg)?)?,
+                "complement" => complement = true,
+                "only-delimited" => only_delimited = true,
+                "output-delimiter" => {
+                    output_delimiter = Some(get_arg_to(&mut args, arg)?.into_bytes())
+                }
+                "version" => print_version(),
+                "help" => print_help(),
+                _ => return Err(unknown_arg_message(arg).into()),
+            },
+        };
+    }
+
+    let (mode, list) = selected.ok_or("you must specify a list of bytes, characters, or fields")?;
+    if mode != Mode::Fields {
+        if delimiter.is_some() {
+            return Err("an input delimiter may be specified only when operating on fields".into());
+        }
+        if only_delimited {
+            return Err(
+                "suppressing non-delimited lines makes sense\n\tonly when operating on fields"
+                    .into(),
+            );
+        }
+    }
+    if files.is_empty() {
+        files.push("-".to_owned());
+    }
+    Ok(Args {
+        mode,
+        list,
+        delimiter: delimiter.unwrap_or(b'\t'),
+        complement,
+        only_delimited,
+        output_delimiter,
+        files,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse_strs(args: &[&str]) -> Result<Args, CutError> {
+        parse(args.iter().map(|s| s.to_string()).collect())
+    }
+
+    #[test]
+    fn fields_with_delimiter() {
+        let args = parse_strs(&["-d:", "-f1,3"]).unwrap();
+        assert_eq!(args.mode, Mode::Fields);
+        assert_eq!(args.delimiter, b':');
+        assert!(args.list.contains(3));
+        assert_eq!(args.files, vec!["-"]);
+    }
+
+    #[test]
+    fn output_delimiter() {
+        let args = parse_strs(&["-f1", "--output-delimiter", "::"]).unwrap();
+        assert_eq!(args.output_delimiter, Some(b"::".to_vec()));
+        let args = parse_strs(&["-c1", "--output-delimiter=|"]).unwrap();
+        assert_eq!(args.output_delimiter, Some(b"|".to_vec()));
+        assert_eq!(parse_strs(&["-f1"]).unwrap().output_delimiter, None);
+    }
+
+    #[test]
+    fn needs_exactly_one_list() {
+        assert!(parse_strs(&[]).is_err());
+        assert!(parse_strs(&["-b1", "-c2"]).is_err());
+    }
+
+    #[test]
+    fn field_options_need_fields() {
+        assert!(parse_strs(&["-c1", "-d:"]).is_err());
+        assert!(parse_strs(&["-b1", "-s"]).is_err());
+        assert!(parse_strs(&["-f1", "-d", "ab"]).is_err());
+    }
+}
@@ -0,0 +1,167 @@
+mod args;
+mod ranges;
+
+use args::Args;
+use args::Mode;
+use coreutils_common::exit_with;
+use coreutils_common::report_error;
+use coreutils_common::CoreError;
+use std::env;
+use std::fmt;
+use std::fmt::Debug;
+use std::fs::File;
+use std::io;
+use std::io::BufRead;
+use std::io::BufReader;
+use std::io::BufWriter;
+use std::io::Write;
+use std::process::exit;
+
+pub struct CutError {
+    message: String,
+}
+
+impl Debug for CutError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "cut: {}", self.message)
+    }
+}
+
+impl CoreError for CutError {}
+
+impl<T> From<T> for CutError
+where
+    T: ToString,
+{
+    fn from(error: T) -> Self {
+        CutError {
+            message: error.to_string(),
+        }
+    }
+}
+
+impl Args {
+    fn selected(&self, position: usize) -> bool {
+        self.list.contains(position) != self.complement
+    }
+
+    /// Whether the 1-based `position` starts a new selected range after an earlier one, which
+    /// is where -b and -c put the output delimiter.
+    fn starts_later_range(&self, position: usize, printed: bool) -> bool {
+        printed && position > 1 && !self.selected(position - 1)
+    }
+}
+
+/// Writes the selected part of one line, without its newline. Returns false if the line should
+/// be left out entirely.
+fn cut_line(line: &[u8], args: &Args, out: &mut Vec<u8>) -> bool {
+    match args.mode {
+        Mode::Bytes => {
+            let mut printed = false;
+            for (i, &byte) in line.iter().enumerate() {
+                if args.selected(i + 1) {
+                    if let Some(delimiter) = &args.output_delimiter {
+                        if args.starts_later_range(i + 1, printed) {
+                            out.extend_from_slice(delimiter);
+                        }
+                    }
+                    out.push(byte);
+                    printed = true;
+                }
+            }
+        }
+        Mode::Characters => {
+            let text = String::from_utf8_lossy(line);
+            let mut printed = false;
+            for (i, c) in text.chars().enumerate() {
+                if args.selected(i + 1) {
+                    if let Some(delimiter) = &args.output_delimiter {
+                        if args.starts_later_range(i + 1, printed) {
+                            out.extend_from_slice(delimiter);
+                        }
+                    }
+                    printed = true;
+                    let mut buffer = [0; 4];
+                    out.extend_from_slice(c.encode_utf8(&mut buffer).as_bytes());
+                }
+            }
+        }
+        Mode::Fields => {
+            if !line.contains(&args.delimiter) {
+                if args.only_delimited {
+                    return false;
+                }
+                out.extend_from_slice(line);
+                return true;
+            }
+            let input_delimiter = [args.delimiter];
+            let delimiter = args.output_delimiter.as_deref().unwrap_or(&input_delimiter);
+            let mut first = true;
+            for (i, field) in line.split(|&b| b == args.delimiter).enumerate() {
+                if args.selected(i + 1) {
+                    if !first {
+                        out.extend_from_slice(delimiter);
+                    }
+                    out.extend_from_slice(field);
+                    first = false;
+                }
+            }
+        }
+    }
+    true
+}
+
+fn cut(input: &mut dyn BufRead, args: &Args, out: &mut dyn Write) -> io::Result<()> {
+    let mut line = vec![];
+    let mut selected = vec![];
+    loop {
+        line.clear();
+        if input.read_until(b'\n', &mut line)? == 0 {
+            return Ok(());
+        }
+        if line.last() == Some(&b'\n') {
+            line.pop();
+        }
+        selected.clear();
+        if cut_line(&line, args, &mut selected) {
+            selected.push(b'\n');
+            out.write_all(&selected)?;
+        }
+    }
+}
+
+fn main() {
+    if let Err(e) = run() {
+        exit_with(&e);
+    }
+}
+
+fn run() -> Result<(), CutError> {
+    let args = args::parse(env::args().skip(1).collect())?;
+    let stdout = io::stdout();
+    let mut out = BufWriter::new(stdout.lock());
+    let mut failed = false;
+    for file in &args.files {
+        let result = if file == "-" {
+            let stdin = io::stdin();
+            let mut input = stdin.lock();
+            cut(&mut input, &args, &mut out)
+        } else {
+            match File::open(file) {
+                Ok(f) => cut(&mut BufReader::new(f), &args, &mut out),
+                Err(e) => {
+                    out.flush()?;
+                    report_error("cut", format!("{}: {}", file, e));
+                    failed = true;
+                    continue;
+                }
+            }
+        };
+        result.map_err(|e| format!("{}: {}", file, e))?;
+    }
+    out.flush()?;
+    if failed {
+        exit(1);
+    }
+    Ok(())
+}
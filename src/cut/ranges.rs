@@ -0,0 +1,110 @@
+use crate::CutError;
+
+/// A set of 1-based positions parsed from a cut LIST such as `1,3-5,7-`.
+///
+/// Ranges are kept sorted and merged, so lookups only need to walk until they pass the
+/// position.
+#[derive(Debug, PartialEq)]
+pub struct RangeSet {
+    ranges: Vec<(usize, usize)>,
+}
+
+fn position(text: &str, list: &str) -> Result<usize, CutError> {
+    match text.parse::<usize>() {
+        Ok(0) => Err("fields and positions are numbered from 1".into()),
+        Ok(n) => Ok(n),
+        Err(_) => Err(format!("invalid field value '{}' in list '{}'", text, list).into()),
+    }
+}
+
+impl RangeSet {
+    pub fn parse(list: &str) -> Result<RangeSet, CutError> {
+        let mut ranges = vec![];
+        for part in list.split([',', ' ']) {
+            if part.is_empty() {
+                return Err(format!("invalid range list '{}'", list).into());
+            }
+            let range = match part.find('-') {
+                Some(_) if part == "-" => {
+                    return Err("invalid range with no endpoint: -".into());
+                }
+                Some(i) => {
+                    let low = match &part[..i] {
+                        "" => 1,
+                        low => position(low, list)?,
+                    };
+                    let high = match &part[i + 1..] {
+                        "" => usize::MAX,
+                        high => position(high, list)?,
+                    };
+                    if low > high {
+                        return Err("invalid decreasing range".into());
+                    }
+                    (low, high)
+                }
+                None => {
+                    let n = position(part, list)?;
+                    (n, n)
+                }
+            };
+            ranges.push(range);
+        }
+        ranges.sort_unstable();
+
+        let mut merged: Vec<(usize, usize)> = vec![];
+        for (low, high) in ranges {
+            match merged.last_mut() {
+                Some(last) if low <= last.1.saturating_add(1) => last.1 = last.1.max(high),
+                _ => merged.push((low, high)),
+            }
+        }
+        Ok(RangeSet { ranges: merged })
+    }
+
+    pub fn contains(&self, position: usize) -> bool {
+        for &(low, high) in &self.ranges {
+            if position < low {
+                return false;
+            }
+            if position <= high {
+                return true;
+            }
+        }
+        false
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn members(list: &str, upto: usize) -> Vec<usize> {
+        let set = RangeSet::parse(list).unwrap();
+        (1..=upto).filter(|&n| set.contains(n)).collect()
+    }
+
+    #[test]
+    fn single_positions_and_ranges() {
+        assert_eq!(members("1,3-5,7-", 9), vec![1, 3, 4, 5, 7, 8, 9]);
+        assert_eq!(members("-2", 5), vec![1, 2]);
+        assert_eq!(members("4-", 5), vec![4, 5]);
+    }
+
+    #[test]
+    fn overlapping_ranges_merge() {
+        let set = RangeSet::parse("5-7,1-3,2-6").unwrap();
+        assert_eq!(set.ranges, vec![(1, 7)]);
+        let set = RangeSet::parse("1,2,3-").unwrap();
+        assert_eq!(set.ranges, vec![(1, usize::MAX)]);
+    }
+
+    #[test]
+    fn rejects_bad_lists() {
+        assert!(RangeSet::parse("0").is_err());
+        assert!(RangeSet::parse("3-1").is_err());
+        assert!(RangeSet::parse("-").is_err());
+        assert!(RangeSet::parse("1,,2").is_err());
+        assert!(RangeSet::parse("a").is_err());
+        assert!(RangeSet::parse("").is_err());
+    }
+}
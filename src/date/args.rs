@@ -0,0 +1,124 @@
+use crate::DateError;
+use arglex::lex;
+use arglex::Arg;
+use coreutils_common::print_and_exit;
+use coreutils_common::unknown_arg_message;
+use coreutils_common::version_string;
+use std::slice::Iter;
+
+const HELP: &str = "
+Usage: date [OPTION]... [+FORMAT]
+Display the current time in the given FORMAT.
+
+Options:
+  -d, --date <STRING>       Display the time described by STRING, a RFC3339 date or @SECONDS,
+                              instead of 'now'
+  -r, --reference <FILE>    Display the last modification time of FILE
+  -u, --utc, --universal    Print Coordinated Universal Time (UTC)
+  --version                 Output version information and exit
+  --help                    Display this help and exit
+
+FORMAT is interpreted like strftime(3). The default is '%a %b %e %H:%M:%S %Z %Y'.
+";
+
+fn print_help() -> ! {
+    print_and_exit(0, HELP);
+}
+
+fn print_version() -> ! {
+    print_and_exit(0, version_string!("date"));
+}
+
+#[derive(Debug, PartialEq)]
+pub enum Source {
+    Now,
+    Date(String),
+    Reference(String),
+}
+
+pub struct Args {
+    pub source: Source,
+    pub utc: bool,
+    pub format: Option<String>,
+}
+
+fn get_arg_to(args: &mut Iter<Arg>, name: &Arg) -> Result<String, DateError> {
+    match args.next() {
+        Some(Arg::Positional(value)) => Ok(value.clone()),
+        _ => Err(format!("{} needs an argument", name).into()),
+    }
+}
+
+pub fn parse(args: Vec<String>) -> Result<Args, DateError> {
+    let args = lex(args);
+
+    let mut args = args.iter();
+    let mut source = Source::Now;
+    let mut utc = false;
+    let mut format = None;
+    while let Some(arg) = args.next() {
+        let next_source = match arg {
+            Arg::Positional(positional) => match positional.strip_prefix('+') {
+                _ if positional == "--" => continue,
+                Some(spec) if format.is_none() => {
+                    format = Some(spec.to_owned());
+                    continue;
+                }
+                Some(_) => return Err(format!("extra operand '{}'", positional).into()),
+                None => return Err("setting the date is not supported".into()),
+            },
+            Arg::Short(short) => match short.as_str() {
+                "d" => Source::Date(get_arg_to(&mut args, arg)?),
+                "r" => Source::Reference(get_arg_to(&mut args, arg)?),
+                "u" => {
+                    utc = true;
+                    continue;
+                }
+                _ => return Err(unknown_arg_message(arg).into()),
+            },
+            Arg::Long(long) => match long.as_str() {
+                "date" => Source::Date(get_arg_to(&mut args, arg)?),
+                "reference" => Source::Reference(get_arg_to(&mut args, arg)?),
+                "utc" | "universal" => {
+                    utc = true;
+                    continue;
+                }
+                "version" => print_version(),
+                "help" => print_help(),
+                _ => return Err(unknown_arg_message(arg).into()),
+            },
+        };
+        if source != Source::Now {
+            return Err("the options to specify dates for printing are mutually exclusive".into());
+        }
+        source = next_source;
+    }
+    Ok(Args {
+        source,
+        utc,
+        format,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse_strs(args: &[&str]) -> Result<Args, DateError> {
+        parse(args.iter().map(|s| s.to_string()).collect())
+    }
+
+    #[test]
+    fn operands() {
+        let args = parse_strs(&["-u", "+%Y"]).unwrap();
+        assert!(args.utc);
+        assert_eq!(args.format.as_deref(), Some("%Y"));
+        assert_eq!(
+            parse_strs(&["-d", "@0"]).unwrap().source,
+            Source::Date("@0".to_owned())
+        );
+        assert!(parse_strs(&["+%Y", "+%m"]).is_err());
+        assert!(parse_strs(&["010203042000"]).is_err());
+        assert!(parse_strs(&["-d", "@0", "-r", "file"]).is_err());
+    }
+}
@@ -0,0 +1,130 @@
+mod args;
+
+use args::Source;
+use chrono::format::Item;
+use chrono::format::StrftimeItems;
+use chrono::DateTime;
+use chrono::Local;
+use chrono::TimeZone;
+use chrono::Utc;
+use coreutils_common::exit_with;
+use coreutils_common::timestamp::parse_date;
+use coreutils_common::CoreError;
+use std::env;
+use std::ffi::CStr;
+use std::fmt;
+use std::fmt::Debug;
+use std::fmt::Display;
+use std::fs;
+
+const DEFAULT_FORMAT: &str = "%a %b %e %H:%M:%S %Z %Y";
+
+pub struct DateError {
+    message: String,
+}
+
+impl Debug for DateError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "date: {}", self.message)
+    }
+}
+
+impl CoreError for DateError {}
+
+impl<T> From<T> for DateError
+where
+    T: ToString,
+{
+    fn from(error: T) -> Self {
+        DateError {
+            message: error.to_string(),
+        }
+    }
+}
+
+/// The local zone's abbreviation at `time`, e.g. `CET`. chrono only knows the numeric offset.
+fn zone_abbreviation(time: &DateTime<Local>) -> Option<String> {
+    let seconds = time.timestamp() as libc::time_t;
+    let mut tm: libc::tm = unsafe { std::mem::zeroed() };
+    if unsafe { libc::localtime_r(&seconds, &mut tm) }.is_null() || tm.tm_zone.is_null() {
+        return None;
+    }
+    Some(
+        unsafe { CStr::from_ptr(tm.tm_zone) }
+            .to_string_lossy()
+            .into_owned(),
+    )
+}
+
+/// Replaces each `%Z` in `format` with `zone`, leaving `%%` escapes alone.
+fn substitute_zone(format: &str, zone: &str) -> String {
+    let mut substituted = String::with_capacity(format.len());
+    let mut chars = format.chars();
+    while let Some(c) = chars.next() {
+        if c != '%' {
+            substituted.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('Z') => substituted.push_str(&zone.replace('%', "%%")),
+            Some(next) => {
+                substituted.push('%');
+                substituted.push(next);
+            }
+            None => substituted.push('%'),
+        }
+    }
+    substituted
+}
+
+fn render<Tz>(time: &DateTime<Tz>, format: &str) -> Result<String, DateError>
+where
+    Tz: TimeZone,
+    Tz::Offset: Display,
+{
+    let items = StrftimeItems::new(format);
+    if items.clone().any(|item| item == Item::Error) {
+        return Err(format!("invalid format '{}'", format).into());
+    }
+    Ok(time.format_with_items(items).to_string())
+}
+
+fn main() {
+    if let Err(e) = run() {
+        exit_with(&e);
+    }
+}
+
+fn run() -> Result<(), DateError> {
+    let args = args::parse(env::args().skip(1).collect())?;
+    let time = match args.source {
+        Source::Now => Local::now(),
+        Source::Date(date) => parse_date(&date).map_err(|e| format!("invalid date: {}", e))?,
+        Source::Reference(file) => fs::metadata(&file)
+            .and_then(|metadata| metadata.modified())
+            .map_err(|e| format!("{}: {}", file, e))?
+            .into(),
+    };
+    let format = args.format.as_deref().unwrap_or(DEFAULT_FORMAT);
+    let output = if args.utc {
+        render(&time.with_timezone(&Utc), format)?
+    } else {
+        match zone_abbreviation(&time) {
+            Some(zone) => render(&time, &substitute_zone(format, &zone))?,
+            None => render(&time, format)?,
+        }
+    };
+    println!("{}", output);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn zone_substitution() {
+        assert_eq!(substitute_zone("%H %Z", "CET"), "%H CET");
+        assert_eq!(substitute_zone("%%Z %Z%", "UTC"), "%%Z UTC%");
+    }
+}
@@ -0,0 +1,127 @@
+use crate::DfError;
+use arglex::lex;
+use arglex::Arg;
+use coreutils_common::print_and_exit;
+use coreutils_common::unknown_arg_message;
+use coreutils_common::version_string;
+use std::slice::Iter;
+
+const HELP: &str = "
+Usage: df [OPTION]... [FILE]...
+Show information about the file system on which each FILE resides,
+or all mounted file systems by default.
+
+Options:
+  -h, --human-readable      Print sizes in powers of 1024 (e.g., 1023M)
+  -i, --inodes              List inode information instead of block usage
+  --si                      Like -h, but use powers of 1000 not 1024
+  -t, --type <TYPE>         Limit listing to file systems of type TYPE
+  --total                   Produce a grand total
+  -x, --exclude-type <TYPE> Limit listing to file systems not of type TYPE
+  --version                 Output version information and exit
+  --help                    Display this help and exit
+
+Sizes are in units of 1024 bytes unless -h is given. -t and -x may be given
+more than once, and take their types from /proc/self/mounts.
+";
+
+fn print_help() -> ! {
+    print_and_exit(0, HELP);
+}
+
+fn print_version() -> ! {
+    print_and_exit(0, version_string!("df"));
+}
+
+pub struct Args {
+    pub human_readable: bool,
+    /// With `human_readable`, use powers of 1000 rather than 1024.
+    pub si: bool,
+    pub inodes: bool,
+    pub total: bool,
+    /// File system types to list; empty for all of them.
+    pub types: Vec<String>,
+    pub exclude_types: Vec<String>,
+    pub files: Vec<String>,
+}
+
+fn get_arg_to(args: &mut Iter<Arg>, name: &Arg) -> Result<String, DfError> {
+    match args.next() {
+        Some(Arg::Positional(value)) => Ok(value.clone()),
+        _ => Err(format!("{} needs an argument", name).into()),
+    }
+}
+
+pub fn parse(args: Vec<String>) -> Result<Args, DfError> {
+    let mut human_readable = false;
+    let mut si = false;
+    let mut inodes = false;
+    let mut total = false;
+    let mut types = vec![];
+    let mut exclude_types = vec![];
+    let mut files = vec![];
+    let args = lex(args);
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg {
+            Arg::Positional(positional) => match positional.as_str() {
+                "--" => continue,
+                _ => files.push(positional.clone()),
+            },
+            Arg::Short(short) => match short.as_str() {
+                "h" => {
+                    human_readable = true;
+                    si = false;
+                }
+                "i" => inodes = true,
+                "t" => types.push(get_arg_to(&mut args, arg)?),
+                "x" => exclude_types.push(get_arg_to(&mut args, arg)?),
+                _ => return Err(unknown_arg_message(arg).into()),
+            },
+            Arg::Long(long) => match long.as_str() {
+                "human-readable" => {
+                    human_readable = true;
+                    si = false;
+                }
+                "si" => {
+                    human_readable = true;
+                    si = true;
+                }
+                "inodes" => inodes = true,
+                "total" => total = true,
+                "type" => types.push(get_arg_to(&mut args, arg)?),
+                "exclude-type" => exclude_types.push(get_arg_to(&mut args, arg)?),
+                "version" => print_version(),
+                "help" => print_help(),
+                _ => return Err(unknown_arg_message(arg).into()),
+            },
+        };
+    }
+    Ok(Args {
+        human_readable,
+        si,
+        inodes,
+        total,
+        types,
+        exclude_types,
+        files,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse_strs(args: &[&str]) -> Result<Args, DfError> {
+        parse(args.iter().map(|s| s.to_string()).collect())
+    }
+
+    #[test]
+    fn type_filters_accumulate() {
+        let args = parse_strs(&["-t", "ext4", "--type=xfs", "-x", "tmpfs", "/"]).unwrap();
+        assert_eq!(args.types, vec!["ext4", "xfs"]);
+        assert_eq!(args.exclude_types, vec!["tmpfs"]);
+        assert_eq!(args.files, vec!["/"]);
+        assert!(parse_strs(&["-x"]).is_err());
+    }
+}
@@ -0,0 +1,312 @@
+mod args;
+
+use args::Args;
+use coreutils_common::exit_with;
+use coreutils_common::report_error;
+use coreutils_common::units::human_readable;
+use coreutils_common::CoreError;
+use std::env;
+use std::ffi::CString;
+use std::fmt;
+use std::fmt::Debug;
+use std::fs;
+use std::io;
+use std::mem::MaybeUninit;
+use std::os::unix::ffi::OsStrExt;
+use std::os::unix::fs::MetadataExt;
+use std::path::Path;
+use std::process::exit;
+
+pub struct DfError {
+    message: String,
+}
+
+impl Debug for DfError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "df: {}", self.message)
+    }
+}
+
+impl CoreError for DfError {}
+
+impl<T> From<T> for DfError
+where
+    T: ToString,
+{
+    fn from(error: T) -> Self {
+        DfError {
+            message: error.to_string(),
+        }
+    }
+}
+
+/// One line of `/proc/self/mounts`.
+#[derive(Clone)]
+struct Mount {
+    source: String,
+    target: String,
+    fs_type: String,
+}
+
+/// Undoes the `\040`-style octal escapes the kernel uses for blanks in mount table fields.
+fn unescape(field: &str) -> String {
+    let bytes = field.as_bytes();
+    let mut unescaped = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        let octal = bytes.get(i + 1..i + 4).and_then(|digits| {
+            std::str::from_utf8(digits)
+                .ok()
+                .and_then(|digits| u8::from_str_radix(digits, 8).ok())
+        });
+        match octal {
+            Some(byte) if bytes[i] == b'\\' => {
+                unescaped.push(byte);
+                i += 4;
+            }
+            _ => {
+                unescaped.push(bytes[i]);
+                i += 1;
+            }
+        }
+    }
+    String::from_utf8_lossy(&unescaped).into_owned()
+}
+
+fn mounts() -> io::Result<Vec<Mount>> {
+    let table = fs::read_to_string("/proc/self/mounts")?;
+    Ok(table
+        .lines()
+        .filter_map(|line| {
+            let mut fields = line.split(' ');
+            Some(Mount {
+                source: unescape(fields.next()?),
+                target: unescape(fields.next()?),
+                fs_type: unescape(fields.next()?),
+            })
+        })
+        .collect())
+}
+
+fn statvfs(path: &Path) -> io::Result<libc::statvfs> {
+    let path = CString::new(path.as_os_str().as_bytes())?;
+    let mut stats = MaybeUninit::<libc::statvfs>::uninit();
+    if unsafe { libc::statvfs(path.as_ptr(), stats.as_mut_ptr()) } != 0 {
+        return Err(io::Error::last_os_error());
+    }
+    Ok(unsafe { stats.assume_init() })
+}
+
+/// Percentage of `used + available` that is used, rounded up the way df does.
+fn percentage(used: u64, available: u64) -> String {
+    let total = used + available;
+    if total == 0 {
+        return "-".to_string();
+    }
+    format!("{}%", (used * 100).div_ceil(total))
+}
+
+/// The numbers df shows for one file system, in bytes or (with -i) inodes.
+#[derive(Clone, Copy, Default)]
+struct Usage {
+    total: u64,
+    used: u64,
+    available: u64,
+}
+
+impl Usage {
+    fn new(stats: &libc::statvfs, args: &Args) -> Self {
+        let (total, free, available) = if args.inodes {
+            (stats.f_files, stats.f_ffree, stats.f_favail)
+        } else {
+            let size = stats.f_frsize;
+            (
+                stats.f_blocks * size,
+                stats.f_bfree * size,
+                stats.f_bavail * size,
+            )
+        };
+        Usage {
+            total,
+            used: total - free,
+            available,
+        }
+    }
+
+    fn add(&mut self, other: Usage) {
+        self.total += other.total;
+        self.used += other.used;
+        self.available += other.available;
+    }
+}
+
+fn row(source: &str, usage: Usage, target: &str, args: &Args) -> Vec<String> {
+    let Usage {
+        total,
+        used,
+        available,
+    } = usage;
+    let amount = |amount: u64| {
+        if args.inodes {
+            amount.to_string()
+        } else if args.human_readable {
+            human_readable(amount, !args.si)
+        } else {
+            amount.div_ceil(1024).to_string()
+        }
+    };
+    vec![
+        source.to_string(),
+        amount(total),
+        amount(used),
+        amount(available),
+        percentage(used, available),
+        target.to_string(),
+    ]
+}
+
+fn header(args: &Args) -> Vec<String> {
+    let header: [&str; 6] = if args.inodes {
+        [
+            "Filesystem",
+            "Inodes",
+            "IUsed",
+            "IFree",
+            "IUse%",
+            "Mounted on",
+        ]
+    } else if args.human_readable {
+        ["Filesystem", "Size", "Used", "Avail", "Use%", "Mounted on"]
+    } else {
+        [
+            "Filesystem",
+            "1K-blocks",
+            "Used",
+            "Available",
+            "Use%",
+            "Mounted on",
+        ]
+    };
+    header.iter().map(|column| column.to_string()).collect()
+}
+
+/// Prints the rows as columns, with the numbers right-aligned.
+fn print_table(rows: &[Vec<String>]) {
+    let mut widths = [0; 6];
+    for row in rows {
+        for (width, column) in widths.iter_mut().zip(row) {
+            *width = (*width).max(column.len());
+        }
+    }
+    widths[0] = widths[0].max(14);
+    for row in rows {
+        let line = format!(
+            "{:<w0$} {:>w1$} {:>w2$} {:>w3$} {:>w4$} {}",
+            row[0],
+            row[1],
+            row[2],
+            row[3],
+            row[4],
+            row[5],
+            w0 = widths[0],
+            w1 = widths[1],
+            w2 = widths[2],
+            w3 = widths[3],
+            w4 = widths[4],
+        );
+        println!("{}", line);
+    }
+}
+
+/// The mount `path` is on, and the usage of its filesystem.
+fn mount_of(mounts: &[Mount], path: &Path) -> io::Result<(Mount, libc::statvfs)> {
+    let dev = fs::metadata(path)?.dev();
+    let stats = statvfs(path)?;
+    // Later mounts hide earlier ones on the same mount point, so the last match is visible.
+    let mount = mounts
+        .iter()
+        .rev()
+        .find(|mount| fs::metadata(&mount.target).is_ok_and(|m| m.dev() == dev))
+        .cloned()
+        .unwrap_or_else(|| Mount {
+            source: "-".to_string(),
+            target: "-".to_string(),
+            fs_type: "-".to_string(),
+        });
+    Ok((mount, stats))
+}
+
+/// Whether -t and -x let `mount` be listed.
+fn selected(mount: &Mount, args: &Args) -> bool {
+    (args.types.is_empty() || args.types.contains(&mount.fs_type))
+        && !args.exclude_types.contains(&mount.fs_type)
+}
+
+fn main() {
+    if let Err(e) = run() {
+        exit_with(&e);
+    }
+}
+
+fn run() -> Result<(), DfError> {
+    let args = args::parse(env::args().skip(1).collect())?;
+    let mounts =
+        mounts().map_err(|e| format!("cannot read table of mounted file systems: {}", e))?;
+    let mut failed = false;
+    let mut rows = vec![header(&args)];
+    let mut total = Usage::default();
+    let mut add_row = |mount: &Mount, stats: &libc::statvfs| {
+        let usage = Usage::new(stats, &args);
+        total.add(usage);
+        rows.push(row(&mount.source, usage, &mount.target, &args));
+    };
+    if args.files.is_empty() {
+        for mount in mounts.iter().filter(|mount| selected(mount, &args)) {
+            // Pseudo filesystems like proc have no blocks and aren't worth listing.
+            match statvfs(Path::new(&mount.target)) {
+                Ok(stats) if stats.f_blocks > 0 => add_row(mount, &stats),
+                _ => {}
+            }
+        }
+    }
+    for file in &args.files {
+        match mount_of(&mounts, Path::new(file)) {
+            Ok((mount, stats)) if selected(&mount, &args) => add_row(&mount, &stats),
+            Ok(_) => {}
+            Err(e) => {
+                report_error("df", format!("{}: {}", file, e));
+                failed = true;
+            }
+        }
+    }
+    if rows.len() == 1 && !failed {
+        return Err("no file systems processed".into());
+    }
+    if args.total {
+        rows.push(row("total", total, "-", &args));
+    }
+    print_table(&rows);
+    if failed {
+        exit(1);
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn unescaping() {
+        assert_eq!(unescape("/mnt/my\\040disk"), "/mnt/my disk");
+        assert_eq!(unescape("back\\134slash"), "back\\slash");
+        assert_eq!(unescape("trailing\\"), "trailing\\");
+    }
+
+    #[test]
+    fn percentages() {
+        assert_eq!(percentage(0, 0), "-");
+        assert_eq!(percentage(1, 2), "34%");
+        assert_eq!(percentage(5, 0), "100%");
+    }
+}
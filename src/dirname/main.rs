@@ -0,0 +1,123 @@
+use arglex::lex;
+use arglex::Arg;
+use coreutils_common::die;
+use coreutils_common::output::Terminator;
+use coreutils_common::print_and_exit;
+use coreutils_common::unknown_arg_message;
+use coreutils_common::version_string;
+use std::env;
+use std::io;
+use std::io::Write;
+
+const HELP: &str = "
+Usage: dirname [OPTION] NAME...
+Output each NAME with its last non-slash component and trailing slashes removed;
+if NAME contains no slashes, output '.' (meaning the current directory).
+
+Options:
+  -z, --zero                End each output line with NUL, not newline
+  --version                 Output version information and exit
+  --help                    Display this help and exit
+";
+
+struct Args {
+    terminator: Terminator,
+    names: Vec<String>,
+}
+
+fn fail(message: impl AsRef<str>) -> ! {
+    die(format!("dirname: {}", message.as_ref()));
+}
+
+fn parse(args: Vec<String>) -> Args {
+    let mut arg_struct = Args {
+        terminator: Terminator::Newline,
+        names: vec![],
+    };
+    let mut delimited = false;
+    for arg in &lex(args) {
+        if delimited {
+            arg_struct.names.push(arg.to_string());
+            continue;
+        }
+        match arg {
+            Arg::Positional(positional) => match positional.as_str() {
+                "--" => delimited = true,
+                _ => arg_struct.names.push(positional.clone()),
+            },
+            Arg::Short(short) => match short.as_str() {
+                "z" => arg_struct.terminator = Terminator::Nul,
+                _ => fail(unknown_arg_message(arg)),
+            },
+            Arg::Long(long) => match long.as_str() {
+                "zero" => arg_struct.terminator = Terminator::Nul,
+                "version" => print_and_exit(0, version_string!("dirname")),
+                "help" => print_and_exit(0, HELP),
+                _ => fail(unknown_arg_message(arg)),
+            },
+        }
+    }
+    arg_struct
+}
+
+/// The parent of `name` following the POSIX dirname steps: trailing slashes are ignored, a name
+/// without slashes yields `.`, and anything that reduces to nothing but slashes yields `/`.
+fn dirname(name: &str) -> &str {
+    let trimmed = name.trim_end_matches('/');
+    if trimmed.is_empty() {
+        return if name.is_empty() { "." } else { "/" };
+    }
+    match trimmed.rfind('/') {
+        Some(i) => {
+            let parent = trimmed[..i].trim_end_matches('/');
+            if parent.is_empty() {
+                "/"
+            } else {
+                parent
+            }
+        }
+        None => ".",
+    }
+}
+
+fn main() {
+    let args = parse(env::args().skip(1).collect());
+    if args.names.is_empty() {
+        fail("missing operand");
+    }
+    let mut output = String::new();
+    for name in &args.names {
+        output.push_str(dirname(name));
+        output.push(args.terminator.as_char());
+    }
+    if let Err(e) = io::stdout().write_all(output.as_bytes()) {
+        fail(format!("write error: {}", e));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parent_directories() {
+        assert_eq!(dirname("/usr/bin/sort"), "/usr/bin");
+        assert_eq!(dirname("usr/bin"), "usr");
+        assert_eq!(dirname("/usr"), "/");
+        assert_eq!(dirname("a//b"), "a");
+    }
+
+    #[test]
+    fn no_slash_is_current_directory() {
+        assert_eq!(dirname("sort"), ".");
+        assert_eq!(dirname("usr/"), ".");
+        assert_eq!(dirname(""), ".");
+    }
+
+    #[test]
+    fn root() {
+        assert_eq!(dirname("/"), "/");
+        assert_eq!(dirname("///"), "/");
+        assert_eq!(dirname("//usr//"), "/");
+    }
+}
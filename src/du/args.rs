@@ -0,0 +1,188 @@
+use crate::DuError;
+use arglex::lex;
+use arglex::Arg;
+use coreutils_common::print_and_exit;
+use coreutils_common::timestamp::TimeStyle;
+use coreutils_common::unknown_arg_message;
+use coreutils_common::version_string;
+use std::slice::Iter;
+
+const HELP: &str = "
+Usage: du [OPTION]... [FILE]...
+Summarize disk usage of each FILE, recursively for directories.
+
+With no FILE, summarize the current directory.
+
+Options:
+  -a, --all                 Write counts for all files, not just directories
+  -d, --max-depth <N>       Print the total for a directory only if it is N or fewer levels
+                              below the command line argument
+  -h, --human-readable      Print sizes in human readable format (e.g., 1K 234M 2G)
+  -L, --dereference         Follow all symbolic links
+  -P, --no-dereference      Don't follow any symbolic links (the default)
+  -s, --summarize           Display only a total for each argument
+  --si                      Like -h, but use powers of 1000 not 1024
+  --time                    Show the latest modification time of any file in
+                              each entry, as well as its size
+  --time-style <STYLE>      Show times with --time in STYLE: full-iso,
+                              long-iso (the default), iso, locale or +FORMAT
+  -x, --one-file-system     Skip directories on different file systems
+  --version                 Output version information and exit
+  --help                    Display this help and exit
+
+Sizes are in units of 1024 bytes unless -h is given.
+";
+
+fn print_help() -> ! {
+    print_and_exit(0, HELP);
+}
+
+fn print_version() -> ! {
+    print_and_exit(0, version_string!("du"));
+}
+
+pub struct Args {
+    pub all: bool,
+    pub dereference: bool,
+    pub human_readable: bool,
+    /// With `human_readable`, use powers of 1000 rather than 1024.
+    pub si: bool,
+    pub max_depth: Option<usize>,
+    pub one_file_system: bool,
+    /// With `--time`, the style to show modification times in.
+    pub time: Option<TimeStyle>,
+    pub files: Vec<String>,
+}
+
+fn get_arg_to(args: &mut Iter<Arg>, name: &Arg) -> Result<String, DuError> {
+    match args.next() {
+        Some(Arg::Positional(value)) => Ok(value.clone()),
+        _ => Err(format!("{} needs an argument", name).into()),
+    }
+}
+
+fn parse_depth(depth: &str) -> Result<usize, DuError> {
+    depth
+        .parse()
+        .map_err(|_| format!("invalid maximum depth '{}'", depth).into())
+}
+
+pub fn parse(args: Vec<String>) -> Result<Args, DuError> {
+    let args = lex(args);
+
+    let mut args = args.iter();
+    let mut all = false;
+    let mut dereference = false;
+    let mut human_readable = false;
+    let mut si = false;
+    let mut summarize = false;
+    let mut max_depth = None;
+    let mut one_file_system = false;
+    let mut time = false;
+    let mut time_style = TimeStyle::LongIso;
+    let mut files = vec![];
+    while let Some(arg) = args.next() {
+        match arg {
+            Arg::Positional(positional) => match positional.as_str() {
+                "--" => continue,
+                _ => files.push(positional.clone()),
+            },
+            Arg::Short(short) => match short.as_str() {
+                "a" => all = true,
+                "L" => dereference = true,
+                "P" => dereference = false,
+                "d" => max_depth = Some(parse_depth(&get_arg_to(&mut args, arg)?)?),
+                "h" => {
+                    human_readable = true;
+                    si = false;
+                }
+                "s" => summarize = true,
+                "x" => one_file_system = true,
+                _ => return Err(unknown_arg_message(arg).into()),
+            },
+            Arg::Long(long) => match long.as_str() {
+                "all" => all = true,
+                "dereference" => dereference = true,
+                "no-dereference" => dereference = false,
+                "max-depth" => max_depth = Some(parse_depth(&get_arg_to(&mut args, arg)?)?),
+                "human-readable" => {
+                    human_readable = true;
+                    si = false;
+                }
+                "si" => {
+                    human_readable = true;
+                    si = true;
+                }
+                "one-file-system" => one_file_system = true,
+                "summarize" => summarize = true,
+                "time" => time = true,
+                "time-style" => time_style = TimeStyle::parse(&get_arg_to(&mut args, arg)?)?,
+                "version" => print_version(),
+                "help" => print_help(),
+                _ => return Err(unknown_arg_message(arg).into()),
+            },
+        };
+    }
+    if summarize {
+        if all {
+            return Err("cannot both summarize and show all entries".into());
+        }
+        if max_depth.is_some_and(|depth| depth != 0) {
+            return Err("cannot both summarize and use --max-depth".into());
+        }
+        max_depth = Some(0);
+    }
+    if files.is_empty() {
+        files.push(".".to_owned());
+    }
+    Ok(Args {
+        all,
+        dereference,
+        human_readable,
+        si,
+        max_depth,
+        one_file_system,
+        time: if time { Some(time_style) } else { None },
+        files,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse_strs(args: &[&str]) -> Result<Args, DuError> {
+        parse(args.iter().map(|s| s.to_string()).collect())
+    }
+
+    #[test]
+    fn depths() {
+        assert_eq!(parse_strs(&[]).unwrap().max_depth, None);
+        assert_eq!(parse_strs(&["-s"]).unwrap().max_depth, Some(0));
+        assert_eq!(parse_strs(&["--max-depth=2"]).unwrap().max_depth, Some(2));
+        assert!(parse_strs(&["-s", "--max-depth=1"]).is_err());
+        assert!(parse_strs(&["-s", "-a"]).is_err());
+        assert!(parse_strs(&["-d", "x"]).is_err());
+    }
+
+    #[test]
+    fn one_file_system() {
+        assert!(!parse_strs(&[]).unwrap().one_file_system);
+        assert!(parse_strs(&["-x"]).unwrap().one_file_system);
+        assert!(parse_strs(&["--one-file-system"]).unwrap().one_file_system);
+    }
+
+    #[test]
+    fn time_styles() {
+        assert_eq!(parse_strs(&[]).unwrap().time, None);
+        assert_eq!(
+            parse_strs(&["--time"]).unwrap().time,
+            Some(TimeStyle::LongIso)
+        );
+        assert_eq!(
+            parse_strs(&["--time-style=+%Y", "--time"]).unwrap().time,
+            Some(TimeStyle::Format("%Y".to_string()))
+        );
+        assert!(parse_strs(&["--time-style=short"]).is_err());
+    }
+}
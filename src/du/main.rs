@@ -0,0 +1,162 @@
+mod args;
+
+use args::Args;
+use coreutils_common::exit_with;
+use coreutils_common::report_error;
+use coreutils_common::units::human_readable;
+use coreutils_common::walk::Ancestors;
+use coreutils_common::CoreError;
+use std::collections::HashSet;
+use std::env;
+use std::fmt;
+use std::fmt::Debug;
+use std::fs;
+use std::os::unix::fs::MetadataExt;
+use std::path::Path;
+use std::process::exit;
+
+pub struct DuError {
+    message: String,
+}
+
+impl Debug for DuError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "du: {}", self.message)
+    }
+}
+
+impl CoreError for DuError {}
+
+impl<T> From<T> for DuError
+where
+    T: ToString,
+{
+    fn from(error: T) -> Self {
+        DuError {
+            message: error.to_string(),
+        }
+    }
+}
+
+struct Walk<'a> {
+    args: &'a Args,
+    /// Files with several links already counted, so each is only counted once.
+    seen: HashSet<(u64, u64)>,
+    ancestors: Ancestors,
+    failed: bool,
+}
+
+/// The space used by a file and everything below it, and the latest time any of it was modified.
+#[derive(Clone, Copy, Default)]
+struct Usage {
+    bytes: u64,
+    /// Seconds and nanoseconds since the epoch.
+    modified: (i64, u32),
+}
+
+impl Usage {
+    fn add(&mut self, other: Usage) {
+        self.bytes += other.bytes;
+        self.modified = self.modified.max(other.modified);
+    }
+}
+
+impl Walk<'_> {
+    fn report(&self, usage: Usage, path: &Path) {
+        let size = if self.args.human_readable {
+            human_readable(usage.bytes, !self.args.si)
+        } else {
+            usage.bytes.div_ceil(1024).to_string()
+        };
+        match &self.args.time {
+            Some(style) => {
+                let (seconds, nanoseconds) = usage.modified;
+                let time = style.format(seconds, nanoseconds);
+                println!("{}\t{}\t{}", size, time, path.display());
+            }
+            None => println!("{}\t{}", size, path.display()),
+        }
+    }
+
+    /// Returns the usage of `path` and everything below it, printing the lines for anything
+    /// no deeper than the maximum depth on the way.
+    fn visit(&mut self, path: &Path, depth: usize) -> Usage {
+        let metadata = if self.args.dereference {
+            fs::metadata(path)
+        } else {
+            fs::symlink_metadata(path)
+        };
+        let metadata = match metadata {
+            Ok(metadata) => metadata,
+            Err(e) => {
+                report_error("du", format!("cannot access '{}': {}", path.display(), e));
+                self.failed = true;
+                return Usage::default();
+            }
+        };
+        if self.args.one_file_system && !self.ancestors.same_file_system(&metadata) {
+            return Usage::default();
+        }
+        let is_dir = metadata.is_dir();
+        let bytes = if !is_dir
+            && metadata.nlink() > 1
+            && !self.seen.insert((metadata.dev(), metadata.ino()))
+        {
+            0
+        } else {
+            metadata.blocks() * 512
+        };
+        let mut usage = Usage {
+            bytes,
+            modified: (metadata.mtime(), metadata.mtime_nsec() as u32),
+        };
+        if is_dir && !self.ancestors.enter("du", path, &metadata) {
+            self.failed = true;
+        } else if is_dir {
+            match fs::read_dir(path).and_then(|entries| entries.collect::<Result<Vec<_>, _>>()) {
+                Ok(mut entries) => {
+                    entries.sort_by_key(|entry| entry.file_name());
+                    for entry in entries {
+                        usage.add(self.visit(&entry.path(), depth + 1));
+                    }
+                }
+                Err(e) => {
+                    report_error(
+                        "du",
+                        format!("cannot read directory '{}': {}", path.display(), e),
+                    );
+                    self.failed = true;
+                }
+            }
+            self.ancestors.leave();
+        }
+        let shown = is_dir || self.args.all || depth == 0;
+        if shown && self.args.max_depth.is_none_or(|max| depth <= max) {
+            self.report(usage, path);
+        }
+        usage
+    }
+}
+
+fn main() {
+    if let Err(e) = run() {
+        exit_with(&e);
+    }
+}
+
+fn run() -> Result<(), DuError> {
+    let args = args::parse(env::args().skip(1).collect())?;
+    let mut walk = Walk {
+        args: &args,
+        seen: HashSet::new(),
+        ancestors: Ancestors::new(),
+        failed: false,
+    };
+    for file in &args.files {
+        walk.visit(Path::new(file), 0);
+    }
+    if walk.failed {
+        exit(1);
+    }
+    Ok(())
+}
@@ -0,0 +1,170 @@
+use arglex::lex;
+use arglex::Arg;
+use coreutils_common::die;
+use coreutils_common::print_and_exit;
+use coreutils_common::version_string;
+use std::env;
+use std::io;
+use std::io::Write;
+
+const HELP: &str = "
+Usage: echo [SHORT-OPTION]... [STRING]...
+Echo the STRING(s) to standard output, separated by spaces.
+
+Options:
+  -n                        Do not output the trailing newline
+  -e                        Enable interpretation of backslash escapes
+  -E                        Disable interpretation of backslash escapes (default)
+  --version                 Output version information and exit
+  --help                    Display this help and exit
+
+--help and --version are only recognized when given as the only argument.
+Option parsing stops at the first argument that isn't made up of the letters above,
+so `echo -x` prints -x.
+
+With -e, the following sequences are recognized:
+  \\\\      backslash
+  \\a      alert (BEL)
+  \\b      backspace
+  \\c      produce no further output
+  \\e      escape
+  \\f      form feed
+  \\n      new line
+  \\r      carriage return
+  \\t      horizontal tab
+  \\v      vertical tab
+  \\0NNN   byte with octal value NNN (1 to 3 digits)
+  \\xHH    byte with hexadecimal value HH (1 to 2 digits)
+";
+
+struct Flags {
+    newline: bool,
+    escapes: bool,
+}
+
+/// The letters of `raw` if it is a cluster of echo's short options, like `-n` or `-neE`.
+fn option_letters(raw: &str) -> Option<String> {
+    let lexed = lex(vec![raw.to_owned()]);
+    let letters = match lexed.as_slice() {
+        [Arg::Short(first)] => first.clone(),
+        [Arg::Short(first), Arg::Positional(rest)] => format!("{}{}", first, rest),
+        _ => return None,
+    };
+    if letters.chars().all(|c| "neE".contains(c)) {
+        Some(letters)
+    } else {
+        None
+    }
+}
+
+/// Expand the `-e` escapes in `s` onto `out`. Returns false if `\c` asked to stop output.
+fn unescape(s: &str, out: &mut Vec<u8>) -> bool {
+    let bytes = s.as_bytes();
+    let mut i = 0;
+    while i < bytes.len() {
+        if bytes[i] != b'\\' || i + 1 == bytes.len() {
+            out.push(bytes[i]);
+            i += 1;
+            continue;
+        }
+        i += 1;
+        let escape = bytes[i];
+        i += 1;
+        match escape {
+            b'\\' => out.push(b'\\'),
+            b'a' => out.push(0x07),
+            b'b' => out.push(0x08),
+            b'c' => return false,
+            b'e' => out.push(0x1b),
+            b'f' => out.push(0x0c),
+            b'n' => out.push(b'\n'),
+            b'r' => out.push(b'\r'),
+            b't' => out.push(b'\t'),
+            b'v' => out.push(0x0b),
+            b'0' => {
+                let (value, len) = parse_digits(&bytes[i..], 8, 3);
+                out.push(value);
+                i += len;
+            }
+            b'x' => {
+                let (value, len) = parse_digits(&bytes[i..], 16, 2);
+                if len == 0 {
+                    out.extend_from_slice(b"\\x");
+                } else {
+                    out.push(value);
+                    i += len;
+                }
+            }
+            other => {
+                out.push(b'\\');
+                out.push(other);
+            }
+        }
+    }
+    true
+}
+
+/// Parse up to `max` leading digits in `radix`, returning the byte value and digits consumed.
+fn parse_digits(bytes: &[u8], radix: u32, max: usize) -> (u8, usize) {
+    let mut value: u32 = 0;
+    let mut len = 0;
+    for &b in bytes.iter().take(max) {
+        match (b as char).to_digit(radix) {
+            Some(digit) => value = value * radix + digit,
+            None => break,
+        }
+        len += 1;
+    }
+    (value as u8, len)
+}
+
+fn main() {
+    let raw_args: Vec<String> = env::args().skip(1).collect();
+    if raw_args.len() == 1 {
+        match raw_args[0].as_str() {
+            "--help" => print_and_exit(0, HELP),
+            "--version" => print_and_exit(0, version_string!("echo")),
+            _ => {}
+        }
+    }
+
+    let mut flags = Flags {
+        newline: true,
+        escapes: false,
+    };
+    let mut strings = raw_args.iter().peekable();
+    while let Some(letters) = strings.peek().and_then(|raw| option_letters(raw)) {
+        for letter in letters.chars() {
+            match letter {
+                'n' => flags.newline = false,
+                'e' => flags.escapes = true,
+                _ => flags.escapes = false,
+            }
+        }
+        strings.next();
+    }
+
+    let mut out = vec![];
+    let mut finished = true;
+    for (i, string) in strings.enumerate() {
+        if i > 0 {
+            out.push(b' ');
+        }
+        if flags.escapes {
+            if !unescape(string, &mut out) {
+                finished = false;
+                break;
+            }
+        } else {
+            out.extend_from_slice(string.as_bytes());
+        }
+    }
+    if flags.newline && finished {
+        out.push(b'\n');
+    }
+    let stdout = io::stdout();
+    let mut stdout = stdout.lock();
+    if let Err(e) = stdout.write_all(&out).and_then(|_| stdout.flush()) {
+        die(format!("echo: write error: {}", e));
+    }
+}
@@ -0,0 +1,254 @@
+use arglex::lex;
+use arglex::Arg;
+use coreutils_common::exit_with;
+use coreutils_common::output::Terminator;
+use coreutils_common::print_and_exit;
+use coreutils_common::unknown_arg_message;
+use coreutils_common::version_string;
+use coreutils_common::CoreError;
+use std::env;
+use std::ffi::OsString;
+use std::fmt;
+use std::fmt::Debug;
+use std::io;
+use std::io::ErrorKind;
+use std::io::Write;
+use std::os::unix::ffi::OsStrExt;
+use std::os::unix::process::CommandExt;
+use std::process::Command;
+use std::slice::Iter;
+
+const HELP: &str = "
+Usage: env [OPTION]... [-] [NAME=VALUE]... [COMMAND [ARG]...]
+Set each NAME to VALUE in the environment and run COMMAND.
+
+Options:
+  -i, --ignore-environment  Start with an empty environment
+  -0, --null                End each output line with NUL, not newline
+  -u, --unset <NAME>        Remove variable from the environment
+  --version                 Output version information and exit
+  --help                    Display this help and exit
+
+A mere - implies -i. If no COMMAND, print the resulting environment.
+";
+
+/// Exit statuses follow GNU env: 125 for env's own failures, 126 if COMMAND could not be run
+/// and 127 if it was not found.
+pub struct EnvError {
+    message: String,
+    code: i32,
+}
+
+impl Debug for EnvError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "env: {}", self.message)
+    }
+}
+
+impl CoreError for EnvError {
+    fn exit_code(&self) -> i32 {
+        self.code
+    }
+}
+
+impl<T> From<T> for EnvError
+where
+    T: ToString,
+{
+    fn from(error: T) -> Self {
+        EnvError {
+            message: error.to_string(),
+            code: 125,
+        }
+    }
+}
+
+struct Args {
+    ignore_environment: bool,
+    null: bool,
+    unset: Vec<String>,
+    assignments: Vec<(String, String)>,
+    command: Vec<String>,
+}
+
+/// The value of an option: the rest of its own argument if there was any, otherwise the next
+/// raw argument.
+fn value_of(
+    name: &Arg,
+    lexed: &mut Iter<Arg>,
+    raw_args: &mut impl Iterator<Item = String>,
+) -> Result<String, EnvError> {
+    match lexed.next() {
+        Some(Arg::Positional(value)) => Ok(value.clone()),
+        _ => raw_args
+            .next()
+            .ok_or_else(|| format!("{} needs an argument", name).into()),
+    }
+}
+
+/// Lexes options one raw argument at a time so that everything from the command word on is
+/// passed through untouched, even if it looks like an option.
+fn parse(raw_args: Vec<String>) -> Result<Args, EnvError> {
+    let mut arg_struct = Args {
+        ignore_environment: false,
+        null: false,
+        unset: vec![],
+        assignments: vec![],
+        command: vec![],
+    };
+    let mut raw_args = raw_args.into_iter().peekable();
+    while let Some(raw) = raw_args.peek() {
+        if raw == "-" {
+            arg_struct.ignore_environment = true;
+            raw_args.next();
+            continue;
+        }
+        if raw == "--" {
+            raw_args.next();
+            break;
+        }
+        if !raw.starts_with('-') {
+            break;
+        }
+        let lexed = lex(vec![raw_args.next().unwrap()]);
+        let mut lexed = lexed.iter();
+        while let Some(arg) = lexed.next() {
+            match arg {
+                Arg::Short(short) => match short.as_str() {
+                    "i" => arg_struct.ignore_environment = true,
+                    "0" => arg_struct.null = true,
+                    "u" => arg_struct
+                        .unset
+                        .push(value_of(arg, &mut lexed, &mut raw_args)?),
+                    _ => return Err(unknown_arg_message(arg).into()),
+                },
+                Arg::Long(long) => match long.as_str() {
+                    "ignore-environment" => arg_struct.ignore_environment = true,
+                    "null" => arg_struct.null = true,
+                    "unset" => arg_struct
+                        .unset
+                        .push(value_of(arg, &mut lexed, &mut raw_args)?),
+                    "version" => print_and_exit(0, version_string!("env")),
+                    "help" => print_and_exit(0, HELP),
+                    _ => return Err(unknown_arg_message(arg).into()),
+                },
+                Arg::Positional(_) => return Err(unknown_arg_message(arg).into()),
+            }
+        }
+    }
+
+    while let Some(raw) = raw_args.peek() {
+        match raw.find('=') {
+            Some(i) if i > 0 => {
+                let raw = raw_args.next().unwrap();
+                arg_struct
+                    .assignments
+                    .push((raw[..i].to_owned(), raw[i + 1..].to_owned()));
+            }
+            _ => break,
+        }
+    }
+    arg_struct.command = raw_args.collect();
+    Ok(arg_struct)
+}
+
+/// The environment after applying `-i`, `-u` and the assignments, in that order.
+fn environment(args: &Args) -> Vec<(OsString, OsString)> {
+    let mut vars: Vec<(OsString, OsString)> = if args.ignore_environment {
+        vec![]
+    } else {
+        env::vars_os().collect()
+    };
+    vars.retain(|(name, _)| !args.unset.iter().any(|unset| name == unset.as_str()));
+    for (name, value) in &args.assignments {
+        vars.retain(|(existing, _)| existing != name.as_str());
+        vars.push((name.into(), value.into()));
+    }
+    vars
+}
+
+fn main() {
+    if let Err(e) = run() {
+        exit_with(&e);
+    }
+}
+
+fn run() -> Result<(), EnvError> {
+    let args = parse(env::args().skip(1).collect())?;
+    let vars = environment(&args);
+
+    if args.command.is_empty() {
+        let terminator = Terminator::new(args.null).as_byte();
+        let mut output = vec![];
+        for (name, value) in &vars {
+            output.extend_from_slice(name.as_bytes());
+            output.push(b'=');
+            output.extend_from_slice(value.as_bytes());
+            output.push(terminator);
+        }
+        io::stdout().write_all(&output)?;
+        return Ok(());
+    }
+    if args.null {
+        return Err("cannot specify --null (-0) with command".into());
+    }
+
+    let program = &args.command[0];
+    let error = Command::new(program)
+        .args(&args.command[1..])
+        .env_clear()
+        .envs(vars)
+        .exec();
+    Err(EnvError {
+        message: format!("'{}': {}", program, error),
+        code: if error.kind() == ErrorKind::NotFound {
+            127
+        } else {
+            126
+        },
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse_strs(args: &[&str]) -> Args {
+        parse(args.iter().map(|s| s.to_string()).collect()).unwrap()
+    }
+
+    #[test]
+    fn stops_at_command_word() {
+        let args = parse_strs(&["-i", "A=1", "B=", "ls", "-l", "C=3"]);
+        assert!(args.ignore_environment);
+        assert_eq!(
+            args.assignments,
+            vec![
+                ("A".to_owned(), "1".to_owned()),
+                ("B".to_owned(), "".to_owned())
+            ]
+        );
+        assert_eq!(args.command, vec!["ls", "-l", "C=3"]);
+    }
+
+    #[test]
+    fn unset_values() {
+        let args = parse_strs(&["-u", "A", "-uB", "--unset=C", "--unset", "D"]);
+        assert_eq!(args.unset, vec!["A", "B", "C", "D"]);
+    }
+
+    #[test]
+    fn dash_ignores_environment() {
+        let args = parse_strs(&["-", "-0"]);
+        assert!(args.ignore_environment);
+        assert!(args.null);
+        assert!(args.command.is_empty());
+    }
+
+    #[test]
+    fn options_after_delimiter_are_commands() {
+        let args = parse_strs(&["--", "-i"]);
+        assert!(!args.ignore_environment);
+        assert_eq!(args.command, vec!["-i"]);
+    }
+}
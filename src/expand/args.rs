@@ -0,0 +1,84 @@
+use crate::ExpandError;
+use arglex::lex;
+use arglex::Arg;
+use coreutils_common::print_and_exit;
+use coreutils_common::tabs::TabStops;
+use coreutils_common::unknown_arg_message;
+use coreutils_common::version_string;
+use std::slice::Iter;
+
+const HELP: &str = "
+Usage: expand [OPTION]... [FILE]...
+Convert tabs in each FILE to spaces, writing to standard output.
+
+With no FILE, or when FILE is -, read standard input.
+
+Options:
+  -i, --initial             Do not convert tabs after non blanks
+  -t, --tabs <N>            Have tabs N characters apart, not 8
+  -t, --tabs <LIST>         Use comma separated list of tab positions
+  --version                 Output version information and exit
+  --help                    Display this help and exit
+";
+
+fn print_help() -> ! {
+    print_and_exit(0, HELP);
+}
+
+fn print_version() -> ! {
+    print_and_exit(0, version_string!("expand"));
+}
+
+pub struct Args {
+    pub initial: bool,
+    pub tabs: TabStops,
+    pub files: Vec<String>,
+}
+
+impl Args {
+    fn new() -> Self {
+        Args {
+            initial: false,
+            tabs: TabStops::default(),
+            files: vec![],
+        }
+    }
+}
+
+fn get_arg_to(args: &mut Iter<Arg>, name: &Arg) -> Result<String, ExpandError> {
+    match args.next() {
+        Some(Arg::Positional(value)) => Ok(value.clone()),
+        _ => Err(format!("{} needs an argument", name).into()),
+    }
+}
+
+pub fn parse(args: Vec<String>) -> Result<Args, ExpandError> {
+    let args = lex(args);
+
+    let mut args = args.iter();
+    let mut arg_struct = Args::new();
+    while let Some(arg) = args.next() {
+        match arg {
+            Arg::Positional(positional) => match positional.as_str() {
+                "--" => continue,
+                _ => arg_struct.files.push(positional.clone()),
+            },
+            Arg::Short(short) => match short.as_str() {
+                "i" => arg_struct.initial = true,
+                "t" => arg_struct.tabs = TabStops::parse(&get_arg_to(&mut args, arg)?)?,
+                _ => return Err(unknown_arg_message(arg).into()),
+            },
+            Arg::Long(long) => match long.as_str() {
+                "initial" => arg_struct.initial = true,
+                "tabs" => arg_struct.tabs = TabStops::parse(&get_arg_to(&mut args, arg)?)?,
+                "version" => print_version(),
+                "help" => print_help(),
+                _ => return Err(unknown_arg_message(arg).into()),
+            },
+        };
+    }
+    if arg_struct.files.is_empty() {
+        arg_struct.files.push("-".to_owned());
+    }
+    Ok(arg_struct)
+}
@@ -0,0 +1,111 @@
+mod args;
+
+use args::Args;
+use coreutils_common::exit_with;
+use coreutils_common::report_error;
+use coreutils_common::CoreError;
+use std::env;
+use std::fmt;
+use std::fmt::Debug;
+use std::fs::File;
+use std::io;
+use std::io::BufRead;
+use std::io::BufReader;
+use std::io::BufWriter;
+use std::io::Write;
+use std::process::exit;
+
+pub struct ExpandError {
+    message: String,
+}
+
+impl Debug for ExpandError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "expand: {}", self.message)
+    }
+}
+
+impl CoreError for ExpandError {}
+
+impl<T> From<T> for ExpandError
+where
+    T: ToString,
+{
+    fn from(error: T) -> Self {
+        ExpandError {
+            message: error.to_string(),
+        }
+    }
+}
+
+/// Replaces the tabs of one line, keeping track of the column each byte lands in. UTF-8
+/// continuation bytes take no column and backspace moves back one.
+fn expand_line(line: &[u8], args: &Args, out: &mut Vec<u8>) {
+    let mut column = 0;
+    let mut leading = true;
+    for &byte in line {
+        match byte {
+            b'\t' if leading || !args.initial => {
+                let stop = args.tabs.next_stop(column);
+                out.resize(out.len() + stop - column, b' ');
+                column = stop;
+                continue;
+            }
+            b'\t' => {}
+            b' ' => column += 1,
+            b'\x08' => column = column.saturating_sub(1),
+            0x80..=0xbf => {}
+            _ => column += 1,
+        }
+        if byte != b' ' && byte != b'\t' {
+            leading = false;
+        }
+        out.push(byte);
+    }
+}
+
+fn expand(input: &mut dyn BufRead, args: &Args, out: &mut dyn Write) -> io::Result<()> {
+    let mut line = vec![];
+    let mut expanded = vec![];
+    loop {
+        line.clear();
+        if input.read_until(b'\n', &mut line)? == 0 {
+            return Ok(());
+        }
+        expanded.clear();
+        expand_line(&line, args, &mut expanded);
+        out.write_all(&expanded)?;
+    }
+}
+
+fn main() {
+    if let Err(e) = run() {
+        exit_with(&e);
+    }
+}
+
+fn run() -> Result<(), ExpandError> {
+    let args = args::parse(env::args().skip(1).collect())?;
+    let stdout = io::stdout();
+    let mut out = BufWriter::new(stdout.lock());
+    let mut failed = false;
+    for file in &args.files {
+        let expanded = if file == "-" {
+            let stdin = io::stdin();
+            let mut input = stdin.lock();
+            expand(&mut input, &args, &mut out)
+        } else {
+            File::open(file).and_then(|f| expand(&mut BufReader::new(f), &args, &mut out))
+        };
+        if let Err(e) = expanded {
+            out.flush()?;
+            report_error("expand", format!("{}: {}", file, e));
+            failed = true;
+        }
+    }
+    out.flush()?;
+    if failed {
+        exit(1);
+    }
+    Ok(())
+}
@@ -0,0 +1,29 @@
+use arglex::lex;
+use arglex::Arg;
+use coreutils_common::version_string;
+use std::env;
+use std::process::exit;
+
+const HELP: &str = "
+Usage: false [ignored command line arguments]
+  or:  false OPTION
+Exit with a status code indicating failure.
+
+Options:
+  --version                 Output version information and exit
+  --help                    Display this help and exit
+";
+
+fn main() {
+    // Like GNU false, options are only recognized when they are the sole argument, and even
+    // then the exit status is still failure.
+    let args: Vec<String> = env::args().skip(1).collect();
+    if args.len() == 1 {
+        match lex(args).first() {
+            Some(Arg::Long(long)) if long == "help" => println!("{}", HELP),
+            Some(Arg::Long(long)) if long == "version" => println!("{}", version_string!("false")),
+            _ => {}
+        }
+    }
+    exit(1);
+}
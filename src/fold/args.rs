@@ -0,0 +1,112 @@
+use crate::FoldError;
+use arglex::lex;
+use arglex::Arg;
+use coreutils_common::print_and_exit;
+use coreutils_common::unknown_arg_message;
+use coreutils_common::version_string;
+use std::slice::Iter;
+
+const HELP: &str = "
+Usage: fold [OPTION]... [FILE]...
+Wrap input lines in each FILE, writing to standard output.
+
+With no FILE, or when FILE is -, read standard input.
+
+Options:
+  -b, --bytes               Count bytes rather than columns
+  -s, --spaces              Break at spaces
+  -w, --width <WIDTH>       Use WIDTH columns instead of 80
+  --version                 Output version information and exit
+  --help                    Display this help and exit
+";
+
+fn print_help() -> ! {
+    print_and_exit(0, HELP);
+}
+
+fn print_version() -> ! {
+    print_and_exit(0, version_string!("fold"));
+}
+
+pub struct Args {
+    pub bytes: bool,
+    pub spaces: bool,
+    pub width: usize,
+    pub files: Vec<String>,
+}
+
+impl Args {
+    fn new() -> Self {
+        Args {
+            bytes: false,
+            spaces: false,
+            width: 80,
+            files: vec![],
+        }
+    }
+}
+
+fn get_arg_to(args: &mut Iter<Arg>, name: &Arg) -> Result<String, FoldError> {
+    match args.next() {
+        Some(Arg::Positional(value)) => Ok(value.clone()),
+        _ => Err(format!("{} needs an argument", name).into()),
+    }
+}
+
+fn parse_width(width: &str) -> Result<usize, FoldError> {
+    match width.parse() {
+        Ok(width) if width > 0 => Ok(width),
+        _ => Err(format!("invalid number of columns: '{}'", width).into()),
+    }
+}
+
+pub fn parse(args: Vec<String>) -> Result<Args, FoldError> {
+    let args = lex(args);
+
+    let mut args = args.iter();
+    let mut arg_struct = Args::new();
+    while let Some(arg) = args.next() {
+        match arg {
+            Arg::Positional(positional) => match positional.as_str() {
+                "--" => continue,
+                _ => arg_struct.files.push(positional.clone()),
+            },
+            Arg::Short(short) => match short.as_str() {
+                "b" => arg_struct.bytes = true,
+                "s" => arg_struct.spaces = true,
+                "w" => arg_struct.width = parse_width(&get_arg_to(&mut args, arg)?)?,
+                _ => return Err(unknown_arg_message(arg).into()),
+            },
+            Arg::Long(long) => match long.as_str() {
+                "bytes" => arg_struct.bytes = true,
+                "spaces" => arg_struct.spaces = true,
+                "width" => arg_struct.width = parse_width(&get_arg_to(&mut args, arg)?)?,
+                "version" => print_version(),
+                "help" => print_help(),
+                _ => return Err(unknown_arg_message(arg).into()),
+            },
+        };
+    }
+    if arg_struct.files.is_empty() {
+        arg_struct.files.push("-".to_owned());
+    }
+    Ok(arg_struct)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse_strs(args: &[&str]) -> Result<Args, FoldError> {
+        parse(args.iter().map(|s| s.to_string()).collect())
+    }
+
+    #[test]
+    fn width() {
+        assert_eq!(parse_strs(&[]).unwrap().width, 80);
+        assert_eq!(parse_strs(&["-w", "10"]).unwrap().width, 10);
+        assert_eq!(parse_strs(&["-w5"]).unwrap().width, 5);
+        assert!(parse_strs(&["-w", "0"]).is_err());
+        assert!(parse_strs(&["--width"]).is_err());
+    }
+}
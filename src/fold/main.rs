@@ -0,0 +1,183 @@
+mod args;
+
+use args::Args;
+use coreutils_common::exit_with;
+use coreutils_common::report_error;
+use coreutils_common::CoreError;
+use std::env;
+use std::fmt;
+use std::fmt::Debug;
+use std::fs::File;
+use std::io;
+use std::io::BufRead;
+use std::io::BufReader;
+use std::io::BufWriter;
+use std::io::Write;
+use std::process::exit;
+
+pub struct FoldError {
+    message: String,
+}
+
+impl Debug for FoldError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "fold: {}", self.message)
+    }
+}
+
+impl CoreError for FoldError {}
+
+impl<T> From<T> for FoldError
+where
+    T: ToString,
+{
+    fn from(error: T) -> Self {
+        FoldError {
+            message: error.to_string(),
+        }
+    }
+}
+
+/// The column after `byte` is printed at `column`. Without -b, tabs jump to the next multiple
+/// of eight, backspace and carriage return move back, and UTF-8 continuation bytes take no
+/// room of their own.
+fn advance(column: usize, byte: u8, bytes: bool) -> usize {
+    if bytes {
+        return column + 1;
+    }
+    match byte {
+        b'\t' => column + 8 - column % 8,
+        b'\x08' => column.saturating_sub(1),
+        b'\r' => 0,
+        0x80..=0xbf => column,
+        _ => column + 1,
+    }
+}
+
+fn width_of(line: &[u8], bytes: bool) -> usize {
+    line.iter()
+        .fold(0, |column, &byte| advance(column, byte, bytes))
+}
+
+/// Wraps one line, without its newline, into `out`.
+fn fold_line(line: &[u8], args: &Args, out: &mut Vec<u8>) {
+    let mut pending: Vec<u8> = vec![];
+    let mut column = 0;
+    for &byte in line {
+        let next = advance(column, byte, args.bytes);
+        // A continuation byte never starts a new line, so characters stay whole.
+        if next > args.width && !pending.is_empty() && byte & 0xc0 != 0x80 {
+            let blank = if args.spaces {
+                pending.iter().rposition(|&b| b == b' ' || b == b'\t')
+            } else {
+                None
+            };
+            match blank {
+                Some(i) => {
+                    out.extend_from_slice(&pending[..=i]);
+                    pending.drain(..=i);
+                }
+                None => {
+                    out.extend_from_slice(&pending);
+                    pending.clear();
+                }
+            }
+            out.push(b'\n');
+            column = width_of(&pending, args.bytes);
+        }
+        pending.push(byte);
+        column = advance(column, byte, args.bytes);
+    }
+    out.extend_from_slice(&pending);
+}
+
+fn fold(input: &mut dyn BufRead, args: &Args, out: &mut dyn Write) -> io::Result<()> {
+    let mut line = vec![];
+    let mut folded = vec![];
+    loop {
+        line.clear();
+        if input.read_until(b'\n', &mut line)? == 0 {
+            return Ok(());
+        }
+        let newline = line.last() == Some(&b'\n');
+        if newline {
+            line.pop();
+        }
+        folded.clear();
+        fold_line(&line, args, &mut folded);
+        if newline {
+            folded.push(b'\n');
+        }
+        out.write_all(&folded)?;
+    }
+}
+
+fn main() {
+    if let Err(e) = run() {
+        exit_with(&e);
+    }
+}
+
+fn run() -> Result<(), FoldError> {
+    let args = args::parse(env::args().skip(1).collect())?;
+    let stdout = io::stdout();
+    let mut out = BufWriter::new(stdout.lock());
+    let mut failed = false;
+    for file in &args.files {
+        let folded = if file == "-" {
+            let stdin = io::stdin();
+            let mut input = stdin.lock();
+            fold(&mut input, &args, &mut out)
+        } else {
+            File::open(file).and_then(|f| fold(&mut BufReader::new(f), &args, &mut out))
+        };
+        if let Err(e) = folded {
+            out.flush()?;
+            report_error("fold", format!("{}: {}", file, e));
+            failed = true;
+        }
+    }
+    out.flush()?;
+    if failed {
+        exit(1);
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fold_str(line: &str, width: usize, spaces: bool, bytes: bool) -> String {
+        let args = Args {
+            bytes,
+            spaces,
+            width,
+            files: vec![],
+        };
+        let mut out = vec![];
+        fold_line(line.as_bytes(), &args, &mut out);
+        String::from_utf8(out).unwrap()
+    }
+
+    #[test]
+    fn hard_wrap() {
+        assert_eq!(fold_str("abcdefgh", 3, false, false), "abc\ndef\ngh");
+        assert_eq!(fold_str("abc", 3, false, false), "abc");
+        assert_eq!(fold_str("", 3, false, false), "");
+    }
+
+    #[test]
+    fn spaces() {
+        assert_eq!(fold_str("ab cd ef", 5, true, false), "ab \ncd ef");
+        assert_eq!(fold_str("abcdefg hi", 4, true, false), "abcd\nefg \nhi");
+    }
+
+    #[test]
+    fn columns() {
+        assert_eq!(fold_str("a\tb", 8, false, false), "a\t\nb");
+        assert_eq!(fold_str("a\tbcdefghij", 10, false, true), "a\tbcdefghi\nj");
+        assert_eq!(fold_str("ééé", 2, false, false), "éé\né");
+        assert_eq!(fold_str("ééé", 2, false, true), "é\né\né");
+    }
+}
@@ -0,0 +1,160 @@
+use crate::HeadError;
+use arglex::lex_clustered_with_negatives;
+use arglex::Arg;
+use coreutils_common::numparse::parse_size;
+use coreutils_common::print_and_exit;
+use coreutils_common::unknown_arg_message;
+use coreutils_common::version_string;
+use std::slice::Iter;
+
+const HELP: &str = "
+Usage: head [option]... [FILE]...
+Print the first 10 lines of each FILE to standard output.
+With more than one FILE, precede each with a header giving the file name.
+
+With no FILE, or when FILE is -, read standard input.
+
+Options:
+  -c, --bytes <[-]NUM>      Print the first NUM bytes of each file;
+                              with the leading '-', print all but the last NUM bytes of each file
+  -n, --lines <[-]NUM>      Print the first NUM lines instead of the first 10;
+                              with the leading '-', print all but the last NUM lines of each file
+  -q, --quiet, --silent     Never print headers giving file names
+  -v, --verbose             Always print headers giving file names
+  --version                 Output version information and exit
+  --help                    Display this help and exit
+
+-NUM on its own as the first option is the same as -n NUM.
+";
+
+fn print_help() -> ! {
+    print_and_exit(0, HELP);
+}
+
+fn print_version() -> ! {
+    print_and_exit(0, version_string!("head"));
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Limit {
+    First(u64),
+    AllButLast(u64),
+}
+
+pub struct Args {
+    pub bytes: bool,
+    pub limit: Limit,
+    pub quiet: bool,
+    pub verbose: bool,
+    pub files: Vec<String>,
+}
+
+impl Args {
+    fn new() -> Self {
+        Args {
+            bytes: false,
+            limit: Limit::First(10),
+            quiet: false,
+            verbose: false,
+            files: vec![],
+        }
+    }
+}
+
+fn get_arg_to(args: &mut Iter<Arg>, name: &Arg) -> Result<String, HeadError> {
+    match args.next() {
+        Some(Arg::Positional(value)) => Ok(value.clone()),
+        _ => Err(format!("{} needs an argument", name).into()),
+    }
+}
+
+fn parse_limit(value: &str) -> Result<Limit, HeadError> {
+    let (all_but, number) = match value.strip_prefix('-') {
+        Some(number) => (true, number),
+        None => (false, value),
+    };
+    let number = parse_size(number)?;
+    Ok(if all_but {
+        Limit::AllButLast(number)
+    } else {
+        Limit::First(number)
+    })
+}
+
+pub fn parse(mut args: Vec<String>) -> Result<Args, HeadError> {
+    // The obsolete `head -5` form, only recognized as the first argument
+    if let Some(number) = args.first().and_then(|first| first.strip_prefix('-')) {
+        if !number.is_empty() && number.chars().all(|c| c.is_ascii_digit()) {
+            let number = number.to_owned();
+            args.splice(0..1, vec!["-n".to_owned(), number]);
+        }
+    }
+    let args = lex_clustered_with_negatives(args, "cn");
+
+    let mut args = args.iter();
+    let mut arg_struct = Args::new();
+    while let Some(arg) = args.next() {
+        match arg {
+            Arg::Positional(positional) => match positional.as_str() {
+                "--" => continue,
+                _ => arg_struct.files.push(positional.clone()),
+            },
+            Arg::Short(short) => match short.as_str() {
+                "c" => {
+                    arg_struct.bytes = true;
+                    arg_struct.limit = parse_limit(&get_arg_to(&mut args, arg)?)?;
+                }
+                "n" => {
+                    arg_struct.bytes = false;
+                    arg_struct.limit = parse_limit(&get_arg_to(&mut args, arg)?)?;
+                }
+                "q" => arg_struct.quiet = true,
+                "v" => arg_struct.verbose = true,
+                _ => return Err(unknown_arg_message(arg).into()),
+            },
+            Arg::Long(long) => match long.as_str() {
+                "bytes" => {
+                    arg_struct.bytes = true;
+                    arg_struct.limit = parse_limit(&get_arg_to(&mut args, arg)?)?;
+                }
+                "lines" => {
+                    arg_struct.bytes = false;
+                    arg_struct.limit = parse_limit(&get_arg_to(&mut args, arg)?)?;
+                }
+                "quiet" | "silent" => arg_struct.quiet = true,
+                "verbose" => arg_struct.verbose = true,
+                "version" => print_version(),
+                "help" => print_help(),
+                _ => return Err(unknown_arg_message(arg).into()),
+            },
+        };
+    }
+    if arg_struct.files.is_empty() {
+        arg_struct.files.push("-".to_owned());
+    }
+    Ok(arg_struct)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse_strs(args: &[&str]) -> Args {
+        parse(args.iter().map(|s| s.to_string()).collect()).unwrap()
+    }
+
+    #[test]
+    fn negative_line_count() {
+        assert_eq!(parse_strs(&["-n", "-3"]).limit, Limit::AllButLast(3));
+        assert_eq!(parse_strs(&["-n-3"]).limit, Limit::AllButLast(3));
+        assert_eq!(parse_strs(&["--lines=-3"]).limit, Limit::AllButLast(3));
+    }
+
+    #[test]
+    fn obsolete_count_form() {
+        let args = parse_strs(&["-4", "file"]);
+        assert_eq!(args.limit, Limit::First(4));
+        assert!(!args.bytes);
+        assert_eq!(args.files, ["file"]);
+    }
+}
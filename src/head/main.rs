@@ -0,0 +1,144 @@
+mod args;
+
+use args::Args;
+use args::Limit;
+use coreutils_common::exit_with;
+use coreutils_common::input::lines;
+use coreutils_common::input::open_input;
+use coreutils_common::report_error;
+use coreutils_common::ring::RingBuffer;
+use coreutils_common::CoreError;
+use std::collections::VecDeque;
+use std::env;
+use std::fmt;
+use std::fmt::Debug;
+use std::io;
+use std::io::BufRead;
+use std::io::BufWriter;
+use std::io::Read;
+use std::io::Write;
+use std::process::exit;
+
+pub struct HeadError {
+    message: String,
+}
+
+impl Debug for HeadError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "head: {}", self.message)
+    }
+}
+
+impl CoreError for HeadError {}
+
+impl<T> From<T> for HeadError
+where
+    T: ToString,
+{
+    fn from(error: T) -> Self {
+        HeadError {
+            message: error.to_string(),
+        }
+    }
+}
+
+fn head_lines(input: &mut dyn BufRead, limit: Limit, out: &mut dyn Write) -> io::Result<()> {
+    match limit {
+        Limit::First(count) => {
+            for line in lines(input).take(count as usize) {
+                out.write_all(&line?)?;
+            }
+        }
+        Limit::AllButLast(count) => {
+            // Hold back the last `count` lines; anything pushed out of the window is safe to print
+            let mut window = RingBuffer::new(count);
+            for line in lines(input) {
+                if let Some(line) = window.push(line?) {
+                    out.write_all(&line)?;
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+fn head_bytes(input: &mut dyn BufRead, limit: Limit, out: &mut dyn Write) -> io::Result<()> {
+    match limit {
+        Limit::First(count) => {
+            io::copy(&mut input.take(count), out)?;
+        }
+        Limit::AllButLast(count) => {
+            let mut window: VecDeque<u8> = VecDeque::new();
+            loop {
+                let buf = input.fill_buf()?;
+                if buf.is_empty() {
+                    break;
+                }
+                window.extend(buf.iter());
+                let len = buf.len();
+                input.consume(len);
+                let excess = window.len().saturating_sub(count as usize);
+                let (front, back) = window.as_slices();
+                if excess <= front.len() {
+                    out.write_all(&front[..excess])?;
+                } else {
+                    out.write_all(front)?;
+                    out.write_all(&back[..excess - front.len()])?;
+                }
+                window.drain(..excess);
+            }
+        }
+    }
+    Ok(())
+}
+
+fn head(input: &mut dyn BufRead, args: &Args, out: &mut dyn Write) -> io::Result<()> {
+    if args.bytes {
+        head_bytes(input, args.limit, out)
+    } else {
+        head_lines(input, args.limit, out)
+    }
+}
+
+fn main() {
+    if let Err(e) = run() {
+        exit_with(&e);
+    }
+}
+
+fn run() -> Result<(), HeadError> {
+    let args = args::parse(env::args().skip(1).collect())?;
+    let headers = args.verbose || (args.files.len() > 1 && !args.quiet);
+    let stdout = io::stdout();
+    let mut out = BufWriter::new(stdout.lock());
+    let mut failed = false;
+    // Separates each header from the previous file's output, so not before the first one.
+    let mut printed_any = false;
+    for file in &args.files {
+        let mut input = match open_input(file) {
+            Ok(input) => input,
+            Err(e) => {
+                out.flush()?;
+                report_error("head", format!("cannot open {} for reading: {}", file, e));
+                failed = true;
+                continue;
+            }
+        };
+        if headers {
+            let name = if file == "-" { "standard input" } else { file };
+            writeln!(
+                out,
+                "{}==> {} <==",
+                if printed_any { "\n" } else { "" },
+                name
+            )?;
+            printed_any = true;
+        }
+        head(&mut input, &args, &mut out).map_err(|e| format!("{}: {}", file, e))?;
+    }
+    out.flush()?;
+    if failed {
+        exit(1);
+    }
+    Ok(())
+}
@@ -0,0 +1,122 @@
+use arglex::lex;
+use arglex::Arg;
+use coreutils_common::die;
+use coreutils_common::print_and_exit;
+use coreutils_common::unknown_arg_message;
+use coreutils_common::version_string;
+use std::env;
+use std::ffi::CStr;
+use std::ffi::CString;
+use std::io;
+use std::os::raw::c_char;
+
+const HELP: &str = "
+Usage: hostname [OPTION]...
+Print the name of this host.
+
+Options:
+  -f, --fqdn                Print the fully qualified domain name
+  -s, --short               Print the name up to the first dot
+  --version                 Output version information and exit
+  --help                    Display this help and exit
+";
+
+#[derive(Clone, Copy, PartialEq)]
+enum Form {
+    Plain,
+    Fqdn,
+    Short,
+}
+
+fn fail(message: impl AsRef<str>) -> ! {
+    die(format!("hostname: {}", message.as_ref()));
+}
+
+fn parse(args: Vec<String>) -> Form {
+    let mut form = Form::Plain;
+    for arg in &lex(args) {
+        match arg {
+            Arg::Positional(positional) => match positional.as_str() {
+                "--" => continue,
+                _ => fail(format!("extra operand '{}'", positional)),
+            },
+            Arg::Short(short) => match short.as_str() {
+                "f" => form = Form::Fqdn,
+                "s" => form = Form::Short,
+                _ => fail(unknown_arg_message(arg)),
+            },
+            Arg::Long(long) => match long.as_str() {
+                "fqdn" | "long" => form = Form::Fqdn,
+                "short" => form = Form::Short,
+                "version" => print_and_exit(0, version_string!("hostname")),
+                "help" => print_and_exit(0, HELP),
+                _ => fail(unknown_arg_message(arg)),
+            },
+        }
+    }
+    form
+}
+
+/// The kernel's host name. POSIX leaves it unspecified whether a truncated name is
+/// NUL-terminated, so the buffer grows until the terminator shows up inside it.
+fn host_name() -> io::Result<String> {
+    let max = unsafe { libc::sysconf(libc::_SC_HOST_NAME_MAX) };
+    let mut size = if max > 0 { max as usize + 1 } else { 256 };
+    loop {
+        let mut buffer = vec![0 as c_char; size];
+        if unsafe { libc::gethostname(buffer.as_mut_ptr(), size) } != 0 {
+            let error = io::Error::last_os_error();
+            if error.raw_os_error() != Some(libc::ENAMETOOLONG) {
+                return Err(error);
+            }
+        } else if buffer.contains(&0) {
+            let name = unsafe { CStr::from_ptr(buffer.as_ptr()) };
+            return Ok(name.to_string_lossy().into_owned());
+        }
+        size *= 2;
+    }
+}
+
+/// The canonical name the resolver reports for `name`.
+fn canonical_name(name: &str) -> Result<String, String> {
+    let c_name = CString::new(name).map_err(|e| e.to_string())?;
+    let hints = libc::addrinfo {
+        ai_flags: libc::AI_CANONNAME,
+        ai_family: libc::AF_UNSPEC,
+        ai_socktype: 0,
+        ai_protocol: 0,
+        ai_addrlen: 0,
+        ai_addr: std::ptr::null_mut(),
+        ai_canonname: std::ptr::null_mut(),
+        ai_next: std::ptr::null_mut(),
+    };
+    let mut result = std::ptr::null_mut();
+    let status =
+        unsafe { libc::getaddrinfo(c_name.as_ptr(), std::ptr::null(), &hints, &mut result) };
+    if status != 0 {
+        let reason = unsafe { CStr::from_ptr(libc::gai_strerror(status)) };
+        return Err(reason.to_string_lossy().into_owned());
+    }
+    let canonical = unsafe {
+        let canonical = (*result).ai_canonname;
+        let canonical = if canonical.is_null() {
+            name.to_owned()
+        } else {
+            CStr::from_ptr(canonical).to_string_lossy().into_owned()
+        };
+        libc::freeaddrinfo(result);
+        canonical
+    };
+    Ok(canonical)
+}
+
+fn main() {
+    let form = parse(env::args().skip(1).collect());
+    let name = host_name().unwrap_or_else(|e| fail(format!("cannot determine name: {}", e)));
+    let name = match form {
+        Form::Plain => name,
+        Form::Short => name.split('.').next().unwrap_or_default().to_owned(),
+        Form::Fqdn => canonical_name(&name).unwrap_or_else(|e| fail(format!("{}: {}", name, e))),
+    };
+    println!("{}", name);
+}
@@ -0,0 +1,106 @@
+use crate::IdError;
+use arglex::lex;
+use arglex::Arg;
+use coreutils_common::print_and_exit;
+use coreutils_common::unknown_arg_message;
+use coreutils_common::version_string;
+
+const HELP: &str = "
+Usage: id [OPTION]...
+Print user and group information for the current process.
+
+Options:
+  -g, --group               Print only the effective group ID
+  -G, --groups              Print all group IDs
+  -n, --name                Print a name instead of a number, for -ugG
+  -r, --real                Print the real ID instead of the effective ID, with -ugG
+  -u, --user                Print only the effective user ID
+  --version                 Output version information and exit
+  --help                    Display this help and exit
+
+Without any OPTION, print some useful set of identified information.
+";
+
+fn print_help() -> ! {
+    print_and_exit(0, HELP);
+}
+
+fn print_version() -> ! {
+    print_and_exit(0, version_string!("id"));
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Only {
+    User,
+    Group,
+    Groups,
+}
+
+pub struct Args {
+    pub only: Option<Only>,
+    pub name: bool,
+    pub real: bool,
+}
+
+pub fn parse(args: Vec<String>) -> Result<Args, IdError> {
+    let mut only = vec![];
+    let mut name = false;
+    let mut real = false;
+    for arg in &lex(args) {
+        match arg {
+            Arg::Positional(positional) => match positional.as_str() {
+                "--" => continue,
+                _ => return Err(format!("extra operand '{}'", positional).into()),
+            },
+            Arg::Short(short) => match short.as_str() {
+                "g" => only.push(Only::Group),
+                "G" => only.push(Only::Groups),
+                "n" => name = true,
+                "r" => real = true,
+                "u" => only.push(Only::User),
+                _ => return Err(unknown_arg_message(arg).into()),
+            },
+            Arg::Long(long) => match long.as_str() {
+                "group" => only.push(Only::Group),
+                "groups" => only.push(Only::Groups),
+                "name" => name = true,
+                "real" => real = true,
+                "user" => only.push(Only::User),
+                "version" => print_version(),
+                "help" => print_help(),
+                _ => return Err(unknown_arg_message(arg).into()),
+            },
+        };
+    }
+    only.dedup();
+    if only.len() > 1 {
+        return Err("cannot print \"only\" of more than one choice".into());
+    }
+    if only.is_empty() && (name || real) {
+        return Err("cannot print only names or real IDs in default format".into());
+    }
+    Ok(Args {
+        only: only.pop(),
+        name,
+        real,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse_strs(args: &[&str]) -> Result<Args, IdError> {
+        parse(args.iter().map(|s| s.to_string()).collect())
+    }
+
+    #[test]
+    fn choices() {
+        assert_eq!(parse_strs(&[]).unwrap().only, None);
+        assert_eq!(parse_strs(&["-u", "-n"]).unwrap().only, Some(Only::User));
+        assert_eq!(parse_strs(&["-G", "-G"]).unwrap().only, Some(Only::Groups));
+        assert!(parse_strs(&["-u", "-g"]).is_err());
+        assert!(parse_strs(&["-n"]).is_err());
+        assert!(parse_strs(&["root"]).is_err());
+    }
+}
@@ -0,0 +1,117 @@
+mod args;
+
+use args::Only;
+use coreutils_common::exit_with;
+use coreutils_common::users::group_name;
+use coreutils_common::users::groups;
+use coreutils_common::users::user_name;
+use coreutils_common::CoreError;
+use std::env;
+use std::fmt;
+use std::fmt::Debug;
+
+pub struct IdError {
+    message: String,
+}
+
+impl Debug for IdError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "id: {}", self.message)
+    }
+}
+
+impl CoreError for IdError {}
+
+impl<T> From<T> for IdError
+where
+    T: ToString,
+{
+    fn from(error: T) -> Self {
+        IdError {
+            message: error.to_string(),
+        }
+    }
+}
+
+/// `1000(name)`, or just the number when the database has no name for it.
+fn labelled(id: u32, name: Option<String>) -> String {
+    match name {
+        Some(name) => format!("{}({})", id, name),
+        None => id.to_string(),
+    }
+}
+
+/// The effective group first, then the supplementary groups it isn't already among.
+fn all_groups(egid: u32) -> Result<Vec<u32>, IdError> {
+    let mut all = vec![egid];
+    for gid in groups().map_err(|e| format!("cannot get groups: {}", e))? {
+        if !all.contains(&gid) {
+            all.push(gid);
+        }
+    }
+    Ok(all)
+}
+
+fn main() {
+    if let Err(e) = run() {
+        exit_with(&e);
+    }
+}
+
+fn run() -> Result<(), IdError> {
+    let args = args::parse(env::args().skip(1).collect())?;
+    let (uid, euid, gid, egid) = unsafe {
+        (
+            libc::getuid(),
+            libc::geteuid(),
+            libc::getgid(),
+            libc::getegid(),
+        )
+    };
+
+    let user = || {
+        let id = if args.real { uid } else { euid };
+        match user_name(id) {
+            Some(name) if args.name => Ok(name),
+            None if args.name => Err(format!("cannot find name for user ID {}", id)),
+            _ => Ok(id.to_string()),
+        }
+    };
+    let group = |id: u32| match group_name(id) {
+        Some(name) if args.name => Ok(name),
+        None if args.name => Err(format!("cannot find name for group ID {}", id)),
+        _ => Ok(id.to_string()),
+    };
+    match args.only {
+        Some(Only::User) => println!("{}", user()?),
+        Some(Only::Group) => println!("{}", group(if args.real { gid } else { egid })?),
+        Some(Only::Groups) => {
+            let first = if args.real { gid } else { egid };
+            let names = all_groups(first)?
+                .into_iter()
+                .map(group)
+                .collect::<Result<Vec<String>, String>>()?;
+            println!("{}", names.join(" "));
+        }
+        None => {
+            let mut line = format!(
+                "uid={} gid={}",
+                labelled(uid, user_name(uid)),
+                labelled(gid, group_name(gid))
+            );
+            if euid != uid {
+                line.push_str(&format!(" euid={}", labelled(euid, user_name(euid))));
+            }
+            if egid != gid {
+                line.push_str(&format!(" egid={}", labelled(egid, group_name(egid))));
+            }
+            let groups: Vec<String> = all_groups(egid)?
+                .into_iter()
+                .map(|id| labelled(id, group_name(id)))
+                .collect();
+            line.push_str(&format!(" groups={}", groups.join(",")));
+            println!("{}", line);
+        }
+    }
+    Ok(())
+}
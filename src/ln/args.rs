@@ -0,0 +1,119 @@
+use crate::LnError;
+use arglex::lex_one;
+use arglex::Arg;
+use coreutils_common::backup::Control;
+use coreutils_common::print_and_exit;
+use coreutils_common::unexpected_value_message;
+use coreutils_common::unknown_arg_message;
+use coreutils_common::version_string;
+
+const HELP: &str = "
+Usage: ln [OPTION]... TARGET LINK_NAME
+  or:  ln [OPTION]... TARGET
+  or:  ln [OPTION]... TARGET... DIRECTORY
+Create a link to TARGET with the name LINK_NAME, a link to TARGET in the
+current directory, or links to each TARGET in DIRECTORY.
+Create hard links by default, symbolic links with --symbolic.
+
+Options:
+  --backup[=CONTROL]        Make a backup of each existing destination file
+  -b                        Like --backup but does not accept an argument
+  -f, --force               Remove existing destination files
+  -r, --relative            With -s, create links relative to link location
+  -s, --symbolic            Make symbolic links instead of hard links
+  -v, --verbose             Print name of each linked file
+  --version                 Output version information and exit
+  --help                    Display this help and exit
+
+The backup CONTROL is one of none (or off), numbered (or t), existing (or nil)
+and simple (or never). A bare --backup or -b uses VERSION_CONTROL, else existing.
+";
+
+fn print_help() -> ! {
+    print_and_exit(0, HELP);
+}
+
+fn print_version() -> ! {
+    print_and_exit(0, version_string!("ln"));
+}
+
+pub struct Args {
+    pub backup: Control,
+    pub force: bool,
+    pub relative: bool,
+    pub symbolic: bool,
+    pub verbose: bool,
+    pub targets: Vec<String>,
+    pub dest: Option<String>,
+}
+
+impl Args {
+    fn new() -> Self {
+        Args {
+            backup: Control::Off,
+            force: false,
+            relative: false,
+            symbolic: false,
+            verbose: false,
+            targets: vec![],
+            dest: None,
+        }
+    }
+}
+
+pub fn parse(args: Vec<String>) -> Result<Args, LnError> {
+    let mut arg_struct = Args::new();
+    let mut delimited = false;
+    // Each argument is lexed on its own, so that `--backup` can tell `--backup=CONTROL` apart
+    // from `--backup FILE`.
+    for raw_arg in args {
+        if delimited {
+            arg_struct.targets.push(raw_arg);
+            continue;
+        }
+        let (lexed, mut attached) = lex_one(raw_arg, "");
+        for arg in &lexed {
+            match arg {
+                Arg::Positional(positional) => match positional.as_str() {
+                    "--" => delimited = true,
+                    _ => arg_struct.targets.push(positional.clone()),
+                },
+                Arg::Short(short) => match short.as_str() {
+                    "b" => arg_struct.backup = Control::from_env()?,
+                    "f" => arg_struct.force = true,
+                    "r" => arg_struct.relative = true,
+                    "s" => arg_struct.symbolic = true,
+                    "v" => arg_struct.verbose = true,
+                    _ => return Err(unknown_arg_message(arg).into()),
+                },
+                Arg::Long(long) => match long.as_str() {
+                    "backup" => {
+                        arg_struct.backup = match attached.take() {
+                            Some(control) => Control::parse(&control)?,
+                            None => Control::from_env()?,
+                        };
+                    }
+                    "force" => arg_struct.force = true,
+                    "relative" => arg_struct.relative = true,
+                    "symbolic" => arg_struct.symbolic = true,
+                    "verbose" => arg_struct.verbose = true,
+                    "version" => print_version(),
+                    "help" => print_help(),
+                    _ => return Err(unknown_arg_message(arg).into()),
+                },
+            };
+        }
+        if let (Some(_), Some(arg)) = (attached, lexed.last()) {
+            return Err(unexpected_value_message(arg).into());
+        }
+    }
+    if arg_struct.relative && !arg_struct.symbolic {
+        return Err("cannot do --relative without --symbolic".into());
+    }
+    match arg_struct.targets.len() {
+        0 => return Err("missing file operand".into()),
+        1 => {}
+        _ => arg_struct.dest = arg_struct.targets.pop(),
+    }
+    Ok(arg_struct)
+}
@@ -0,0 +1,169 @@
+mod args;
+
+use args::Args;
+use coreutils_common::backup::backup;
+use coreutils_common::backup::Control;
+use coreutils_common::exit_with;
+use coreutils_common::paths::canonicalize;
+use coreutils_common::paths::relative_path;
+use coreutils_common::paths::same_file;
+use coreutils_common::paths::Missing;
+use coreutils_common::report_error;
+use coreutils_common::CoreError;
+use std::env;
+use std::fmt;
+use std::fmt::Debug;
+use std::fs;
+use std::io;
+use std::os::unix::fs::symlink;
+use std::path::Path;
+use std::path::PathBuf;
+use std::process::exit;
+
+pub struct LnError {
+    message: String,
+}
+
+impl Debug for LnError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "ln: {}", self.message)
+    }
+}
+
+impl CoreError for LnError {}
+
+impl<T> From<T> for LnError
+where
+    T: ToString,
+{
+    fn from(error: T) -> Self {
+        LnError {
+            message: error.to_string(),
+        }
+    }
+}
+
+fn make_link(target: &Path, link_name: &Path, args: &Args) -> io::Result<()> {
+    if !args.symbolic {
+        return fs::hard_link(target, link_name);
+    }
+    if !args.relative {
+        return symlink(target, link_name);
+    }
+    // Only the directory is resolved: `link_name` itself may be a symlink that -f replaces.
+    let link_dir = match link_name.parent() {
+        Some(dir) if !dir.as_os_str().is_empty() => canonicalize(dir, Missing::Allowed)?,
+        _ => canonicalize(Path::new("."), Missing::Allowed)?,
+    };
+    let target = canonicalize(target, Missing::Allowed)?;
+    symlink(relative_path(&link_dir, &target), link_name)
+}
+
+/// Whether `link_name` already is `target`, as in `ln -f a a` or `ln --backup a a`, so that
+/// removing it or moving it aside before linking would lose the target.
+fn is_target(target: &Path, link_name: &Path, args: &Args) -> bool {
+    let existing = match link_name.symlink_metadata() {
+        Ok(existing) => existing,
+        Err(_) => return false,
+    };
+    if !args.symbolic {
+        return target
+            .symlink_metadata()
+            .is_ok_and(|metadata| same_file(&metadata, &existing));
+    }
+    // A relative symlink is resolved from the directory it's in, unless -r made it relative.
+    let target = match link_name.parent() {
+        Some(dir) if !args.relative => dir.join(target),
+        _ => target.to_path_buf(),
+    };
+    fs::metadata(target).is_ok_and(|metadata| same_file(&metadata, &existing))
+}
+
+/// Makes the link, first moving any existing `link_name` aside as a backup or removing it with
+/// -f. Returns the backup's name if one was made.
+fn link(target: &Path, link_name: &Path, args: &Args) -> io::Result<Option<PathBuf>> {
+    let backed_up = backup(link_name, args.backup)?;
+    if backed_up.is_none() && args.force && link_name.symlink_metadata().is_ok() {
+        fs::remove_file(link_name)?;
+    }
+    make_link(target, link_name, args)?;
+    Ok(backed_up)
+}
+
+fn main() {
+    if let Err(e) = run() {
+        exit_with(&e);
+    }
+}
+
+fn run() -> Result<(), LnError> {
+    let args = args::parse(env::args().skip(1).collect())?;
+    let (dest, into_dir) = match &args.dest {
+        Some(dest) => (Path::new(dest), Path::new(dest).is_dir()),
+        None => (Path::new("."), true),
+    };
+    if args.targets.len() > 1 && !into_dir {
+        return Err(format!("target '{}' is not a directory", dest.display()).into());
+    }
+
+    let mut failed = false;
+    for target in &args.targets {
+        let target = Path::new(target);
+        let link_name = if into_dir {
+            match target.file_name() {
+                Some(name) => dest.join(name),
+                None => {
+                    report_error(
+                        "ln",
+                        format!("cannot link '{}': invalid file name", target.display()),
+                    );
+                    failed = true;
+                    continue;
+                }
+            }
+        } else {
+            dest.to_path_buf()
+        };
+        let replaces = args.force || args.backup != Control::Off;
+        if replaces && is_target(target, &link_name, &args) {
+            report_error(
+                "ln",
+                format!(
+                    "'{}' and '{}' are the same file",
+                    target.display(),
+                    link_name.display()
+                ),
+            );
+            failed = true;
+            continue;
+        }
+        match link(target, &link_name, &args) {
+            Ok(backed_up) => {
+                if args.verbose {
+                    let arrow = if args.symbolic { "->" } else { "=>" };
+                    print!("'{}' {} '{}'", link_name.display(), arrow, target.display());
+                    match backed_up {
+                        Some(backed_up) => println!(" (backup: '{}')", backed_up.display()),
+                        None => println!(),
+                    }
+                }
+            }
+            Err(e) => {
+                let kind = if args.symbolic {
+                    "symbolic link"
+                } else {
+                    "hard link"
+                };
+                report_error(
+                    "ln",
+                    format!("failed to create {} '{}': {}", kind, link_name.display(), e),
+                );
+                failed = true;
+            }
+        }
+    }
+    if failed {
+        exit(1);
+    }
+    Ok(())
+}
@@ -0,0 +1,276 @@
+use crate::LsError;
+use arglex::lex_one;
+use arglex::Arg;
+use coreutils_common::glob::expand_all;
+use coreutils_common::print_and_exit;
+use coreutils_common::timestamp::TimeStyle;
+use coreutils_common::unexpected_value_message;
+use coreutils_common::unknown_arg_message;
+use coreutils_common::version_string;
+
+const HELP: &str = "
+Usage: ls [OPTION]... [FILE]...
+List information about the FILEs (the current directory by default).
+Entries are sorted alphabetically unless -S or -t is given.
+
+Options:
+  -a, --all                 Do not ignore entries starting with .
+  -A, --almost-all          Do not list implied . and ..
+  --color[=WHEN]            Color names by file type; WHEN is 'always' (the default),
+                            'auto' or 'never'. Colors are taken from LS_COLORS
+  -C                        List entries in columns, even when not writing to a
+                            terminal
+  -d, --directory           List directories themselves, not their contents;
+                            overrides -R
+  --glob                    Expand wildcards in FILEs, for when no shell has done so
+  -l                        Use a long listing format
+  -L, --dereference         Show the file a symlink points to instead of the
+                            symlink, and follow symlinks to directories with -R
+  -r, --reverse             Reverse order while sorting
+  -R, --recursive           List subdirectories recursively
+  -S                        Sort by file size, largest first
+  -t                        Sort by modification time, newest first
+  --time-style <STYLE>      Show times with -l in STYLE: full-iso, long-iso,
+                            iso, locale (the default) or +FORMAT
+  -w, --width <COLS>        Fit the columns into COLS characters instead of the
+                            terminal width; 0 means no limit
+  -1                        List one file per line
+  --version                 Output version information and exit
+  --help                    Display this help and exit
+
+A +FORMAT style is interpreted like strftime. If it contains a newline, the
+part before it is used for times more than six months old, and the part after
+it for recent ones. The iso and locale styles also show old times differently.
+";
+
+fn print_help() -> ! {
+    print_and_exit(0, HELP);
+}
+
+fn print_version() -> ! {
+    print_and_exit(0, version_string!("ls"));
+}
+
+/// The value attached to an option with `=`, or else the next argument.
+fn value_of(
+    attached: Option<String>,
+    raw_args: &mut impl Iterator<Item = String>,
+    name: &Arg,
+) -> Result<String, LsError> {
+    attached
+        .or_else(|| raw_args.next())
+        .ok_or_else(|| format!("{} needs an argument", name).into())
+}
+
+fn parse_width(width: &str) -> Result<usize, LsError> {
+    width
+        .parse()
+        .map_err(|_| format!("invalid line width '{}'", width).into())
+}
+
+/// Which entries starting with `.` are listed.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Hidden {
+    Ignored,
+    /// Everything but `.` and `..`.
+    AlmostAll,
+    All,
+}
+
+/// When to color names, from --color.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum When {
+    Never,
+    Always,
+    /// Only when stdout is a terminal.
+    Auto,
+}
+
+fn parse_when(when: &str) -> Result<When, LsError> {
+    match when {
+        "always" | "yes" | "force" => Ok(When::Always),
+        "never" | "no" | "none" => Ok(When::Never),
+        "auto" | "tty" | "if-tty" => Ok(When::Auto),
+        _ => Err(format!("invalid argument '{}' for '--color'", when).into()),
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Sort {
+    Name,
+    Size,
+    Time,
+}
+
+pub struct Args {
+    pub color: When,
+    pub hidden: Hidden,
+    /// Set by -C: lay out columns even when stdout isn't a terminal.
+    pub columns: bool,
+    pub dereference: bool,
+    pub directory: bool,
+    pub long: bool,
+    pub one_per_line: bool,
+    pub recursive: bool,
+    pub reverse: bool,
+    pub sort: Sort,
+    pub time_style: TimeStyle,
+    pub width: Option<usize>,
+    pub files: Vec<String>,
+}
+
+impl Args {
+    fn new() -> Self {
+        Args {
+            color: When::Never,
+            hidden: Hidden::Ignored,
+            columns: false,
+            dereference: false,
+            directory: false,
+            long: false,
+            one_per_line: false,
+            recursive: false,
+            reverse: false,
+            sort: Sort::Name,
+            time_style: TimeStyle::Locale,
+            width: None,
+            files: vec![],
+        }
+    }
+}
+
+pub fn parse(args: Vec<String>) -> Result<Args, LsError> {
+    let mut arg_struct = Args::new();
+    let mut raw_args = args.into_iter();
+    let mut delimited = false;
+    let mut glob = false;
+    // Each argument is lexed on its own, so that `--color` can tell `--color=WHEN` apart from
+    // `--color FILE`.
+    while let Some(raw_arg) = raw_args.next() {
+        if delimited {
+            arg_struct.files.push(raw_arg);
+            continue;
+        }
+        let (lexed, mut attached) = lex_one(raw_arg, "w");
+        for arg in &lexed {
+            match arg {
+                Arg::Positional(positional) => match positional.as_str() {
+                    "--" => delimited = true,
+                    _ => arg_struct.files.push(positional.clone()),
+                },
+                Arg::Short(short) => match short.as_str() {
+                    "a" => arg_struct.hidden = Hidden::All,
+                    "A" => arg_struct.hidden = Hidden::AlmostAll,
+                    "C" => {
+                        arg_struct.columns = true;
+                        arg_struct.long = false;
+                        arg_struct.one_per_line = false;
+                    }
+                    "d" => arg_struct.directory = true,
+                    "l" => {
+                        arg_struct.long = true;
+                        arg_struct.columns = false;
+                    }
+                    "L" => arg_struct.dereference = true,
+                    "r" => arg_struct.reverse = true,
+                    "R" => arg_struct.recursive = true,
+                    "S" => arg_struct.sort = Sort::Size,
+                    "t" => arg_struct.sort = Sort::Time,
+                    "w" => {
+                        let width = value_of(attached.take(), &mut raw_args, arg)?;
+                        arg_struct.width = Some(parse_width(&width)?);
+                    }
+                    "1" => {
+                        arg_struct.one_per_line = true;
+                        arg_struct.columns = false;
+                    }
+                    _ => return Err(unknown_arg_message(arg).into()),
+                },
+                Arg::Long(long) => match long.as_str() {
+                    "all" => arg_struct.hidden = Hidden::All,
+                    "almost-all" => arg_struct.hidden = Hidden::AlmostAll,
+                    "color" => {
+                        arg_struct.color = match attached.take() {
+                            Some(when) => parse_when(&when)?,
+                            None => When::Always,
+                        };
+                    }
+                    "dereference" => arg_struct.dereference = true,
+                    "directory" => arg_struct.directory = true,
+                    "glob" => glob = true,
+                    "reverse" => arg_struct.reverse = true,
+                    "recursive" => arg_struct.recursive = true,
+                    "time-style" => {
+                        let style = value_of(attached.take(), &mut raw_args, arg)?;
+                        arg_struct.time_style = TimeStyle::parse(&style)?;
+                    }
+                    "width" => {
+                        let width = value_of(attached.take(), &mut raw_args, arg)?;
+                        arg_struct.width = Some(parse_width(&width)?);
+                    }
+                    "version" => print_version(),
+                    "help" => print_help(),
+                    _ => return Err(unknown_arg_message(arg).into()),
+                },
+            };
+        }
+        if let (Some(_), Some(arg)) = (attached, lexed.last()) {
+            return Err(unexpected_value_message(arg).into());
+        }
+    }
+    if arg_struct.directory {
+        arg_struct.recursive = false;
+    }
+    if glob {
+        arg_struct.files = expand_all(arg_struct.files);
+    }
+    if arg_struct.files.is_empty() {
+        arg_struct.files.push(".".to_owned());
+    }
+    Ok(arg_struct)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse_strs(args: &[&str]) -> Result<Args, LsError> {
+        parse(args.iter().map(|s| s.to_string()).collect())
+    }
+
+    #[test]
+    fn options() {
+        let args = parse_strs(&[]).unwrap();
+        assert_eq!(args.files, vec!["."]);
+        assert_eq!((args.hidden, args.sort), (Hidden::Ignored, Sort::Name));
+        let args = parse_strs(&["-a", "-t", "-S", "dir"]).unwrap();
+        assert_eq!((args.hidden, args.sort), (Hidden::All, Sort::Size));
+        assert_eq!(args.files, vec!["dir"]);
+        assert!(parse_strs(&["-Z"]).is_err());
+    }
+
+    #[test]
+    fn last_format_wins() {
+        let args = parse_strs(&["-l", "-C"]).unwrap();
+        assert!(args.columns && !args.long);
+        let args = parse_strs(&["-C", "-1", "--width=40"]).unwrap();
+        assert!(!args.columns && args.one_per_line);
+        assert_eq!(args.width, Some(40));
+        assert!(parse_strs(&["-w", "wide"]).is_err());
+        let args = parse_strs(&["-R", "--directory"]).unwrap();
+        assert!(args.directory && !args.recursive);
+    }
+
+    #[test]
+    fn color() {
+        assert_eq!(parse_strs(&[]).unwrap().color, When::Never);
+        let args = parse_strs(&["--color", "dir"]).unwrap();
+        assert_eq!(args.color, When::Always);
+        assert_eq!(args.files, vec!["dir"]);
+        let args = parse_strs(&["--color=auto"]).unwrap();
+        assert_eq!((args.color, args.files), (When::Auto, vec![".".to_owned()]));
+        assert!(parse_strs(&["--color=sometimes"]).is_err());
+        let args = parse_strs(&["--", "--color"]).unwrap();
+        assert_eq!(args.files, vec!["--color"]);
+    }
+}
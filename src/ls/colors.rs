@@ -0,0 +1,106 @@
+use std::collections::HashMap;
+use std::fs::Metadata;
+use std::os::unix::fs::FileTypeExt;
+use std::os::unix::fs::PermissionsExt;
+
+/// What GNU's dircolors gives the common file types, used for anything LS_COLORS leaves out.
+const DEFAULT_COLORS: &str = "di=01;34:ln=01;36:pi=40;33:so=01;35:bd=40;33;01:cd=40;33;01:ex=01;32";
+
+/// The SGR codes to color names with, keyed by file type or extension as in `LS_COLORS`.
+pub struct Colors {
+    /// Two-letter file type keys such as `di` or `ex`.
+    types: HashMap<String, String>,
+    /// `*SUFFIX` patterns, later ones taking precedence as in GNU ls.
+    suffixes: Vec<(String, String)>,
+}
+
+impl Colors {
+    /// The defaults, overridden by whatever `ls_colors` sets.
+    pub fn new(ls_colors: Option<&str>) -> Self {
+        let mut colors = Colors {
+            types: HashMap::new(),
+            suffixes: vec![],
+        };
+        colors.parse(DEFAULT_COLORS);
+        if let Some(ls_colors) = ls_colors {
+            colors.parse(ls_colors);
+        }
+        colors
+    }
+
+    /// Adds the `key=codes` entries of an `LS_COLORS` value, skipping any it can't make sense of.
+    fn parse(&mut self, ls_colors: &str) {
+        for entry in ls_colors.split(':') {
+            let (key, codes) = match entry.split_once('=') {
+                Some(pair) => pair,
+                None => continue,
+            };
+            if let Some(suffix) = key.strip_prefix('*') {
+                self.suffixes.retain(|(existing, _)| existing != suffix);
+                self.suffixes.push((suffix.to_owned(), codes.to_owned()));
+            } else if key.len() == 2 {
+                self.types.insert(key.to_owned(), codes.to_owned());
+            }
+        }
+    }
+
+    /// The codes to print `name` with, if it gets a color at all.
+    pub fn lookup(&self, name: &[u8], metadata: &Metadata) -> Option<&str> {
+        let file_type = metadata.file_type();
+        let key = if file_type.is_symlink() {
+            "ln"
+        } else if file_type.is_dir() {
+            "di"
+        } else if file_type.is_fifo() {
+            "pi"
+        } else if file_type.is_socket() {
+            "so"
+        } else if file_type.is_block_device() {
+            "bd"
+        } else if file_type.is_char_device() {
+            "cd"
+        } else if metadata.permissions().mode() & 0o111 != 0 {
+            "ex"
+        } else {
+            let suffix = self
+                .suffixes
+                .iter()
+                .rev()
+                .find(|(suffix, _)| name.ends_with(suffix.as_bytes()));
+            if let Some((_, codes)) = suffix {
+                return Some(codes);
+            }
+            "fi"
+        };
+        self.types
+            .get(key)
+            .map(String::as_str)
+            .filter(|codes| !codes.is_empty() && *codes != "0" && *codes != "00")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::env;
+    use std::path::Path;
+
+    #[test]
+    fn defaults_color_directories() {
+        let dir = env::temp_dir().symlink_metadata().unwrap();
+        assert_eq!(Colors::new(None).lookup(b"tmp", &dir), Some("01;34"));
+        let colors = Colors::new(Some("di=00;33:no-equals:*.rs=32"));
+        assert_eq!(colors.lookup(b"tmp", &dir), Some("00;33"));
+        assert_eq!(Colors::new(Some("di=")).lookup(b"tmp", &dir), None);
+    }
+
+    #[test]
+    fn suffixes_only_apply_to_plain_files() {
+        let colors = Colors::new(Some("*.rs=32:*.rs=35"));
+        let dir = env::temp_dir().symlink_metadata().unwrap();
+        assert_eq!(colors.lookup(b"src.rs", &dir), Some("01;34"));
+        let plain = Path::new(file!()).metadata().unwrap();
+        assert_eq!(colors.lookup(b"colors.rs", &plain), Some("35"));
+        assert_eq!(colors.lookup(b"colors.txt", &plain), None);
+    }
+}
@@ -0,0 +1,74 @@
+/// The everything-fits-somewhere narrowest column: one character and two of padding.
+const MIN_COLUMN_WIDTH: usize = 3;
+
+/// Names laid out down then across, the way GNU ls arranges its default output.
+#[derive(Debug, PartialEq)]
+pub struct Grid {
+    pub rows: usize,
+    /// The width of each column, not counting the two spaces separating it from the next.
+    pub widths: Vec<usize>,
+}
+
+impl Grid {
+    /// Picks the most columns whose lines stay shorter than `line_width`, given the lengths of
+    /// the names in order. Columns are only as wide as their longest name, so a few long names
+    /// don't spread the whole listing out. A `line_width` of 0 means lines can be any length.
+    pub fn fit(lengths: &[usize], line_width: usize) -> Grid {
+        let line_width = if line_width == 0 {
+            usize::MAX
+        } else {
+            line_width
+        };
+        let most = (line_width / MIN_COLUMN_WIDTH).clamp(1, lengths.len().max(1));
+        for columns in (1..=most).rev() {
+            let rows = lengths.len().div_ceil(columns).max(1);
+            let widths: Vec<usize> = lengths
+                .chunks(rows)
+                .map(|column| column.iter().copied().max().unwrap_or(0))
+                .collect();
+            let total = widths.iter().sum::<usize>() + 2 * widths.len().saturating_sub(1);
+            if total < line_width || columns == 1 {
+                return Grid { rows, widths };
+            }
+        }
+        unreachable!("a single column always fits")
+    }
+
+    /// The indices of the names on `row`, left to right.
+    pub fn row(&self, row: usize, len: usize) -> impl Iterator<Item = usize> {
+        (row..len).step_by(self.rows)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn columns_fit_their_longest_name() {
+        let grid = Grid::fit(&[1, 5, 1, 1, 2], 13);
+        assert_eq!(
+            grid,
+            Grid {
+                rows: 2,
+                widths: vec![5, 1, 2],
+            }
+        );
+        assert_eq!(grid.row(0, 5).collect::<Vec<_>>(), [0, 2, 4]);
+        assert_eq!(grid.row(1, 5).collect::<Vec<_>>(), [1, 3]);
+    }
+
+    #[test]
+    fn lines_stay_shorter_than_the_width() {
+        // Three columns would need exactly 11 characters.
+        assert_eq!(Grid::fit(&[3, 3, 1], 11).rows, 2);
+        assert_eq!(Grid::fit(&[3, 3, 1], 12).rows, 1);
+    }
+
+    #[test]
+    fn narrow_and_unlimited_widths() {
+        assert_eq!(Grid::fit(&[10, 10], 5).rows, 2);
+        assert_eq!(Grid::fit(&[10; 50], 0).rows, 1);
+        assert_eq!(Grid::fit(&[], 80).widths, Vec::<usize>::new());
+    }
+}
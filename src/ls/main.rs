@@ -0,0 +1,386 @@
+mod args;
+mod colors;
+mod layout;
+
+use args::Args;
+use args::Hidden;
+use args::Sort;
+use args::When;
+use colors::Colors;
+use coreutils_common::exit_with;
+use coreutils_common::mode::mode_string;
+use coreutils_common::report_error;
+use coreutils_common::users::group_name;
+use coreutils_common::users::user_name;
+use coreutils_common::walk::Ancestors;
+use coreutils_common::CoreError;
+use layout::Grid;
+use std::env;
+use std::ffi::OsString;
+use std::fmt;
+use std::fmt::Debug;
+use std::fs;
+use std::fs::Metadata;
+use std::io;
+use std::io::Write;
+use std::os::unix::ffi::OsStrExt;
+use std::os::unix::fs::MetadataExt;
+use std::path::Path;
+use std::path::PathBuf;
+use std::process::exit;
+
+pub struct LsError {
+    message: String,
+}
+
+impl Debug for LsError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "ls: {}", self.message)
+    }
+}
+
+impl CoreError for LsError {}
+
+impl<T> From<T> for LsError
+where
+    T: ToString,
+{
+    fn from(error: T) -> Self {
+        LsError {
+            message: error.to_string(),
+        }
+    }
+}
+
+struct Entry {
+    name: OsString,
+    path: PathBuf,
+    metadata: Metadata,
+}
+
+struct Listing<'a> {
+    args: &'a Args,
+    stdout: io::StdoutLock<'a>,
+    /// The line width to fit columns into, or `None` to print one name per line.
+    line_width: Option<usize>,
+    /// The name colors, when --color says to use them.
+    colors: Option<Colors>,
+    /// Set when a section has already been printed, so the next one is preceded by a blank line.
+    printed: bool,
+    ancestors: Ancestors,
+    exit_code: i32,
+}
+
+impl Listing<'_> {
+    fn fail(&mut self, code: i32) {
+        self.exit_code = self.exit_code.max(code);
+    }
+
+    /// The metadata to show for `path`: its target's with -L, unless it's a dangling symlink.
+    fn metadata(&self, path: &Path) -> io::Result<Metadata> {
+        if self.args.dereference {
+            fs::metadata(path).or_else(|_| fs::symlink_metadata(path))
+        } else {
+            fs::symlink_metadata(path)
+        }
+    }
+
+    fn sort(&self, entries: &mut [Entry]) {
+        entries.sort_by(|a, b| {
+            let name = a.name.as_bytes().cmp(b.name.as_bytes());
+            match self.args.sort {
+                Sort::Name => name,
+                Sort::Size => b.metadata.len().cmp(&a.metadata.len()).then(name),
+                Sort::Time => b.metadata.mtime().cmp(&a.metadata.mtime()).then(name),
+            }
+        });
+        if self.args.reverse {
+            entries.reverse();
+        }
+    }
+
+    fn write_name(&mut self, entry: &Entry) -> io::Result<()> {
+        let name = entry.name.as_bytes();
+        let codes = self
+            .colors
+            .as_ref()
+            .and_then(|colors| colors.lookup(name, &entry.metadata));
+        match codes {
+            Some(codes) => {
+                write!(self.stdout, "\x1b[{}m", codes)?;
+                self.stdout.write_all(name)?;
+                self.stdout.write_all(b"\x1b[0m")
+            }
+            None => self.stdout.write_all(name),
+        }
+    }
+
+    fn print(&mut self, entries: &[Entry]) -> io::Result<()> {
+        if self.args.long {
+            self.print_long(entries)
+        } else if let Some(line_width) = self.line_width {
+            self.print_grid(entries, line_width)
+        } else {
+            for entry in entries {
+                self.write_name(entry)?;
+                self.stdout.write_all(b"\n")?;
+            }
+            Ok(())
+        }
+    }
+
+    fn print_grid(&mut self, entries: &[Entry], line_width: usize) -> io::Result<()> {
+        if entries.is_empty() {
+            return Ok(());
+        }
+        let lengths: Vec<usize> = entries
+            .iter()
+            .map(|entry| entry.name.to_string_lossy().chars().count())
+            .collect();
+        let grid = Grid::fit(&lengths, line_width);
+        for row in 0..grid.rows {
+            for index in grid.row(row, entries.len()) {
+                self.write_name(&entries[index])?;
+                if index + grid.rows < entries.len() {
+                    let padding = grid.widths[index / grid.rows] - lengths[index] + 2;
+                    write!(self.stdout, "{:1$}", "", padding)?;
+                }
+            }
+            self.stdout.write_all(b"\n")?;
+        }
+        Ok(())
+    }
+
+    fn print_long(&mut self, entries: &[Entry]) -> io::Result<()> {
+        let rows: Vec<[String; 6]> = entries
+            .iter()
+            .map(|entry| {
+                let metadata = &entry.metadata;
+                [
+                    mode_string(metadata),
+                    metadata.nlink().to_string(),
+                    user_name(metadata.uid()).unwrap_or_else(|| metadata.uid().to_string()),
+                    group_name(metadata.gid()).unwrap_or_else(|| metadata.gid().to_string()),
+                    metadata.len().to_string(),
+                    self.args
+                        .time_style
+                        .format(metadata.mtime(), metadata.mtime_nsec() as u32),
+                ]
+            })
+            .collect();
+        let mut widths = [0; 6];
+        for row in &rows {
+            for (width, field) in widths.iter_mut().zip(row) {
+                *width = (*width).max(field.chars().count());
+            }
+        }
+        for (row, entry) in rows.iter().zip(entries) {
+            write!(
+                self.stdout,
+                "{} {:>nlink$} {:<owner$} {:<group$} {:>size$} {} ",
+                row[0],
+                row[1],
+                row[2],
+                row[3],
+                row[4],
+                row[5],
+                nlink = widths[1],
+                owner = widths[2],
+                group = widths[3],
+                size = widths[4],
+            )?;
+            self.write_name(entry)?;
+            if entry.metadata.file_type().is_symlink() {
+                if let Ok(target) = fs::read_link(&entry.path) {
+                    self.stdout.write_all(b" -> ")?;
+                    self.stdout.write_all(target.as_os_str().as_bytes())?;
+                }
+            }
+            self.stdout.write_all(b"\n")?;
+        }
+        Ok(())
+    }
+
+    /// Lists the directory at `path` and, with -R, the directories inside it.
+    fn list_directory(&mut self, path: &Path, header: bool) -> io::Result<()> {
+        let metadata = match fs::metadata(path) {
+            Ok(metadata) => metadata,
+            Err(e) => {
+                report_error("ls", format!("cannot access '{}': {}", path.display(), e));
+                self.fail(if header { 1 } else { 2 });
+                return Ok(());
+            }
+        };
+        if !self.ancestors.enter("ls", path, &metadata) {
+            self.fail(1);
+            return Ok(());
+        }
+        let listed = self.list_entries(path, header);
+        self.ancestors.leave();
+        listed
+    }
+
+    fn list_entries(&mut self, path: &Path, header: bool) -> io::Result<()> {
+        let read = fs::read_dir(path).and_then(|entries| entries.collect::<Result<Vec<_>, _>>());
+        let dir_entries = match read {
+            Ok(dir_entries) => dir_entries,
+            Err(e) => {
+                report_error(
+                    "ls",
+                    format!("cannot open directory '{}': {}", path.display(), e),
+                );
+                self.fail(if header { 1 } else { 2 });
+                return Ok(());
+            }
+        };
+        let mut entries = vec![];
+        if self.args.hidden == Hidden::All {
+            for name in &[".", ".."] {
+                if let Ok(metadata) = fs::symlink_metadata(path.join(name)) {
+                    entries.push(Entry {
+                        name: OsString::from(name),
+                        path: path.join(name),
+                        metadata,
+                    });
+                }
+            }
+        }
+        for dir_entry in dir_entries {
+            let name = dir_entry.file_name();
+            if self.args.hidden == Hidden::Ignored && name.as_bytes().starts_with(b".") {
+                continue;
+            }
+            let path = dir_entry.path();
+            match self.metadata(&path) {
+                Ok(metadata) => entries.push(Entry {
+                    name,
+                    path,
+                    metadata,
+                }),
+                Err(e) => {
+                    report_error("ls", format!("cannot access '{}': {}", path.display(), e));
+                    self.fail(1);
+                }
+            }
+        }
+        self.sort(&mut entries);
+
+        if self.printed {
+            self.stdout.write_all(b"\n")?;
+        }
+        self.printed = true;
+        if header {
+            self.stdout.write_all(path.as_os_str().as_bytes())?;
+            self.stdout.write_all(b":\n")?;
+        }
+        if self.args.long {
+            let blocks: u64 = entries.iter().map(|entry| entry.metadata.blocks()).sum();
+            writeln!(self.stdout, "total {}", blocks.div_ceil(2))?;
+        }
+        self.print(&entries)?;
+
+        if self.args.recursive {
+            for entry in &entries {
+                let name = entry.name.as_bytes();
+                if entry.metadata.is_dir() && name != b"." && name != b".." {
+                    self.list_directory(&entry.path, true)?;
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+fn stdout_is_terminal() -> bool {
+    unsafe { libc::isatty(libc::STDOUT_FILENO) == 1 }
+}
+
+/// The width to lay columns out in: from -w, then the terminal, then `$COLUMNS`. Columns are
+/// only used on a terminal unless -C asks for them.
+fn line_width(args: &Args) -> Option<usize> {
+    let terminal = stdout_is_terminal();
+    if args.long || args.one_per_line || !(terminal || args.columns) {
+        return None;
+    }
+    if args.width.is_some() {
+        return args.width;
+    }
+    let mut size: libc::winsize = unsafe { std::mem::zeroed() };
+    if terminal
+        && unsafe { libc::ioctl(libc::STDOUT_FILENO, libc::TIOCGWINSZ, &mut size) } == 0
+        && size.ws_col > 0
+    {
+        return Some(size.ws_col.into());
+    }
+    Some(
+        env::var("COLUMNS")
+            .ok()
+            .and_then(|columns| columns.parse().ok())
+            .unwrap_or(80),
+    )
+}
+
+fn main() {
+    if let Err(e) = run() {
+        exit_with(&e);
+    }
+}
+
+fn run() -> Result<(), LsError> {
+    let args = args::parse(env::args().skip(1).collect())?;
+    let stdout = io::stdout();
+    let mut listing = Listing {
+        args: &args,
+        stdout: stdout.lock(),
+        line_width: line_width(&args),
+        colors: match args.color {
+            When::Always => true,
+            When::Auto => stdout_is_terminal(),
+            When::Never => false,
+        }
+        .then(|| Colors::new(env::var("LS_COLORS").ok().as_deref())),
+        printed: false,
+        ancestors: Ancestors::new(),
+        exit_code: 0,
+    };
+
+    let mut files = vec![];
+    let mut directories = vec![];
+    for file in &args.files {
+        let path = PathBuf::from(file);
+        // Without -l or -d (or with -L), a symlink to a directory given on the command line is listed as
+        // that directory.
+        let metadata = if (args.long || args.directory) && !args.dereference {
+            fs::symlink_metadata(&path)
+        } else {
+            fs::metadata(&path).or_else(|_| fs::symlink_metadata(&path))
+        };
+        match metadata {
+            Ok(metadata) if metadata.is_dir() && !args.directory => directories.push(path),
+            Ok(metadata) => files.push(Entry {
+                name: file.into(),
+                path,
+                metadata,
+            }),
+            Err(e) => {
+                report_error("ls", format!("cannot access '{}': {}", file, e));
+                listing.fail(2);
+            }
+        }
+    }
+
+    listing.sort(&mut files);
+    if !files.is_empty() {
+        listing.print(&files)?;
+        listing.printed = true;
+    }
+    let header = args.recursive || args.files.len() > 1;
+    for directory in &directories {
+        listing.list_directory(directory, header)?;
+    }
+    listing.stdout.flush()?;
+    if listing.exit_code != 0 {
+        exit(listing.exit_code);
+    }
+    Ok(())
+}
@@ -1,5 +1,9 @@
 use arglex::lex;
 use arglex::Arg;
+use coreutils_common::die;
+use coreutils_common::needs_arg;
+use coreutils_common::print_and_exit;
+use coreutils_common::version_string;
 use std::env;
 
 #[derive(Debug)]
@@ -8,7 +12,6 @@ enum ArgType {
 }
 
 const HELP: &str = "
-testproject version 1.0.0
 Usage: testproject [options]
 Options:
   -h, --help                Print this help message
@@ -17,22 +20,15 @@ Options:
 ";
 
 fn print_help() -> ! {
-    eprintln!("{}", HELP);
-    std::process::exit(0);
+    print_and_exit(0, format!("\n{}{}", version_string!("testproject"), HELP));
 }
 
 fn print_version() -> ! {
-    println!("testproject version 1.0.0");
-    std::process::exit(0);
-}
-
-fn needs_arg(arg: &str) -> ! {
-    eprintln!("{} needs an argument. See --help for more details.", arg);
-    std::process::exit(1);
+    print_and_exit(0, version_string!("testproject"));
 }
 
 fn unknown_arg(which: &Arg) -> ! {
-    eprintln!(
+    die(format!(
         "error: an unknown {} argument was passed: {}\nSee --help for a list of valid arguments.",
         match which {
             Arg::Positional(_) => "positional",
@@ -40,8 +36,7 @@ fn unknown_arg(which: &Arg) -> ! {
             Arg::Long(_) => "long",
         },
         which
-    );
-    std::process::exit(1);
+    ));
 }
 
 fn main() {
@@ -0,0 +1,73 @@
+use crate::Md5sumError;
+use arglex::lex;
+use arglex::Arg;
+use coreutils_common::print_and_exit;
+use coreutils_common::unknown_arg_message;
+use coreutils_common::version_string;
+
+const HELP: &str = "
+Usage: md5sum [OPTION]... [FILE]...
+Print or check MD5 (128-bit) checksums.
+
+With no FILE, or when FILE is -, read standard input.
+
+Options:
+  -c, --check               Read MD5 sums from the FILEs and check them
+  --tag                     Create a BSD-style checksum
+  --version                 Output version information and exit
+  --help                    Display this help and exit
+";
+
+fn print_help() -> ! {
+    print_and_exit(0, HELP);
+}
+
+fn print_version() -> ! {
+    print_and_exit(0, version_string!("md5sum"));
+}
+
+pub struct Args {
+    pub check: bool,
+    pub tag: bool,
+    pub files: Vec<String>,
+}
+
+impl Args {
+    fn new() -> Self {
+        Args {
+            check: false,
+            tag: false,
+            files: vec![],
+        }
+    }
+}
+
+pub fn parse(args: Vec<String>) -> Result<Args, Md5sumError> {
+    let mut arg_struct = Args::new();
+    for arg in &lex(args) {
+        match arg {
+            Arg::Positional(positional) => match positional.as_str() {
+                "--" => continue,
+                _ => arg_struct.files.push(positional.clone()),
+            },
+            Arg::Short(short) => match short.as_str() {
+                "c" => arg_struct.check = true,
+                _ => return Err(unknown_arg_message(arg).into()),
+            },
+            Arg::Long(long) => match long.as_str() {
+                "check" => arg_struct.check = true,
+                "tag" => arg_struct.tag = true,
+                "version" => print_version(),
+                "help" => print_help(),
+                _ => return Err(unknown_arg_message(arg).into()),
+            },
+        };
+    }
+    if arg_struct.check && arg_struct.tag {
+        return Err("the --tag option is meaningless when verifying checksums".into());
+    }
+    if arg_struct.files.is_empty() {
+        arg_struct.files.push("-".to_owned());
+    }
+    Ok(arg_struct)
+}
@@ -0,0 +1,84 @@
+mod args;
+
+use coreutils_common::checksum::digest_file;
+use coreutils_common::checksum::hex;
+use coreutils_common::checksum::read_chunks;
+use coreutils_common::checksum::verify;
+use coreutils_common::checksum::Algorithm;
+use coreutils_common::exit_with;
+use coreutils_common::report_error;
+use coreutils_common::CoreError;
+use md5::Digest;
+use md5::Md5;
+use std::env;
+use std::fmt;
+use std::fmt::Debug;
+use std::io;
+use std::io::Read;
+use std::process::exit;
+
+pub struct Md5sumError {
+    message: String,
+}
+
+impl Debug for Md5sumError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "md5sum: {}", self.message)
+    }
+}
+
+impl CoreError for Md5sumError {}
+
+impl<T> From<T> for Md5sumError
+where
+    T: ToString,
+{
+    fn from(error: T) -> Self {
+        Md5sumError {
+            message: error.to_string(),
+        }
+    }
+}
+
+const MD5: Algorithm = Algorithm {
+    tool: "md5sum",
+    tag: "MD5",
+    digest_length: 32,
+    digest,
+};
+
+fn digest(input: &mut dyn Read) -> io::Result<String> {
+    let mut hasher = Md5::new();
+    read_chunks(input, &mut |chunk| hasher.update(chunk))?;
+    Ok(hex(&hasher.finalize()))
+}
+
+fn main() {
+    if let Err(e) = run() {
+        exit_with(&e);
+    }
+}
+
+fn run() -> Result<(), Md5sumError> {
+    let args = args::parse(env::args().skip(1).collect())?;
+
+    let mut failed = false;
+    for file in &args.files {
+        if args.check {
+            failed |= !verify(&MD5, file);
+            continue;
+        }
+        match digest_file(&MD5, file) {
+            Ok(hash) if args.tag => println!("MD5 ({}) = {}", file, hash),
+            Ok(hash) => println!("{}  {}", hash, file),
+            Err(e) => {
+                report_error("md5sum", format!("{}: {}", file, e));
+                failed = true;
+            }
+        }
+    }
+    if failed {
+        exit(1);
+    }
+    Ok(())
+}
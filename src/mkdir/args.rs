@@ -0,0 +1,106 @@
+use crate::MkdirError;
+use arglex::lex;
+use arglex::Arg;
+use coreutils_common::print_and_exit;
+use coreutils_common::unknown_arg_message;
+use coreutils_common::version_string;
+use std::slice::Iter;
+
+const HELP: &str = "
+Usage: mkdir [OPTION]... DIRECTORY...
+Create the DIRECTORY(ies), if they do not already exist.
+
+Options:
+  -m, --mode <MODE>         Set file mode (octal, as in chmod), not a=rwx - umask
+  -p, --parents             No error if existing, make parent directories as needed
+  -v, --verbose             Print a message for each created directory
+  --version                 Output version information and exit
+  --help                    Display this help and exit
+";
+
+fn print_help() -> ! {
+    print_and_exit(0, HELP);
+}
+
+fn print_version() -> ! {
+    print_and_exit(0, version_string!("mkdir"));
+}
+
+pub struct Args {
+    pub mode: Option<u32>,
+    pub parents: bool,
+    pub verbose: bool,
+    pub dirs: Vec<String>,
+}
+
+impl Args {
+    fn new() -> Self {
+        Args {
+            mode: None,
+            parents: false,
+            verbose: false,
+            dirs: vec![],
+        }
+    }
+}
+
+fn get_arg_to(args: &mut Iter<Arg>, name: &Arg) -> Result<String, MkdirError> {
+    match args.next() {
+        Some(Arg::Positional(value)) => Ok(value.clone()),
+        _ => Err(format!("{} needs an argument", name).into()),
+    }
+}
+
+fn parse_mode(mode: &str) -> Result<u32, MkdirError> {
+    match u32::from_str_radix(mode, 8) {
+        Ok(mode) if mode <= 0o7777 => Ok(mode),
+        _ => Err(format!("invalid mode '{}'", mode).into()),
+    }
+}
+
+pub fn parse(args: Vec<String>) -> Result<Args, MkdirError> {
+    let args = lex(args);
+
+    let mut args = args.iter();
+    let mut arg_struct = Args::new();
+    while let Some(arg) = args.next() {
+        match arg {
+            Arg::Positional(positional) => match positional.as_str() {
+                "--" => continue,
+                _ => arg_struct.dirs.push(positional.clone()),
+            },
+            Arg::Short(short) => match short.as_str() {
+                "m" => arg_struct.mode = Some(parse_mode(&get_arg_to(&mut args, arg)?)?),
+                "p" => arg_struct.parents = true,
+                "v" => arg_struct.verbose = true,
+                _ => return Err(unknown_arg_message(arg).into()),
+            },
+            Arg::Long(long) => match long.as_str() {
+                "mode" => arg_struct.mode = Some(parse_mode(&get_arg_to(&mut args, arg)?)?),
+                "parents" => arg_struct.parents = true,
+                "verbose" => arg_struct.verbose = true,
+                "version" => print_version(),
+                "help" => print_help(),
+                _ => return Err(unknown_arg_message(arg).into()),
+            },
+        };
+    }
+    if arg_struct.dirs.is_empty() {
+        return Err("missing operand".into());
+    }
+    Ok(arg_struct)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn octal_modes() {
+        assert_eq!(parse_mode("700").unwrap(), 0o700);
+        assert_eq!(parse_mode("1777").unwrap(), 0o1777);
+        assert!(parse_mode("800").is_err());
+        assert!(parse_mode("17777").is_err());
+        assert!(parse_mode("u+x").is_err());
+    }
+}
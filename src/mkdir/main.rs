@@ -0,0 +1,101 @@
+mod args;
+
+use args::Args;
+use coreutils_common::exit_with;
+use coreutils_common::report_error;
+use coreutils_common::CoreError;
+use std::env;
+use std::fmt;
+use std::fmt::Debug;
+use std::fs;
+use std::io;
+use std::io::ErrorKind;
+use std::os::unix::fs::PermissionsExt;
+use std::path::Path;
+use std::process::exit;
+
+pub struct MkdirError {
+    message: String,
+}
+
+impl Debug for MkdirError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "mkdir: {}", self.message)
+    }
+}
+
+impl CoreError for MkdirError {}
+
+impl<T> From<T> for MkdirError
+where
+    T: ToString,
+{
+    fn from(error: T) -> Self {
+        MkdirError {
+            message: error.to_string(),
+        }
+    }
+}
+
+fn created(dir: &Path, args: &Args) {
+    if args.verbose {
+        println!("mkdir: created directory '{}'", dir.display());
+    }
+}
+
+/// Creates the missing ancestors of `dir` one by one, so each can be reported with `-v`.
+fn create_parents(dir: &Path, args: &Args) -> io::Result<()> {
+    let missing: Vec<&Path> = dir
+        .ancestors()
+        .skip(1)
+        .filter(|ancestor| !ancestor.as_os_str().is_empty())
+        .take_while(|ancestor| !ancestor.is_dir())
+        .collect();
+    for ancestor in missing.into_iter().rev() {
+        match fs::create_dir(ancestor) {
+            Ok(()) => created(ancestor, args),
+            Err(e) if e.kind() == ErrorKind::AlreadyExists && ancestor.is_dir() => {}
+            Err(e) => return Err(e),
+        }
+    }
+    Ok(())
+}
+
+fn mkdir(dir: &Path, args: &Args) -> io::Result<()> {
+    if args.parents {
+        create_parents(dir, args)?;
+    }
+    match fs::create_dir(dir) {
+        Ok(()) => created(dir, args),
+        Err(e) if args.parents && e.kind() == ErrorKind::AlreadyExists && dir.is_dir() => {
+            return Ok(());
+        }
+        Err(e) => return Err(e),
+    }
+    if let Some(mode) = args.mode {
+        // Set explicitly rather than passing it to mkdir(2), so the umask doesn't apply.
+        fs::set_permissions(dir, fs::Permissions::from_mode(mode))?;
+    }
+    Ok(())
+}
+
+fn main() {
+    if let Err(e) = run() {
+        exit_with(&e);
+    }
+}
+
+fn run() -> Result<(), MkdirError> {
+    let args = args::parse(env::args().skip(1).collect())?;
+    let mut failed = false;
+    for dir in &args.dirs {
+        if let Err(e) = mkdir(Path::new(dir), &args) {
+            report_error("mkdir", format!("cannot create directory '{}': {}", dir, e));
+            failed = true;
+        }
+    }
+    if failed {
+        exit(1);
+    }
+    Ok(())
+}
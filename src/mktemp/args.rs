@@ -0,0 +1,164 @@
+use crate::MktempError;
+use arglex::lex_one;
+use arglex::Arg;
+use coreutils_common::print_and_exit;
+use coreutils_common::unexpected_value_message;
+use coreutils_common::unknown_arg_message;
+use coreutils_common::version_string;
+use std::env;
+
+const HELP: &str = "
+Usage: mktemp [OPTION]... [TEMPLATE]
+Create a temporary file or directory, safely, and print its name.
+TEMPLATE must contain at least 3 consecutive 'X's in last component.
+If TEMPLATE is not specified, use tmp.XXXXXXXXXX, and --tmpdir is implied.
+Files are created u+rw, and directories u+rwx.
+
+Options:
+  -d, --directory           Create a directory, not a file
+  -p <DIR>, --tmpdir[=DIR]  Interpret TEMPLATE relative to DIR; if DIR is not specified, use
+                              $TMPDIR if set, else /tmp. TEMPLATE must not contain a /
+  -u, --dry-run             Do not create anything; merely print a name (unsafe)
+  --version                 Output version information and exit
+  --help                    Display this help and exit
+";
+
+const DEFAULT_TEMPLATE: &str = "tmp.XXXXXXXXXX";
+
+fn print_help() -> ! {
+    print_and_exit(0, HELP);
+}
+
+fn print_version() -> ! {
+    print_and_exit(0, version_string!("mktemp"));
+}
+
+pub struct Args {
+    pub directory: bool,
+    pub dry_run: bool,
+    /// Where the template is created, if not relative to the current directory.
+    pub tmpdir: Option<String>,
+    pub template: String,
+}
+
+fn default_tmpdir() -> String {
+    env::var("TMPDIR")
+        .ok()
+        .filter(|dir| !dir.is_empty())
+        .unwrap_or_else(|| "/tmp".to_owned())
+}
+
+pub fn parse(args: Vec<String>) -> Result<Args, MktempError> {
+    let mut directory = false;
+    let mut dry_run = false;
+    let mut tmpdir = None;
+    let mut templates = vec![];
+    let mut delimited = false;
+    let mut raw_args = args.into_iter();
+    // Each argument is lexed on its own, so that `--tmpdir` can tell `--tmpdir=DIR` apart from
+    // `--tmpdir TEMPLATE`.
+    while let Some(raw_arg) = raw_args.next() {
+        if delimited {
+            templates.push(raw_arg);
+            continue;
+        }
+        let (lexed, mut attached) = lex_one(raw_arg, "p");
+        for arg in &lexed {
+            match arg {
+                Arg::Positional(positional) => match positional.as_str() {
+                    "--" => delimited = true,
+                    _ => templates.push(positional.clone()),
+                },
+                Arg::Short(short) => match short.as_str() {
+                    "d" => directory = true,
+                    "p" => match attached.take().or_else(|| raw_args.next()) {
+                        Some(dir) => tmpdir = Some(dir),
+                        None => return Err(format!("{} needs an argument", arg).into()),
+                    },
+                    "u" => dry_run = true,
+                    _ => return Err(unknown_arg_message(arg).into()),
+                },
+                Arg::Long(long) => match long.as_str() {
+                    "directory" => directory = true,
+                    "tmpdir" => {
+                        tmpdir = Some(attached.take().unwrap_or_else(default_tmpdir));
+                    }
+                    "dry-run" => dry_run = true,
+                    "version" => print_version(),
+                    "help" => print_help(),
+                    _ => return Err(unknown_arg_message(arg).into()),
+                },
+            };
+        }
+        if let (Some(_), Some(arg)) = (attached, lexed.last()) {
+            return Err(unexpected_value_message(arg).into());
+        }
+    }
+    if templates.len() > 1 {
+        return Err(format!("too many templates: '{}'", templates[1]).into());
+    }
+    let template = match templates.pop() {
+        Some(template) => {
+            if tmpdir.is_some() && template.contains('/') {
+                return Err(format!(
+                    "invalid template, '{}', contains directory separator",
+                    template
+                )
+                .into());
+            }
+            template
+        }
+        None => {
+            tmpdir = tmpdir.or_else(|| Some(default_tmpdir()));
+            DEFAULT_TEMPLATE.to_owned()
+        }
+    };
+    Ok(Args {
+        directory,
+        dry_run,
+        tmpdir,
+        template,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse_strs(args: &[&str]) -> Result<Args, MktempError> {
+        parse(args.iter().map(|s| s.to_string()).collect())
+    }
+
+    #[test]
+    fn directories() {
+        let args = parse_strs(&["-p", "/var", "a.XXX"]).unwrap();
+        assert_eq!(args.tmpdir.as_deref(), Some("/var"));
+        assert_eq!(args.template, "a.XXX");
+        let args = parse_strs(&["--tmpdir=/srv", "-d"]).unwrap();
+        assert_eq!(args.tmpdir.as_deref(), Some("/srv"));
+        assert_eq!(args.template, DEFAULT_TEMPLATE);
+        assert!(args.directory);
+        let args = parse_strs(&["--tmpdir", "b.XXX"]).unwrap();
+        assert_eq!(args.template, "b.XXX");
+        assert!(args.tmpdir.is_some());
+        assert_eq!(parse_strs(&["sub/c.XXX"]).unwrap().tmpdir, None);
+    }
+
+    #[test]
+    fn clustered_short_options() {
+        let args = parse_strs(&["-du", "a.XXX"]).unwrap();
+        assert!(args.directory && args.dry_run);
+        assert_eq!(args.template, "a.XXX");
+        let args = parse_strs(&["-dp/var", "a.XXX"]).unwrap();
+        assert!(args.directory);
+        assert_eq!(args.tmpdir.as_deref(), Some("/var"));
+        assert_eq!(args.template, "a.XXX");
+    }
+
+    #[test]
+    fn bad_templates() {
+        assert!(parse_strs(&["-p", "/tmp", "sub/c.XXX"]).is_err());
+        assert!(parse_strs(&["a.XXX", "b.XXX"]).is_err());
+        assert!(parse_strs(&["-p"]).is_err());
+    }
+}
@@ -0,0 +1,145 @@
+mod args;
+
+use coreutils_common::exit_with;
+use coreutils_common::CoreError;
+use std::env;
+use std::fmt;
+use std::fmt::Debug;
+use std::fs;
+use std::fs::DirBuilder;
+use std::fs::File;
+use std::fs::OpenOptions;
+use std::io;
+use std::io::Read;
+use std::os::unix::fs::DirBuilderExt;
+use std::os::unix::fs::OpenOptionsExt;
+use std::path::Path;
+use std::path::PathBuf;
+
+/// Names that collide are retried with fresh random characters this many times.
+const ATTEMPTS: usize = 100;
+const CHARACTERS: &[u8] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789";
+
+pub struct MktempError {
+    message: String,
+}
+
+impl Debug for MktempError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "mktemp: {}", self.message)
+    }
+}
+
+impl CoreError for MktempError {}
+
+impl<T> From<T> for MktempError
+where
+    T: ToString,
+{
+    fn from(error: T) -> Self {
+        MktempError {
+            message: error.to_string(),
+        }
+    }
+}
+
+/// How many `X`s end the template's last component.
+fn trailing_xs(template: &str) -> usize {
+    template
+        .rsplit('/')
+        .next()
+        .unwrap_or("")
+        .bytes()
+        .rev()
+        .take_while(|&b| b == b'X')
+        .count()
+}
+
+/// The template with its trailing `xs` `X`s replaced by random letters and digits.
+fn fill(template: &str, xs: usize, random: &mut File) -> io::Result<String> {
+    let mut bytes = vec![0; xs];
+    random.read_exact(&mut bytes)?;
+    let suffix: String = bytes
+        .iter()
+        .map(|&b| CHARACTERS[b as usize % CHARACTERS.len()] as char)
+        .collect();
+    Ok(format!("{}{}", &template[..template.len() - xs], suffix))
+}
+
+/// Creates `path` only if nothing already exists there, so a name is never shared.
+fn create(path: &Path, directory: bool) -> io::Result<()> {
+    if directory {
+        DirBuilder::new().mode(0o700).create(path)
+    } else {
+        OpenOptions::new()
+            .write(true)
+            .create_new(true)
+            .mode(0o600)
+            .open(path)
+            .map(drop)
+    }
+}
+
+fn main() {
+    if let Err(e) = run() {
+        exit_with(&e);
+    }
+}
+
+fn run() -> Result<(), MktempError> {
+    let args = args::parse(env::args().skip(1).collect())?;
+    let xs = trailing_xs(&args.template);
+    if xs < 3 {
+        return Err(format!("too few X's in template '{}'", args.template).into());
+    }
+    let mut random =
+        File::open("/dev/urandom").map_err(|e| format!("cannot open /dev/urandom: {}", e))?;
+    let what = if args.directory { "directory" } else { "file" };
+    for _ in 0..ATTEMPTS {
+        let name = fill(&args.template, xs, &mut random)?;
+        let path = match &args.tmpdir {
+            Some(dir) => Path::new(dir).join(name),
+            None => PathBuf::from(name),
+        };
+        let result = if args.dry_run {
+            match fs::symlink_metadata(&path) {
+                Ok(_) => Err(io::ErrorKind::AlreadyExists.into()),
+                Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(()),
+                Err(e) => Err(e),
+            }
+        } else {
+            create(&path, args.directory)
+        };
+        match result {
+            Ok(()) => {
+                println!("{}", path.display());
+                return Ok(());
+            }
+            Err(e) if e.kind() == io::ErrorKind::AlreadyExists => continue,
+            Err(e) => {
+                return Err(format!(
+                    "failed to create {} via template '{}': {}",
+                    what, args.template, e
+                )
+                .into())
+            }
+        }
+    }
+    Err(format!(
+        "failed to create {} via template '{}': no unused name found",
+        what, args.template
+    )
+    .into())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn counting_xs() {
+        assert_eq!(trailing_xs("tmp.XXXXXX"), 6);
+        assert_eq!(trailing_xs("XXXX.tmp"), 0);
+        assert_eq!(trailing_xs("dirXXX/aXX"), 2);
+    }
+}
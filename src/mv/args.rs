@@ -0,0 +1,177 @@
+use crate::MvError;
+use arglex::lex_one;
+use arglex::Arg;
+use coreutils_common::backup::Control;
+use coreutils_common::paths::strip_trailing_slashes;
+use coreutils_common::print_and_exit;
+use coreutils_common::unexpected_value_message;
+use coreutils_common::unknown_arg_message;
+use coreutils_common::version_string;
+
+const HELP: &str = "
+Usage: mv [OPTION]... SOURCE DEST
+  or:  mv [OPTION]... SOURCE... DIRECTORY
+Rename SOURCE to DEST, or move SOURCE(s) to DIRECTORY.
+
+Options:
+  --backup[=CONTROL]        Make a backup of each existing destination file
+  -b                        Like --backup but does not accept an argument
+  -f, --force               Do not prompt before overwriting
+  -i, --interactive         Prompt before overwrite
+  -n, --no-clobber          Do not overwrite an existing file
+  --strip-trailing-slashes  Remove any trailing slashes from each SOURCE
+  -v, --verbose             Explain what is being done
+  --version                 Output version information and exit
+  --help                    Display this help and exit
+
+A trailing slash on a SOURCE that's a symlink to a directory means the directory
+itself, as it does to the kernel; --strip-trailing-slashes makes it the symlink.
+
+The backup CONTROL is one of none (or off), numbered (or t), existing (or nil)
+and simple (or never). A bare --backup or -b uses VERSION_CONTROL, else existing.
+
+If you specify more than one of -i, -f, -n, only the final one takes effect.
+Moves across file systems copy SOURCE, keeping its mode and timestamps, then remove it.
+";
+
+fn print_help() -> ! {
+    print_and_exit(0, HELP);
+}
+
+fn print_version() -> ! {
+    print_and_exit(0, version_string!("mv"));
+}
+
+/// What to do when the destination already exists.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Overwrite {
+    Always,
+    Ask,
+    Never,
+}
+
+pub struct Args {
+    pub backup: Control,
+    pub overwrite: Overwrite,
+    pub strip_trailing_slashes: bool,
+    pub verbose: bool,
+    pub sources: Vec<String>,
+    pub dest: String,
+}
+
+impl Args {
+    fn new() -> Self {
+        Args {
+            backup: Control::Off,
+            overwrite: Overwrite::Always,
+            strip_trailing_slashes: false,
+            verbose: false,
+            sources: vec![],
+            dest: String::new(),
+        }
+    }
+}
+
+pub fn parse(args: Vec<String>) -> Result<Args, MvError> {
+    let mut arg_struct = Args::new();
+    let mut delimited = false;
+    // Each argument is lexed on its own, so that `--backup` can tell `--backup=CONTROL` apart
+    // from `--backup FILE`.
+    for raw_arg in args {
+        if delimited {
+            arg_struct.sources.push(raw_arg);
+            continue;
+        }
+        let (lexed, mut attached) = lex_one(raw_arg, "");
+        for arg in &lexed {
+            match arg {
+                Arg::Positional(positional) => match positional.as_str() {
+                    "--" => delimited = true,
+                    _ => arg_struct.sources.push(positional.clone()),
+                },
+                Arg::Short(short) => match short.as_str() {
+                    "b" => arg_struct.backup = Control::from_env()?,
+                    "f" => arg_struct.overwrite = Overwrite::Always,
+                    "i" => arg_struct.overwrite = Overwrite::Ask,
+                    "n" => arg_struct.overwrite = Overwrite::Never,
+                    "v" => arg_struct.verbose = true,
+                    _ => return Err(unknown_arg_message(arg).into()),
+                },
+                Arg::Long(long) => match long.as_str() {
+                    "backup" => {
+                        arg_struct.backup = match attached.take() {
+                            Some(control) => Control::parse(&control)?,
+                            None => Control::from_env()?,
+                        };
+                    }
+                    "force" => arg_struct.overwrite = Overwrite::Always,
+                    "interactive" => arg_struct.overwrite = Overwrite::Ask,
+                    "no-clobber" => arg_struct.overwrite = Overwrite::Never,
+                    "strip-trailing-slashes" => arg_struct.strip_trailing_slashes = true,
+                    "verbose" => arg_struct.verbose = true,
+                    "version" => print_version(),
+                    "help" => print_help(),
+                    _ => return Err(unknown_arg_message(arg).into()),
+                },
+            };
+        }
+        if let (Some(_), Some(arg)) = (attached, lexed.last()) {
+            return Err(unexpected_value_message(arg).into());
+        }
+    }
+    match arg_struct.sources.len() {
+        0 => return Err("missing file operand".into()),
+        1 => {
+            return Err(format!(
+                "missing destination file operand after '{}'",
+                arg_struct.sources[0]
+            )
+            .into())
+        }
+        _ => arg_struct.dest = arg_struct.sources.pop().unwrap(),
+    }
+    if arg_struct.strip_trailing_slashes {
+        for source in &mut arg_struct.sources {
+            let stripped = strip_trailing_slashes(source).len();
+            source.truncate(stripped);
+        }
+    }
+    Ok(arg_struct)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse_strs(args: &[&str]) -> Result<Args, MvError> {
+        parse(args.iter().map(|s| s.to_string()).collect())
+    }
+
+    #[test]
+    fn last_overwrite_mode_wins() {
+        assert_eq!(
+            parse_strs(&["-n", "-i", "a", "b"]).unwrap().overwrite,
+            Overwrite::Ask
+        );
+        assert_eq!(
+            parse_strs(&["-i", "-f", "a", "b"]).unwrap().overwrite,
+            Overwrite::Always
+        );
+        assert_eq!(
+            parse_strs(&["-f", "-n", "a", "b"]).unwrap().overwrite,
+            Overwrite::Never
+        );
+        assert_eq!(
+            parse_strs(&["-in", "a", "b"]).unwrap().overwrite,
+            Overwrite::Never
+        );
+    }
+
+    #[test]
+    fn clustered_short_options() {
+        let args = parse_strs(&["-fv", "a", "b"]).unwrap();
+        assert!(args.verbose && args.overwrite == Overwrite::Always);
+        assert_eq!(args.sources, vec!["a"]);
+        assert!(parse_strs(&["--verbose=yes", "a", "b"]).is_err());
+    }
+}
@@ -0,0 +1,204 @@
+mod args;
+
+use args::Args;
+use args::Overwrite;
+use coreutils_common::backup::backup;
+use coreutils_common::backup::Control;
+use coreutils_common::copy::copy;
+use coreutils_common::copy::into_itself;
+use coreutils_common::copy::CopyOptions;
+use coreutils_common::copy::Preserve;
+use coreutils_common::copy::Reflink;
+use coreutils_common::exit_with;
+use coreutils_common::paths::same_file;
+use coreutils_common::prompt;
+use coreutils_common::report_error;
+use coreutils_common::signals::remove_partial_on_interrupt;
+use coreutils_common::CoreError;
+use std::env;
+use std::fmt;
+use std::fmt::Debug;
+use std::fs;
+use std::io;
+use std::path::Path;
+use std::path::PathBuf;
+use std::process::exit;
+
+pub struct MvError {
+    message: String,
+}
+
+impl Debug for MvError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "mv: {}", self.message)
+    }
+}
+
+impl CoreError for MvError {}
+
+impl<T> From<T> for MvError
+where
+    T: ToString,
+{
+    fn from(error: T) -> Self {
+        MvError {
+            message: error.to_string(),
+        }
+    }
+}
+
+fn confirm(question: &str) -> bool {
+    prompt::confirm(&format!("mv: {}", question)).unwrap_or(false)
+}
+
+/// Moves across file systems by copying everything, metadata included, then removing the
+/// source.
+fn copy_and_remove(src: &Path, dest: &Path) -> io::Result<bool> {
+    let options = CopyOptions {
+        tool: "mv",
+        backup: Control::Off,
+        force: true,
+        interactive: false,
+        no_clobber: false,
+        one_file_system: false,
+        preserve: Preserve::all(),
+        recursive: true,
+        reflink: Reflink::Auto,
+        verbose: false,
+    };
+    if !copy(src, dest, &options) {
+        return Ok(false);
+    }
+    if src.symlink_metadata()?.is_dir() {
+        fs::remove_dir_all(src)?;
+    } else {
+        fs::remove_file(src)?;
+    }
+    Ok(true)
+}
+
+/// Moves `src` to exactly `dest`. Returns false if it failed; the failure has already been
+/// reported.
+fn move_to(src: &Path, dest: &Path, args: &Args) -> bool {
+    let metadata = match src.symlink_metadata() {
+        Ok(metadata) => metadata,
+        Err(e) => {
+            report_error("mv", format!("cannot stat '{}': {}", src.display(), e));
+            return false;
+        }
+    };
+    // Checked before any backup, which would otherwise move the only copy out of the way.
+    let existing = dest.symlink_metadata().ok();
+    if existing
+        .as_ref()
+        .is_some_and(|existing| same_file(&metadata, existing))
+    {
+        report_error(
+            "mv",
+            format!(
+                "'{}' and '{}' are the same file",
+                src.display(),
+                dest.display()
+            ),
+        );
+        return false;
+    }
+    if existing.is_some() {
+        match args.overwrite {
+            Overwrite::Never => return true,
+            Overwrite::Ask if !confirm(&format!("overwrite '{}'", dest.display())) => return true,
+            _ => {}
+        }
+    }
+    let backed_up = match backup(dest, args.backup) {
+        Ok(backed_up) => backed_up,
+        Err(e) => {
+            report_error("mv", format!("cannot back up '{}': {}", dest.display(), e));
+            return false;
+        }
+    };
+
+    let moved = match fs::rename(src, dest) {
+        Err(e) if e.raw_os_error() == Some(libc::EXDEV) => copy_and_remove(src, dest),
+        renamed => renamed.map(|()| true),
+    };
+    match moved {
+        Ok(true) => {
+            if args.verbose {
+                match backed_up {
+                    Some(backed_up) => println!(
+                        "renamed '{}' -> '{}' (backup: '{}')",
+                        src.display(),
+                        dest.display(),
+                        backed_up.display()
+                    ),
+                    None => println!("renamed '{}' -> '{}'", src.display(), dest.display()),
+                }
+            }
+            true
+        }
+        Ok(false) => false,
+        Err(e) => {
+            report_error(
+                "mv",
+                format!(
+                    "cannot move '{}' to '{}': {}",
+                    src.display(),
+                    dest.display(),
+                    e
+                ),
+            );
+            false
+        }
+    }
+}
+
+fn main() {
+    if let Err(e) = run() {
+        exit_with(&e);
+    }
+}
+
+fn run() -> Result<(), MvError> {
+    let args = args::parse(env::args().skip(1).collect())?;
+    remove_partial_on_interrupt();
+    let dest = Path::new(&args.dest);
+    let into_dir = dest.is_dir();
+    if args.sources.len() > 1 && !into_dir {
+        return Err(format!("target '{}' is not a directory", args.dest).into());
+    }
+
+    let mut failed = false;
+    for source in &args.sources {
+        let src = Path::new(source);
+        let target: PathBuf = if into_dir {
+            match src.file_name() {
+                Some(name) => dest.join(name),
+                None => {
+                    report_error("mv", format!("cannot move '{}': invalid file name", source));
+                    failed = true;
+                    continue;
+                }
+            }
+        } else {
+            dest.to_path_buf()
+        };
+        if src.is_dir() && into_itself(src, &target) {
+            report_error(
+                "mv",
+                format!(
+                    "cannot move '{}' to a subdirectory of itself, '{}'",
+                    source,
+                    target.display()
+                ),
+            );
+            failed = true;
+            continue;
+        }
+        failed |= !move_to(src, &target, &args);
+    }
+    if failed {
+        exit(1);
+    }
+    Ok(())
+}
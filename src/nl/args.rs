@@ -0,0 +1,172 @@
+use crate::NlError;
+use arglex::lex;
+use arglex::Arg;
+use coreutils_common::print_and_exit;
+use coreutils_common::unknown_arg_message;
+use coreutils_common::version_string;
+use std::slice::Iter;
+
+const HELP: &str = "
+Usage: nl [OPTION]... [FILE]...
+Write each FILE to standard output, with line numbers added.
+
+With no FILE, or when FILE is -, read standard input.
+
+Options:
+  -b, --body-numbering <STYLE>
+                            Use STYLE for numbering body lines
+  -i, --line-increment <NUMBER>
+                            Line number increment at each line
+  -s, --number-separator <STRING>
+                            Add STRING after (possible) line number
+  -w, --number-width <NUMBER>
+                            Use NUMBER columns for line numbers
+  --version                 Output version information and exit
+  --help                    Display this help and exit
+
+STYLE is one of:
+  a                         Number all lines
+  t                         Number only nonempty lines
+  n                         Number no lines
+
+By default, nonempty lines are numbered from 1 in steps of 1, six columns wide,
+followed by a TAB.
+";
+
+fn print_help() -> ! {
+    print_and_exit(0, HELP);
+}
+
+fn print_version() -> ! {
+    print_and_exit(0, version_string!("nl"));
+}
+
+/// Which body lines get a number.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Style {
+    All,
+    NonEmpty,
+    None,
+}
+
+pub struct Args {
+    pub style: Style,
+    pub increment: u64,
+    pub separator: String,
+    pub width: usize,
+    pub files: Vec<String>,
+}
+
+impl Args {
+    fn new() -> Self {
+        Args {
+            style: Style::NonEmpty,
+            increment: 1,
+            separator: "\t".to_owned(),
+            width: 6,
+            files: vec![],
+        }
+    }
+}
+
+fn get_arg_to(args: &mut Iter<Arg>, name: &Arg) -> Result<String, NlError> {
+    match args.next() {
+        Some(Arg::Positional(value)) => Ok(value.clone()),
+        _ => Err(format!("{} needs an argument", name).into()),
+    }
+}
+
+fn parse_style(style: &str) -> Result<Style, NlError> {
+    match style {
+        "a" => Ok(Style::All),
+        "t" => Ok(Style::NonEmpty),
+        "n" => Ok(Style::None),
+        _ => Err(format!("invalid body numbering style: '{}'", style).into()),
+    }
+}
+
+fn parse_increment(increment: &str) -> Result<u64, NlError> {
+    increment
+        .parse()
+        .map_err(|_| format!("invalid line number increment: '{}'", increment).into())
+}
+
+fn parse_width(width: &str) -> Result<usize, NlError> {
+    match width.parse() {
+        Ok(width) if width > 0 => Ok(width),
+        _ => Err(format!("invalid line number field width: '{}'", width).into()),
+    }
+}
+
+pub fn parse(args: Vec<String>) -> Result<Args, NlError> {
+    let args = lex(args);
+
+    let mut args = args.iter();
+    let mut arg_struct = Args::new();
+    while let Some(arg) = args.next() {
+        match arg {
+            Arg::Positional(positional) => match positional.as_str() {
+                "--" => continue,
+                _ => arg_struct.files.push(positional.clone()),
+            },
+            Arg::Short(short) => match short.as_str() {
+                "b" => arg_struct.style = parse_style(&get_arg_to(&mut args, arg)?)?,
+                "i" => arg_struct.increment = parse_increment(&get_arg_to(&mut args, arg)?)?,
+                "s" => arg_struct.separator = get_arg_to(&mut args, arg)?,
+                "w" => arg_struct.width = parse_width(&get_arg_to(&mut args, arg)?)?,
+                _ => return Err(unknown_arg_message(arg).into()),
+            },
+            Arg::Long(long) => match long.as_str() {
+                "body-numbering" => arg_struct.style = parse_style(&get_arg_to(&mut args, arg)?)?,
+                "line-increment" => {
+                    arg_struct.increment = parse_increment(&get_arg_to(&mut args, arg)?)?
+                }
+                "number-separator" => arg_struct.separator = get_arg_to(&mut args, arg)?,
+                "number-width" => arg_struct.width = parse_width(&get_arg_to(&mut args, arg)?)?,
+                "version" => print_version(),
+                "help" => print_help(),
+                _ => return Err(unknown_arg_message(arg).into()),
+            },
+        };
+    }
+    if arg_struct.files.is_empty() {
+        arg_struct.files.push("-".to_owned());
+    }
+    Ok(arg_struct)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse_strs(args: &[&str]) -> Result<Args, NlError> {
+        parse(args.iter().map(|s| s.to_string()).collect())
+    }
+
+    #[test]
+    fn defaults() {
+        let args = parse_strs(&[]).unwrap();
+        assert_eq!(args.style, Style::NonEmpty);
+        assert_eq!(args.increment, 1);
+        assert_eq!(args.separator, "\t");
+        assert_eq!(args.width, 6);
+        assert_eq!(args.files, vec!["-"]);
+    }
+
+    #[test]
+    fn attached_and_separate_values() {
+        let args = parse_strs(&["-ba", "-w", "3", "--number-separator=: ", "-i2"]).unwrap();
+        assert_eq!(args.style, Style::All);
+        assert_eq!(args.width, 3);
+        assert_eq!(args.separator, ": ");
+        assert_eq!(args.increment, 2);
+    }
+
+    #[test]
+    fn rejects_bad_values() {
+        assert!(parse_strs(&["-b", "x"]).is_err());
+        assert!(parse_strs(&["-w", "0"]).is_err());
+        assert!(parse_strs(&["-i", "-1"]).is_err());
+        assert!(parse_strs(&["-s"]).is_err());
+    }
+}
@@ -0,0 +1,114 @@
+mod args;
+
+use args::Args;
+use args::Style;
+use coreutils_common::exit_with;
+use coreutils_common::input::lines;
+use coreutils_common::input::open_input;
+use coreutils_common::report_error;
+use coreutils_common::CoreError;
+use std::env;
+use std::fmt;
+use std::fmt::Debug;
+use std::io;
+use std::io::BufRead;
+use std::io::BufWriter;
+use std::io::Write;
+use std::process::exit;
+
+pub struct NlError {
+    message: String,
+}
+
+impl Debug for NlError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "nl: {}", self.message)
+    }
+}
+
+impl CoreError for NlError {}
+
+impl<T> From<T> for NlError
+where
+    T: ToString,
+{
+    fn from(error: T) -> Self {
+        NlError {
+            message: error.to_string(),
+        }
+    }
+}
+
+/// Numbering state that carries over from one file to the next.
+struct Nl<'a> {
+    args: &'a Args,
+    line_number: u64,
+    /// What unnumbered lines are prefixed with, so their text lines up with numbered ones.
+    blank_prefix: String,
+}
+
+impl<'a> Nl<'a> {
+    fn new(args: &'a Args) -> Self {
+        Nl {
+            args,
+            line_number: 1,
+            blank_prefix: " ".repeat(args.width + args.separator.chars().count()),
+        }
+    }
+
+    fn copy(&mut self, input: &mut dyn BufRead, out: &mut dyn Write) -> io::Result<()> {
+        for line in lines(input) {
+            let line = line?;
+            let numbered = match self.args.style {
+                Style::All => true,
+                Style::NonEmpty => line != b"\n",
+                Style::None => false,
+            };
+            if numbered {
+                write!(
+                    out,
+                    "{:>width$}{}",
+                    self.line_number,
+                    self.args.separator,
+                    width = self.args.width
+                )?;
+                self.line_number = self.line_number.wrapping_add(self.args.increment);
+            } else {
+                out.write_all(self.blank_prefix.as_bytes())?;
+            }
+            out.write_all(&line)?;
+        }
+        Ok(())
+    }
+}
+
+fn main() {
+    if let Err(e) = run() {
+        exit_with(&e);
+    }
+}
+
+fn run() -> Result<(), NlError> {
+    let args = args::parse(env::args().skip(1).collect())?;
+    let stdout = io::stdout();
+    let mut out = BufWriter::new(stdout.lock());
+    let mut nl = Nl::new(&args);
+    let mut failed = false;
+    for file in &args.files {
+        let result = match open_input(file) {
+            Ok(mut input) => nl.copy(&mut input, &mut out),
+            Err(e) => {
+                out.flush()?;
+                report_error("nl", format!("{}: {}", file, e));
+                failed = true;
+                continue;
+            }
+        };
+        result.map_err(|e| format!("{}: {}", file, e))?;
+    }
+    out.flush()?;
+    if failed {
+        exit(1);
+    }
+    Ok(())
+}
@@ -0,0 +1,336 @@
+use crate::PrintfError;
+
+/// One piece of a printf FORMAT: literal bytes (escapes already expanded), a conversion, or
+/// `\c`, which stops all further output.
+#[derive(Debug, PartialEq)]
+pub enum Piece {
+    Literal(Vec<u8>),
+    Directive(Spec),
+    Stop,
+}
+
+#[derive(Debug, Default, PartialEq)]
+pub struct Spec {
+    left_align: bool,
+    zero_pad: bool,
+    plus: bool,
+    space: bool,
+    alternate: bool,
+    width: usize,
+    precision: Option<usize>,
+    conversion: u8,
+}
+
+/// Expands the backslash escape at the front of `rest` (just past the backslash) onto `out`,
+/// returning how many bytes it took, or `None` for `\c`.
+fn escape(rest: &[u8], out: &mut Vec<u8>) -> Option<usize> {
+    let (byte, width) = match rest.first() {
+        None => (b'\\', 0),
+        Some(b'\\') => (b'\\', 1),
+        Some(b'"') => (b'"', 1),
+        Some(b'\'') => (b'\'', 1),
+        Some(b'a') => (0x07, 1),
+        Some(b'b') => (0x08, 1),
+        Some(b'c') => return None,
+        Some(b'e') => (0x1b, 1),
+        Some(b'f') => (0x0c, 1),
+        Some(b'n') => (b'\n', 1),
+        Some(b'r') => (b'\r', 1),
+        Some(b't') => (b'\t', 1),
+        Some(b'v') => (0x0b, 1),
+        Some(b'0'..=b'7') => {
+            let digits = rest
+                .iter()
+                .take(3)
+                .take_while(|b| (b'0'..=b'7').contains(b))
+                .count();
+            let value = rest[..digits]
+                .iter()
+                .fold(0u32, |value, &b| value * 8 + u32::from(b - b'0'));
+            (value as u8, digits)
+        }
+        Some(b'x') => {
+            let digits = rest[1..]
+                .iter()
+                .take(2)
+                .take_while(|b| b.is_ascii_hexdigit())
+                .count();
+            if digits == 0 {
+                out.extend_from_slice(b"\\x");
+                return Some(1);
+            }
+            let hex = std::str::from_utf8(&rest[1..1 + digits]).unwrap();
+            (u8::from_str_radix(hex, 16).unwrap(), 1 + digits)
+        }
+        Some(&other) => {
+            out.push(b'\\');
+            (other, 1)
+        }
+    };
+    out.push(byte);
+    Some(width)
+}
+
+fn number(format: &[u8], i: &mut usize) -> usize {
+    let mut value = 0usize;
+    while let Some(digit) = format.get(*i).filter(|b| b.is_ascii_digit()) {
+        value = value
+            .saturating_mul(10)
+            .saturating_add(usize::from(digit - b'0'));
+        *i += 1;
+    }
+    value
+}
+
+pub fn parse(format: &str) -> Result<Vec<Piece>, PrintfError> {
+    let format = format.as_bytes();
+    let mut pieces = vec![];
+    let mut literal = vec![];
+    let mut i = 0;
+    while i < format.len() {
+        match format[i] {
+            b'\\' => match escape(&format[i + 1..], &mut literal) {
+                Some(width) => i += 1 + width,
+                None => {
+                    pieces.push(Piece::Literal(literal));
+                    pieces.push(Piece::Stop);
+                    return Ok(pieces);
+                }
+            },
+            b'%' if format.get(i + 1) == Some(&b'%') => {
+                literal.push(b'%');
+                i += 2;
+            }
+            b'%' => {
+                let start = i;
+                i += 1;
+                let mut spec = Spec::default();
+                while let Some(&flag) = format.get(i) {
+                    match flag {
+                        b'-' => spec.left_align = true,
+                        b'0' => spec.zero_pad = true,
+                        b'+' => spec.plus = true,
+                        b' ' => spec.space = true,
+                        b'#' => spec.alternate = true,
+                        _ => break,
+                    }
+                    i += 1;
+                }
+                spec.width = number(format, &mut i);
+                if format.get(i) == Some(&b'.') {
+                    i += 1;
+                    spec.precision = Some(number(format, &mut i));
+                }
+                match format.get(i) {
+                    Some(&c) if b"sdiuxXoc".contains(&c) => spec.conversion = c,
+                    _ => {
+                        let end = (i + 1).min(format.len());
+                        return Err(format!(
+                            "{}: invalid conversion specification",
+                            String::from_utf8_lossy(&format[start..end])
+                        )
+                        .into());
+                    }
+                }
+                i += 1;
+                if !literal.is_empty() {
+                    pieces.push(Piece::Literal(literal));
+                    literal = vec![];
+                }
+                pieces.push(Piece::Directive(spec));
+            }
+            byte => {
+                literal.push(byte);
+                i += 1;
+            }
+        }
+    }
+    if !literal.is_empty() {
+        pieces.push(Piece::Literal(literal));
+    }
+    Ok(pieces)
+}
+
+/// Parses a numeric argument the way printf does: decimal, `0x` hex, `0` octal, or `'c` for a
+/// character's code.
+pub fn parse_integer(arg: &str) -> Result<i64, PrintfError> {
+    let invalid = || PrintfError::from(format!("'{}': expected a numeric value", arg));
+    if let Some(quoted) = arg.strip_prefix('\'').or_else(|| arg.strip_prefix('"')) {
+        return quoted.chars().next().map(|c| c as i64).ok_or_else(invalid);
+    }
+    let trimmed = arg.trim_start();
+    let (negative, digits) = match trimmed.as_bytes().first() {
+        Some(b'-') => (true, &trimmed[1..]),
+        Some(b'+') => (false, &trimmed[1..]),
+        _ => (false, trimmed),
+    };
+    let magnitude = if let Some(hex) = digits
+        .strip_prefix("0x")
+        .or_else(|| digits.strip_prefix("0X"))
+    {
+        u64::from_str_radix(hex, 16)
+    } else if digits.len() > 1 && digits.starts_with('0') {
+        u64::from_str_radix(&digits[1..], 8)
+    } else {
+        digits.parse()
+    }
+    .map_err(|_| invalid())?;
+    let value = magnitude as i64;
+    Ok(if negative {
+        value.wrapping_neg()
+    } else {
+        value
+    })
+}
+
+impl Spec {
+    pub fn takes_number(&self) -> bool {
+        self.conversion != b's' && self.conversion != b'c'
+    }
+
+    fn pad(&self, body: &[u8], out: &mut Vec<u8>) {
+        let fill = self.width.saturating_sub(body.len());
+        if !self.left_align {
+            out.resize(out.len() + fill, b' ');
+        }
+        out.extend_from_slice(body);
+        if self.left_align {
+            out.resize(out.len() + fill, b' ');
+        }
+    }
+
+    pub fn format_text(&self, arg: &str, out: &mut Vec<u8>) {
+        let text = if self.conversion == b'c' {
+            arg.chars().next().map(String::from).unwrap_or_default()
+        } else {
+            match self.precision {
+                Some(precision) => arg.chars().take(precision).collect(),
+                None => arg.to_owned(),
+            }
+        };
+        self.pad(text.as_bytes(), out);
+    }
+
+    pub fn format_number(&self, value: i64, out: &mut Vec<u8>) {
+        let (sign, digits) = match self.conversion {
+            b'd' | b'i' => {
+                let sign = if value < 0 {
+                    "-"
+                } else if self.plus {
+                    "+"
+                } else if self.space {
+                    " "
+                } else {
+                    ""
+                };
+                (sign, value.unsigned_abs().to_string())
+            }
+            b'u' => ("", (value as u64).to_string()),
+            b'o' => ("", format!("{:o}", value as u64)),
+            b'x' => ("", format!("{:x}", value as u64)),
+            _ => ("", format!("{:X}", value as u64)),
+        };
+        let mut digits = match self.precision {
+            Some(0) if value == 0 => String::new(),
+            Some(precision) if precision > digits.len() => {
+                format!("{}{}", "0".repeat(precision - digits.len()), digits)
+            }
+            _ => digits,
+        };
+        let prefix = match self.conversion {
+            b'o' if self.alternate && !digits.starts_with('0') => {
+                digits.insert(0, '0');
+                ""
+            }
+            b'x' if self.alternate && value != 0 => "0x",
+            b'X' if self.alternate && value != 0 => "0X",
+            _ => "",
+        };
+        let lead = format!("{}{}", sign, prefix);
+        let zero_fill = self.zero_pad && !self.left_align && self.precision.is_none();
+        if zero_fill {
+            let fill = self.width.saturating_sub(lead.len() + digits.len());
+            let body = format!("{}{}{}", lead, "0".repeat(fill), digits);
+            out.extend_from_slice(body.as_bytes());
+        } else {
+            self.pad(format!("{}{}", lead, digits).as_bytes(), out);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn directive(format: &str) -> Spec {
+        let pieces = parse(format).unwrap();
+        match pieces.into_iter().next() {
+            Some(Piece::Directive(spec)) => spec,
+            other => panic!("{:?}", other),
+        }
+    }
+
+    fn number(format: &str, value: i64) -> String {
+        let mut out = vec![];
+        directive(format).format_number(value, &mut out);
+        String::from_utf8(out).unwrap()
+    }
+
+    fn text(format: &str, arg: &str) -> String {
+        let mut out = vec![];
+        directive(format).format_text(arg, &mut out);
+        String::from_utf8(out).unwrap()
+    }
+
+    #[test]
+    fn literals_and_escapes() {
+        assert_eq!(
+            parse("a\\tb%%\\101\\x41\\q").unwrap(),
+            vec![Piece::Literal(b"a\tb%AA\\q".to_vec())]
+        );
+        assert_eq!(
+            parse("a\\cb").unwrap(),
+            vec![Piece::Literal(b"a".to_vec()), Piece::Stop]
+        );
+    }
+
+    #[test]
+    fn integers() {
+        assert_eq!(number("%d", -42), "-42");
+        assert_eq!(number("%5d", 42), "   42");
+        assert_eq!(number("%-5d", 42), "42   ");
+        assert_eq!(number("%05d", -42), "-0042");
+        assert_eq!(number("%.3d", 7), "007");
+        assert_eq!(number("%+d", 7), "+7");
+        assert_eq!(number("%x", 255), "ff");
+        assert_eq!(number("%#X", 255), "0XFF");
+        assert_eq!(number("%o", 8), "10");
+        assert_eq!(number("%#o", 8), "010");
+        assert_eq!(number("%u", -1), "18446744073709551615");
+    }
+
+    #[test]
+    fn strings() {
+        assert_eq!(text("%s", "hi"), "hi");
+        assert_eq!(text("%5s", "hi"), "   hi");
+        assert_eq!(text("%-5s", "hi"), "hi   ");
+        assert_eq!(text("%.2s", "hello"), "he");
+        assert_eq!(text("%c", "hello"), "h");
+    }
+
+    #[test]
+    fn numeric_arguments() {
+        assert_eq!(parse_integer("42").unwrap(), 42);
+        assert_eq!(parse_integer("-0x10").unwrap(), -16);
+        assert_eq!(parse_integer("010").unwrap(), 8);
+        assert_eq!(parse_integer("'A").unwrap(), 65);
+        assert!(parse_integer("abc").is_err());
+        assert!(parse_integer("").is_err());
+    }
+
+    #[test]
+    fn invalid_conversion() {
+        assert!(parse("%z").is_err());
+        assert!(parse("%").is_err());
+    }
+}
@@ -0,0 +1,138 @@
+mod format;
+
+use arglex::lex;
+use arglex::Arg;
+use coreutils_common::eprintln_error;
+use coreutils_common::exit_with;
+use coreutils_common::print_and_exit;
+use coreutils_common::version_string;
+use coreutils_common::CoreError;
+use format::Piece;
+use std::env;
+use std::fmt;
+use std::fmt::Debug;
+use std::io;
+use std::io::Write;
+use std::process::exit;
+
+const HELP: &str = "
+Usage: printf FORMAT [ARGUMENT]...
+  or:  printf OPTION
+Print ARGUMENT(s) according to FORMAT.
+
+Options:
+  --version                 Output version information and exit
+  --help                    Display this help and exit
+
+FORMAT controls the output as in C printf. Interpreted sequences are:
+  \\\\, \\\", \\a, \\b, \\e, \\f, \\n, \\r, \\t, \\v
+                            The usual C escapes
+  \\c                        Produce no further output
+  \\NNN                      Byte with octal value NNN (1 to 3 digits)
+  \\xHH                      Byte with hexadecimal value HH (1 to 2 digits)
+  %%                        A single %
+  %s, %c                    An ARGUMENT as a string, or its first character
+  %d, %i, %u, %o, %x, %X    An ARGUMENT as a signed, unsigned, octal or hex integer
+
+Directives take the flags -, 0, +, space and #, a width and a precision.
+FORMAT is reused as necessary to consume all ARGUMENTs.
+";
+
+pub struct PrintfError {
+    message: String,
+}
+
+impl Debug for PrintfError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "printf: {}", self.message)
+    }
+}
+
+impl CoreError for PrintfError {}
+
+impl<T> From<T> for PrintfError
+where
+    T: ToString,
+{
+    fn from(error: T) -> Self {
+        PrintfError {
+            message: error.to_string(),
+        }
+    }
+}
+
+/// Splits off the FORMAT. Only the first argument goes through the lexer, so everything after
+/// it is taken literally, even `-1` or `--help`.
+fn split_format(mut raw_args: Vec<String>) -> Result<(String, Vec<String>), PrintfError> {
+    if raw_args.is_empty() {
+        return Err("missing operand".into());
+    }
+    let first = raw_args.remove(0);
+    match lex(vec![first.clone()]).as_slice() {
+        [Arg::Long(long)] if long == "help" && raw_args.is_empty() => print_and_exit(0, HELP),
+        [Arg::Long(long)] if long == "version" && raw_args.is_empty() => {
+            print_and_exit(0, version_string!("printf"))
+        }
+        [Arg::Positional(delimiter)] if delimiter == "--" => {
+            if raw_args.is_empty() {
+                return Err("missing operand".into());
+            }
+            let format = raw_args.remove(0);
+            Ok((format, raw_args))
+        }
+        _ => Ok((first, raw_args)),
+    }
+}
+
+fn main() {
+    if let Err(e) = run() {
+        exit_with(&e);
+    }
+}
+
+fn run() -> Result<(), PrintfError> {
+    let (format, args) = split_format(env::args().skip(1).collect())?;
+    let pieces = format::parse(&format)?;
+    let consumes = pieces
+        .iter()
+        .any(|piece| matches!(piece, Piece::Directive(_)));
+
+    let mut out = vec![];
+    let mut failed = false;
+    let mut args = args.iter();
+    'outer: loop {
+        for piece in &pieces {
+            match piece {
+                Piece::Literal(bytes) => out.extend_from_slice(bytes),
+                Piece::Stop => break 'outer,
+                Piece::Directive(spec) => {
+                    let arg = args.next().map_or("", String::as_str);
+                    if !spec.takes_number() {
+                        spec.format_text(arg, &mut out);
+                        continue;
+                    }
+                    let value = if arg.is_empty() {
+                        0
+                    } else {
+                        format::parse_integer(arg).unwrap_or_else(|e| {
+                            eprintln_error(format!("{:?}", e));
+                            failed = true;
+                            0
+                        })
+                    };
+                    spec.format_number(value, &mut out);
+                }
+            }
+        }
+        if !consumes || args.len() == 0 {
+            break;
+        }
+    }
+
+    io::stdout().write_all(&out)?;
+    if failed {
+        io::stdout().flush()?;
+        exit(1);
+    }
+    Ok(())
+}
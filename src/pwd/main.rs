@@ -0,0 +1,89 @@
+use arglex::lex;
+use arglex::Arg;
+use coreutils_common::die;
+use coreutils_common::print_and_exit;
+use coreutils_common::unknown_arg_message;
+use coreutils_common::version_string;
+use std::env;
+use std::fs;
+use std::os::unix::ffi::OsStrExt;
+use std::os::unix::fs::MetadataExt;
+use std::path::PathBuf;
+
+const HELP: &str = "
+Usage: pwd [OPTION]...
+Print the full filename of the current working directory.
+
+Options:
+  -L, --logical             Use PWD from environment, even if it contains symlinks
+  -P, --physical            Resolve all symlinks
+  --version                 Output version information and exit
+  --help                    Display this help and exit
+
+If no option is specified, -L is assumed.
+";
+
+fn fail(message: impl AsRef<str>) -> ! {
+    die(format!("pwd: {}", message.as_ref()));
+}
+
+/// Whether the last of -L/-P given asks for the physical directory.
+fn parse(args: Vec<String>) -> bool {
+    let mut physical = false;
+    for arg in &lex(args) {
+        match arg {
+            Arg::Short(short) => match short.as_str() {
+                "L" => physical = false,
+                "P" => physical = true,
+                _ => fail(unknown_arg_message(arg)),
+            },
+            Arg::Long(long) => match long.as_str() {
+                "logical" => physical = false,
+                "physical" => physical = true,
+                "version" => print_and_exit(0, version_string!("pwd")),
+                "help" => print_and_exit(0, HELP),
+                _ => fail(unknown_arg_message(arg)),
+            },
+            Arg::Positional(_) => {}
+        }
+    }
+    physical
+}
+
+/// `$PWD`, if it is an absolute path without `.` or `..` components that names the same
+/// directory as `.`.
+fn logical() -> Option<PathBuf> {
+    let pwd = PathBuf::from(env::var_os("PWD")?);
+    // `Path::components` normalizes `.` away, so look at the raw segments.
+    let dotted = pwd
+        .as_os_str()
+        .as_bytes()
+        .split(|&b| b == b'/')
+        .any(|segment| segment == b"." || segment == b"..");
+    if !pwd.is_absolute() || dotted {
+        return None;
+    }
+    let named = fs::metadata(&pwd).ok()?;
+    let current = fs::metadata(".").ok()?;
+    if named.dev() == current.dev() && named.ino() == current.ino() {
+        Some(pwd)
+    } else {
+        None
+    }
+}
+
+fn physical() -> PathBuf {
+    match env::current_dir().and_then(|dir| dir.canonicalize()) {
+        Ok(dir) => dir,
+        Err(e) => fail(format!("cannot determine current directory: {}", e)),
+    }
+}
+
+fn main() {
+    let dir = if parse(env::args().skip(1).collect()) {
+        physical()
+    } else {
+        logical().unwrap_or_else(physical)
+    };
+    println!("{}", dir.display());
+}
@@ -0,0 +1,117 @@
+use crate::ReadlinkError;
+use arglex::lex;
+use arglex::Arg;
+use coreutils_common::output::Terminator;
+use coreutils_common::print_and_exit;
+use coreutils_common::unknown_arg_message;
+use coreutils_common::version_string;
+
+const HELP: &str = "
+Usage: readlink [OPTION]... FILE...
+Print value of a symbolic link or canonical file name.
+
+Options:
+  -f, --canonicalize        Canonicalize by following every symlink in every
+                            component of the given name recursively; all but
+                            the last component must exist
+  -e, --canonicalize-existing
+                            Canonicalize by following every symlink in every
+                            component of the given name recursively; all
+                            components must exist
+  -n, --no-newline          Do not output the trailing delimiter
+  -z, --zero                End each output line with NUL, not newline
+  --version                 Output version information and exit
+  --help                    Display this help and exit
+";
+
+fn print_help() -> ! {
+    print_and_exit(0, HELP);
+}
+
+fn print_version() -> ! {
+    print_and_exit(0, version_string!("readlink"));
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Mode {
+    /// Print the value of the link itself.
+    Read,
+    /// Canonicalize, allowing the final component to be missing.
+    Canonicalize,
+    /// Canonicalize, requiring every component to exist.
+    Existing,
+}
+
+pub struct Args {
+    pub mode: Mode,
+    pub no_newline: bool,
+    pub terminator: Terminator,
+    pub files: Vec<String>,
+}
+
+impl Args {
+    fn new() -> Self {
+        Args {
+            mode: Mode::Read,
+            no_newline: false,
+            terminator: Terminator::Newline,
+            files: vec![],
+        }
+    }
+}
+
+pub fn parse(args: Vec<String>) -> Result<Args, ReadlinkError> {
+    let mut arg_struct = Args::new();
+    for arg in &lex(args) {
+        match arg {
+            Arg::Positional(positional) => match positional.as_str() {
+                "--" => continue,
+                _ => arg_struct.files.push(positional.clone()),
+            },
+            Arg::Short(short) => match short.as_str() {
+                "f" => arg_struct.mode = Mode::Canonicalize,
+                "e" => arg_struct.mode = Mode::Existing,
+                "n" => arg_struct.no_newline = true,
+                "z" => arg_struct.terminator = Terminator::Nul,
+                _ => return Err(unknown_arg_message(arg).into()),
+            },
+            Arg::Long(long) => match long.as_str() {
+                "canonicalize" => arg_struct.mode = Mode::Canonicalize,
+                "canonicalize-existing" => arg_struct.mode = Mode::Existing,
+                "no-newline" => arg_struct.no_newline = true,
+                "zero" => arg_struct.terminator = Terminator::Nul,
+                "version" => print_version(),
+                "help" => print_help(),
+                _ => return Err(unknown_arg_message(arg).into()),
+            },
+        };
+    }
+    if arg_struct.files.is_empty() {
+        return Err("missing operand".into());
+    }
+    Ok(arg_struct)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse_strs(args: &[&str]) -> Result<Args, ReadlinkError> {
+        parse(args.iter().map(|s| s.to_string()).collect())
+    }
+
+    #[test]
+    fn last_mode_wins() {
+        assert_eq!(parse_strs(&["-f", "-e", "x"]).unwrap().mode, Mode::Existing);
+        assert_eq!(
+            parse_strs(&["-e", "-f", "x"]).unwrap().mode,
+            Mode::Canonicalize
+        );
+        assert_eq!(parse_strs(&["x"]).unwrap().mode, Mode::Read);
+    }
+
+    #[test]
+    fn needs_operand() {
+        assert!(parse_strs(&["-n"]).is_err());
+    }
+}
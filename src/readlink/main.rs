@@ -0,0 +1,80 @@
+mod args;
+
+use args::Mode;
+use coreutils_common::exit_with;
+use coreutils_common::paths::canonicalize;
+use coreutils_common::paths::Missing;
+use coreutils_common::CoreError;
+use std::env;
+use std::fmt;
+use std::fmt::Debug;
+use std::fs;
+use std::io;
+use std::io::Write;
+use std::os::unix::ffi::OsStrExt;
+use std::path::Path;
+use std::path::PathBuf;
+use std::process::exit;
+
+pub struct ReadlinkError {
+    message: String,
+}
+
+impl Debug for ReadlinkError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "readlink: {}", self.message)
+    }
+}
+
+impl CoreError for ReadlinkError {}
+
+impl<T> From<T> for ReadlinkError
+where
+    T: ToString,
+{
+    fn from(error: T) -> Self {
+        ReadlinkError {
+            message: error.to_string(),
+        }
+    }
+}
+
+fn resolve(file: &str, mode: Mode) -> io::Result<PathBuf> {
+    let path = Path::new(file);
+    match mode {
+        Mode::Read => fs::read_link(path),
+        Mode::Canonicalize => canonicalize(path, Missing::LastAllowed),
+        Mode::Existing => canonicalize(path, Missing::Forbidden),
+    }
+}
+
+fn main() {
+    if let Err(e) = run() {
+        exit_with(&e);
+    }
+}
+
+fn run() -> Result<(), ReadlinkError> {
+    let args = args::parse(env::args().skip(1).collect())?;
+    let stdout = io::stdout();
+    let mut stdout = stdout.lock();
+
+    let mut failed = false;
+    for file in &args.files {
+        match resolve(file, args.mode) {
+            Ok(path) => {
+                stdout.write_all(path.as_os_str().as_bytes())?;
+                if !args.no_newline {
+                    stdout.write_all(&[args.terminator.as_byte()])?;
+                }
+            }
+            // Like GNU readlink, failures are reported only through the exit status.
+            Err(_) => failed = true,
+        }
+    }
+    stdout.flush()?;
+    if failed {
+        exit(1);
+    }
+    Ok(())
+}
@@ -0,0 +1,111 @@
+use crate::RealpathError;
+use arglex::lex;
+use arglex::Arg;
+use coreutils_common::paths::Missing;
+use coreutils_common::print_and_exit;
+use coreutils_common::unknown_arg_message;
+use coreutils_common::version_string;
+use std::slice::Iter;
+
+const HELP: &str = "
+Usage: realpath [OPTION]... FILE...
+Print the resolved absolute file name;
+all but the last component must exist.
+
+Options:
+  -e, --canonicalize-existing
+                            All components of the path must exist
+  -m, --canonicalize-missing
+                            No path components need exist or be a directory
+  --relative-to=DIR         Print the resolved path relative to DIR
+  --version                 Output version information and exit
+  --help                    Display this help and exit
+";
+
+fn print_help() -> ! {
+    print_and_exit(0, HELP);
+}
+
+fn print_version() -> ! {
+    print_and_exit(0, version_string!("realpath"));
+}
+
+pub struct Args {
+    pub missing: Missing,
+    pub relative_to: Option<String>,
+    pub files: Vec<String>,
+}
+
+fn get_arg_to(args: &mut Iter<Arg>, name: &Arg) -> Result<String, RealpathError> {
+    match args.next() {
+        Some(Arg::Positional(value)) => Ok(value.clone()),
+        _ => Err(format!("{} needs an argument", name).into()),
+    }
+}
+
+pub fn parse(args: Vec<String>) -> Result<Args, RealpathError> {
+    let args = lex(args);
+
+    let mut args = args.iter();
+    let mut missing = Missing::LastAllowed;
+    let mut relative_to = None;
+    let mut files = vec![];
+    while let Some(arg) = args.next() {
+        match arg {
+            Arg::Positional(positional) => match positional.as_str() {
+                "--" => continue,
+                _ => files.push(positional.clone()),
+            },
+            Arg::Short(short) => match short.as_str() {
+                "e" => missing = Missing::Forbidden,
+                "m" => missing = Missing::Allowed,
+                _ => return Err(unknown_arg_message(arg).into()),
+            },
+            Arg::Long(long) => match long.as_str() {
+                "canonicalize-existing" => missing = Missing::Forbidden,
+                "canonicalize-missing" => missing = Missing::Allowed,
+                "relative-to" => relative_to = Some(get_arg_to(&mut args, arg)?),
+                "version" => print_version(),
+                "help" => print_help(),
+                _ => return Err(unknown_arg_message(arg).into()),
+            },
+        };
+    }
+    if files.is_empty() {
+        return Err("missing operand".into());
+    }
+    Ok(Args {
+        missing,
+        relative_to,
+        files,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse_strs(args: &[&str]) -> Result<Args, RealpathError> {
+        parse(args.iter().map(|s| s.to_string()).collect())
+    }
+
+    #[test]
+    fn relative_to() {
+        let args = parse_strs(&["--relative-to=base", "x"]).unwrap();
+        assert_eq!(args.relative_to.as_deref(), Some("base"));
+        assert_eq!(args.files, vec!["x"]);
+        let args = parse_strs(&["--relative-to", "base", "x"]).unwrap();
+        assert_eq!(args.relative_to.as_deref(), Some("base"));
+        assert!(parse_strs(&["x", "--relative-to"]).is_err());
+    }
+
+    #[test]
+    fn modes() {
+        assert_eq!(parse_strs(&["x"]).unwrap().missing, Missing::LastAllowed);
+        assert_eq!(
+            parse_strs(&["-e", "x"]).unwrap().missing,
+            Missing::Forbidden
+        );
+        assert_eq!(parse_strs(&["-m", "x"]).unwrap().missing, Missing::Allowed);
+    }
+}
@@ -0,0 +1,79 @@
+mod args;
+
+use coreutils_common::exit_with;
+use coreutils_common::paths::canonicalize;
+use coreutils_common::paths::relative_path;
+use coreutils_common::report_error;
+use coreutils_common::CoreError;
+use std::env;
+use std::fmt;
+use std::fmt::Debug;
+use std::io;
+use std::io::Write;
+use std::os::unix::ffi::OsStrExt;
+use std::path::Path;
+use std::process::exit;
+
+pub struct RealpathError {
+    message: String,
+}
+
+impl Debug for RealpathError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "realpath: {}", self.message)
+    }
+}
+
+impl CoreError for RealpathError {}
+
+impl<T> From<T> for RealpathError
+where
+    T: ToString,
+{
+    fn from(error: T) -> Self {
+        RealpathError {
+            message: error.to_string(),
+        }
+    }
+}
+
+fn main() {
+    if let Err(e) = run() {
+        exit_with(&e);
+    }
+}
+
+fn run() -> Result<(), RealpathError> {
+    let args = args::parse(env::args().skip(1).collect())?;
+    let base = match &args.relative_to {
+        Some(base) => Some(
+            canonicalize(Path::new(base), args.missing).map_err(|e| format!("{}: {}", base, e))?,
+        ),
+        None => None,
+    };
+    let stdout = io::stdout();
+    let mut stdout = stdout.lock();
+
+    let mut failed = false;
+    for file in &args.files {
+        match canonicalize(Path::new(file), args.missing) {
+            Ok(path) => {
+                let path = match &base {
+                    Some(base) => relative_path(base, &path),
+                    None => path,
+                };
+                stdout.write_all(path.as_os_str().as_bytes())?;
+                stdout.write_all(b"\n")?;
+            }
+            Err(e) => {
+                report_error("realpath", format!("{}: {}", file, e));
+                failed = true;
+            }
+        }
+    }
+    stdout.flush()?;
+    if failed {
+        exit(1);
+    }
+    Ok(())
+}
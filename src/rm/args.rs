@@ -0,0 +1,119 @@
+use crate::RmError;
+use arglex::lex_clustered;
+use arglex::Arg;
+use coreutils_common::print_and_exit;
+use coreutils_common::unknown_arg_message;
+use coreutils_common::version_string;
+
+const HELP: &str = "
+Usage: rm [OPTION]... [FILE]...
+Remove (unlink) the FILE(s).
+
+Options:
+  -f, --force               Ignore nonexistent files and arguments, never prompt
+  -i, --interactive         Prompt before every removal
+  --one-file-system         With -r, skip directories on a different file system
+                            from the command line argument
+  -r, -R, --recursive       Remove directories and their contents recursively
+  -v, --verbose             Explain what is being done
+  --version                 Output version information and exit
+  --help                    Display this help and exit
+
+Symbolic links are removed, never followed. Whichever of -f and -i comes last wins.
+";
+
+fn print_help() -> ! {
+    print_and_exit(0, HELP);
+}
+
+fn print_version() -> ! {
+    print_and_exit(0, version_string!("rm"));
+}
+
+pub struct Args {
+    pub force: bool,
+    pub interactive: bool,
+    pub one_file_system: bool,
+    pub recursive: bool,
+    pub verbose: bool,
+    pub files: Vec<String>,
+}
+
+impl Args {
+    fn new() -> Self {
+        Args {
+            force: false,
+            interactive: false,
+            one_file_system: false,
+            recursive: false,
+            verbose: false,
+            files: vec![],
+        }
+    }
+
+    fn set_force(&mut self) {
+        self.force = true;
+        self.interactive = false;
+    }
+
+    fn set_interactive(&mut self) {
+        self.interactive = true;
+        self.force = false;
+    }
+}
+
+pub fn parse(args: Vec<String>) -> Result<Args, RmError> {
+    let mut arg_struct = Args::new();
+    for arg in &lex_clustered(args, "") {
+        match arg {
+            Arg::Positional(positional) => match positional.as_str() {
+                "--" => continue,
+                _ => arg_struct.files.push(positional.clone()),
+            },
+            Arg::Short(short) => match short.as_str() {
+                "f" => arg_struct.set_force(),
+                "i" => arg_struct.set_interactive(),
+                "r" | "R" => arg_struct.recursive = true,
+                "v" => arg_struct.verbose = true,
+                _ => return Err(unknown_arg_message(arg).into()),
+            },
+            Arg::Long(long) => match long.as_str() {
+                "force" => arg_struct.set_force(),
+                "interactive" => arg_struct.set_interactive(),
+                "one-file-system" => arg_struct.one_file_system = true,
+                "recursive" => arg_struct.recursive = true,
+                "verbose" => arg_struct.verbose = true,
+                "version" => print_version(),
+                "help" => print_help(),
+                _ => return Err(unknown_arg_message(arg).into()),
+            },
+        };
+    }
+    if arg_struct.files.is_empty() && !arg_struct.force {
+        return Err("missing operand".into());
+    }
+    Ok(arg_struct)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse_strs(args: &[&str]) -> Result<Args, RmError> {
+        parse(args.iter().map(|s| s.to_string()).collect())
+    }
+
+    #[test]
+    fn last_of_force_and_interactive_wins() {
+        let args = parse_strs(&["-i", "-f", "x"]).unwrap();
+        assert!(args.force && !args.interactive);
+        let args = parse_strs(&["-f", "-i", "x"]).unwrap();
+        assert!(args.interactive && !args.force);
+    }
+
+    #[test]
+    fn force_allows_no_operands() {
+        assert!(parse_strs(&[]).is_err());
+        assert!(parse_strs(&["-f"]).unwrap().files.is_empty());
+    }
+}
@@ -0,0 +1,194 @@
+mod args;
+
+use args::Args;
+use coreutils_common::exit_with;
+use coreutils_common::paths::strip_trailing_slashes;
+use coreutils_common::prompt;
+use coreutils_common::report_error;
+use coreutils_common::walk::Ancestors;
+use coreutils_common::CoreError;
+use std::env;
+use std::fmt;
+use std::fmt::Debug;
+use std::fs;
+use std::io;
+use std::io::ErrorKind;
+use std::path::Path;
+use std::process::exit;
+
+pub struct RmError {
+    message: String,
+}
+
+impl Debug for RmError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "rm: {}", self.message)
+    }
+}
+
+impl CoreError for RmError {}
+
+impl<T> From<T> for RmError
+where
+    T: ToString,
+{
+    fn from(error: T) -> Self {
+        RmError {
+            message: error.to_string(),
+        }
+    }
+}
+
+fn confirm(question: &str) -> bool {
+    prompt::confirm(&format!("rm: {}", question)).unwrap_or(false)
+}
+
+fn report(path: &Path, error: &io::Error) {
+    report_error(
+        "rm",
+        format!("cannot remove '{}': {}", path.display(), error),
+    );
+}
+
+/// Removes `path`, descending into directories with `-r`. Returns whether everything under it
+/// was removed (a declined prompt counts as success, like GNU rm).
+fn remove(path: &Path, args: &Args, ancestors: &mut Ancestors) -> bool {
+    let metadata = match fs::symlink_metadata(path) {
+        Ok(metadata) => metadata,
+        Err(e) if args.force && e.kind() == ErrorKind::NotFound => return true,
+        Err(e) => {
+            report(path, &e);
+            return false;
+        }
+    };
+    if !metadata.is_dir() {
+        if args.interactive && !confirm(&format!("remove '{}'", path.display())) {
+            return true;
+        }
+        return match fs::remove_file(path) {
+            Ok(()) => {
+                if args.verbose {
+                    println!("removed '{}'", path.display());
+                }
+                true
+            }
+            Err(e) => {
+                report(path, &e);
+                false
+            }
+        };
+    }
+
+    if !args.recursive {
+        report_error(
+            "rm",
+            format!("cannot remove '{}': Is a directory", path.display()),
+        );
+        return false;
+    }
+    if args.interactive && !confirm(&format!("descend into directory '{}'", path.display())) {
+        return true;
+    }
+    if args.one_file_system && !ancestors.same_file_system(&metadata) {
+        report_error(
+            "rm",
+            format!(
+                "skipping '{}', since it's on a different device",
+                path.display()
+            ),
+        );
+        return false;
+    }
+    if !ancestors.enter("rm", path, &metadata) {
+        return false;
+    }
+    let mut ok = true;
+    match fs::read_dir(path) {
+        Ok(entries) => {
+            for entry in entries {
+                match entry {
+                    Ok(entry) => ok &= remove(&entry.path(), args, ancestors),
+                    Err(e) => {
+                        report(path, &e);
+                        ok = false;
+                    }
+                }
+            }
+        }
+        Err(e) => {
+            report(path, &e);
+            ok = false;
+        }
+    }
+    ancestors.leave();
+    if !ok {
+        return false;
+    }
+    if args.interactive && !confirm(&format!("remove directory '{}'", path.display())) {
+        return true;
+    }
+    match fs::remove_dir(path) {
+        Ok(()) => {
+            if args.verbose {
+                println!("removed directory '{}'", path.display());
+            }
+            true
+        }
+        Err(e) => {
+            report(path, &e);
+            false
+        }
+    }
+}
+
+/// Operands real rm refuses outright: `.`, `..`, and `/`.
+fn refused(file: &str) -> Option<&'static str> {
+    let trimmed = strip_trailing_slashes(file);
+    if trimmed == "/" {
+        return Some("it is dangerous to operate recursively on '/'");
+    }
+    let last = trimmed.rsplit('/').next().unwrap_or(trimmed);
+    if last == "." || last == ".." {
+        return Some("refusing to remove '.' or '..' directory");
+    }
+    None
+}
+
+fn main() {
+    if let Err(e) = run() {
+        exit_with(&e);
+    }
+}
+
+fn run() -> Result<(), RmError> {
+    let args = args::parse(env::args().skip(1).collect())?;
+    let mut failed = false;
+    for file in &args.files {
+        if let Some(reason) = refused(file) {
+            report_error("rm", format!("{}: skipping '{}'", reason, file));
+            failed = true;
+            continue;
+        }
+        failed |= !remove(Path::new(file), &args, &mut Ancestors::new());
+    }
+    if failed {
+        exit(1);
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn refuses_dot_dotdot_and_root() {
+        assert!(refused(".").is_some());
+        assert!(refused("a/..").is_some());
+        assert!(refused("./").is_some());
+        assert!(refused("/").is_some());
+        assert!(refused("//").is_some());
+        assert!(refused("a/.b").is_none());
+        assert!(refused("...").is_none());
+    }
+}
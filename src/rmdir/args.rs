@@ -0,0 +1,76 @@
+use crate::RmdirError;
+use arglex::lex;
+use arglex::Arg;
+use coreutils_common::print_and_exit;
+use coreutils_common::unknown_arg_message;
+use coreutils_common::version_string;
+
+const HELP: &str = "
+Usage: rmdir [OPTION]... DIRECTORY...
+Remove the DIRECTORY(ies), if they are empty.
+
+Options:
+  --ignore-fail-on-non-empty
+                            Ignore each failure that is solely because a
+                            directory is non-empty
+  -p, --parents             Remove DIRECTORY and its ancestors; e.g., 'rmdir -p a/b/c'
+                            is similar to 'rmdir a/b/c a/b a'
+  -v, --verbose             Output a diagnostic for every directory processed
+  --version                 Output version information and exit
+  --help                    Display this help and exit
+";
+
+fn print_help() -> ! {
+    print_and_exit(0, HELP);
+}
+
+fn print_version() -> ! {
+    print_and_exit(0, version_string!("rmdir"));
+}
+
+pub struct Args {
+    pub ignore_non_empty: bool,
+    pub parents: bool,
+    pub verbose: bool,
+    pub dirs: Vec<String>,
+}
+
+impl Args {
+    fn new() -> Self {
+        Args {
+            ignore_non_empty: false,
+            parents: false,
+            verbose: false,
+            dirs: vec![],
+        }
+    }
+}
+
+pub fn parse(args: Vec<String>) -> Result<Args, RmdirError> {
+    let mut arg_struct = Args::new();
+    for arg in &lex(args) {
+        match arg {
+            Arg::Positional(positional) => match positional.as_str() {
+                "--" => continue,
+                _ => arg_struct.dirs.push(positional.clone()),
+            },
+            Arg::Short(short) => match short.as_str() {
+                "p" => arg_struct.parents = true,
+                "v" => arg_struct.verbose = true,
+                _ => return Err(unknown_arg_message(arg).into()),
+            },
+            Arg::Long(long) => match long.as_str() {
+                "ignore-fail-on-non-empty" => arg_struct.ignore_non_empty = true,
+                "parents" => arg_struct.parents = true,
+                "verbose" => arg_struct.verbose = true,
+                "version" => print_version(),
+                "help" => print_help(),
+                _ => return Err(unknown_arg_message(arg).into()),
+            },
+        };
+    }
+    if arg_struct.dirs.is_empty() {
+        return Err("missing operand".into());
+    }
+    Ok(arg_struct)
+}
@@ -0,0 +1,92 @@
+mod args;
+
+use args::Args;
+use coreutils_common::exit_with;
+use coreutils_common::report_error;
+use coreutils_common::CoreError;
+use std::env;
+use std::fmt;
+use std::fmt::Debug;
+use std::fs;
+use std::io;
+use std::path::Path;
+use std::process::exit;
+
+pub struct RmdirError {
+    message: String,
+}
+
+impl Debug for RmdirError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "rmdir: {}", self.message)
+    }
+}
+
+impl CoreError for RmdirError {}
+
+impl<T> From<T> for RmdirError
+where
+    T: ToString,
+{
+    fn from(error: T) -> Self {
+        RmdirError {
+            message: error.to_string(),
+        }
+    }
+}
+
+fn is_non_empty(error: &io::Error) -> bool {
+    matches!(
+        error.raw_os_error(),
+        Some(libc::ENOTEMPTY) | Some(libc::EEXIST)
+    )
+}
+
+/// Removes one directory, announcing it first with `-v`.
+fn remove(dir: &Path, args: &Args) -> io::Result<()> {
+    if args.verbose {
+        println!("rmdir: removing directory, '{}'", dir.display());
+    }
+    fs::remove_dir(dir)
+}
+
+/// Removes `dir` and, with `-p`, each of its ancestors as written, stopping at the first one
+/// that can't be removed. Returns whether everything went as asked.
+fn rmdir(dir: &Path, args: &Args) -> bool {
+    let mut current = Some(dir);
+    while let Some(dir) = current {
+        if let Err(e) = remove(dir, args) {
+            if args.ignore_non_empty && is_non_empty(&e) {
+                return true;
+            }
+            report_error(
+                "rmdir",
+                format!("failed to remove '{}': {}", dir.display(), e),
+            );
+            return false;
+        }
+        if !args.parents {
+            break;
+        }
+        current = dir.parent().filter(|parent| !parent.as_os_str().is_empty());
+    }
+    true
+}
+
+fn main() {
+    if let Err(e) = run() {
+        exit_with(&e);
+    }
+}
+
+fn run() -> Result<(), RmdirError> {
+    let args = args::parse(env::args().skip(1).collect())?;
+    let mut failed = false;
+    for dir in &args.dirs {
+        failed |= !rmdir(Path::new(dir), &args);
+    }
+    if failed {
+        exit(1);
+    }
+    Ok(())
+}
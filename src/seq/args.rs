@@ -0,0 +1,96 @@
+use crate::SeqError;
+use arglex::lex_with_negatives;
+use arglex::Arg;
+use coreutils_common::print_and_exit;
+use coreutils_common::unknown_arg_message;
+use coreutils_common::version_string;
+use std::slice::Iter;
+
+const HELP: &str = "
+Usage: seq [option]... LAST
+  or:  seq [option]... FIRST LAST
+  or:  seq [option]... FIRST INCREMENT LAST
+Print numbers from FIRST to LAST, in steps of INCREMENT.
+
+If FIRST or INCREMENT is omitted, it defaults to 1. INCREMENT may be negative or fractional;
+output stops before passing LAST. Numbers are printed with as many decimal places as FIRST
+or INCREMENT has.
+
+Options:
+  -f, --format <FORMAT>     Use printf style floating-point FORMAT (%e, %f or %g)
+  -s, --separator <STRING>  Use STRING to separate numbers (default: \\n)
+  -w, --equal-width         Equalize width by padding with leading zeroes
+  --version                 Output version information and exit
+  --help                    Display this help and exit
+";
+
+fn print_help() -> ! {
+    print_and_exit(0, HELP);
+}
+
+fn print_version() -> ! {
+    print_and_exit(0, version_string!("seq"));
+}
+
+pub struct Args {
+    pub format: Option<String>,
+    pub separator: String,
+    pub equal_width: bool,
+    pub numbers: Vec<String>,
+}
+
+impl Args {
+    fn new() -> Self {
+        Args {
+            format: None,
+            separator: "\n".to_owned(),
+            equal_width: false,
+            numbers: vec![],
+        }
+    }
+}
+
+fn get_arg_to(args: &mut Iter<Arg>, name: &Arg) -> Result<String, SeqError> {
+    match args.next() {
+        Some(Arg::Positional(value)) => Ok(value.clone()),
+        _ => Err(format!("{} needs an argument", name).into()),
+    }
+}
+
+pub fn parse(args: Vec<String>) -> Result<Args, SeqError> {
+    let args = lex_with_negatives(args);
+
+    let mut args = args.iter();
+    let mut arg_struct = Args::new();
+    while let Some(arg) = args.next() {
+        match arg {
+            Arg::Positional(positional) => match positional.as_str() {
+                "--" => continue,
+                _ => arg_struct.numbers.push(positional.clone()),
+            },
+            Arg::Short(short) => match short.as_str() {
+                "f" => arg_struct.format = Some(get_arg_to(&mut args, arg)?),
+                "s" => arg_struct.separator = get_arg_to(&mut args, arg)?,
+                "w" => arg_struct.equal_width = true,
+                _ => return Err(unknown_arg_message(arg).into()),
+            },
+            Arg::Long(long) => match long.as_str() {
+                "format" => arg_struct.format = Some(get_arg_to(&mut args, arg)?),
+                "separator" => arg_struct.separator = get_arg_to(&mut args, arg)?,
+                "equal-width" => arg_struct.equal_width = true,
+                "version" => print_version(),
+                "help" => print_help(),
+                _ => return Err(unknown_arg_message(arg).into()),
+            },
+        };
+    }
+    match arg_struct.numbers.len() {
+        0 => return Err("missing operand".into()),
+        1..=3 => {}
+        _ => return Err(format!("extra operand {}", arg_struct.numbers[3]).into()),
+    }
+    if arg_struct.equal_width && arg_struct.format.is_some() {
+        return Err("format string may not be specified when printing equal width strings".into());
+    }
+    Ok(arg_struct)
+}
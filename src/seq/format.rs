@@ -0,0 +1,262 @@
+use crate::SeqError;
+
+/// A printf-style format with exactly one floating-point conversion, as taken by `seq -f`.
+pub struct Format {
+    prefix: String,
+    spec: Spec,
+    suffix: String,
+}
+
+struct Spec {
+    left_align: bool,
+    zero_pad: bool,
+    plus: bool,
+    space: bool,
+    alternate: bool,
+    width: usize,
+    precision: Option<usize>,
+    conversion: char,
+}
+
+/// Turn `%%` into `%` in literal text.
+fn literal(text: &str) -> Result<String, SeqError> {
+    let mut out = String::new();
+    let mut chars = text.chars();
+    while let Some(c) = chars.next() {
+        if c == '%' && chars.next() != Some('%') {
+            return Err("format has too many % directives".into());
+        }
+        out.push(c);
+    }
+    Ok(out)
+}
+
+impl Format {
+    pub fn parse(format: &str) -> Result<Format, SeqError> {
+        let mut start = None;
+        let mut i = 0;
+        let bytes = format.as_bytes();
+        while i < bytes.len() {
+            if bytes[i] == b'%' {
+                if bytes.get(i + 1) == Some(&b'%') {
+                    i += 2;
+                    continue;
+                }
+                start = Some(i);
+                break;
+            }
+            i += 1;
+        }
+        let start = start.ok_or_else(|| format!("format {} has no % directive", format))?;
+        let prefix = literal(&format[..start])?;
+
+        let mut chars = format[start + 1..].char_indices().peekable();
+        let mut spec = Spec {
+            left_align: false,
+            zero_pad: false,
+            plus: false,
+            space: false,
+            alternate: false,
+            width: 0,
+            precision: None,
+            conversion: 'g',
+        };
+        while let Some(&(_, c)) = chars.peek() {
+            match c {
+                '-' => spec.left_align = true,
+                '0' => spec.zero_pad = true,
+                '+' => spec.plus = true,
+                ' ' => spec.space = true,
+                '#' => spec.alternate = true,
+                _ => break,
+            }
+            chars.next();
+        }
+        while let Some(&(_, c)) = chars.peek() {
+            match c.to_digit(10) {
+                Some(digit) => spec.width = spec.width * 10 + digit as usize,
+                None => break,
+            }
+            chars.next();
+        }
+        if let Some(&(_, '.')) = chars.peek() {
+            chars.next();
+            let mut precision = 0;
+            while let Some(&(_, c)) = chars.peek() {
+                match c.to_digit(10) {
+                    Some(digit) => precision = precision * 10 + digit as usize,
+                    None => break,
+                }
+                chars.next();
+            }
+            spec.precision = Some(precision);
+        }
+        // Length modifiers are accepted and ignored, like GNU seq's %Lg
+        while let Some(&(_, 'l')) | Some(&(_, 'L')) = chars.peek() {
+            chars.next();
+        }
+        let (end, conversion) = match chars.next() {
+            Some((i, c)) if "eEfFgG".contains(c) => (start + 1 + i + c.len_utf8(), c),
+            _ => {
+                return Err(format!(
+                    "format {} has unknown %{} directive",
+                    format,
+                    &format[start + 1..]
+                )
+                .into())
+            }
+        };
+        spec.conversion = conversion;
+        let suffix = literal(&format[end..])?;
+        Ok(Format {
+            prefix,
+            spec,
+            suffix,
+        })
+    }
+
+    pub fn format(&self, value: f64) -> String {
+        format!("{}{}{}", self.prefix, self.spec.format(value), self.suffix)
+    }
+}
+
+/// `{:e}` with C's exponent style: a sign and at least two digits.
+fn format_exponent(value: f64, precision: usize, upper: bool) -> String {
+    let formatted = format!("{:.*e}", precision, value);
+    let (mantissa, exponent) = formatted.split_at(formatted.find('e').unwrap());
+    let exponent: i32 = exponent[1..].parse().unwrap();
+    format!(
+        "{}{}{}{:02}",
+        mantissa,
+        if upper { 'E' } else { 'e' },
+        if exponent < 0 { '-' } else { '+' },
+        exponent.abs()
+    )
+}
+
+fn strip_trailing_zeros(formatted: &str) -> String {
+    let (number, exponent) = match formatted.find(['e', 'E']) {
+        Some(i) => formatted.split_at(i),
+        None => (formatted, ""),
+    };
+    let number = if number.contains('.') {
+        number.trim_end_matches('0').trim_end_matches('.')
+    } else {
+        number
+    };
+    format!("{}{}", number, exponent)
+}
+
+impl Spec {
+    fn format(&self, value: f64) -> String {
+        let precision = self.precision.unwrap_or(6);
+        let upper = self.conversion.is_ascii_uppercase();
+        let magnitude = value.abs();
+        let mut body = if !value.is_finite() {
+            let word = if value.is_nan() { "nan" } else { "inf" };
+            if upper {
+                word.to_uppercase()
+            } else {
+                word.to_owned()
+            }
+        } else {
+            match self.conversion {
+                'f' | 'F' => format!("{:.*}", precision, magnitude),
+                'e' | 'E' => format_exponent(magnitude, precision, upper),
+                _ => {
+                    let precision = precision.max(1);
+                    let exponent = if magnitude == 0.0 {
+                        0
+                    } else {
+                        // Round first so 9.9999999 doesn't pick the wrong style
+                        let rounded = format_exponent(magnitude, precision - 1, false);
+                        rounded[rounded.find('e').unwrap() + 1..].parse().unwrap()
+                    };
+                    let formatted = if exponent < -4 || exponent >= precision as i32 {
+                        format_exponent(magnitude, precision - 1, upper)
+                    } else {
+                        format!(
+                            "{:.*}",
+                            (precision as i32 - 1 - exponent) as usize,
+                            magnitude
+                        )
+                    };
+                    if self.alternate {
+                        formatted
+                    } else {
+                        strip_trailing_zeros(&formatted)
+                    }
+                }
+            }
+        };
+        let sign = if value.is_sign_negative() && !value.is_nan() {
+            "-"
+        } else if self.plus {
+            "+"
+        } else if self.space {
+            " "
+        } else {
+            ""
+        };
+        let len = sign.len() + body.len();
+        if len >= self.width {
+            return format!("{}{}", sign, body);
+        }
+        let padding = self.width - len;
+        if self.left_align {
+            format!("{}{}{}", sign, body, " ".repeat(padding))
+        } else if self.zero_pad && value.is_finite() {
+            body.insert_str(0, &"0".repeat(padding));
+            format!("{}{}", sign, body)
+        } else {
+            format!("{}{}{}", " ".repeat(padding), sign, body)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fmt(format: &str, value: f64) -> String {
+        Format::parse(format).unwrap().format(value)
+    }
+
+    #[test]
+    fn fixed() {
+        assert_eq!(fmt("%f", 1.5), "1.500000");
+        assert_eq!(fmt("%.2f", -1.005), "-1.00");
+        assert_eq!(fmt("%06.1f", 2.25), "0002.2");
+        assert_eq!(fmt("%-6.1fx", 2.0), "2.0   x");
+        assert_eq!(fmt("%+.0f", 3.0), "+3");
+    }
+
+    #[test]
+    fn exponent() {
+        assert_eq!(fmt("%e", 1500.0), "1.500000e+03");
+        assert_eq!(fmt("%.1E", 0.00025), "2.5E-04");
+    }
+
+    #[test]
+    fn general() {
+        assert_eq!(fmt("%g", 1.0), "1");
+        assert_eq!(fmt("%g", 0.1), "0.1");
+        assert_eq!(fmt("%g", 1_000_000.0), "1e+06");
+        assert_eq!(fmt("%g", 0.0001), "0.0001");
+        assert_eq!(fmt("%g", 0.00001), "1e-05");
+        assert_eq!(fmt("%.3g", 1.23456), "1.23");
+        assert_eq!(fmt("%#g", 1.0), "1.00000");
+    }
+
+    #[test]
+    fn literal_text() {
+        assert_eq!(fmt("%% %g%%", 2.0), "% 2%");
+    }
+
+    #[test]
+    fn invalid_formats() {
+        assert!(Format::parse("no directive").is_err());
+        assert!(Format::parse("%d").is_err());
+        assert!(Format::parse("%g %g").is_err());
+    }
+}
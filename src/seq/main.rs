@@ -0,0 +1,207 @@
+mod args;
+mod format;
+
+use args::Args;
+use coreutils_common::exit_with;
+use coreutils_common::restore_sigpipe;
+use coreutils_common::CoreError;
+use format::Format;
+use std::env;
+use std::fmt;
+use std::fmt::Debug;
+use std::io;
+use std::io::BufWriter;
+use std::io::Write;
+
+pub struct SeqError {
+    message: String,
+}
+
+impl Debug for SeqError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "seq: {}", self.message)
+    }
+}
+
+impl CoreError for SeqError {}
+
+impl<T> From<T> for SeqError
+where
+    T: ToString,
+{
+    fn from(error: T) -> Self {
+        SeqError {
+            message: error.to_string(),
+        }
+    }
+}
+
+/// A plain decimal operand, `mantissa / 10^scale`.
+#[derive(Clone, Copy)]
+struct Decimal {
+    mantissa: i128,
+    scale: u32,
+}
+
+impl Decimal {
+    fn parse(s: &str) -> Option<Decimal> {
+        let (negative, digits) = match s.as_bytes().first() {
+            Some(b'-') => (true, &s[1..]),
+            Some(b'+') => (false, &s[1..]),
+            _ => (false, s),
+        };
+        let (int, frac) = match digits.find('.') {
+            Some(i) => (&digits[..i], &digits[i + 1..]),
+            None => (digits, ""),
+        };
+        let all_digits = |part: &str| part.bytes().all(|b| b.is_ascii_digit());
+        if int.is_empty() && frac.is_empty() || !all_digits(int) || !all_digits(frac) {
+            return None;
+        }
+        let mantissa: i128 = format!("{}{}", int, frac).parse().ok()?;
+        Some(Decimal {
+            mantissa: if negative { -mantissa } else { mantissa },
+            scale: frac.len() as u32,
+        })
+    }
+
+    /// The mantissa at a larger `scale`.
+    fn rescale(self, scale: u32) -> Option<i128> {
+        self.mantissa
+            .checked_mul(10i128.checked_pow(scale - self.scale)?)
+    }
+}
+
+/// `value / 10^scale` written out with exactly `scale` decimal places.
+fn fixed(value: i128, scale: u32) -> String {
+    let digits = value.unsigned_abs().to_string();
+    let scale = scale as usize;
+    let digits = if digits.len() <= scale {
+        format!("{}{}", "0".repeat(scale + 1 - digits.len()), digits)
+    } else {
+        digits
+    };
+    let (int, frac) = digits.split_at(digits.len() - scale);
+    let sign = if value < 0 { "-" } else { "" };
+    if frac.is_empty() {
+        format!("{}{}", sign, int)
+    } else {
+        format!("{}{}.{}", sign, int, frac)
+    }
+}
+
+/// Zero-pad `number` to `width`, keeping any sign in front.
+fn pad(number: &str, width: usize) -> String {
+    match number.strip_prefix('-') {
+        Some(digits) if number.len() < width => {
+            format!("-{}{}", "0".repeat(width - number.len()), digits)
+        }
+        _ if number.len() < width => format!("{}{}", "0".repeat(width - number.len()), number),
+        _ => number.to_owned(),
+    }
+}
+
+fn parse_float(s: &str) -> Result<f64, SeqError> {
+    s.parse::<f64>()
+        .ok()
+        .filter(|value| !value.is_nan())
+        .ok_or_else(|| format!("invalid floating point argument: {}", s).into())
+}
+
+/// Generate the sequence as strings, calling `emit` for each one in order.
+fn sequence(args: &Args, mut emit: impl FnMut(String) -> io::Result<()>) -> Result<(), SeqError> {
+    let (first, step, last) = match args.numbers.as_slice() {
+        [last] => ("1", "1", last.as_str()),
+        [first, last] => (first.as_str(), "1", last.as_str()),
+        [first, step, last] => (first.as_str(), step.as_str(), last.as_str()),
+        _ => unreachable!("operand count is checked while parsing"),
+    };
+    let floats = (parse_float(first)?, parse_float(step)?, parse_float(last)?);
+    if floats.1 == 0.0 {
+        return Err(format!("invalid Zero increment value: {}", step).into());
+    }
+    let format = match &args.format {
+        Some(format) => Some(Format::parse(format)?),
+        None => None,
+    };
+
+    // Plain decimals are stepped through exactly as scaled integers, so 0.1 steps land on
+    // their bounds; anything else (exponents, inf) falls back to floating point.
+    if let (Some(d_first), Some(d_step), Some(d_last)) = (
+        Decimal::parse(first),
+        Decimal::parse(step),
+        Decimal::parse(last),
+    ) {
+        let scale = d_first.scale.max(d_step.scale).max(d_last.scale);
+        let precision = d_first.scale.max(d_step.scale);
+        let scaled = (
+            d_first.rescale(scale),
+            d_step.rescale(scale),
+            d_last.rescale(scale),
+        );
+        if let (Some(first), Some(step), Some(last)) = scaled {
+            let divisor = 10i128.pow(scale - precision);
+            let render = |value: i128| match &format {
+                Some(format) => format.format(value as f64 / 10f64.powi(scale as i32)),
+                None => fixed(value / divisor, precision),
+            };
+            let width = if args.equal_width {
+                render(first).len().max(render(last).len())
+            } else {
+                0
+            };
+            let mut value = first;
+            while (step > 0 && value <= last) || (step < 0 && value >= last) {
+                emit(pad(&render(value), width))?;
+                value = match value.checked_add(step) {
+                    Some(next) => next,
+                    None => break,
+                };
+            }
+            return Ok(());
+        }
+    }
+
+    let (first, step, last) = floats;
+    let format = format.unwrap_or_else(|| Format::parse("%g").unwrap());
+    let width = if args.equal_width {
+        format.format(first).len().max(format.format(last).len())
+    } else {
+        0
+    };
+    let mut i = 0u64;
+    loop {
+        let value = first + i as f64 * step;
+        if (step > 0.0 && value > last) || (step < 0.0 && value < last) {
+            return Ok(());
+        }
+        emit(pad(&format.format(value), width))?;
+        i += 1;
+    }
+}
+
+fn main() {
+    restore_sigpipe();
+    if let Err(e) = run() {
+        exit_with(&e);
+    }
+}
+
+fn run() -> Result<(), SeqError> {
+    let args = args::parse(env::args().skip(1).collect())?;
+    let stdout = io::stdout();
+    let mut out = BufWriter::new(stdout.lock());
+    let mut any = false;
+    sequence(&args, |number| {
+        if any {
+            out.write_all(args.separator.as_bytes())?;
+        }
+        any = true;
+        out.write_all(number.as_bytes())
+    })?;
+    if any {
+        out.write_all(b"\n")?;
+    }
+    out.flush()?;
+    Ok(())
+}
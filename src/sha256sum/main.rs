@@ -0,0 +1,84 @@
+mod args;
+
+use coreutils_common::checksum::digest_file;
+use coreutils_common::checksum::hex;
+use coreutils_common::checksum::read_chunks;
+use coreutils_common::checksum::verify;
+use coreutils_common::checksum::Algorithm;
+use coreutils_common::exit_with;
+use coreutils_common::report_error;
+use coreutils_common::CoreError;
+use sha2::Digest;
+use sha2::Sha256;
+use std::env;
+use std::fmt;
+use std::fmt::Debug;
+use std::io;
+use std::io::Read;
+use std::process::exit;
+
+pub struct Sha256sumError {
+    message: String,
+}
+
+impl Debug for Sha256sumError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "sha256sum: {}", self.message)
+    }
+}
+
+impl CoreError for Sha256sumError {}
+
+impl<T> From<T> for Sha256sumError
+where
+    T: ToString,
+{
+    fn from(error: T) -> Self {
+        Sha256sumError {
+            message: error.to_string(),
+        }
+    }
+}
+
+const SHA256: Algorithm = Algorithm {
+    tool: "sha256sum",
+    tag: "SHA256",
+    digest_length: 64,
+    digest,
+};
+
+fn digest(input: &mut dyn Read) -> io::Result<String> {
+    let mut hasher = Sha256::new();
+    read_chunks(input, &mut |chunk| hasher.update(chunk))?;
+    Ok(hex(&hasher.finalize()))
+}
+
+fn main() {
+    if let Err(e) = run() {
+        exit_with(&e);
+    }
+}
+
+fn run() -> Result<(), Sha256sumError> {
+    let args = args::parse(env::args().skip(1).collect())?;
+
+    let mut failed = false;
+    for file in &args.files {
+        if args.check {
+            failed |= !verify(&SHA256, file);
+            continue;
+        }
+        match digest_file(&SHA256, file) {
+            Ok(hash) if args.tag => println!("SHA256 ({}) = {}", file, hash),
+            Ok(hash) => println!("{}  {}", hash, file),
+            Err(e) => {
+                report_error("sha256sum", format!("{}: {}", file, e));
+                failed = true;
+            }
+        }
+    }
+    if failed {
+        exit(1);
+    }
+    Ok(())
+}
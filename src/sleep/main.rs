@@ -0,0 +1,110 @@
+use arglex::lex;
+use arglex::Arg;
+use coreutils_common::die;
+use coreutils_common::print_and_exit;
+use coreutils_common::unknown_arg_message;
+use coreutils_common::version_string;
+use std::env;
+use std::thread;
+use std::time::Duration;
+
+const HELP: &str = "
+Usage: sleep NUMBER[SUFFIX]...
+Pause for NUMBER seconds. SUFFIX may be 's' for seconds (the default),
+'m' for minutes, 'h' for hours or 'd' for days. NUMBER need not be an
+integer. Given two or more arguments, pause for the sum of their values.
+
+Options:
+  --version                 Output version information and exit
+  --help                    Display this help and exit
+";
+
+fn fail(message: impl AsRef<str>) -> ! {
+    die(format!("sleep: {}", message.as_ref()));
+}
+
+fn parse(args: Vec<String>) -> Vec<String> {
+    let mut intervals = vec![];
+    for arg in &lex(args) {
+        match arg {
+            Arg::Positional(positional) => match positional.as_str() {
+                "--" => continue,
+                _ => intervals.push(positional.clone()),
+            },
+            Arg::Long(long) => match long.as_str() {
+                "version" => print_and_exit(0, version_string!("sleep")),
+                "help" => print_and_exit(0, HELP),
+                _ => fail(unknown_arg_message(arg)),
+            },
+            Arg::Short(_) => fail(unknown_arg_message(arg)),
+        }
+    }
+    intervals
+}
+
+/// Seconds in one NUMBER[SUFFIX] interval, like `0.5s` or `2m`.
+fn seconds(interval: &str) -> Option<f64> {
+    let (number, scale) = match interval.char_indices().last()? {
+        (i, 's') => (&interval[..i], 1.0),
+        (i, 'm') => (&interval[..i], 60.0),
+        (i, 'h') => (&interval[..i], 60.0 * 60.0),
+        (i, 'd') => (&interval[..i], 60.0 * 60.0 * 24.0),
+        _ => (interval, 1.0),
+    };
+    // `f64::from_str` would also take "NaN" and signs, which aren't intervals
+    let starts_well = number
+        .chars()
+        .next()
+        .is_some_and(|c| c.is_ascii_digit() || c == '.' || c == 'i');
+    match number.parse::<f64>() {
+        Ok(value) if starts_well && value >= 0.0 => Some(value * scale),
+        _ => None,
+    }
+}
+
+fn main() {
+    let intervals = parse(env::args().skip(1).collect());
+    if intervals.is_empty() {
+        fail("missing operand");
+    }
+    let mut total = 0.0;
+    for interval in &intervals {
+        match seconds(interval) {
+            Some(value) => total += value,
+            None => fail(format!("invalid time interval '{}'", interval)),
+        }
+    }
+    // SIGINT keeps its default action, so Ctrl-C ends the sleep immediately.
+    match Duration::try_from_secs_f64(total) {
+        Ok(duration) => thread::sleep(duration),
+        Err(_) => loop {
+            thread::sleep(Duration::from_secs(u64::MAX));
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn suffixes() {
+        assert_eq!(seconds("2"), Some(2.0));
+        assert_eq!(seconds("2s"), Some(2.0));
+        assert_eq!(seconds("1.5m"), Some(90.0));
+        assert_eq!(seconds("2h"), Some(7200.0));
+        assert_eq!(seconds("1d"), Some(86400.0));
+        assert_eq!(seconds(".5"), Some(0.5));
+        assert_eq!(seconds("inf"), Some(f64::INFINITY));
+    }
+
+    #[test]
+    fn invalid_intervals() {
+        assert_eq!(seconds(""), None);
+        assert_eq!(seconds("s"), None);
+        assert_eq!(seconds("1x"), None);
+        assert_eq!(seconds("NaN"), None);
+        assert_eq!(seconds("+1"), None);
+        assert_eq!(seconds("2ms"), None);
+    }
+}
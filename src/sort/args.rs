@@ -0,0 +1,195 @@
+use crate::key::Key;
+use crate::key::Modifiers;
+use crate::SortError;
+use arglex::lex_clustered;
+use arglex::Arg;
+use coreutils_common::numparse::parse_count;
+use coreutils_common::numparse::parse_size;
+use coreutils_common::print_and_exit;
+use coreutils_common::unknown_arg_message;
+use coreutils_common::version_string;
+use std::convert::TryFrom;
+use std::slice::Iter;
+
+const HELP: &str = "
+Usage: sort [OPTION]... [FILE]...
+Write sorted concatenation of all FILE(s) to standard output.
+
+With no FILE, or when FILE is -, read standard input.
+
+Options:
+  -c, --check               Check whether the input is sorted; do not sort
+  -f, --ignore-case         Fold lower case to upper case characters
+  -k, --key <KEYDEF>        Sort via a key; KEYDEF gives location and type
+  -n, --numeric-sort        Compare according to string numerical value
+  -r, --reverse             Reverse the result of comparisons
+  -S, --buffer-size <SIZE>  Use at most about SIZE of memory for lines before
+                            spilling sorted runs to $TMPDIR (default 256M)
+  -t, --field-separator <SEP>
+                            Use SEP instead of non-blank to blank transition
+  -u, --unique              Output only the first of an equal run
+  --version                 Output version information and exit
+  --help                    Display this help and exit
+
+KEYDEF is F[.C][OPTS][,F[.C][OPTS]] for start and stop position, where F is a
+field number and C a character position in the field; both are origin 1, and
+the stop position defaults to the line's end. A stop C of 0 means the end of
+the field. OPTS is one or more of the letters b (skip the field's leading
+blanks before counting C), f, n and r, which then replace the global ordering
+options for that key.
+
+Lines that compare equal keep their input order.
+
+SIZE is in KiB unless it ends in b (bytes) or one of K, M, G, T, ... (powers of
+1024) or KB, MB, GB, ... (powers of 1000).
+";
+
+fn print_help() -> ! {
+    print_and_exit(0, HELP);
+}
+
+fn print_version() -> ! {
+    print_and_exit(0, version_string!("sort"));
+}
+
+/// The default -S budget.
+const DEFAULT_BUFFER_SIZE: usize = 256 << 20;
+
+pub struct Args {
+    pub buffer_size: usize,
+    pub check: bool,
+    pub keys: Vec<Key>,
+    /// The ordering options given outside any key.
+    pub modifiers: Modifiers,
+    pub separator: Option<u8>,
+    pub unique: bool,
+    pub files: Vec<String>,
+}
+
+impl Args {
+    fn new() -> Self {
+        Args {
+            buffer_size: DEFAULT_BUFFER_SIZE,
+            check: false,
+            keys: vec![],
+            modifiers: Modifiers::default(),
+            separator: None,
+            unique: false,
+            files: vec![],
+        }
+    }
+}
+
+fn get_arg_to(args: &mut Iter<Arg>, name: &Arg) -> Result<String, SortError> {
+    match args.next() {
+        Some(Arg::Positional(value)) => Ok(value.clone()),
+        _ => Err(format!("{} needs an argument", name).into()),
+    }
+}
+
+fn parse_separator(separator: &str) -> Result<u8, SortError> {
+    match separator.as_bytes() {
+        [byte] => Ok(*byte),
+        [] => Err("empty tab".into()),
+        _ => Err(format!("multi-character tab '{}'", separator).into()),
+    }
+}
+
+/// Parses -S as GNU sort does: a bare number counts KiB, and a `b` suffix counts bytes.
+fn parse_buffer_size(size: &str) -> Result<usize, SortError> {
+    let bytes = if let Some(bytes) = size.strip_suffix('b') {
+        parse_count(bytes)?
+    } else if size.bytes().all(|b| b.is_ascii_digit()) {
+        parse_count(size)?
+            .checked_mul(1024)
+            .ok_or_else(|| format!("number too large: '{}'", size))?
+    } else {
+        parse_size(size)?
+    };
+    Ok(usize::try_from(bytes).unwrap_or(usize::MAX))
+}
+
+pub fn parse(args: Vec<String>) -> Result<Args, SortError> {
+    let args = lex_clustered(args, "kSt");
+
+    let mut args = args.iter();
+    let mut arg_struct = Args::new();
+    while let Some(arg) = args.next() {
+        match arg {
+            Arg::Positional(positional) => match positional.as_str() {
+                "--" => continue,
+                _ => arg_struct.files.push(positional.clone()),
+            },
+            Arg::Short(short) => match short.as_str() {
+                "c" => arg_struct.check = true,
+                "f" => arg_struct.modifiers.ignore_case = true,
+                "k" => arg_struct
+                    .keys
+                    .push(Key::parse(&get_arg_to(&mut args, arg)?)?),
+                "n" => arg_struct.modifiers.numeric = true,
+                "r" => arg_struct.modifiers.reverse = true,
+                "S" => arg_struct.buffer_size = parse_buffer_size(&get_arg_to(&mut args, arg)?)?,
+                "t" => arg_struct.separator = Some(parse_separator(&get_arg_to(&mut args, arg)?)?),
+                "u" => arg_struct.unique = true,
+                _ => return Err(unknown_arg_message(arg).into()),
+            },
+            Arg::Long(long) => match long.as_str() {
+                "check" => arg_struct.check = true,
+                "ignore-case" => arg_struct.modifiers.ignore_case = true,
+                "key" => arg_struct
+                    .keys
+                    .push(Key::parse(&get_arg_to(&mut args, arg)?)?),
+                "numeric-sort" => arg_struct.modifiers.numeric = true,
+                "reverse" => arg_struct.modifiers.reverse = true,
+                "buffer-size" => {
+                    arg_struct.buffer_size = parse_buffer_size(&get_arg_to(&mut args, arg)?)?
+                }
+                "field-separator" => {
+                    arg_struct.separator = Some(parse_separator(&get_arg_to(&mut args, arg)?)?)
+                }
+                "unique" => arg_struct.unique = true,
+                "version" => print_version(),
+                "help" => print_help(),
+                _ => return Err(unknown_arg_message(arg).into()),
+            },
+        };
+    }
+    if arg_struct.check && arg_struct.files.len() > 1 {
+        return Err(format!(
+            "extra operand '{}' not allowed with -c",
+            arg_struct.files[1]
+        )
+        .into());
+    }
+    if arg_struct.files.is_empty() {
+        arg_struct.files.push("-".to_owned());
+    }
+    Ok(arg_struct)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse_strs(args: &[&str]) -> Result<Args, SortError> {
+        parse(args.iter().map(|s| s.to_string()).collect())
+    }
+
+    #[test]
+    fn buffer_sizes() {
+        assert_eq!(parse_buffer_size("10").unwrap(), 10 << 10);
+        assert_eq!(parse_buffer_size("100b").unwrap(), 100);
+        assert_eq!(parse_buffer_size("2M").unwrap(), 2 << 20);
+        assert_eq!(parse_buffer_size("1KB").unwrap(), 1000);
+        assert!(parse_buffer_size("b").is_err());
+        assert!(parse_buffer_size("lots").is_err());
+    }
+
+    #[test]
+    fn check_takes_one_file() {
+        let args = parse_strs(&["-c", "a"]).unwrap();
+        assert!(args.check);
+        assert_eq!(args.files, vec!["a"]);
+        assert!(parse_strs(&["--check", "a", "b"]).is_err());
+    }
+}
@@ -0,0 +1,269 @@
+use crate::SortError;
+
+/// How text is compared: by the global options, or by a key's own `OPTS` letters.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Modifiers {
+    pub ignore_case: bool,
+    pub numeric: bool,
+    pub reverse: bool,
+}
+
+/// One end of a key: field `field`, character `char` within it (both 1-based). An end
+/// position's `char` may be 0, meaning the end of the field. With `skip_blanks` (`b`), the
+/// field's leading blanks don't count towards `char`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Position {
+    pub field: usize,
+    pub char: usize,
+    pub skip_blanks: bool,
+}
+
+/// A `-k F[.C][OPTS][,F[.C][OPTS]]` key. Without an end it runs to the end of the line.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Key {
+    pub start: Position,
+    pub end: Option<Position>,
+    /// The key's own `OPTS`, if it has any; otherwise the global options apply to it.
+    pub modifiers: Option<Modifiers>,
+}
+
+fn number(text: &str, spec: &str) -> Result<usize, SortError> {
+    text.parse()
+        .map_err(|_| format!("invalid key specification '{}'", spec).into())
+}
+
+/// Parses one side of a key spec, recording its option letters in `modifiers`.
+fn parse_position(
+    text: &str,
+    spec: &str,
+    is_start: bool,
+    modifiers: &mut Option<Modifiers>,
+) -> Result<Position, SortError> {
+    let end = text
+        .find(|c: char| !c.is_ascii_digit() && c != '.')
+        .unwrap_or(text.len());
+    let (numbers, options) = text.split_at(end);
+    let (field, char) = match numbers.split_once('.') {
+        Some((field, char)) => (number(field, spec)?, Some(number(char, spec)?)),
+        None => (number(numbers, spec)?, None),
+    };
+    if field == 0 {
+        return Err(format!(
+            "field number is zero: invalid field specification '{}'",
+            spec
+        )
+        .into());
+    }
+    if is_start && char == Some(0) {
+        return Err(format!(
+            "character offset is zero: invalid field specification '{}'",
+            spec
+        )
+        .into());
+    }
+    let mut skip_blanks = false;
+    for option in options.chars() {
+        let key_modifiers = modifiers.get_or_insert_with(Modifiers::default);
+        match option {
+            'b' => skip_blanks = true,
+            'f' => key_modifiers.ignore_case = true,
+            'n' => key_modifiers.numeric = true,
+            'r' => key_modifiers.reverse = true,
+            _ => {
+                return Err(format!(
+                    "stray character in field spec: invalid field specification '{}'",
+                    spec
+                )
+                .into())
+            }
+        }
+    }
+    Ok(Position {
+        field,
+        char: char.unwrap_or(if is_start { 1 } else { 0 }),
+        skip_blanks,
+    })
+}
+
+impl Key {
+    pub fn parse(spec: &str) -> Result<Key, SortError> {
+        let (start, end) = match spec.split_once(',') {
+            Some((start, end)) => (start, Some(end)),
+            None => (spec, None),
+        };
+        let mut modifiers = None;
+        let start = parse_position(start, spec, true, &mut modifiers)?;
+        let end = match end {
+            Some(end) => Some(parse_position(end, spec, false, &mut modifiers)?),
+            None => None,
+        };
+        Ok(Key {
+            start,
+            end,
+            modifiers,
+        })
+    }
+
+    /// The bytes of `line` this key covers. Without a separator, each field starts with the
+    /// blanks that precede it, matching POSIX sort.
+    pub fn extract<'a>(&self, line: &'a [u8], separator: Option<u8>) -> &'a [u8] {
+        let bounds = fields(line, separator);
+        let start = match bounds.get(self.start.field - 1) {
+            Some(&field) => offset(line, field, self.start),
+            None => return &line[line.len()..],
+        };
+        let end = match self.end {
+            Some(end) => match bounds.get(end.field - 1) {
+                Some(&field) if end.char == 0 => field.1,
+                Some(&field) => (offset(line, field, end) + 1).min(field.1),
+                None => line.len(),
+            },
+            None => line.len(),
+        };
+        if end < start {
+            return &line[start..start];
+        }
+        &line[start..end]
+    }
+}
+
+/// Where character `position.char` of a field falls, clamped to the end of the field.
+fn offset(line: &[u8], (mut start, end): (usize, usize), position: Position) -> usize {
+    if position.skip_blanks {
+        while start < end && is_blank(line[start]) {
+            start += 1;
+        }
+    }
+    (start + position.char - 1).min(end)
+}
+
+fn is_blank(byte: u8) -> bool {
+    byte == b' ' || byte == b'\t'
+}
+
+/// The start and end offsets of every field in `line`.
+fn fields(line: &[u8], separator: Option<u8>) -> Vec<(usize, usize)> {
+    let mut bounds = vec![];
+    let mut start = 0;
+    match separator {
+        Some(separator) => {
+            for (i, &byte) in line.iter().enumerate() {
+                if byte == separator {
+                    bounds.push((start, i));
+                    start = i + 1;
+                }
+            }
+            bounds.push((start, line.len()));
+        }
+        None => {
+            let mut i = 0;
+            while i < line.len() {
+                while i < line.len() && is_blank(line[i]) {
+                    i += 1;
+                }
+                while i < line.len() && !is_blank(line[i]) {
+                    i += 1;
+                }
+                bounds.push((start, i));
+                start = i;
+            }
+        }
+    }
+    bounds
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn position(field: usize, char: usize) -> Position {
+        Position {
+            field,
+            char,
+            skip_blanks: false,
+        }
+    }
+
+    #[test]
+    fn parses_specs() {
+        assert_eq!(
+            Key::parse("2").unwrap(),
+            Key {
+                start: position(2, 1),
+                end: None,
+                modifiers: None,
+            }
+        );
+        assert_eq!(
+            Key::parse("2,3").unwrap(),
+            Key {
+                start: position(2, 1),
+                end: Some(position(3, 0)),
+                modifiers: None,
+            }
+        );
+        assert!(Key::parse("0").is_err());
+        assert!(Key::parse("x").is_err());
+    }
+
+    #[test]
+    fn parses_characters_and_options() {
+        let key = Key::parse("2.3b,2.5nr").unwrap();
+        assert_eq!(
+            key.start,
+            Position {
+                field: 2,
+                char: 3,
+                skip_blanks: true,
+            }
+        );
+        assert_eq!(key.end, Some(position(2, 5)));
+        assert_eq!(
+            key.modifiers,
+            Some(Modifiers {
+                ignore_case: false,
+                numeric: true,
+                reverse: true,
+            })
+        );
+        assert!(Key::parse("1.0").is_err());
+        assert!(Key::parse("1,1.0").is_ok());
+        assert!(Key::parse("1z").is_err());
+        assert!(Key::parse("1.").is_err());
+    }
+
+    #[test]
+    fn blank_separated_fields_keep_leading_blanks() {
+        let key = Key::parse("2,2").unwrap();
+        assert_eq!(key.extract(b"a  bb c", None), b"  bb");
+        let key = Key::parse("2").unwrap();
+        assert_eq!(key.extract(b"a  bb c", None), b"  bb c");
+        assert_eq!(key.extract(b"a", None), b"");
+    }
+
+    #[test]
+    fn explicit_separator() {
+        let key = Key::parse("2,3").unwrap();
+        assert_eq!(key.extract(b"a:b:c:d", Some(b':')), b"b:c");
+        assert_eq!(key.extract(b"a::c", Some(b':')), b":c");
+        assert_eq!(key.extract(b"a", Some(b':')), b"");
+    }
+
+    #[test]
+    fn character_offsets() {
+        let key = Key::parse("2.2,2.3").unwrap();
+        assert_eq!(key.extract(b"x:abcd:y", Some(b':')), b"bc");
+        let key = Key::parse("1.3").unwrap();
+        assert_eq!(key.extract(b"abcdef", None), b"cdef");
+        let key = Key::parse("1.9,1").unwrap();
+        assert_eq!(key.extract(b"ab cd", None), b"");
+    }
+
+    #[test]
+    fn skipping_blanks_before_counting() {
+        let key = Key::parse("2.2,2.2").unwrap();
+        assert_eq!(key.extract(b"a  bc d", None), b" ");
+        let key = Key::parse("2.2b,2.2b").unwrap();
+        assert_eq!(key.extract(b"a  bc d", None), b"c");
+    }
+}
@@ -0,0 +1,223 @@
+mod args;
+mod key;
+mod merge;
+
+use args::Args;
+use coreutils_common::exit_with;
+use coreutils_common::report_error;
+use coreutils_common::CoreError;
+use key::Modifiers;
+use merge::Buffer;
+use std::cmp::Ordering;
+use std::env;
+use std::fmt;
+use std::fmt::Debug;
+use std::fs::File;
+use std::io;
+use std::io::BufRead;
+use std::io::BufReader;
+use std::io::BufWriter;
+use std::io::Write;
+use std::mem;
+use std::process::exit;
+
+pub struct SortError {
+    message: String,
+}
+
+impl Debug for SortError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "sort: {}", self.message)
+    }
+}
+
+impl CoreError for SortError {}
+
+impl<T> From<T> for SortError
+where
+    T: ToString,
+{
+    fn from(error: T) -> Self {
+        SortError {
+            message: error.to_string(),
+        }
+    }
+}
+
+/// The leading number of `text`, after optional blanks, or 0 if there is none.
+fn numeric_value(text: &[u8]) -> f64 {
+    let text = String::from_utf8_lossy(text);
+    let text = text.trim_start_matches([' ', '\t']);
+    let mut end = 0;
+    let mut seen_digit = false;
+    let mut seen_point = false;
+    for (i, c) in text.char_indices() {
+        match c {
+            '-' if i == 0 => {}
+            '0'..='9' => seen_digit = true,
+            '.' if !seen_point => seen_point = true,
+            _ => break,
+        }
+        end = i + c.len_utf8();
+    }
+    if !seen_digit {
+        return 0.0;
+    }
+    text[..end].parse().unwrap_or(0.0)
+}
+
+fn compare_text(a: &[u8], b: &[u8], modifiers: Modifiers) -> Ordering {
+    let ordering = if modifiers.numeric {
+        numeric_value(a)
+            .partial_cmp(&numeric_value(b))
+            .unwrap_or(Ordering::Equal)
+    } else if modifiers.ignore_case {
+        a.iter()
+            .map(u8::to_ascii_uppercase)
+            .cmp(b.iter().map(u8::to_ascii_uppercase))
+    } else {
+        a.cmp(b)
+    };
+    if modifiers.reverse {
+        ordering.reverse()
+    } else {
+        ordering
+    }
+}
+
+/// Compares two lines by each key in turn, or by the whole line without keys.
+fn compare(a: &[u8], b: &[u8], args: &Args) -> Ordering {
+    if args.keys.is_empty() {
+        return compare_text(a, b, args.modifiers);
+    }
+    args.keys
+        .iter()
+        .map(|key| {
+            compare_text(
+                key.extract(a, args.separator),
+                key.extract(b, args.separator),
+                key.modifiers.unwrap_or(args.modifiers),
+            )
+        })
+        .find(|&ordering| ordering != Ordering::Equal)
+        .unwrap_or(Ordering::Equal)
+}
+
+fn read_lines(input: &mut dyn BufRead, lines: &mut Buffer) -> io::Result<()> {
+    loop {
+        let mut line = vec![];
+        if input.read_until(b'\n', &mut line)? == 0 {
+            return Ok(());
+        }
+        if line.last() == Some(&b'\n') {
+            line.pop();
+        }
+        lines.push(line)?;
+    }
+}
+
+/// Reads `input` once, reporting the first line that sorts before the one above it (or, with
+/// -u, equals it). Returns whether the input was in order.
+fn check(input: &mut dyn BufRead, name: &str, args: &Args) -> io::Result<bool> {
+    let mut previous = vec![];
+    let mut line = vec![];
+    let mut number = 0;
+    loop {
+        line.clear();
+        if input.read_until(b'\n', &mut line)? == 0 {
+            return Ok(true);
+        }
+        if line.last() == Some(&b'\n') {
+            line.pop();
+        }
+        number += 1;
+        if number > 1 {
+            let ordering = compare(&previous, &line, args);
+            if ordering == Ordering::Greater || (args.unique && ordering == Ordering::Equal) {
+                report_error(
+                    "sort",
+                    format!(
+                        "{}:{}: disorder: {}",
+                        name,
+                        number,
+                        String::from_utf8_lossy(&line)
+                    ),
+                );
+                return Ok(false);
+            }
+        }
+        mem::swap(&mut previous, &mut line);
+    }
+}
+
+/// `sort -c`: checks the single input without sorting it.
+fn run_check(args: &Args) -> Result<(), SortError> {
+    let file = &args.files[0];
+    let sorted = if file == "-" {
+        let stdin = io::stdin();
+        let mut input = stdin.lock();
+        check(&mut input, file, args)
+    } else {
+        let f = File::open(file).map_err(|e| format!("{}: {}", file, e))?;
+        check(&mut BufReader::new(f), file, args)
+    };
+    if !sorted.map_err(|e| format!("{}: {}", file, e))? {
+        exit(1);
+    }
+    Ok(())
+}
+
+fn main() {
+    if let Err(e) = run() {
+        exit_with(&e);
+    }
+}
+
+fn run() -> Result<(), SortError> {
+    let args = args::parse(env::args().skip(1).collect())?;
+    if args.check {
+        return run_check(&args);
+    }
+    let mut lines = Buffer::new(&args);
+    let mut failed = false;
+    for file in &args.files {
+        let result = if file == "-" {
+            let stdin = io::stdin();
+            let mut input = stdin.lock();
+            read_lines(&mut input, &mut lines)
+        } else {
+            match File::open(file) {
+                Ok(f) => read_lines(&mut BufReader::new(f), &mut lines),
+                Err(e) => {
+                    report_error("sort", format!("{}: {}", file, e));
+                    failed = true;
+                    continue;
+                }
+            }
+        };
+        result.map_err(|e| format!("{}: {}", file, e))?;
+    }
+
+    let stdout = io::stdout();
+    let mut out = BufWriter::new(stdout.lock());
+    lines.finish(&mut out)?;
+    out.flush()?;
+    if failed {
+        exit(1);
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn numeric_prefixes() {
+        assert_eq!(numeric_value(b"42"), 42.0);
+        assert_eq!(numeric_value(b"  -3.5kg"), -3.5);
+        assert_eq!(numeric_value(b"abc"), 0.0);
+        assert_eq!(numeric_value(b"-"), 0.0);
+        assert_eq!(numeric_value(b"1.2.3"), 1.2);
+    }
+}
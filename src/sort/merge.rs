@@ -0,0 +1,264 @@
+use crate::compare;
+use crate::Args;
+use std::cmp::Ordering;
+use std::collections::BinaryHeap;
+use std::env;
+use std::fs;
+use std::fs::File;
+use std::fs::OpenOptions;
+use std::io;
+use std::io::BufRead;
+use std::io::BufReader;
+use std::io::BufWriter;
+use std::io::ErrorKind;
+use std::io::Write;
+use std::mem;
+use std::os::unix::fs::OpenOptionsExt;
+use std::path::PathBuf;
+use std::process;
+
+/// The most runs read at once, so that a huge input can't run out of file descriptors. More
+/// runs than this are merged in several passes.
+const MERGE_WIDTH: usize = 16;
+
+/// Lines read so far. Whenever they outgrow the -S budget they are sorted and spilled to a
+/// temporary file as a run, to be merged back together by `finish`.
+pub struct Buffer<'a> {
+    args: &'a Args,
+    lines: Vec<Vec<u8>>,
+    used: usize,
+    runs: Runs,
+}
+
+impl<'a> Buffer<'a> {
+    pub fn new(args: &'a Args) -> Self {
+        Buffer {
+            args,
+            lines: vec![],
+            used: 0,
+            runs: Runs::new(),
+        }
+    }
+
+    pub fn push(&mut self, line: Vec<u8>) -> io::Result<()> {
+        self.used += line.len() + mem::size_of::<Vec<u8>>();
+        self.lines.push(line);
+        if self.used > self.args.buffer_size {
+            self.spill()?;
+        }
+        Ok(())
+    }
+
+    fn sort(&mut self) {
+        let args = self.args;
+        self.lines.sort_by(|a, b| compare(a, b, args));
+        if args.unique {
+            self.lines
+                .dedup_by(|b, a| compare(a, b, args) == Ordering::Equal);
+        }
+    }
+
+    fn spill(&mut self) -> io::Result<()> {
+        self.sort();
+        self.runs.write(&self.lines)?;
+        self.lines.clear();
+        self.used = 0;
+        Ok(())
+    }
+
+    /// Writes every line pushed so far to `out`, in order.
+    pub fn finish(mut self, out: &mut dyn Write) -> io::Result<()> {
+        if self.runs.is_empty() {
+            self.sort();
+            for line in &self.lines {
+                out.write_all(line)?;
+                out.write_all(b"\n")?;
+            }
+            return Ok(());
+        }
+        if !self.lines.is_empty() {
+            self.spill()?;
+        }
+        self.runs.merge(self.args, out)
+    }
+}
+
+/// Sorted runs in temporary files, in input order. Every file created is removed again when
+/// this is dropped, including the intermediate ones of a multi-pass merge.
+struct Runs {
+    runs: Vec<PathBuf>,
+    created: Vec<PathBuf>,
+}
+
+impl Runs {
+    fn new() -> Self {
+        Runs {
+            runs: vec![],
+            created: vec![],
+        }
+    }
+
+    fn is_empty(&self) -> bool {
+        self.runs.is_empty()
+    }
+
+    /// Creates a new, empty file in `$TMPDIR` (or `/tmp`) that only this user can read.
+    fn create(&mut self) -> io::Result<(PathBuf, File)> {
+        let dir = env::temp_dir();
+        let mut attempt = self.created.len();
+        loop {
+            let path = dir.join(format!("sort{}.{}", process::id(), attempt));
+            // The names are easy to guess, so keep other users from reading the data.
+            let created = OpenOptions::new()
+                .write(true)
+                .create_new(true)
+                .mode(0o600)
+                .open(&path);
+            match created {
+                Ok(file) => {
+                    self.created.push(path.clone());
+                    return Ok((path, file));
+                }
+                Err(e) if e.kind() == ErrorKind::AlreadyExists => attempt += 1,
+                Err(e) => {
+                    return Err(io::Error::new(
+                        e.kind(),
+                        format!("cannot create temporary file in '{}': {}", dir.display(), e),
+                    ))
+                }
+            }
+        }
+    }
+
+    /// Writes `lines`, already sorted, as the next run.
+    fn write(&mut self, lines: &[Vec<u8>]) -> io::Result<()> {
+        let (path, file) = self.create()?;
+        let mut out = BufWriter::new(file);
+        for line in lines {
+            out.write_all(line)?;
+            out.write_all(b"\n")?;
+        }
+        out.flush()?;
+        self.runs.push(path);
+        Ok(())
+    }
+
+    /// Merges every run into `out`. While there are too many to open at once, neighbouring runs
+    /// are merged into bigger ones first; keeping them in order keeps the sort stable.
+    fn merge(mut self, args: &Args, out: &mut dyn Write) -> io::Result<()> {
+        while self.runs.len() > MERGE_WIDTH {
+            let runs = mem::take(&mut self.runs);
+            for group in runs.chunks(MERGE_WIDTH) {
+                let (path, file) = self.create()?;
+                let mut merged = BufWriter::new(file);
+                merge_runs(group, args, &mut merged)?;
+                merged.flush()?;
+                for run in group {
+                    fs::remove_file(run)?;
+                }
+                self.runs.push(path);
+            }
+        }
+        merge_runs(&self.runs, args, out)
+    }
+}
+
+impl Drop for Runs {
+    fn drop(&mut self) {
+        for path in &self.created {
+            let _ = fs::remove_file(path);
+        }
+    }
+}
+
+/// The next unmerged line of one run. The heap pops the smallest line first, and the earliest
+/// run among equal lines, so that equal lines keep their input order.
+struct Head<'a> {
+    line: Vec<u8>,
+    run: usize,
+    args: &'a Args,
+}
+
+impl Ord for Head<'_> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        compare(&other.line, &self.line, self.args).then(other.run.cmp(&self.run))
+    }
+}
+
+impl PartialOrd for Head<'_> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl PartialEq for Head<'_> {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Head<'_> {}
+
+fn read_line(input: &mut dyn BufRead) -> io::Result<Option<Vec<u8>>> {
+    let mut line = vec![];
+    if input.read_until(b'\n', &mut line)? == 0 {
+        return Ok(None);
+    }
+    if line.last() == Some(&b'\n') {
+        line.pop();
+    }
+    Ok(Some(line))
+}
+
+/// K-way merges the sorted `runs` into `out`, dropping repeats under -u.
+fn merge_runs(runs: &[PathBuf], args: &Args, out: &mut dyn Write) -> io::Result<()> {
+    let mut inputs = runs
+        .iter()
+        .map(|run| File::open(run).map(BufReader::new))
+        .collect::<io::Result<Vec<_>>>()?;
+    let mut heap = BinaryHeap::new();
+    for (run, input) in inputs.iter_mut().enumerate() {
+        if let Some(line) = read_line(input)? {
+            heap.push(Head { line, run, args });
+        }
+    }
+    let mut last: Option<Vec<u8>> = None;
+    while let Some(Head { line, run, .. }) = heap.pop() {
+        if let Some(next) = read_line(&mut inputs[run])? {
+            heap.push(Head {
+                line: next,
+                run,
+                args,
+            });
+        }
+        if args.unique {
+            if let Some(last) = &last {
+                if compare(last, &line, args) == Ordering::Equal {
+                    continue;
+                }
+            }
+        }
+        out.write_all(&line)?;
+        out.write_all(b"\n")?;
+        if args.unique {
+            last = Some(line);
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::os::unix::fs::PermissionsExt;
+
+    #[test]
+    fn runs_are_private() {
+        let mut runs = Runs::new();
+        let (path, _) = runs.create().unwrap();
+        let mode = fs::metadata(&path).unwrap().permissions().mode();
+        assert_eq!(mode & 0o777, 0o600);
+        drop(runs);
+        assert!(!path.exists());
+    }
+}
@@ -0,0 +1,143 @@
+use crate::SplitError;
+use arglex::lex;
+use arglex::Arg;
+use coreutils_common::numparse::parse_count;
+use coreutils_common::numparse::parse_size;
+use coreutils_common::print_and_exit;
+use coreutils_common::unknown_arg_message;
+use coreutils_common::version_string;
+use std::slice::Iter;
+
+const HELP: &str = "
+Usage: split [OPTION]... [FILE [PREFIX]]
+Output pieces of FILE to PREFIXaa, PREFIXab, ...;
+default size is 1000 lines, and default PREFIX is 'x'.
+
+With no FILE, or when FILE is -, read standard input.
+
+Options:
+  -a, --suffix-length <N>   Generate suffixes of length N (default 2)
+  -b, --bytes <SIZE>        Put SIZE bytes per output file
+  -l, --lines <NUMBER>      Put NUMBER lines per output file
+  --version                 Output version information and exit
+  --help                    Display this help and exit
+
+SIZE is an integer and optional unit (example: 10K is 10*1024).
+Units are K, M, G (powers of 1024) or KB, MB, GB (powers of 1000).
+";
+
+fn print_help() -> ! {
+    print_and_exit(0, HELP);
+}
+
+fn print_version() -> ! {
+    print_and_exit(0, version_string!("split"));
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Chunk {
+    Lines(u64),
+    Bytes(u64),
+}
+
+pub struct Args {
+    pub chunk: Chunk,
+    pub suffix_length: usize,
+    pub file: String,
+    pub prefix: String,
+}
+
+impl Args {
+    fn new() -> Self {
+        Args {
+            chunk: Chunk::Lines(1000),
+            suffix_length: 2,
+            file: "-".to_owned(),
+            prefix: "x".to_owned(),
+        }
+    }
+}
+
+fn get_arg_to(args: &mut Iter<Arg>, name: &Arg) -> Result<String, SplitError> {
+    match args.next() {
+        Some(Arg::Positional(value)) => Ok(value.clone()),
+        _ => Err(format!("{} needs an argument", name).into()),
+    }
+}
+
+/// Parses a positive chunk size, which may have a unit such as `K` or `MB`.
+fn parse_chunk(size: &str) -> Result<u64, SplitError> {
+    match parse_size(size)? {
+        0 => Err(format!("invalid number: '{}'", size).into()),
+        size => Ok(size),
+    }
+}
+
+pub fn parse(args: Vec<String>) -> Result<Args, SplitError> {
+    let args = lex(args);
+
+    let mut args = args.iter();
+    let mut arg_struct = Args::new();
+    let mut operands = vec![];
+    while let Some(arg) = args.next() {
+        match arg {
+            Arg::Positional(positional) => match positional.as_str() {
+                "--" => continue,
+                _ => operands.push(positional.clone()),
+            },
+            Arg::Short(short) => match short.as_str() {
+                "a" => {
+                    arg_struct.suffix_length = parse_count(&get_arg_to(&mut args, arg)?)? as usize
+                }
+                "b" => arg_struct.chunk = Chunk::Bytes(parse_chunk(&get_arg_to(&mut args, arg)?)?),
+                "l" => arg_struct.chunk = Chunk::Lines(parse_chunk(&get_arg_to(&mut args, arg)?)?),
+                _ => return Err(unknown_arg_message(arg).into()),
+            },
+            Arg::Long(long) => match long.as_str() {
+                "suffix-length" => {
+                    arg_struct.suffix_length = parse_count(&get_arg_to(&mut args, arg)?)? as usize
+                }
+                "bytes" => {
+                    arg_struct.chunk = Chunk::Bytes(parse_chunk(&get_arg_to(&mut args, arg)?)?)
+                }
+                "lines" => {
+                    arg_struct.chunk = Chunk::Lines(parse_chunk(&get_arg_to(&mut args, arg)?)?)
+                }
+                "version" => print_version(),
+                "help" => print_help(),
+                _ => return Err(unknown_arg_message(arg).into()),
+            },
+        };
+    }
+    if arg_struct.suffix_length == 0 {
+        return Err("invalid suffix length: 0".into());
+    }
+    let mut operands = operands.into_iter();
+    if let Some(file) = operands.next() {
+        arg_struct.file = file;
+    }
+    if let Some(prefix) = operands.next() {
+        arg_struct.prefix = prefix;
+    }
+    if let Some(extra) = operands.next() {
+        return Err(format!("extra operand '{}'", extra).into());
+    }
+    Ok(arg_struct)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sizes() {
+        assert_eq!(parse_chunk("10").unwrap(), 10);
+        assert_eq!(parse_chunk("2K").unwrap(), 2048);
+        assert_eq!(parse_chunk("1M").unwrap(), 1 << 20);
+        assert_eq!(parse_chunk("3KB").unwrap(), 3000);
+        assert!(parse_chunk("0").is_err());
+        assert!(parse_chunk("K").is_err());
+        assert!(parse_chunk("5X").is_err());
+        assert!(parse_chunk("99999999999999G").is_err());
+    }
+}
@@ -0,0 +1,177 @@
+mod args;
+
+use args::Args;
+use args::Chunk;
+use coreutils_common::exit_with;
+use coreutils_common::CoreError;
+use std::env;
+use std::fmt;
+use std::fmt::Debug;
+use std::fs::File;
+use std::io;
+use std::io::BufRead;
+use std::io::BufReader;
+use std::io::BufWriter;
+use std::io::ErrorKind;
+use std::io::Read;
+use std::io::Write;
+
+pub struct SplitError {
+    message: String,
+}
+
+impl Debug for SplitError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "split: {}", self.message)
+    }
+}
+
+impl CoreError for SplitError {}
+
+impl<T> From<T> for SplitError
+where
+    T: ToString,
+{
+    fn from(error: T) -> Self {
+        SplitError {
+            message: error.to_string(),
+        }
+    }
+}
+
+/// The alphabetic suffix of piece `index`: `aa`, `ab`, ..., `zz` for length 2.
+fn suffix(mut index: u64, length: usize) -> Option<String> {
+    let mut letters = vec![b'a'; length];
+    for letter in letters.iter_mut().rev() {
+        *letter = b'a' + (index % 26) as u8;
+        index /= 26;
+    }
+    if index == 0 {
+        Some(String::from_utf8(letters).unwrap())
+    } else {
+        None
+    }
+}
+
+/// Hands out the output files one piece at a time.
+struct Outputs<'a> {
+    args: &'a Args,
+    next: u64,
+    current: Option<BufWriter<File>>,
+}
+
+impl<'a> Outputs<'a> {
+    fn new(args: &'a Args) -> Self {
+        Outputs {
+            args,
+            next: 0,
+            current: None,
+        }
+    }
+
+    fn start_next(&mut self) -> Result<(), SplitError> {
+        self.finish()?;
+        let suffix =
+            suffix(self.next, self.args.suffix_length).ok_or("output file suffixes exhausted")?;
+        let name = format!("{}{}", self.args.prefix, suffix);
+        let file = File::create(&name).map_err(|e| format!("{}: {}", name, e))?;
+        self.current = Some(BufWriter::new(file));
+        self.next += 1;
+        Ok(())
+    }
+
+    fn write(&mut self, data: &[u8]) -> Result<(), SplitError> {
+        match &mut self.current {
+            Some(out) => Ok(out.write_all(data)?),
+            None => Ok(()),
+        }
+    }
+
+    fn finish(&mut self) -> Result<(), SplitError> {
+        if let Some(mut out) = self.current.take() {
+            out.flush()?;
+        }
+        Ok(())
+    }
+}
+
+fn split_lines(
+    input: &mut dyn BufRead,
+    lines: u64,
+    outputs: &mut Outputs,
+) -> Result<(), SplitError> {
+    let mut line = vec![];
+    let mut written = 0;
+    loop {
+        line.clear();
+        if input.read_until(b'\n', &mut line)? == 0 {
+            return outputs.finish();
+        }
+        if written % lines == 0 {
+            outputs.start_next()?;
+        }
+        outputs.write(&line)?;
+        written += 1;
+    }
+}
+
+fn split_bytes(input: &mut dyn Read, bytes: u64, outputs: &mut Outputs) -> Result<(), SplitError> {
+    let mut buffer = [0; 8192];
+    let mut left = 0;
+    loop {
+        let n = match input.read(&mut buffer) {
+            Ok(0) => return outputs.finish(),
+            Ok(n) => n,
+            Err(e) if e.kind() == ErrorKind::Interrupted => continue,
+            Err(e) => return Err(e.into()),
+        };
+        let mut data = &buffer[..n];
+        while !data.is_empty() {
+            if left == 0 {
+                outputs.start_next()?;
+                left = bytes;
+            }
+            let take = data.len().min(left as usize);
+            outputs.write(&data[..take])?;
+            data = &data[take..];
+            left -= take as u64;
+        }
+    }
+}
+
+fn main() {
+    if let Err(e) = run() {
+        exit_with(&e);
+    }
+}
+
+fn run() -> Result<(), SplitError> {
+    let args = args::parse(env::args().skip(1).collect())?;
+    let stdin = io::stdin();
+    let mut input: Box<dyn BufRead> = if args.file == "-" {
+        Box::new(stdin.lock())
+    } else {
+        let file = File::open(&args.file).map_err(|e| format!("{}: {}", args.file, e))?;
+        Box::new(BufReader::new(file))
+    };
+    let mut outputs = Outputs::new(&args);
+    match args.chunk {
+        Chunk::Lines(lines) => split_lines(&mut input, lines, &mut outputs),
+        Chunk::Bytes(bytes) => split_bytes(&mut input, bytes, &mut outputs),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn suffixes() {
+        assert_eq!(suffix(0, 2).unwrap(), "aa");
+        assert_eq!(suffix(1, 2).unwrap(), "ab");
+        assert_eq!(suffix(26, 2).unwrap(), "ba");
+        assert_eq!(suffix(675, 2).unwrap(), "zz");
+        assert_eq!(suffix(676, 2), None);
+        assert_eq!(suffix(676, 3).unwrap(), "baa");
+    }
+}
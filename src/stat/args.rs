@@ -0,0 +1,129 @@
+use crate::StatError;
+use arglex::lex;
+use arglex::Arg;
+use coreutils_common::print_and_exit;
+use coreutils_common::timestamp::TimeStyle;
+use coreutils_common::unknown_arg_message;
+use coreutils_common::version_string;
+use std::slice::Iter;
+
+const HELP: &str = "
+Usage: stat [OPTION]... FILE...
+Display file status.
+
+Options:
+  -c, --format=FORMAT       Use the specified FORMAT instead of the default;
+                            output a newline after each use of FORMAT
+  -L, --dereference         Follow symbolic links (the default)
+  -P, --no-dereference      Describe symbolic links themselves, not the files
+                            they point to
+  --printf=FORMAT           Like --format, but interpret backslash escapes,
+                            and do not output a mandatory trailing newline
+  --time-style=STYLE        Show the human-readable times in STYLE: full-iso
+                            (the default), long-iso, iso, locale or +FORMAT
+  --version                 Output version information and exit
+  --help                    Display this help and exit
+
+The valid format sequences for files:
+  %a   permission bits in octal (see '#' and '0' printf flags)
+  %A   permission bits in human readable form
+  %b   number of blocks allocated (see %B)
+  %B   the size in bytes of each block reported by %b
+  %d   device number in decimal
+  %D   device number in hex
+  %f   raw mode in hex
+  %F   file type
+  %g   group ID of owner
+  %G   group name of owner
+  %h   number of hard links
+  %i   inode number
+  %n   file name
+  %o   optimal I/O transfer size hint
+  %s   total size, in bytes
+  %u   user ID of owner
+  %U   user name of owner
+  %x   time of last access, human-readable
+  %X   time of last access, seconds since Epoch
+  %y   time of last data modification, human-readable
+  %Y   time of last data modification, seconds since Epoch
+  %z   time of last status change, human-readable
+  %Z   time of last status change, seconds since Epoch
+";
+
+fn print_help() -> ! {
+    print_and_exit(0, HELP);
+}
+
+fn print_version() -> ! {
+    print_and_exit(0, version_string!("stat"));
+}
+
+pub struct Args {
+    pub dereference: bool,
+    pub format: Option<String>,
+    /// Set by `--printf`: expand backslash escapes in `format`, with no newline after it.
+    pub printf: bool,
+    pub time_style: TimeStyle,
+    pub files: Vec<String>,
+}
+
+fn get_arg_to(args: &mut Iter<Arg>, name: &Arg) -> Result<String, StatError> {
+    match args.next() {
+        Some(Arg::Positional(value)) => Ok(value.clone()),
+        _ => Err(format!("{} needs an argument", name).into()),
+    }
+}
+
+pub fn parse(args: Vec<String>) -> Result<Args, StatError> {
+    let args = lex(args);
+
+    let mut args = args.iter();
+    let mut dereference = true;
+    let mut format = None;
+    let mut printf = false;
+    let mut time_style = TimeStyle::FullIso;
+    let mut files = vec![];
+    while let Some(arg) = args.next() {
+        match arg {
+            Arg::Positional(positional) => match positional.as_str() {
+                "--" => continue,
+                _ => files.push(positional.clone()),
+            },
+            Arg::Short(short) => match short.as_str() {
+                "L" => dereference = true,
+                "P" => dereference = false,
+                "c" => {
+                    format = Some(get_arg_to(&mut args, arg)?);
+                    printf = false;
+                }
+                _ => return Err(unknown_arg_message(arg).into()),
+            },
+            Arg::Long(long) => match long.as_str() {
+                "dereference" => dereference = true,
+                "no-dereference" => dereference = false,
+                "format" => {
+                    format = Some(get_arg_to(&mut args, arg)?);
+                    printf = false;
+                }
+                "printf" => {
+                    format = Some(get_arg_to(&mut args, arg)?);
+                    printf = true;
+                }
+                "time-style" => time_style = TimeStyle::parse(&get_arg_to(&mut args, arg)?)?,
+                "version" => print_version(),
+                "help" => print_help(),
+                _ => return Err(unknown_arg_message(arg).into()),
+            },
+        };
+    }
+    if files.is_empty() {
+        return Err("missing operand".into());
+    }
+    Ok(Args {
+        dereference,
+        format,
+        printf,
+        time_style,
+        files,
+    })
+}
@@ -0,0 +1,263 @@
+use coreutils_common::mode::mode_string;
+use coreutils_common::timestamp::TimeStyle;
+use coreutils_common::users::group_name;
+use coreutils_common::users::user_name;
+use std::fs::Metadata;
+use std::iter::Peekable;
+use std::os::unix::fs::FileTypeExt;
+use std::os::unix::fs::MetadataExt;
+use std::str::CharIndices;
+
+/// What `stat` prints without `-c`, written in its own format language.
+pub const DEFAULT_FORMAT: &str = "  File: %n
+  Size: %-10s\tBlocks: %-10b IO Block: %-6o %F
+Device: %Dh/%dd\tInode: %-10i  Links: %h
+Access: (%04a/%10.10A)  Uid: (%5u/%8U)   Gid: (%5g/%8G)
+Access: %x
+Modify: %y
+Change: %z";
+
+/// Flags, width and precision between a `%` and its conversion character.
+#[derive(Default)]
+struct Spec {
+    left: bool,
+    zero: bool,
+    alternate: bool,
+    width: usize,
+    precision: Option<usize>,
+}
+
+enum Value {
+    Number(String),
+    Text(String),
+}
+
+impl Spec {
+    fn pad(&self, value: Value) -> String {
+        let (mut text, numeric) = match value {
+            Value::Number(text) => (text, true),
+            Value::Text(text) => (text, false),
+        };
+        if let (Some(precision), false) = (self.precision, numeric) {
+            text = text.chars().take(precision).collect();
+        }
+        let length = text.chars().count();
+        if length >= self.width {
+            return text;
+        }
+        let fill = self.width - length;
+        if self.left {
+            format!("{}{}", text, " ".repeat(fill))
+        } else if self.zero && numeric {
+            format!("{}{}", "0".repeat(fill), text)
+        } else {
+            format!("{}{}", " ".repeat(fill), text)
+        }
+    }
+}
+
+fn file_type_name(metadata: &Metadata) -> &'static str {
+    let file_type = metadata.file_type();
+    if file_type.is_file() {
+        if metadata.len() == 0 {
+            "regular empty file"
+        } else {
+            "regular file"
+        }
+    } else if file_type.is_dir() {
+        "directory"
+    } else if file_type.is_symlink() {
+        "symbolic link"
+    } else if file_type.is_block_device() {
+        "block special file"
+    } else if file_type.is_char_device() {
+        "character special file"
+    } else if file_type.is_fifo() {
+        "fifo"
+    } else if file_type.is_socket() {
+        "socket"
+    } else {
+        "weird file"
+    }
+}
+
+fn conversion(
+    spec: &Spec,
+    conversion: char,
+    name: &str,
+    metadata: &Metadata,
+    time_style: &TimeStyle,
+) -> Option<Value> {
+    use Value::Number;
+    use Value::Text;
+    let value = match conversion {
+        'a' if spec.alternate => Number(format!("0{:o}", metadata.mode() & 0o7777)),
+        'a' => Number(format!("{:o}", metadata.mode() & 0o7777)),
+        'A' => Text(mode_string(metadata)),
+        'b' => Number(metadata.blocks().to_string()),
+        'B' => Number("512".to_string()),
+        'd' => Number(metadata.dev().to_string()),
+        'D' => Number(format!("{:x}", metadata.dev())),
+        'f' => Number(format!("{:x}", metadata.mode())),
+        'F' => Text(file_type_name(metadata).to_string()),
+        'g' => Number(metadata.gid().to_string()),
+        'G' => Text(group_name(metadata.gid()).unwrap_or_else(|| "UNKNOWN".to_string())),
+        'h' => Number(metadata.nlink().to_string()),
+        'i' => Number(metadata.ino().to_string()),
+        'n' => Text(name.to_string()),
+        'o' => Number(metadata.blksize().to_string()),
+        's' => Number(metadata.len().to_string()),
+        'u' => Number(metadata.uid().to_string()),
+        'U' => Text(user_name(metadata.uid()).unwrap_or_else(|| "UNKNOWN".to_string())),
+        'x' => Text(time_style.format(metadata.atime(), metadata.atime_nsec() as u32)),
+        'X' => Number(metadata.atime().to_string()),
+        'y' => Text(time_style.format(metadata.mtime(), metadata.mtime_nsec() as u32)),
+        'Y' => Number(metadata.mtime().to_string()),
+        'z' => Text(time_style.format(metadata.ctime(), metadata.ctime_nsec() as u32)),
+        'Z' => Number(metadata.ctime().to_string()),
+        _ => return None,
+    };
+    Some(value)
+}
+
+/// Expands the backslash escape just past a `\\` of a `--printf` format onto `output`.
+/// Unknown escapes, and a backslash at the end of the format, are copied through unchanged.
+fn escape(chars: &mut Peekable<CharIndices>, output: &mut Vec<u8>) {
+    let byte = match chars.next() {
+        None => b'\\',
+        Some((_, c @ ('\\' | '"' | '\''))) => c as u8,
+        Some((_, 'a')) => 0x07,
+        Some((_, 'b')) => 0x08,
+        Some((_, 'e')) => 0x1b,
+        Some((_, 'f')) => 0x0c,
+        Some((_, 'n')) => b'\n',
+        Some((_, 'r')) => b'\r',
+        Some((_, 't')) => b'\t',
+        Some((_, 'v')) => 0x0b,
+        Some((_, c @ '0'..='7')) => {
+            let mut value = c.to_digit(8).unwrap();
+            for _ in 0..2 {
+                match chars.peek().and_then(|&(_, c)| c.to_digit(8)) {
+                    Some(digit) => value = value * 8 + digit,
+                    None => break,
+                }
+                chars.next();
+            }
+            value as u8
+        }
+        Some((_, 'x')) if chars.peek().is_some_and(|&(_, c)| c.is_ascii_hexdigit()) => {
+            let mut value = 0;
+            for _ in 0..2 {
+                match chars.peek().and_then(|&(_, c)| c.to_digit(16)) {
+                    Some(digit) => value = value * 16 + digit,
+                    None => break,
+                }
+                chars.next();
+            }
+            value as u8
+        }
+        Some((_, other)) => {
+            output.push(b'\\');
+            output.extend_from_slice(other.encode_utf8(&mut [0; 4]).as_bytes());
+            return;
+        }
+    };
+    output.push(byte);
+}
+
+/// Expands every `%` directive of `format` for the file `name`, and with `escapes` (for
+/// `--printf`) every backslash escape too.
+///
+/// Directives take printf-style `-`, `0` and `#` flags, a width and a precision. Unknown
+/// directives are copied through unchanged. The human-readable times are shown in `time_style`.
+pub fn render(
+    format: &str,
+    name: &str,
+    metadata: &Metadata,
+    time_style: &TimeStyle,
+    escapes: bool,
+) -> Vec<u8> {
+    let mut output = vec![];
+    let mut chars = format.char_indices().peekable();
+    while let Some((start, c)) = chars.next() {
+        if c == '\\' && escapes {
+            escape(&mut chars, &mut output);
+            continue;
+        }
+        if c != '%' {
+            output.extend_from_slice(c.encode_utf8(&mut [0; 4]).as_bytes());
+            continue;
+        }
+        let mut spec = Spec::default();
+        while let Some(&(_, flag)) = chars.peek() {
+            match flag {
+                '-' => spec.left = true,
+                '0' => spec.zero = true,
+                '#' => spec.alternate = true,
+                _ => break,
+            }
+            chars.next();
+        }
+        while let Some(digit) = chars.peek().and_then(|&(_, c)| c.to_digit(10)) {
+            spec.width = spec.width * 10 + digit as usize;
+            chars.next();
+        }
+        if let Some(&(_, '.')) = chars.peek() {
+            chars.next();
+            let mut precision = 0;
+            while let Some(digit) = chars.peek().and_then(|&(_, c)| c.to_digit(10)) {
+                precision = precision * 10 + digit as usize;
+                chars.next();
+            }
+            spec.precision = Some(precision);
+        }
+        match chars.next() {
+            Some((_, '%')) => output.push(b'%'),
+            Some((end, c)) => match conversion(&spec, c, name, metadata, time_style) {
+                Some(value) => output.extend_from_slice(spec.pad(value).as_bytes()),
+                None => output.extend_from_slice(&format.as_bytes()[start..end + c.len_utf8()]),
+            },
+            None => output.extend_from_slice(&format.as_bytes()[start..]),
+        }
+    }
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::env;
+    use std::fs;
+    use std::os::unix::fs::PermissionsExt;
+    use std::process;
+
+    #[test]
+    fn directives() {
+        let path = env::temp_dir().join(format!("stat-format-{}", process::id()));
+        fs::write(&path, "hello").unwrap();
+        fs::set_permissions(&path, fs::Permissions::from_mode(0o4754)).unwrap();
+        let metadata = fs::metadata(&path).unwrap();
+        let render = |format| {
+            String::from_utf8(render(format, "f", &metadata, &TimeStyle::FullIso, false)).unwrap()
+        };
+        assert_eq!(render("%s %n %a"), "5 f 4754");
+        assert_eq!(render("%A"), "-rwsr-xr--");
+        assert_eq!(render("[%5s|%-5s|%05s]"), "[    5|5    |00005]");
+        assert_eq!(render("%.3F"), "reg");
+        assert_eq!(render("%F"), "regular file");
+        assert_eq!(render("100%% %q %"), "100% %q %");
+        assert_eq!(render("%s\\n"), "5\\n");
+        fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn escapes() {
+        let metadata = fs::metadata(env::temp_dir()).unwrap();
+        let render = |format| render(format, "f", &metadata, &TimeStyle::FullIso, true);
+        assert_eq!(render("%n\\t%n\\n"), b"f\tf\n");
+        assert_eq!(render("\\\\ \\\"\\e"), b"\\ \"\x1b");
+        assert_eq!(render("\\101\\0\\x41\\xff"), b"A\0A\xff");
+        assert_eq!(render("\\x\\q\\"), b"\\x\\q\\");
+        assert_eq!(render("\\045n"), b"%n");
+    }
+}
@@ -0,0 +1,76 @@
+mod args;
+mod format;
+
+use coreutils_common::exit_with;
+use coreutils_common::report_error;
+use coreutils_common::CoreError;
+use std::env;
+use std::fmt;
+use std::fmt::Debug;
+use std::fs;
+use std::io;
+use std::io::Write;
+use std::process::exit;
+
+pub struct StatError {
+    message: String,
+}
+
+impl Debug for StatError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "stat: {}", self.message)
+    }
+}
+
+impl CoreError for StatError {}
+
+impl<T> From<T> for StatError
+where
+    T: ToString,
+{
+    fn from(error: T) -> Self {
+        StatError {
+            message: error.to_string(),
+        }
+    }
+}
+
+fn main() {
+    if let Err(e) = run() {
+        exit_with(&e);
+    }
+}
+
+fn run() -> Result<(), StatError> {
+    let args = args::parse(env::args().skip(1).collect())?;
+    let format = args.format.as_deref().unwrap_or(format::DEFAULT_FORMAT);
+    let stdout = io::stdout();
+    let mut stdout = stdout.lock();
+
+    let mut failed = false;
+    for file in &args.files {
+        let metadata = if args.dereference {
+            fs::metadata(file)
+        } else {
+            fs::symlink_metadata(file)
+        };
+        match metadata {
+            Ok(metadata) => {
+                let output = format::render(format, file, &metadata, &args.time_style, args.printf);
+                stdout.write_all(&output)?;
+                if !args.printf {
+                    stdout.write_all(b"\n")?;
+                }
+            }
+            Err(e) => {
+                report_error("stat", format!("cannot stat '{}': {}", file, e));
+                failed = true;
+            }
+        }
+    }
+    stdout.flush()?;
+    if failed {
+        exit(1);
+    }
+    Ok(())
+}
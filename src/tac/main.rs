@@ -0,0 +1,129 @@
+mod args;
+
+use coreutils_common::exit_with;
+use coreutils_common::input::open_input;
+use coreutils_common::report_error;
+use coreutils_common::CoreError;
+use std::env;
+use std::fmt;
+use std::fmt::Debug;
+use std::io;
+use std::io::BufWriter;
+use std::io::Read;
+use std::io::Write;
+use std::process::exit;
+
+pub struct TacError {
+    message: String,
+}
+
+impl Debug for TacError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "tac: {}", self.message)
+    }
+}
+
+impl CoreError for TacError {}
+
+impl<T> From<T> for TacError
+where
+    T: ToString,
+{
+    fn from(error: T) -> Self {
+        TacError {
+            message: error.to_string(),
+        }
+    }
+}
+
+/// Splits `data` into records, each keeping its separator at the end (or, with `before`, at the
+/// start), so that writing the records back in reverse order reverses the input.
+fn records<'a>(data: &'a [u8], separator: &[u8], before: bool) -> Vec<&'a [u8]> {
+    let mut records = vec![];
+    let mut start = 0;
+    let mut i = 0;
+    while i + separator.len() <= data.len() {
+        if &data[i..i + separator.len()] == separator {
+            let end = if before { i } else { i + separator.len() };
+            if end > start {
+                records.push(&data[start..end]);
+            }
+            start = end;
+            i += separator.len();
+        } else {
+            i += 1;
+        }
+    }
+    if start < data.len() {
+        records.push(&data[start..]);
+    }
+    records
+}
+
+fn tac(data: &[u8], args: &args::Args, out: &mut dyn Write) -> io::Result<()> {
+    for record in records(data, args.separator.as_bytes(), args.before)
+        .iter()
+        .rev()
+    {
+        out.write_all(record)?;
+    }
+    Ok(())
+}
+
+fn main() {
+    if let Err(e) = run() {
+        exit_with(&e);
+    }
+}
+
+fn run() -> Result<(), TacError> {
+    let args = args::parse(env::args().skip(1).collect())?;
+    let stdout = io::stdout();
+    let mut out = BufWriter::new(stdout.lock());
+    let mut failed = false;
+    for file in &args.files {
+        let mut data = vec![];
+        let read = open_input(file).and_then(|mut input| input.read_to_end(&mut data));
+        if let Err(e) = read {
+            out.flush()?;
+            report_error("tac", format!("{}: {}", file, e));
+            failed = true;
+            continue;
+        }
+        tac(&data, &args, &mut out)?;
+    }
+    out.flush()?;
+    if failed {
+        exit(1);
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn separator_after() {
+        assert_eq!(records(b"a\nb\n", b"\n", false), vec![&b"a\n"[..], b"b\n"]);
+        assert_eq!(records(b"a\nb", b"\n", false), vec![&b"a\n"[..], b"b"]);
+        assert_eq!(records(b"\n\n", b"\n", false), vec![&b"\n"[..], b"\n"]);
+        assert!(records(b"", b"\n", false).is_empty());
+    }
+
+    #[test]
+    fn separator_before() {
+        assert_eq!(
+            records(b"a\nb\n", b"\n", true),
+            vec![&b"a"[..], b"\nb", b"\n"]
+        );
+    }
+
+    #[test]
+    fn multi_byte_separator() {
+        assert_eq!(
+            records(b"1::2:3::", b"::", false),
+            vec![&b"1::"[..], b"2:3::"]
+        );
+    }
+}
@@ -0,0 +1,174 @@
+use crate::TailError;
+use arglex::lex_with_negatives;
+use arglex::Arg;
+use coreutils_common::numparse::parse_size;
+use coreutils_common::print_and_exit;
+use coreutils_common::unknown_arg_message;
+use coreutils_common::version_string;
+use std::slice::Iter;
+use std::time::Duration;
+
+const HELP: &str = "
+Usage: tail [option]... [FILE]...
+Print the last 10 lines of each FILE to standard output.
+With more than one FILE, precede each with a header giving the file name.
+
+With no FILE, or when FILE is -, read standard input.
+
+Options:
+  -c, --bytes <[+]NUM>      Output the last NUM bytes; or use -c +NUM to output starting with
+                              byte NUM of each file
+  -f, --follow              Output appended data as the file grows, reopening it if it is
+                              truncated
+  -n, --lines <[+]NUM>      Output the last NUM lines, instead of the last 10; or use -n +NUM
+                              to output starting with line NUM
+  -q, --quiet, --silent     Never output headers giving file names
+  -s, --sleep-interval <N>  With -f, check the files for changes every N seconds (default 1.0)
+  -v, --verbose             Always output headers giving file names
+  --version                 Output version information and exit
+  --help                    Display this help and exit
+
+-NUM on its own as the first option is the same as -n NUM.
+";
+
+fn print_help() -> ! {
+    print_and_exit(0, HELP);
+}
+
+fn print_version() -> ! {
+    print_and_exit(0, version_string!("tail"));
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Limit {
+    Last(u64),
+    /// Start at this 1-based line or byte
+    From(u64),
+}
+
+pub struct Args {
+    pub bytes: bool,
+    pub limit: Limit,
+    pub follow: bool,
+    pub quiet: bool,
+    pub sleep_interval: Duration,
+    pub verbose: bool,
+    pub files: Vec<String>,
+}
+
+impl Args {
+    fn new() -> Self {
+        Args {
+            bytes: false,
+            limit: Limit::Last(10),
+            follow: false,
+            quiet: false,
+            sleep_interval: Duration::from_secs(1),
+            verbose: false,
+            files: vec![],
+        }
+    }
+}
+
+fn get_arg_to(args: &mut Iter<Arg>, name: &Arg) -> Result<String, TailError> {
+    match args.next() {
+        Some(Arg::Positional(value)) => Ok(value.clone()),
+        _ => Err(format!("{} needs an argument", name).into()),
+    }
+}
+
+fn parse_limit(value: &str) -> Result<Limit, TailError> {
+    if let Some(number) = value.strip_prefix('+') {
+        Ok(Limit::From(parse_size(number)?))
+    } else {
+        let number = value.strip_prefix('-').unwrap_or(value);
+        Ok(Limit::Last(parse_size(number)?))
+    }
+}
+
+fn parse_interval(value: &str) -> Result<Duration, TailError> {
+    match value.parse::<f64>() {
+        Ok(seconds) if seconds >= 0.0 && seconds.is_finite() => {
+            Ok(Duration::from_secs_f64(seconds))
+        }
+        _ => Err(format!("invalid number of seconds: {}", value).into()),
+    }
+}
+
+pub fn parse(mut args: Vec<String>) -> Result<Args, TailError> {
+    // The obsolete `tail -5` form, only recognized as the first argument
+    if let Some(number) = args.first().and_then(|first| first.strip_prefix('-')) {
+        if !number.is_empty() && number.chars().all(|c| c.is_ascii_digit()) {
+            let number = number.to_owned();
+            args.splice(0..1, vec!["-n".to_owned(), number]);
+        }
+    }
+    let args = lex_with_negatives(args);
+
+    let mut args = args.iter();
+    let mut arg_struct = Args::new();
+    while let Some(arg) = args.next() {
+        match arg {
+            Arg::Positional(positional) => match positional.as_str() {
+                "--" => continue,
+                _ => arg_struct.files.push(positional.clone()),
+            },
+            Arg::Short(short) => match short.as_str() {
+                "c" => {
+                    arg_struct.bytes = true;
+                    arg_struct.limit = parse_limit(&get_arg_to(&mut args, arg)?)?;
+                }
+                "f" => arg_struct.follow = true,
+                "n" => {
+                    arg_struct.bytes = false;
+                    arg_struct.limit = parse_limit(&get_arg_to(&mut args, arg)?)?;
+                }
+                "q" => arg_struct.quiet = true,
+                "s" => arg_struct.sleep_interval = parse_interval(&get_arg_to(&mut args, arg)?)?,
+                "v" => arg_struct.verbose = true,
+                _ => return Err(unknown_arg_message(arg).into()),
+            },
+            Arg::Long(long) => match long.as_str() {
+                "bytes" => {
+                    arg_struct.bytes = true;
+                    arg_struct.limit = parse_limit(&get_arg_to(&mut args, arg)?)?;
+                }
+                "follow" => arg_struct.follow = true,
+                "lines" => {
+                    arg_struct.bytes = false;
+                    arg_struct.limit = parse_limit(&get_arg_to(&mut args, arg)?)?;
+                }
+                "quiet" | "silent" => arg_struct.quiet = true,
+                "sleep-interval" => {
+                    arg_struct.sleep_interval = parse_interval(&get_arg_to(&mut args, arg)?)?
+                }
+                "verbose" => arg_struct.verbose = true,
+                "version" => print_version(),
+                "help" => print_help(),
+                _ => return Err(unknown_arg_message(arg).into()),
+            },
+        };
+    }
+    if arg_struct.files.is_empty() {
+        arg_struct.files.push("-".to_owned());
+    }
+    Ok(arg_struct)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse_strs(args: &[&str]) -> Args {
+        parse(args.iter().map(|s| s.to_string()).collect()).unwrap()
+    }
+
+    #[test]
+    fn limits() {
+        assert_eq!(parse_strs(&[]).limit, Limit::Last(10));
+        assert_eq!(parse_strs(&["-n", "3"]).limit, Limit::Last(3));
+        assert_eq!(parse_strs(&["-n", "-3"]).limit, Limit::Last(3));
+        assert_eq!(parse_strs(&["-n", "+3"]).limit, Limit::From(3));
+        assert_eq!(parse_strs(&["-7"]).limit, Limit::Last(7));
+    }
+}
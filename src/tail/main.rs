@@ -0,0 +1,216 @@
+mod args;
+
+use args::Args;
+use args::Limit;
+use coreutils_common::exit_with;
+use coreutils_common::report_error;
+use coreutils_common::ring::RingBuffer;
+use coreutils_common::CoreError;
+use std::collections::VecDeque;
+use std::env;
+use std::fmt;
+use std::fmt::Debug;
+use std::fs;
+use std::fs::File;
+use std::io;
+use std::io::BufRead;
+use std::io::BufReader;
+use std::io::Read;
+use std::io::Seek;
+use std::io::SeekFrom;
+use std::io::Write;
+use std::process::exit;
+use std::thread;
+
+pub struct TailError {
+    message: String,
+}
+
+impl Debug for TailError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "tail: {}", self.message)
+    }
+}
+
+impl CoreError for TailError {}
+
+impl<T> From<T> for TailError
+where
+    T: ToString,
+{
+    fn from(error: T) -> Self {
+        TailError {
+            message: error.to_string(),
+        }
+    }
+}
+
+fn tail_lines(input: &mut dyn BufRead, limit: Limit, out: &mut dyn Write) -> io::Result<()> {
+    let mut line = vec![];
+    match limit {
+        Limit::Last(count) => {
+            let mut window = RingBuffer::new(count);
+            loop {
+                line.clear();
+                if input.read_until(b'\n', &mut line)? == 0 {
+                    break;
+                }
+                window.push(line.clone());
+            }
+            for line in window {
+                out.write_all(&line)?;
+            }
+        }
+        Limit::From(start) => {
+            for _ in 1..start {
+                line.clear();
+                if input.read_until(b'\n', &mut line)? == 0 {
+                    return Ok(());
+                }
+            }
+            io::copy(input, out)?;
+        }
+    }
+    Ok(())
+}
+
+fn tail_bytes(input: &mut dyn BufRead, limit: Limit, out: &mut dyn Write) -> io::Result<()> {
+    match limit {
+        Limit::Last(count) => {
+            let mut window: VecDeque<u8> = VecDeque::new();
+            loop {
+                let buf = input.fill_buf()?;
+                if buf.is_empty() {
+                    break;
+                }
+                window.extend(buf.iter());
+                let len = buf.len();
+                input.consume(len);
+                let excess = window.len().saturating_sub(count as usize);
+                window.drain(..excess);
+            }
+            let (front, back) = window.as_slices();
+            out.write_all(front)?;
+            out.write_all(back)?;
+        }
+        Limit::From(start) => {
+            io::copy(&mut input.take(start.saturating_sub(1)), &mut io::sink())?;
+            io::copy(input, out)?;
+        }
+    }
+    Ok(())
+}
+
+fn tail(input: &mut dyn BufRead, args: &Args, out: &mut dyn Write) -> io::Result<()> {
+    if args.bytes {
+        tail_bytes(input, args.limit, out)
+    } else {
+        tail_lines(input, args.limit, out)
+    }
+}
+
+fn write_header(out: &mut dyn Write, name: &str, first: bool) -> io::Result<()> {
+    let name = if name == "-" { "standard input" } else { name };
+    writeln!(out, "{}==> {} <==", if first { "" } else { "\n" }, name)
+}
+
+/// A file being watched by `--follow`, and how much of it has been printed.
+struct Followed {
+    name: String,
+    file: File,
+    position: u64,
+}
+
+/// Poll the files forever, printing anything appended to them.
+fn follow(mut files: Vec<Followed>, args: &Args, headers: bool) -> Result<(), TailError> {
+    let stdout = io::stdout();
+    let mut last_printed = files.len().checked_sub(1);
+    loop {
+        for (i, followed) in files.iter_mut().enumerate() {
+            let len = match fs::metadata(&followed.name) {
+                Ok(metadata) => metadata.len(),
+                Err(_) => continue,
+            };
+            if len < followed.position {
+                report_error("tail", format!("{}: file truncated", followed.name));
+                followed.file = File::open(&followed.name)?;
+                followed.position = 0;
+            }
+            if len == followed.position {
+                continue;
+            }
+            let mut out = stdout.lock();
+            if headers && last_printed != Some(i) {
+                write_header(&mut out, &followed.name, false)?;
+            }
+            last_printed = Some(i);
+            followed.file.seek(SeekFrom::Start(followed.position))?;
+            followed.position += io::copy(&mut followed.file, &mut out)?;
+            out.flush()?;
+        }
+        thread::sleep(args.sleep_interval);
+    }
+}
+
+fn main() {
+    if let Err(e) = run() {
+        exit_with(&e);
+    }
+}
+
+fn run() -> Result<(), TailError> {
+    let args = args::parse(env::args().skip(1).collect())?;
+    let headers = args.verbose || (args.files.len() > 1 && !args.quiet);
+    let mut followed = vec![];
+    let mut failed = false;
+    {
+        let stdout = io::stdout();
+        let mut out = stdout.lock();
+        let mut first = true;
+        for file in &args.files {
+            if file == "-" {
+                if headers {
+                    write_header(&mut out, file, first)?;
+                }
+                first = false;
+                let stdin = io::stdin();
+                let mut input = stdin.lock();
+                tail(&mut input, &args, &mut out).map_err(|e| format!("{}: {}", file, e))?;
+                continue;
+            }
+            let mut handle = match File::open(file) {
+                Ok(handle) => handle,
+                Err(e) => {
+                    out.flush()?;
+                    report_error("tail", format!("cannot open {} for reading: {}", file, e));
+                    failed = true;
+                    continue;
+                }
+            };
+            if headers {
+                write_header(&mut out, file, first)?;
+            }
+            first = false;
+            tail(&mut BufReader::new(&mut handle), &args, &mut out)
+                .map_err(|e| format!("{}: {}", file, e))?;
+            if args.follow {
+                // Where reading stopped rather than the current end, so nothing appended in
+                // the meantime is skipped
+                let position = handle.stream_position()?;
+                followed.push(Followed {
+                    name: file.clone(),
+                    file: handle,
+                    position,
+                });
+            }
+        }
+        out.flush()?;
+    }
+    if !followed.is_empty() {
+        follow(followed, &args, headers)?;
+    }
+    if failed {
+        exit(1);
+    }
+    Ok(())
+}
@@ -0,0 +1,126 @@
+mod args;
+
+use coreutils_common::exit_with;
+use coreutils_common::ignore_sigint;
+use coreutils_common::report_error;
+use coreutils_common::CoreError;
+use std::env;
+use std::fmt;
+use std::fmt::Debug;
+use std::fs::OpenOptions;
+use std::io;
+use std::io::ErrorKind;
+use std::io::Read;
+use std::io::Write;
+use std::process::exit;
+
+pub struct TeeError {
+    message: String,
+}
+
+impl Debug for TeeError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "tee: {}", self.message)
+    }
+}
+
+impl CoreError for TeeError {}
+
+impl<T> From<T> for TeeError
+where
+    T: ToString,
+{
+    fn from(error: T) -> Self {
+        TeeError {
+            message: error.to_string(),
+        }
+    }
+}
+
+/// A destination that is dropped from the copy once a write to it fails.
+struct Output {
+    name: String,
+    writer: Option<Box<dyn Write>>,
+}
+
+impl Output {
+    fn write_all(&mut self, data: &[u8]) -> bool {
+        if let Some(writer) = &mut self.writer {
+            if let Err(e) = writer.write_all(data) {
+                report_error("tee", format!("{}: {}", self.name, e));
+                self.writer = None;
+                return false;
+            }
+        }
+        true
+    }
+
+    fn flush(&mut self) -> bool {
+        if let Some(writer) = &mut self.writer {
+            if let Err(e) = writer.flush() {
+                report_error("tee", format!("{}: {}", self.name, e));
+                return false;
+            }
+        }
+        true
+    }
+}
+
+fn main() {
+    if let Err(e) = run() {
+        exit_with(&e);
+    }
+}
+
+fn run() -> Result<(), TeeError> {
+    let args = args::parse(env::args().skip(1).collect())?;
+    if args.ignore_interrupts {
+        ignore_sigint();
+    }
+
+    let mut failed = false;
+    let mut outputs = vec![Output {
+        name: "standard output".to_owned(),
+        writer: Some(Box::new(io::stdout())),
+    }];
+    for file in &args.files {
+        let opened = OpenOptions::new()
+            .write(true)
+            .create(true)
+            .append(args.append)
+            .truncate(!args.append)
+            .open(file);
+        match opened {
+            Ok(f) => outputs.push(Output {
+                name: file.clone(),
+                writer: Some(Box::new(f)),
+            }),
+            Err(e) => {
+                report_error("tee", format!("{}: {}", file, e));
+                failed = true;
+            }
+        }
+    }
+
+    let stdin = io::stdin();
+    let mut input = stdin.lock();
+    let mut buffer = [0; 8192];
+    loop {
+        let n = match input.read(&mut buffer) {
+            Ok(0) => break,
+            Ok(n) => n,
+            Err(e) if e.kind() == ErrorKind::Interrupted => continue,
+            Err(e) => return Err(format!("standard input: {}", e).into()),
+        };
+        for output in &mut outputs {
+            failed |= !output.write_all(&buffer[..n]);
+        }
+    }
+    for output in &mut outputs {
+        failed |= !output.flush();
+    }
+    if failed {
+        exit(1);
+    }
+    Ok(())
+}
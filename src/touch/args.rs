@@ -1,29 +1,36 @@
+use crate::TouchError;
 use arglex::lex;
 use arglex::Arg;
-use crate::TouchError;
+use coreutils_common::print_and_exit;
+use coreutils_common::version_string;
 
 use std::fmt;
 use std::fmt::Debug;
 use std::fmt::Formatter;
-use std::process::exit;
 use std::slice::Iter;
 
 const HELP: &str = "
 Usage: touch [option]... <FILE> ...
 Update the access and modification times of each FILE to the current time.
 
-A FILE argument that does not exist is created empty, unless -c or -h is supplied.
+A FILE argument that does not exist is created empty, unless -c is supplied.
 
 A FILE argument string of - is handled specially and causes touch to change the times of the file associated with standard output.
 
 Options:
   -a                        Change only the access time
   -c, --no-create           Do not create any files
-  -d, --date <STRING>       Parse STRING as a RFC3339 date and use it instead of the current time
+  -d, --date <STRING>       Parse STRING as a RFC3339 date or @SECONDS and use it instead of the
+                            current time
+  --files-from <FILE>       Also touch the newline-separated file names listed in FILE;
+                              if FILE is -, read the list from standard input
   -h, --no-dereference      Affect each symbolic link instead of any referenced file
   -m                        Change only the modification time
   -r, --reference <FILE>    Use the times of FILE instead of the current time
+  --summary                 Print how many files were updated, created, or failed when done
   -t <STAMP>                Use [[CC]YY]MMDDhhmm[.ss] instead of the current time
+  -u, --utc                 Interpret the -t STAMP as UTC instead of local time
+  -z, --null                With --files-from, names are separated by NUL instead of newline
   --time <WORD>             Change the specified time:
                               if WORD is access, atime, or use: equivalent to -a
                               if WORD is modify or mtime: equivalent to -m
@@ -32,13 +39,11 @@ Options:
 ";
 
 fn print_help() -> ! {
-    eprintln!("{}", HELP);
-    exit(0);
+    print_and_exit(0, HELP);
 }
 
 fn print_version() -> ! {
-    eprintln!("touch version 1.0.0");
-    exit(0);
+    print_and_exit(0, version_string!("touch"));
 }
 
 pub struct Args {
@@ -50,6 +55,10 @@ pub struct Args {
     pub reference: Option<String>,
     pub timestamp: Option<String>,
     pub time: Option<String>,
+    pub files_from: Option<String>,
+    pub null: bool,
+    pub summary: bool,
+    pub utc: bool,
     pub files: Vec<String>,
 }
 
@@ -64,6 +73,10 @@ impl Args {
             reference: None,
             timestamp: None,
             time: None,
+            files_from: None,
+            null: false,
+            summary: false,
+            utc: false,
             files: vec![],
         }
     }
@@ -73,9 +86,9 @@ pub struct ArgError {
     why: String,
 }
 
-impl Into<TouchError> for ArgError {
-    fn into(self) -> TouchError {
-        TouchError::from(format!("{:?}", self))
+impl From<ArgError> for TouchError {
+    fn from(err: ArgError) -> TouchError {
+        TouchError::from(format!("{:?}", err))
     }
 }
 
@@ -99,10 +112,8 @@ where
 fn get_arg_to(args: &mut Iter<Arg>) -> Result<String, ArgError> {
     let arg = args.next();
     let error_message = "An argument must be supplied".to_owned();
-    if let Some(arg) = arg {
-        if let Arg::Positional(value) = arg {
-            return Ok(value.clone());
-        }
+    if let Some(Arg::Positional(value)) = arg {
+        return Ok(value.clone());
     }
     Err(error_message.into())
 }
@@ -130,6 +141,8 @@ pub fn parse(args: Vec<String>) -> Result<Args, ArgError> {
                 "m" => arg_struct.modification = true,
                 "r" => arg_struct.reference = Some(get_arg_to(&mut args)?),
                 "t" => arg_struct.timestamp = Some(get_arg_to(&mut args)?),
+                "u" => arg_struct.utc = true,
+                "z" => arg_struct.null = true,
                 _ => return Err(unknown_argument(arg)),
             },
             Arg::Long(long) => match long.as_str() {
@@ -138,6 +151,10 @@ pub fn parse(args: Vec<String>) -> Result<Args, ArgError> {
                 "no-dereference" => arg_struct.no_dereference = true,
                 "reference" => arg_struct.reference = Some(get_arg_to(&mut args)?),
                 "time" => arg_struct.time = Some(get_arg_to(&mut args)?),
+                "files-from" => arg_struct.files_from = Some(get_arg_to(&mut args)?),
+                "null" => arg_struct.null = true,
+                "summary" => arg_struct.summary = true,
+                "utc" => arg_struct.utc = true,
                 "version" => print_version(),
                 "help" => print_help(),
                 _ => {}
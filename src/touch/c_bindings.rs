@@ -1,2 +0,0 @@
-#![allow(clippy::unreadable_literal)]
-include!(concat!(env!("OUT_DIR"), "/bindings.rs"));
@@ -1,24 +1,29 @@
 mod args;
-mod c_bindings;
-use c_bindings::AT_FDCWD;
-use c_bindings::AT_SYMLINK_NOFOLLOW;
-use c_bindings::UTIME_OMIT;
 
-use chrono::offset::TimeZone;
 use chrono::DateTime;
-use chrono::Datelike;
 use chrono::Local;
-use chrono::NaiveDate;
+use coreutils_common::eprintln_error;
+use coreutils_common::exit_with;
+use coreutils_common::input::open_input;
+use coreutils_common::input::read_names;
+use coreutils_common::report_error;
+use coreutils_common::times::set_times;
+use coreutils_common::timestamp::parse_date;
+use coreutils_common::timestamp::parse_timestamp;
+use coreutils_common::CoreError;
 use libc::timespec;
 use std::env;
-use std::ffi::CString;
+use std::ffi::OsStr;
+use std::ffi::OsString;
 use std::fmt;
 use std::fmt::Debug;
 use std::fs::File;
 use std::io;
+use std::os::unix::ffi::OsStrExt;
+use std::path::Path;
 use std::path::PathBuf;
+use std::process::exit;
 use std::time::SystemTime;
-use syscall::syscall;
 
 struct TouchFlags {
     change_access_time: bool,
@@ -29,6 +34,33 @@ struct TouchFlags {
     modified_time: DateTime<Local>,
 }
 
+/// What happened to a single FILE argument.
+enum Outcome {
+    Updated,
+    Created,
+    Skipped,
+}
+
+#[derive(Default)]
+struct Summary {
+    updated: usize,
+    created: usize,
+    failed: usize,
+}
+
+impl fmt::Display for Summary {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{} file{} updated, {} created, {} failed",
+            self.updated,
+            if self.updated == 1 { "" } else { "s" },
+            self.created,
+            self.failed
+        )
+    }
+}
+
 struct TouchError {
     message: String,
 }
@@ -40,6 +72,8 @@ impl Debug for TouchError {
     }
 }
 
+impl CoreError for TouchError {}
+
 impl<T> From<T> for TouchError
 where
     T: ToString,
@@ -51,74 +85,14 @@ where
     }
 }
 
-fn parse_timestamp(timestamp: &str) -> Result<DateTime<Local>, TouchError> {
-    let chars: Vec<char> = timestamp.chars().collect();
-    // the "has_" flags are checking for optional parts of the timestamp string.
-    // Not much validation is done here until we try to parse integers.
-    let has_seconds = chars.contains(&'.');
-    let expected_len = 8 + if has_seconds { 3 } else { 0 };
-    if timestamp.len() < expected_len {
-        return Err("timestamp is too short".into());
-    }
-    let has_century = chars.len() == expected_len + 4;
-    let has_year = has_century || chars.len() == expected_len + 2;
-    // Take slices for the significant parts of the timestamp to clean up later code.
-    // "Shift" the input by using a `rest` slice.
-    let (raw_century, rest) = if has_century {
-        (&timestamp[0..2], &timestamp[2..])
-    } else {
-        ("", timestamp)
-    };
-    let (raw_year, rest) = if has_year {
-        (&rest[0..2], &rest[2..])
-    } else {
-        ("", rest)
-    };
-    // No more shifting here, it's unnecessary
-    let raw_month = &rest[0..2];
-    let raw_day = &rest[2..4];
-    let raw_hours = &rest[4..6];
-    let raw_minutes = &rest[6..8];
-    let raw_seconds = if has_seconds { &rest[9..] } else { "" };
-
-    // Missing fields will be substituted with the current date
-    let today = Local::today();
-
-    // Try and parse the fields now
-    let century: i32 = if has_century {
-        raw_century.parse::<i32>().map_err(|_| "invalid century")? * 100
-    } else {
-        today.year() / 100 * 100
-    };
-    let year: i32 = if has_year {
-        raw_year.parse().map_err(|_| "invalid year")?
-    } else {
-        today.year() % 100
-    };
-
-    let month: u32 = raw_month.parse().map_err(|_| "invalid month")?;
-    let day: u32 = raw_day.parse().map_err(|_| "invalid day")?;
-    let hours: u32 = raw_hours.parse().map_err(|_| "invalid hour")?;
-    let minutes: u32 = raw_minutes.parse().map_err(|_| "invalid minute")?;
-    let seconds: u32 = if has_seconds {
-        raw_seconds.parse::<u32>().map_err(|_| "invalid second")?
-    } else {
-        0
-    };
-
-    // Done! Construct a DateTime (and also check none of the numbers were OOB)
-    if let Some(date) = NaiveDate::from_ymd_opt(century + year, month, day)
-        .and_then(|d| d.and_hms_opt(hours, minutes, seconds))
-    {
-        Ok(Local.from_local_datetime(&date).unwrap())
-    } else {
-        Err("invalid date".into())
+fn main() {
+    if let Err(e) = run() {
+        exit_with(&e);
     }
 }
 
-fn main() -> Result<(), TouchError> {
-    let args =
-        args::parse(env::args().skip(1).collect()).map_err(|e| -> TouchError { e.into() })?;
+fn run() -> Result<(), TouchError> {
+    let args = args::parse(env::args().skip(1).collect())?;
     let (change_only_access_time, change_only_modification_time) = if let Some(time) = args.time {
         match time.as_str() {
             "access" | "atime" | "use" => (true, false),
@@ -138,13 +112,11 @@ fn main() -> Result<(), TouchError> {
     let affect_symlinks = args.no_dereference;
     let (accessed_time, modified_time) = {
         if let Some(date) = args.date {
-            let time = DateTime::parse_from_rfc3339(&date)
-                .map_err(|e| format!("error parsing {} as an RFC 3339 date: {}", date, e))?;
-            let local_time = time.with_timezone(&Local);
-            (local_time, local_time)
+            let time = parse_date(&date)?;
+            (time, time)
         } else if let Some(timestamp) = args.timestamp {
-            let time = parse_timestamp(&timestamp)
-                .map_err(|e| format!("error parsing {} as a timestamp: {:?}", timestamp, e))?;
+            let time = parse_timestamp(&timestamp, args.utc)
+                .map_err(|e| format!("error parsing {} as a timestamp: {}", timestamp, e))?;
             (time, time)
         } else if let Some(reference) = args.reference {
             let reference_path = PathBuf::from(&reference);
@@ -170,10 +142,14 @@ fn main() -> Result<(), TouchError> {
             (now, now)
         }
     };
-    if args.files.is_empty() {
+    let mut files: Vec<OsString> = args.files.into_iter().map(OsString::from).collect();
+    if let Some(source) = args.files_from {
+        let separator = if args.null { b'\0' } else { b'\n' };
+        files.extend(read_file_list(&source, separator)?);
+    }
+    if files.is_empty() {
         return Err("must specify at least one file".into());
     }
-    let files: Vec<String> = args.files;
     let flags = TouchFlags {
         change_access_time,
         change_modification_time,
@@ -182,58 +158,76 @@ fn main() -> Result<(), TouchError> {
         accessed_time,
         modified_time,
     };
+    let mut summary = Summary::default();
     for file in files {
-        touch(&file, &flags)?;
+        match touch(&file, &flags) {
+            Ok(Outcome::Updated) => summary.updated += 1,
+            Ok(Outcome::Created) => summary.created += 1,
+            Ok(Outcome::Skipped) => {}
+            Err(e) => {
+                eprintln_error(format!("{:?}", e));
+                summary.failed += 1;
+            }
+        }
+    }
+    if args.summary {
+        report_error("touch", format!("{}", summary));
+    }
+    if summary.failed > 0 {
+        exit(1);
     }
     Ok(())
 }
 
-fn touch(file_name: &str, flags: &TouchFlags) -> Result<(), TouchError> {
-    if !PathBuf::from(file_name).exists() {
-        if flags.no_creating_files {
-            println!(
-                "Skipping {} as --no-create was passed and it does not already exist",
-                file_name
-            );
-            return Ok(());
-        } else if let Err(e) = File::create(PathBuf::from(file_name)) {
-            return Err(e.into());
-        }
+/// Read file names separated by `separator` from `source`, or from stdin if it is `-`.
+fn read_file_list(source: &str, separator: u8) -> Result<Vec<OsString>, TouchError> {
+    let mut input =
+        open_input(source).map_err(|e| format!("cannot open file list {}: {}", source, e))?;
+    let names = read_names(&mut input, separator)
+        .map_err(|e| format!("cannot read file list {}: {}", source, e))?;
+    Ok(names.into_iter().filter(|name| !name.is_empty()).collect())
+}
+
+fn touch(file_name: &OsStr, flags: &TouchFlags) -> Result<Outcome, TouchError> {
+    let path = Path::new(file_name);
+    // Checked before anything is created; utimensat would only reject the name afterwards.
+    if file_name.as_bytes().contains(&0) {
+        let shown = path.display().to_string().replace('\0', "\\0");
+        return Err(format!("invalid file name '{}': contains NUL", shown).into());
     }
-    let atime = timespec {
-        tv_sec: flags.accessed_time.timestamp(),
-        tv_nsec: if !flags.change_modification_time || flags.change_access_time {
-            flags.accessed_time.timestamp_subsec_nanos() as i64
-        } else {
-            UTIME_OMIT as i64
-        },
-    };
-    let mtime = timespec {
-        tv_sec: flags.modified_time.timestamp(),
-        tv_nsec: if !flags.change_access_time || flags.change_modification_time {
-            flags.modified_time.timestamp_subsec_nanos() as i64
-        } else {
-            UTIME_OMIT as i64
-        },
-    };
-    let c_file_name = CString::new(file_name).unwrap().into_bytes_with_nul();
-    let flag = if flags.affect_symlinks {
-        0
+    let atime = if !flags.change_modification_time || flags.change_access_time {
+        Some(timespec {
+            tv_sec: flags.accessed_time.timestamp(),
+            tv_nsec: flags.accessed_time.timestamp_subsec_nanos() as i64,
+        })
     } else {
-        AT_SYMLINK_NOFOLLOW
+        None
     };
-    let ret = unsafe {
-        syscall!(
-            UTIMENSAT,
-            AT_FDCWD,
-            c_file_name.as_ptr(),
-            [atime, mtime].as_ptr(),
-            flag
-        )
+    let mtime = if !flags.change_access_time || flags.change_modification_time {
+        Some(timespec {
+            tv_sec: flags.modified_time.timestamp(),
+            tv_nsec: flags.modified_time.timestamp_subsec_nanos() as i64,
+        })
+    } else {
+        None
     };
-    if ret != 0 {
-        let error = io::Error::last_os_error();
-        return Err(format!("could not set time(s) for {}: {}", file_name, error).into());
+    let describe =
+        |error: io::Error| format!("could not set time(s) for {}: {}", path.display(), error);
+    // Most files already exist, so try stamping first and only create on ENOENT; that saves a
+    // stat per file. With -h a dangling symlink's own times are set, so it counts as existing.
+    match set_times(path, atime, mtime, flags.affect_symlinks) {
+        Ok(()) => return Ok(Outcome::Updated),
+        Err(error) if error.kind() == io::ErrorKind::NotFound => {}
+        Err(error) => return Err(describe(error).into()),
     }
-    Ok(())
+    if flags.no_creating_files {
+        println!(
+            "Skipping {} as --no-create was passed and it does not already exist",
+            path.display()
+        );
+        return Ok(Outcome::Skipped);
+    }
+    File::create(path)?;
+    set_times(path, atime, mtime, flags.affect_symlinks).map_err(describe)?;
+    Ok(Outcome::Created)
 }
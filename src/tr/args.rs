@@ -0,0 +1,123 @@
+use crate::TrError;
+use arglex::lex;
+use arglex::Arg;
+use coreutils_common::print_and_exit;
+use coreutils_common::unknown_arg_message;
+use coreutils_common::version_string;
+
+const HELP: &str = "
+Usage: tr [OPTION]... SET1 [SET2]
+Translate, squeeze, and/or delete characters from standard input,
+writing to standard output.
+
+Options:
+  -c, --complement          Use the complement of SET1
+  -d, --delete              Delete characters in SET1, do not translate
+  -s, --squeeze-repeats     Replace each sequence of a repeated character
+                            that is listed in the last specified SET,
+                            with a single occurrence of that character
+  -t, --truncate-set1       First truncate SET1 to the length of SET2
+  --version                 Output version information and exit
+  --help                    Display this help and exit
+
+SETs are specified as strings of characters. Most represent themselves.
+Interpreted sequences are:
+  \\NNN                      Character with octal value NNN (1 to 3 octal digits)
+  \\\\                        Backslash
+  \\a, \\b, \\f, \\n, \\r, \\t, \\v
+                            The usual C escapes
+  CHAR1-CHAR2               All characters from CHAR1 to CHAR2 in ascending order
+  [:CLASS:]                 All characters in CLASS, one of alnum, alpha, blank,
+                            cntrl, digit, graph, lower, print, punct, space,
+                            upper, xdigit
+
+When translating, each character of SET1 becomes the character at the same
+position in SET2. If SET1 is longer, SET2 is extended to its length by repeating
+its last character, unless -t is given, in which case the characters of SET1
+past the end of SET2 are left alone. With -c, SET1 is the complement of the
+given set, in ascending byte order.
+";
+
+fn print_help() -> ! {
+    print_and_exit(0, HELP);
+}
+
+fn print_version() -> ! {
+    print_and_exit(0, version_string!("tr"));
+}
+
+pub struct Args {
+    pub complement: bool,
+    pub delete: bool,
+    pub squeeze: bool,
+    pub truncate: bool,
+    pub sets: Vec<String>,
+}
+
+impl Args {
+    fn new() -> Self {
+        Args {
+            complement: false,
+            delete: false,
+            squeeze: false,
+            truncate: false,
+            sets: vec![],
+        }
+    }
+}
+
+pub fn parse(args: Vec<String>) -> Result<Args, TrError> {
+    let mut arg_struct = Args::new();
+    for arg in &lex(args) {
+        match arg {
+            Arg::Positional(positional) => match positional.as_str() {
+                "--" => continue,
+                _ => arg_struct.sets.push(positional.clone()),
+            },
+            Arg::Short(short) => match short.as_str() {
+                "c" | "C" => arg_struct.complement = true,
+                "d" => arg_struct.delete = true,
+                "s" => arg_struct.squeeze = true,
+                "t" => arg_struct.truncate = true,
+                _ => return Err(unknown_arg_message(arg).into()),
+            },
+            Arg::Long(long) => match long.as_str() {
+                "complement" => arg_struct.complement = true,
+                "delete" => arg_struct.delete = true,
+                "squeeze-repeats" => arg_struct.squeeze = true,
+                "truncate-set1" => arg_struct.truncate = true,
+                "version" => print_version(),
+                "help" => print_help(),
+                _ => return Err(unknown_arg_message(arg).into()),
+            },
+        };
+    }
+
+    let wanted = if arg_struct.delete == arg_struct.squeeze {
+        2
+    } else {
+        1
+    };
+    match arg_struct.sets.len() {
+        0 => return Err("missing operand".into()),
+        1 if wanted == 2 && !arg_struct.delete => {
+            return Err(format!(
+                "missing operand after '{}'\nTwo strings must be given when translating.",
+                arg_struct.sets[0]
+            )
+            .into())
+        }
+        1 if wanted == 2 => {
+            return Err(format!(
+                "missing operand after '{}'\nTwo strings must be given when both deleting and squeezing repeats.",
+                arg_struct.sets[0]
+            )
+            .into())
+        }
+        n if n > 2 || (n == 2 && wanted == 1 && arg_struct.delete) => {
+            return Err(format!("extra operand '{}'", arg_struct.sets[wanted]).into())
+        }
+        _ => {}
+    }
+    Ok(arg_struct)
+}
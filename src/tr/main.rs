@@ -0,0 +1,134 @@
+mod args;
+mod set;
+
+use coreutils_common::exit_with;
+use coreutils_common::CoreError;
+use std::env;
+use std::fmt;
+use std::fmt::Debug;
+use std::io;
+use std::io::BufWriter;
+use std::io::ErrorKind;
+use std::io::Read;
+use std::io::Write;
+
+pub struct TrError {
+    message: String,
+}
+
+impl Debug for TrError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "tr: {}", self.message)
+    }
+}
+
+impl CoreError for TrError {}
+
+impl<T> From<T> for TrError
+where
+    T: ToString,
+{
+    fn from(error: T) -> Self {
+        TrError {
+            message: error.to_string(),
+        }
+    }
+}
+
+/// Per-byte lookup tables built once from the sets.
+struct Tables {
+    translate: [u8; 256],
+    delete: [bool; 256],
+    squeeze: [bool; 256],
+}
+
+impl Tables {
+    fn new(args: &args::Args) -> Result<Tables, TrError> {
+        let mut set1 = set::expand(&args.sets[0])?;
+        if args.complement {
+            set1 = set::complement(&set1);
+        }
+        let set2 = match args.sets.get(1) {
+            Some(set2) => set::expand(set2)?,
+            None => vec![],
+        };
+
+        let mut tables = Tables {
+            translate: [0; 256],
+            delete: [false; 256],
+            squeeze: [false; 256],
+        };
+        for (i, entry) in tables.translate.iter_mut().enumerate() {
+            *entry = i as u8;
+        }
+
+        if args.delete {
+            for &b in &set1 {
+                tables.delete[usize::from(b)] = true;
+            }
+        } else if let Some(&last) = set2.last() {
+            if args.truncate {
+                set1.truncate(set2.len());
+            }
+            // Later repeats of a character in SET1 override earlier ones, as in GNU tr.
+            for (i, &from) in set1.iter().enumerate() {
+                tables.translate[usize::from(from)] = set2.get(i).copied().unwrap_or(last);
+            }
+        } else if !args.squeeze && !args.truncate {
+            return Err("when not truncating set1, string2 must be non-empty".into());
+        }
+
+        if args.squeeze {
+            let squeezed = if args.sets.len() == 2 { &set2 } else { &set1 };
+            for &b in squeezed {
+                tables.squeeze[usize::from(b)] = true;
+            }
+        }
+        Ok(tables)
+    }
+}
+
+/// Translates `input` one buffer at a time, so memory use doesn't grow with the input.
+fn tr(input: &mut dyn Read, tables: &Tables, out: &mut dyn Write) -> io::Result<()> {
+    let mut buffer = [0; 8192];
+    let mut translated = Vec::with_capacity(buffer.len());
+    let mut last = None;
+    loop {
+        let n = match input.read(&mut buffer) {
+            Ok(0) => return Ok(()),
+            Ok(n) => n,
+            Err(e) if e.kind() == ErrorKind::Interrupted => continue,
+            Err(e) => return Err(e),
+        };
+        translated.clear();
+        for &b in &buffer[..n] {
+            if tables.delete[usize::from(b)] {
+                continue;
+            }
+            let b = tables.translate[usize::from(b)];
+            if tables.squeeze[usize::from(b)] && last == Some(b) {
+                continue;
+            }
+            last = Some(b);
+            translated.push(b);
+        }
+        out.write_all(&translated)?;
+    }
+}
+
+fn main() {
+    if let Err(e) = run() {
+        exit_with(&e);
+    }
+}
+
+fn run() -> Result<(), TrError> {
+    let args = args::parse(env::args().skip(1).collect())?;
+    let tables = Tables::new(&args)?;
+    let stdin = io::stdin();
+    let stdout = io::stdout();
+    let mut out = BufWriter::new(stdout.lock());
+    tr(&mut stdin.lock(), &tables, &mut out)?;
+    out.flush()?;
+    Ok(())
+}
@@ -0,0 +1,140 @@
+use crate::TrError;
+
+fn class(name: &str) -> Option<Vec<u8>> {
+    let test: fn(&u8) -> bool = match name {
+        "alnum" => u8::is_ascii_alphanumeric,
+        "alpha" => u8::is_ascii_alphabetic,
+        "blank" => |&b| b == b' ' || b == b'\t',
+        "cntrl" => u8::is_ascii_control,
+        "digit" => u8::is_ascii_digit,
+        "graph" => u8::is_ascii_graphic,
+        "lower" => u8::is_ascii_lowercase,
+        "print" => |&b| b == b' ' || b.is_ascii_graphic(),
+        "punct" => u8::is_ascii_punctuation,
+        "space" => |&b| b == b' ' || (b'\t'..=b'\r').contains(&b),
+        "upper" => u8::is_ascii_uppercase,
+        "xdigit" => u8::is_ascii_hexdigit,
+        _ => return None,
+    };
+    Some((0..=u8::MAX).filter(test).collect())
+}
+
+/// Reads one possibly backslash-escaped byte from the front of `set`.
+fn next_byte(set: &[u8]) -> (u8, usize) {
+    if set[0] != b'\\' || set.len() == 1 {
+        return (set[0], 1);
+    }
+    let escaped = match set[1] {
+        b'a' => 0x07,
+        b'b' => 0x08,
+        b'f' => 0x0c,
+        b'n' => b'\n',
+        b'r' => b'\r',
+        b't' => b'\t',
+        b'v' => 0x0b,
+        b'0'..=b'7' => {
+            let digits = set[1..]
+                .iter()
+                .take(3)
+                .take_while(|b| (b'0'..=b'7').contains(b))
+                .count();
+            let value = set[1..1 + digits]
+                .iter()
+                .fold(0u32, |value, &b| value * 8 + u32::from(b - b'0'));
+            // Three octal digits can reach 0o777; like GNU, only take ones that fit a byte.
+            if value > 0xff {
+                let value = set[1..digits]
+                    .iter()
+                    .fold(0u32, |value, &b| value * 8 + u32::from(b - b'0'));
+                return (value as u8, digits);
+            }
+            return (value as u8, 1 + digits);
+        }
+        other => other,
+    };
+    (escaped, 2)
+}
+
+/// Expands a tr SET into the bytes it stands for, in order: ranges like `a-z`, classes like
+/// `[:digit:]`, and backslash escapes.
+pub fn expand(set: &str) -> Result<Vec<u8>, TrError> {
+    let set = set.as_bytes();
+    let mut bytes = vec![];
+    let mut i = 0;
+    while i < set.len() {
+        if set[i..].starts_with(b"[:") {
+            if let Some(end) = set[i + 2..].windows(2).position(|w| w == b":]") {
+                let name = String::from_utf8_lossy(&set[i + 2..i + 2 + end]);
+                match class(&name) {
+                    Some(members) => bytes.extend(members),
+                    None => return Err(format!("invalid character class '{}'", name).into()),
+                }
+                i += end + 4;
+                continue;
+            }
+        }
+        let (low, width) = next_byte(&set[i..]);
+        i += width;
+        if set.get(i) == Some(&b'-') && i + 1 < set.len() {
+            let (high, width) = next_byte(&set[i + 1..]);
+            if high < low {
+                return Err(format!(
+                    "range-endpoints of '{}-{}' are in reverse collating sequence order",
+                    char::from(low),
+                    char::from(high)
+                )
+                .into());
+            }
+            bytes.extend(low..=high);
+            i += 1 + width;
+        } else {
+            bytes.push(low);
+        }
+    }
+    Ok(bytes)
+}
+
+/// Every byte that is not in `set`, in ascending order.
+pub fn complement(set: &[u8]) -> Vec<u8> {
+    (0..=u8::MAX).filter(|b| !set.contains(b)).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ranges() {
+        assert_eq!(expand("a-e").unwrap(), b"abcde");
+        assert_eq!(expand("a-cx").unwrap(), b"abcx");
+        assert_eq!(expand("a-").unwrap(), b"a-");
+        assert_eq!(expand("-a").unwrap(), b"-a");
+        assert!(expand("z-a").is_err());
+    }
+
+    #[test]
+    fn classes() {
+        assert_eq!(expand("[:digit:]").unwrap(), b"0123456789");
+        assert_eq!(expand("[:xdigit:]").unwrap(), b"0123456789ABCDEFabcdef");
+        assert_eq!(expand("[:upper:]").unwrap().len(), 26);
+        assert!(expand("[:bogus:]").is_err());
+        assert_eq!(expand("[:").unwrap(), b"[:");
+    }
+
+    #[test]
+    fn escapes() {
+        assert_eq!(expand("\\n\\t\\\\").unwrap(), b"\n\t\\");
+        assert_eq!(expand("\\101\\0").unwrap(), b"A\0");
+        assert_eq!(expand("\\400").unwrap(), b" 0");
+        assert_eq!(expand("\\n-\\r").unwrap(), b"\n\x0b\x0c\r");
+        assert_eq!(expand("\\").unwrap(), b"\\");
+    }
+
+    #[test]
+    fn complements() {
+        let all_but_a = complement(b"a");
+        assert_eq!(all_but_a.len(), 255);
+        assert!(!all_but_a.contains(&b'a'));
+        assert!(all_but_a.windows(2).all(|w| w[0] < w[1]));
+    }
+}
@@ -0,0 +1,30 @@
+use arglex::lex;
+use arglex::Arg;
+use coreutils_common::print_and_exit;
+use coreutils_common::version_string;
+use std::env;
+
+const HELP: &str = "
+Usage: true [ignored command line arguments]
+  or:  true OPTION
+Exit with a status code indicating success.
+
+Options:
+  --version                 Output version information and exit
+  --help                    Display this help and exit
+";
+
+fn main() {
+    // Like GNU true, options are only recognized when they are the sole argument; anything
+    // else is ignored.
+    let args: Vec<String> = env::args().skip(1).collect();
+    if args.len() == 1 {
+        match lex(args).first() {
+            Some(Arg::Long(long)) if long == "help" => print_and_exit(0, HELP),
+            Some(Arg::Long(long)) if long == "version" => {
+                print_and_exit(0, version_string!("true"))
+            }
+            _ => {}
+        }
+    }
+}
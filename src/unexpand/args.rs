@@ -0,0 +1,102 @@
+use crate::UnexpandError;
+use arglex::lex;
+use arglex::Arg;
+use coreutils_common::print_and_exit;
+use coreutils_common::tabs::TabStops;
+use coreutils_common::unknown_arg_message;
+use coreutils_common::version_string;
+use std::slice::Iter;
+
+const HELP: &str = "
+Usage: unexpand [OPTION]... [FILE]...
+Convert blanks in each FILE to tabs, writing to standard output.
+
+With no FILE, or when FILE is -, read standard input.
+
+Options:
+  -a, --all                 Convert all blanks, instead of just initial blanks
+  --first-only              Convert only leading sequences of blanks (overrides -a)
+  -t, --tabs <N>            Have tabs N characters apart instead of 8 (enables -a)
+  -t, --tabs <LIST>         Use comma separated list of tab positions (enables -a)
+  --version                 Output version information and exit
+  --help                    Display this help and exit
+";
+
+fn print_help() -> ! {
+    print_and_exit(0, HELP);
+}
+
+fn print_version() -> ! {
+    print_and_exit(0, version_string!("unexpand"));
+}
+
+pub struct Args {
+    /// Convert blanks after the first non-blank too.
+    pub all: bool,
+    pub tabs: TabStops,
+    pub files: Vec<String>,
+}
+
+fn get_arg_to(args: &mut Iter<Arg>, name: &Arg) -> Result<String, UnexpandError> {
+    match args.next() {
+        Some(Arg::Positional(value)) => Ok(value.clone()),
+        _ => Err(format!("{} needs an argument", name).into()),
+    }
+}
+
+pub fn parse(args: Vec<String>) -> Result<Args, UnexpandError> {
+    let args = lex(args);
+
+    let mut args = args.iter();
+    let mut all = false;
+    let mut first_only = false;
+    let mut tabs = None;
+    let mut files = vec![];
+    while let Some(arg) = args.next() {
+        match arg {
+            Arg::Positional(positional) => match positional.as_str() {
+                "--" => continue,
+                _ => files.push(positional.clone()),
+            },
+            Arg::Short(short) => match short.as_str() {
+                "a" => all = true,
+                "t" => tabs = Some(TabStops::parse(&get_arg_to(&mut args, arg)?)?),
+                _ => return Err(unknown_arg_message(arg).into()),
+            },
+            Arg::Long(long) => match long.as_str() {
+                "all" => all = true,
+                "first-only" => first_only = true,
+                "tabs" => tabs = Some(TabStops::parse(&get_arg_to(&mut args, arg)?)?),
+                "version" => print_version(),
+                "help" => print_help(),
+                _ => return Err(unknown_arg_message(arg).into()),
+            },
+        };
+    }
+    if files.is_empty() {
+        files.push("-".to_owned());
+    }
+    Ok(Args {
+        all: (all || tabs.is_some()) && !first_only,
+        tabs: tabs.unwrap_or_default(),
+        files,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse_strs(args: &[&str]) -> Result<Args, UnexpandError> {
+        parse(args.iter().map(|s| s.to_string()).collect())
+    }
+
+    #[test]
+    fn all() {
+        assert!(!parse_strs(&[]).unwrap().all);
+        assert!(parse_strs(&["-a"]).unwrap().all);
+        assert!(parse_strs(&["-t", "4"]).unwrap().all);
+        assert!(!parse_strs(&["-a", "--first-only"]).unwrap().all);
+        assert!(!parse_strs(&["--first-only", "-t", "4"]).unwrap().all);
+    }
+}
@@ -0,0 +1,175 @@
+mod args;
+
+use args::Args;
+use coreutils_common::exit_with;
+use coreutils_common::report_error;
+use coreutils_common::CoreError;
+use std::env;
+use std::fmt;
+use std::fmt::Debug;
+use std::fs::File;
+use std::io;
+use std::io::BufRead;
+use std::io::BufReader;
+use std::io::BufWriter;
+use std::io::Write;
+use std::process::exit;
+
+pub struct UnexpandError {
+    message: String,
+}
+
+impl Debug for UnexpandError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "unexpand: {}", self.message)
+    }
+}
+
+impl CoreError for UnexpandError {}
+
+impl<T> From<T> for UnexpandError
+where
+    T: ToString,
+{
+    fn from(error: T) -> Self {
+        UnexpandError {
+            message: error.to_string(),
+        }
+    }
+}
+
+/// Replaces runs of blanks that reach a tab stop with a tab, tracking columns the same way
+/// expand does. A lone space that reaches a stop stays a space unless more blanks follow,
+/// which is what GNU unexpand does.
+fn unexpand_line(line: &[u8], args: &Args, out: &mut Vec<u8>) {
+    let mut column: usize = 0;
+    let mut converting = true;
+    // Blanks seen since the last stop, kept verbatim in case no stop is reached.
+    let mut pending: Vec<u8> = vec![];
+    // A single space that ended exactly on a stop.
+    let mut lone_space = false;
+    for &byte in line {
+        let blank = byte == b' ' || byte == b'\t';
+        if lone_space {
+            out.push(if blank { b'\t' } else { b' ' });
+            lone_space = false;
+        }
+        if !(blank && converting) {
+            out.append(&mut pending);
+            if !blank && byte != b'\n' {
+                converting = args.all;
+            }
+            match byte {
+                b'\x08' => column = column.saturating_sub(1),
+                0x80..=0xbf => {}
+                b'\t' => column = args.tabs.next_stop(column),
+                _ => column += 1,
+            }
+            out.push(byte);
+            continue;
+        }
+
+        column = if byte == b'\t' {
+            args.tabs.next_stop(column)
+        } else {
+            column + 1
+        };
+        pending.push(byte);
+        if args.tabs.is_stop(column) {
+            if pending == b" " {
+                lone_space = true;
+            } else {
+                out.push(b'\t');
+            }
+            pending.clear();
+        }
+    }
+    if lone_space {
+        out.push(b' ');
+    }
+    out.append(&mut pending);
+}
+
+fn unexpand(input: &mut dyn BufRead, args: &Args, out: &mut dyn Write) -> io::Result<()> {
+    let mut line = vec![];
+    let mut unexpanded = vec![];
+    loop {
+        line.clear();
+        if input.read_until(b'\n', &mut line)? == 0 {
+            return Ok(());
+        }
+        unexpanded.clear();
+        unexpand_line(&line, args, &mut unexpanded);
+        out.write_all(&unexpanded)?;
+    }
+}
+
+fn main() {
+    if let Err(e) = run() {
+        exit_with(&e);
+    }
+}
+
+fn run() -> Result<(), UnexpandError> {
+    let args = args::parse(env::args().skip(1).collect())?;
+    let stdout = io::stdout();
+    let mut out = BufWriter::new(stdout.lock());
+    let mut failed = false;
+    for file in &args.files {
+        let unexpanded = if file == "-" {
+            let stdin = io::stdin();
+            let mut input = stdin.lock();
+            unexpand(&mut input, &args, &mut out)
+        } else {
+            File::open(file).and_then(|f| unexpand(&mut BufReader::new(f), &args, &mut out))
+        };
+        if let Err(e) = unexpanded {
+            out.flush()?;
+            report_error("unexpand", format!("{}: {}", file, e));
+            failed = true;
+        }
+    }
+    out.flush()?;
+    if failed {
+        exit(1);
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use coreutils_common::tabs::TabStops;
+
+    fn unexpand_str(line: &str, all: bool, tabs: TabStops) -> String {
+        let args = Args {
+            all,
+            tabs,
+            files: vec![],
+        };
+        let mut out = vec![];
+        unexpand_line(line.as_bytes(), &args, &mut out);
+        String::from_utf8(out).unwrap()
+    }
+
+    #[test]
+    fn lone_spaces() {
+        let eight = || TabStops::Every(8);
+        assert_eq!(unexpand_str("abcdefg b", true, eight()), "abcdefg b");
+        assert_eq!(unexpand_str("abcdefg  b", true, eight()), "abcdefg\t b");
+        assert_eq!(unexpand_str("abcdefg \tb", true, eight()), "abcdefg\t\tb");
+    }
+
+    #[test]
+    fn mixed_blanks() {
+        let eight = || TabStops::Every(8);
+        assert_eq!(unexpand_str("  \t  x", false, eight()), "\t  x");
+        assert_eq!(unexpand_str("        ", false, eight()), "\t");
+    }
+
+    #[test]
+    fn past_the_last_listed_stop() {
+        let list = TabStops::List(vec![2, 4]);
+        assert_eq!(unexpand_str("      x", false, list), "\t\t  x");
+    }
+}
@@ -0,0 +1,295 @@
+use crate::UniqError;
+use arglex::lex_one;
+use arglex::Arg;
+use coreutils_common::print_and_exit;
+use coreutils_common::unexpected_value_message;
+use coreutils_common::unknown_arg_message;
+use coreutils_common::version_string;
+
+const HELP: &str = "
+Usage: uniq [OPTION]... [INPUT [OUTPUT]]
+Filter adjacent matching lines from INPUT (or standard input),
+writing to OUTPUT (or standard output).
+
+With no options, matching lines are merged to the first occurrence.
+
+Options:
+  -c, --count               Prefix lines by the number of occurrences
+  -d, --repeated            Only print duplicate lines, one for each group
+  -D                        Print all duplicate lines
+  --all-repeated[=METHOD]   Like -D, but allow separating groups with an empty
+                            line; METHOD is none (default), prepend or separate
+  -f, --skip-fields <N>     Avoid comparing the first N fields
+  --group[=METHOD]          Show all lines, separating groups with an empty
+                            line; METHOD is separate (default), prepend, append
+                            or both
+  -i, --ignore-case         Ignore differences in case when comparing
+  -s, --skip-chars <N>      Avoid comparing the first N characters
+  -u, --unique              Only print unique lines
+  --version                 Output version information and exit
+  --help                    Display this help and exit
+
+A field is a run of blanks, then non-blank characters. Fields are skipped
+before characters.
+";
+
+fn print_help() -> ! {
+    print_and_exit(0, HELP);
+}
+
+fn print_version() -> ! {
+    print_and_exit(0, version_string!("uniq"));
+}
+
+/// Where `--all-repeated` and `--group` put empty lines. Groups are always separated from each
+/// other, except under `none`; `prepend` and `append` also mark the start of the first group
+/// and the end of the last, and `both` does both.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Delimit {
+    None,
+    Prepend,
+    Append,
+    Separate,
+    Both,
+}
+
+impl Delimit {
+    pub fn before_first(self) -> bool {
+        matches!(self, Delimit::Prepend | Delimit::Both)
+    }
+
+    pub fn between(self) -> bool {
+        self != Delimit::None
+    }
+
+    pub fn after_last(self) -> bool {
+        matches!(self, Delimit::Append | Delimit::Both)
+    }
+}
+
+pub struct Args {
+    pub all_repeated: Option<Delimit>,
+    pub count: bool,
+    pub group: Option<Delimit>,
+    pub repeated: bool,
+    pub unique: bool,
+    pub ignore_case: bool,
+    pub skip_fields: usize,
+    pub skip_chars: usize,
+    pub input: String,
+    pub output: Option<String>,
+}
+
+impl Args {
+    fn new() -> Self {
+        Args {
+            all_repeated: None,
+            count: false,
+            group: None,
+            repeated: false,
+            unique: false,
+            ignore_case: false,
+            skip_fields: 0,
+            skip_chars: 0,
+            input: "-".to_owned(),
+            output: None,
+        }
+    }
+}
+
+/// The value attached to an option with `=`, or else the next argument.
+fn value_of(
+    attached: Option<String>,
+    raw_args: &mut impl Iterator<Item = String>,
+    name: &Arg,
+) -> Result<String, UniqError> {
+    attached
+        .or_else(|| raw_args.next())
+        .ok_or_else(|| format!("{} needs an argument", name).into())
+}
+
+fn parse_skip(value: &str, what: &str) -> Result<usize, UniqError> {
+    value
+        .parse()
+        .map_err(|_| format!("{}: invalid number of {} to skip", value, what).into())
+}
+
+/// Parses the METHOD of `--option=METHOD`, which must be one of `allowed`.
+fn parse_delimit(
+    method: Option<String>,
+    option: &str,
+    default: Delimit,
+    allowed: &[Delimit],
+) -> Result<Delimit, UniqError> {
+    let method = match method {
+        Some(method) => method,
+        None => return Ok(default),
+    };
+    let delimit = match method.as_str() {
+        "none" => Some(Delimit::None),
+        "prepend" => Some(Delimit::Prepend),
+        "append" => Some(Delimit::Append),
+        "separate" => Some(Delimit::Separate),
+        "both" => Some(Delimit::Both),
+        _ => None,
+    };
+    match delimit {
+        Some(delimit) if allowed.contains(&delimit) => Ok(delimit),
+        _ => Err(format!("invalid argument '{}' for '--{}'", method, option).into()),
+    }
+}
+
+pub fn parse(args: Vec<String>) -> Result<Args, UniqError> {
+    let mut arg_struct = Args::new();
+    let mut raw_args = args.into_iter();
+    let mut delimited = false;
+    let mut operands = vec![];
+    // Each argument is lexed on its own, so that `--group` can tell `--group=METHOD` apart from
+    // `--group INPUT`.
+    while let Some(raw_arg) = raw_args.next() {
+        if delimited {
+            operands.push(raw_arg);
+            continue;
+        }
+        let (lexed, mut attached) = lex_one(raw_arg, "fs");
+        for arg in &lexed {
+            match arg {
+                Arg::Positional(positional) => match positional.as_str() {
+                    "--" => delimited = true,
+                    _ => operands.push(positional.clone()),
+                },
+                Arg::Short(short) => match short.as_str() {
+                    "c" => arg_struct.count = true,
+                    "d" => arg_struct.repeated = true,
+                    "D" => arg_struct.all_repeated = Some(Delimit::None),
+                    "f" => {
+                        let skip = value_of(attached.take(), &mut raw_args, arg)?;
+                        arg_struct.skip_fields = parse_skip(&skip, "fields")?;
+                    }
+                    "i" => arg_struct.ignore_case = true,
+                    "s" => {
+                        let skip = value_of(attached.take(), &mut raw_args, arg)?;
+                        arg_struct.skip_chars = parse_skip(&skip, "bytes")?;
+                    }
+                    "u" => arg_struct.unique = true,
+                    _ => return Err(unknown_arg_message(arg).into()),
+                },
+                Arg::Long(long) => match long.as_str() {
+                    "all-repeated" => {
+                        arg_struct.all_repeated = Some(parse_delimit(
+                            attached.take(),
+                            long,
+                            Delimit::None,
+                            &[Delimit::None, Delimit::Prepend, Delimit::Separate],
+                        )?);
+                    }
+                    "count" => arg_struct.count = true,
+                    "group" => {
+                        arg_struct.group = Some(parse_delimit(
+                            attached.take(),
+                            long,
+                            Delimit::Separate,
+                            &[
+                                Delimit::Prepend,
+                                Delimit::Append,
+                                Delimit::Separate,
+                                Delimit::Both,
+                            ],
+                        )?);
+                    }
+                    "repeated" => arg_struct.repeated = true,
+                    "skip-fields" => {
+                        let skip = value_of(attached.take(), &mut raw_args, arg)?;
+                        arg_struct.skip_fields = parse_skip(&skip, "fields")?;
+                    }
+                    "ignore-case" => arg_struct.ignore_case = true,
+                    "skip-chars" => {
+                        let skip = value_of(attached.take(), &mut raw_args, arg)?;
+                        arg_struct.skip_chars = parse_skip(&skip, "bytes")?;
+                    }
+                    "unique" => arg_struct.unique = true,
+                    "version" => print_version(),
+                    "help" => print_help(),
+                    _ => return Err(unknown_arg_message(arg).into()),
+                },
+            };
+        }
+        if let (Some(_), Some(arg)) = (attached, lexed.last()) {
+            return Err(unexpected_value_message(arg).into());
+        }
+    }
+
+    if arg_struct.group.is_some()
+        && (arg_struct.count
+            || arg_struct.repeated
+            || arg_struct.all_repeated.is_some()
+            || arg_struct.unique)
+    {
+        return Err("--group is mutually exclusive with -c/-d/-D/-u".into());
+    }
+    if arg_struct.all_repeated.is_some() && arg_struct.count {
+        return Err("printing all duplicated lines and repeat counts is meaningless".into());
+    }
+
+    let mut operands = operands.into_iter();
+    if let Some(input) = operands.next() {
+        arg_struct.input = input;
+    }
+    arg_struct.output = operands.next();
+    if let Some(extra) = operands.next() {
+        return Err(format!("extra operand '{}'", extra).into());
+    }
+    Ok(arg_struct)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse_strs(args: &[&str]) -> Result<Args, UniqError> {
+        parse(args.iter().map(|s| s.to_string()).collect())
+    }
+
+    #[test]
+    fn skip_counts() {
+        let args = parse_strs(&["-f2", "--skip-chars=3"]).unwrap();
+        assert_eq!(args.skip_fields, 2);
+        assert_eq!(args.skip_chars, 3);
+        assert!(parse_strs(&["-f", "x"]).is_err());
+        let args = parse_strs(&["-ics2", "in"]).unwrap();
+        assert!(args.ignore_case && args.count);
+        assert_eq!(args.skip_chars, 2);
+        assert_eq!(args.input, "in");
+        assert!(parse_strs(&["--count=yes"]).is_err());
+    }
+
+    #[test]
+    fn delimit_methods() {
+        let args = parse_strs(&["--group", "in"]).unwrap();
+        assert_eq!(args.group, Some(Delimit::Separate));
+        assert_eq!(args.input, "in");
+        let args = parse_strs(&["--group=both"]).unwrap();
+        assert_eq!(args.group, Some(Delimit::Both));
+        assert_eq!(
+            parse_strs(&["-D"]).unwrap().all_repeated,
+            Some(Delimit::None)
+        );
+        let args = parse_strs(&["--all-repeated=prepend"]).unwrap();
+        assert_eq!(args.all_repeated, Some(Delimit::Prepend));
+        assert!(parse_strs(&["--all-repeated=both"]).is_err());
+        assert!(parse_strs(&["--group=none"]).is_err());
+        assert!(parse_strs(&["--group", "-c"]).is_err());
+        assert!(parse_strs(&["-D", "-c"]).is_err());
+    }
+
+    #[test]
+    fn input_and_output_operands() {
+        let args = parse_strs(&[]).unwrap();
+        assert_eq!(args.input, "-");
+        assert_eq!(args.output, None);
+        let args = parse_strs(&["in", "out"]).unwrap();
+        assert_eq!(args.input, "in");
+        assert_eq!(args.output.as_deref(), Some("out"));
+        assert!(parse_strs(&["in", "out", "extra"]).is_err());
+    }
+}
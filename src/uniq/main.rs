@@ -0,0 +1,191 @@
+mod args;
+
+use args::Args;
+use args::Delimit;
+use coreutils_common::exit_with;
+use coreutils_common::input::lines;
+use coreutils_common::input::open_input;
+use coreutils_common::CoreError;
+use std::env;
+use std::fmt;
+use std::fmt::Debug;
+use std::fs::File;
+use std::io;
+use std::io::BufRead;
+use std::io::BufWriter;
+use std::io::Write;
+
+pub struct UniqError {
+    message: String,
+}
+
+impl Debug for UniqError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "uniq: {}", self.message)
+    }
+}
+
+impl CoreError for UniqError {}
+
+impl<T> From<T> for UniqError
+where
+    T: ToString,
+{
+    fn from(error: T) -> Self {
+        UniqError {
+            message: error.to_string(),
+        }
+    }
+}
+
+fn is_blank(byte: u8) -> bool {
+    byte == b' ' || byte == b'\t'
+}
+
+/// The part of `line` that takes part in comparisons, after skipping fields and then
+/// characters.
+fn key<'a>(line: &'a [u8], args: &Args) -> &'a [u8] {
+    let mut rest = line;
+    for _ in 0..args.skip_fields {
+        let start = rest
+            .iter()
+            .position(|&b| !is_blank(b))
+            .unwrap_or(rest.len());
+        let end = rest[start..]
+            .iter()
+            .position(|&b| is_blank(b))
+            .map_or(rest.len(), |i| start + i);
+        rest = &rest[end..];
+    }
+    &rest[args.skip_chars.min(rest.len())..]
+}
+
+fn same(a: &[u8], b: &[u8], args: &Args) -> bool {
+    let (a, b) = (key(a, args), key(b, args));
+    if args.ignore_case {
+        a.eq_ignore_ascii_case(b)
+    } else {
+        a == b
+    }
+}
+
+/// One run of matching lines. Only -D and --group print more than the first line, so only they
+/// keep the rest.
+struct Group {
+    lines: Vec<Vec<u8>>,
+    count: usize,
+}
+
+/// Prints `group` as the options ask. `printed` says whether -D or --group has printed a group
+/// yet, which decides whether an empty line goes before this one.
+fn emit(group: &Group, args: &Args, printed: &mut bool, out: &mut dyn Write) -> io::Result<()> {
+    if let Some(delimit) = args.all_repeated.or(args.group) {
+        if args.all_repeated.is_some() && group.count == 1 {
+            return Ok(());
+        }
+        let delimited = if *printed {
+            delimit.between()
+        } else {
+            delimit.before_first()
+        };
+        if delimited {
+            out.write_all(b"\n")?;
+        }
+        for line in &group.lines {
+            out.write_all(line)?;
+            out.write_all(b"\n")?;
+        }
+        *printed = true;
+        return Ok(());
+    }
+    let count = group.count;
+    if (args.repeated && count == 1) || (args.unique && count > 1) {
+        return Ok(());
+    }
+    if args.count {
+        write!(out, "{:>7} ", count)?;
+    }
+    out.write_all(&group.lines[0])?;
+    out.write_all(b"\n")
+}
+
+fn uniq(input: &mut dyn BufRead, args: &Args, out: &mut dyn Write) -> io::Result<()> {
+    let keep_all = args.all_repeated.is_some() || args.group.is_some();
+    let mut current: Option<Group> = None;
+    let mut printed = false;
+    for line in lines(input) {
+        let mut line = line?;
+        if line.last() == Some(&b'\n') {
+            line.pop();
+        }
+        match &mut current {
+            Some(group) if same(&group.lines[0], &line, args) => {
+                group.count += 1;
+                if keep_all {
+                    group.lines.push(line);
+                }
+            }
+            _ => {
+                if let Some(group) = &current {
+                    emit(group, args, &mut printed, out)?;
+                }
+                current = Some(Group {
+                    lines: vec![line],
+                    count: 1,
+                });
+            }
+        }
+    }
+    if let Some(group) = &current {
+        emit(group, args, &mut printed, out)?;
+    }
+    if printed && args.group.is_some_and(Delimit::after_last) {
+        out.write_all(b"\n")?;
+    }
+    Ok(())
+}
+
+fn main() {
+    if let Err(e) = run() {
+        exit_with(&e);
+    }
+}
+
+fn run() -> Result<(), UniqError> {
+    let args = args::parse(env::args().skip(1).collect())?;
+
+    let mut input = open_input(&args.input).map_err(|e| format!("{}: {}", args.input, e))?;
+
+    let stdout = io::stdout();
+    let mut out: Box<dyn Write> = match &args.output {
+        Some(output) if output != "-" => {
+            let file = File::create(output).map_err(|e| format!("{}: {}", output, e))?;
+            Box::new(BufWriter::new(file))
+        }
+        _ => Box::new(BufWriter::new(stdout.lock())),
+    };
+
+    uniq(&mut input, &args, &mut out)?;
+    out.flush()?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args() -> Args {
+        args::parse(vec![]).unwrap()
+    }
+
+    #[test]
+    fn key_skips_fields_then_chars() {
+        let mut args = args();
+        args.skip_fields = 1;
+        assert_eq!(key(b"  one two", &args), b" two");
+        args.skip_chars = 2;
+        assert_eq!(key(b"one two", &args), b"wo");
+        args.skip_fields = 5;
+        assert_eq!(key(b"one two", &args), b"");
+    }
+}
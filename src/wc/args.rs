@@ -0,0 +1,109 @@
+use crate::WcError;
+use arglex::lex;
+use arglex::Arg;
+use coreutils_common::print_and_exit;
+use coreutils_common::unknown_arg_message;
+use coreutils_common::version_string;
+use std::slice::Iter;
+
+const HELP: &str = "
+Usage: wc [option]... [FILE]...
+Print newline, word, and byte counts for each FILE, and a total line if more than one FILE is
+specified. A word is a non-empty sequence of characters delimited by white space.
+
+With no FILE, or when FILE is -, read standard input.
+
+The options below may be used to select which counts are printed, always in the following order:
+newline, word, character, byte.
+
+Options:
+  -c, --bytes               Print the byte counts
+  --files0-from <F>         Read the files to count from the NUL-separated names in F;
+                            if F is - then read names from standard input
+  -m, --chars               Print the character counts
+  -l, --lines               Print the newline counts
+  -w, --words               Print the word counts
+  --version                 Output version information and exit
+  --help                    Display this help and exit
+";
+
+fn print_help() -> ! {
+    print_and_exit(0, HELP);
+}
+
+fn print_version() -> ! {
+    print_and_exit(0, version_string!("wc"));
+}
+
+fn get_arg_to(args: &mut Iter<Arg>, name: &Arg) -> Result<String, WcError> {
+    match args.next() {
+        Some(Arg::Positional(value)) => Ok(value.clone()),
+        _ => Err(format!("{} needs an argument", name).into()),
+    }
+}
+
+pub struct Args {
+    pub bytes: bool,
+    pub chars: bool,
+    pub lines: bool,
+    pub words: bool,
+    pub files: Vec<String>,
+    pub files0_from: Option<String>,
+}
+
+impl Args {
+    fn new() -> Self {
+        Args {
+            bytes: false,
+            chars: false,
+            lines: false,
+            words: false,
+            files: vec![],
+            files0_from: None,
+        }
+    }
+}
+
+pub fn parse(args: Vec<String>) -> Result<Args, WcError> {
+    let mut arg_struct = Args::new();
+    let args = lex(args);
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg {
+            Arg::Positional(positional) => match positional.as_str() {
+                "--" => continue,
+                _ => arg_struct.files.push(positional.clone()),
+            },
+            Arg::Short(short) => match short.as_str() {
+                "c" => arg_struct.bytes = true,
+                "m" => arg_struct.chars = true,
+                "l" => arg_struct.lines = true,
+                "w" => arg_struct.words = true,
+                _ => return Err(unknown_arg_message(arg).into()),
+            },
+            Arg::Long(long) => match long.as_str() {
+                "bytes" => arg_struct.bytes = true,
+                "chars" => arg_struct.chars = true,
+                "files0-from" => arg_struct.files0_from = Some(get_arg_to(&mut args, arg)?),
+                "lines" => arg_struct.lines = true,
+                "words" => arg_struct.words = true,
+                "version" => print_version(),
+                "help" => print_help(),
+                _ => return Err(unknown_arg_message(arg).into()),
+            },
+        };
+    }
+    if let (Some(_), Some(file)) = (&arg_struct.files0_from, arg_struct.files.first()) {
+        return Err(format!(
+            "extra operand '{}'\nfile operands cannot be combined with --files0-from",
+            file
+        )
+        .into());
+    }
+    if !(arg_struct.bytes || arg_struct.chars || arg_struct.lines || arg_struct.words) {
+        arg_struct.lines = true;
+        arg_struct.words = true;
+        arg_struct.bytes = true;
+    }
+    Ok(arg_struct)
+}
@@ -0,0 +1,196 @@
+mod args;
+
+use args::Args;
+use coreutils_common::exit_with;
+use coreutils_common::input::open_input;
+use coreutils_common::input::read_names;
+use coreutils_common::report_error;
+use coreutils_common::CoreError;
+use std::env;
+use std::ffi::OsString;
+use std::fmt;
+use std::fmt::Debug;
+use std::io;
+use std::io::BufRead;
+use std::ops::AddAssign;
+use std::process::exit;
+
+pub struct WcError {
+    message: String,
+}
+
+impl Debug for WcError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "wc: {}", self.message)
+    }
+}
+
+impl CoreError for WcError {}
+
+impl<T> From<T> for WcError
+where
+    T: ToString,
+{
+    fn from(error: T) -> Self {
+        WcError {
+            message: error.to_string(),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Default)]
+struct Counts {
+    lines: u64,
+    words: u64,
+    chars: u64,
+    bytes: u64,
+}
+
+impl AddAssign for Counts {
+    fn add_assign(&mut self, other: Counts) {
+        self.lines += other.lines;
+        self.words += other.words;
+        self.chars += other.chars;
+        self.bytes += other.bytes;
+    }
+}
+
+/// Count `input` a buffer at a time, so the whole input never has to be in memory.
+fn count(input: &mut dyn BufRead) -> io::Result<Counts> {
+    let mut counts = Counts::default();
+    let mut in_word = false;
+    loop {
+        let buf = input.fill_buf()?;
+        if buf.is_empty() {
+            return Ok(counts);
+        }
+        for &byte in buf {
+            if byte == b'\n' {
+                counts.lines += 1;
+            }
+            // Every byte except UTF-8 continuation bytes starts a character
+            if byte & 0xc0 != 0x80 {
+                counts.chars += 1;
+            }
+            if byte.is_ascii_whitespace() || byte == 0x0b {
+                in_word = false;
+            } else if !in_word {
+                in_word = true;
+                counts.words += 1;
+            }
+        }
+        let len = buf.len();
+        counts.bytes += len as u64;
+        input.consume(len);
+    }
+}
+
+fn format_counts(counts: &Counts, args: &Args, width: usize, name: Option<&str>) -> String {
+    let mut columns = vec![];
+    if args.lines {
+        columns.push(counts.lines);
+    }
+    if args.words {
+        columns.push(counts.words);
+    }
+    if args.chars {
+        columns.push(counts.chars);
+    }
+    if args.bytes {
+        columns.push(counts.bytes);
+    }
+    let mut line = columns
+        .iter()
+        .map(|count| format!("{:>width$}", count, width = width))
+        .collect::<Vec<_>>()
+        .join(" ");
+    if let Some(name) = name {
+        line.push(' ');
+        line.push_str(name);
+    }
+    line
+}
+
+/// The NUL-separated file names in `source`, or in stdin if it is `-`.
+fn read_file_list(source: &str) -> Result<Vec<OsString>, WcError> {
+    let mut input = open_input(source).map_err(|e| format!("cannot open '{}': {}", source, e))?;
+    let names = read_names(&mut input, b'\0').map_err(|e| format!("{}: {}", source, e))?;
+    if source == "-" && names.iter().any(|name| name == "-") {
+        return Err("when reading file names from stdin, no file name of '-' allowed".into());
+    }
+    Ok(names)
+}
+
+fn main() {
+    if let Err(e) = run() {
+        exit_with(&e);
+    }
+}
+
+fn run() -> Result<(), WcError> {
+    let args = args::parse(env::args().skip(1).collect())?;
+    let files = match &args.files0_from {
+        Some(source) => read_file_list(source)?,
+        None => args.files.iter().map(OsString::from).collect(),
+    };
+    let named = !files.is_empty() || args.files0_from.is_some();
+    let files = if named {
+        files
+    } else {
+        vec![OsString::from("-")]
+    };
+
+    let mut results = vec![];
+    let mut total = Counts::default();
+    let mut failed = false;
+    for (i, file) in files.iter().enumerate() {
+        if let (true, Some(source)) = (file.is_empty(), &args.files0_from) {
+            report_error(
+                "wc",
+                format!("{}:{}: invalid zero-length file name", source, i + 1),
+            );
+            failed = true;
+            continue;
+        }
+        let counted = open_input(file).and_then(|mut input| count(&mut input));
+        match counted {
+            Ok(counts) => {
+                total += counts;
+                results.push((file, counts));
+            }
+            Err(e) => {
+                report_error("wc", format!("{}: {}", file.to_string_lossy(), e));
+                failed = true;
+            }
+        }
+    }
+
+    // Like GNU wc, size the columns for the total byte count, leaving extra room when reading
+    // from stdin since its size can't be known up front
+    let columns = [args.lines, args.words, args.chars, args.bytes]
+        .iter()
+        .filter(|&&c| c)
+        .count();
+    let width = if columns == 1 && files.len() == 1 {
+        1
+    } else {
+        let digits = total.bytes.to_string().len();
+        if files.iter().any(|f| f == "-") {
+            digits.max(7)
+        } else {
+            digits
+        }
+    };
+    for (file, counts) in &results {
+        let name = file.to_string_lossy();
+        let name = if named { Some(name.as_ref()) } else { None };
+        println!("{}", format_counts(counts, &args, width, name));
+    }
+    if files.len() > 1 {
+        println!("{}", format_counts(&total, &args, width, Some("total")));
+    }
+    if failed {
+        exit(1);
+    }
+    Ok(())
+}
@@ -0,0 +1,49 @@
+use arglex::lex;
+use arglex::Arg;
+use coreutils_common::die;
+use coreutils_common::print_and_exit;
+use coreutils_common::unknown_arg_message;
+use coreutils_common::users::user_name;
+use coreutils_common::version_string;
+use std::env;
+
+const HELP: &str = "
+Usage: whoami [OPTION]...
+Print the user name associated with the current effective user ID.
+
+Options:
+  --version                 Output version information and exit
+  --help                    Display this help and exit
+";
+
+fn fail(message: impl AsRef<str>) -> ! {
+    die(format!("whoami: {}", message.as_ref()));
+}
+
+/// whoami takes no operands, so every argument but `--help`, `--version` and `--` is an error.
+fn parse(args: Vec<String>) {
+    let args = lex(args);
+    let first = args
+        .iter()
+        .find(|arg| !matches!(arg, Arg::Positional(p) if p == "--"));
+    if let Some(arg) = first {
+        match arg {
+            Arg::Long(long) => match long.as_str() {
+                "version" => print_and_exit(0, version_string!("whoami")),
+                "help" => print_and_exit(0, HELP),
+                _ => fail(unknown_arg_message(arg)),
+            },
+            Arg::Short(_) => fail(unknown_arg_message(arg)),
+            Arg::Positional(positional) => fail(format!("extra operand '{}'", positional)),
+        }
+    }
+}
+
+fn main() {
+    parse(env::args().skip(1).collect());
+    let uid = unsafe { libc::geteuid() };
+    match user_name(uid) {
+        Some(name) => println!("{}", name),
+        None => fail(format!("cannot find name for user ID {}", uid)),
+    }
+}
@@ -0,0 +1,80 @@
+use arglex::lex;
+use arglex::Arg;
+use coreutils_common::die;
+use coreutils_common::print_and_exit;
+use coreutils_common::restore_sigpipe;
+use coreutils_common::version_string;
+use std::env;
+use std::io;
+use std::io::Write;
+
+const HELP: &str = "
+Usage: yes [STRING]...
+  or:  yes OPTION
+Repeatedly output a line with all specified STRING(s), or 'y'.
+
+Options:
+  --version                 Output version information and exit
+  --help                    Display this help and exit
+";
+
+const BUFFER_SIZE: usize = 8 * 1024;
+
+/// As many whole copies of `line` as fit in `BUFFER_SIZE` (at least one), so each write
+/// syscall carries a full buffer.
+fn fill_buffer(line: &[u8]) -> Vec<u8> {
+    let copies = (BUFFER_SIZE / line.len()).max(1);
+    line.repeat(copies)
+}
+
+fn main() {
+    restore_sigpipe();
+    let raw_args: Vec<String> = env::args().skip(1).collect();
+    for arg in lex(raw_args.clone()) {
+        match arg {
+            Arg::Positional(positional) if positional == "--" => break,
+            Arg::Long(long) if long == "help" => print_and_exit(0, HELP),
+            Arg::Long(long) if long == "version" => print_and_exit(0, version_string!("yes")),
+            _ => {}
+        }
+    }
+    let strings = match raw_args.first() {
+        Some(first) if first == "--" => &raw_args[1..],
+        _ => &raw_args[..],
+    };
+    let mut line = if strings.is_empty() {
+        "y".to_owned()
+    } else {
+        strings.join(" ")
+    };
+    line.push('\n');
+
+    let buffer = fill_buffer(line.as_bytes());
+    let stdout = io::stdout();
+    let mut stdout = stdout.lock();
+    loop {
+        if let Err(e) = stdout.write_all(&buffer) {
+            die(format!("yes: standard output: {}", e));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn buffer_holds_whole_lines() {
+        let buffer = fill_buffer(b"y\n");
+        assert_eq!(buffer.len(), BUFFER_SIZE);
+        let buffer = fill_buffer(b"abc\n");
+        assert!(buffer.len() <= BUFFER_SIZE);
+        assert_eq!(buffer.len() % 4, 0);
+    }
+
+    #[test]
+    fn buffer_fits_long_lines() {
+        let line = vec![b'x'; BUFFER_SIZE * 2];
+        assert_eq!(fill_buffer(&line), line);
+    }
+}
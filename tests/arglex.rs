@@ -0,0 +1,201 @@
+use arglex::lex;
+use arglex::lex_clustered;
+use arglex::lex_clustered_with_negatives;
+use arglex::lex_one;
+use arglex::lex_with_negatives;
+use arglex::Arg;
+use proptest::prelude::*;
+
+fn raw_arg() -> impl Strategy<Value = String> {
+    prop_oneof![
+        any::<String>(),
+        any::<String>().prop_map(|s| format!("-{}", s)),
+        any::<String>().prop_map(|s| format!("--{}", s)),
+        Just("--".to_owned()),
+        Just("-".to_owned()),
+    ]
+}
+
+fn args(raw: &[&str]) -> Vec<Arg> {
+    lex(raw.iter().map(|s| s.to_string()).collect())
+}
+
+fn rendered(args: &[Arg]) -> Vec<String> {
+    args.iter()
+        .map(|arg| match arg {
+            Arg::Positional(p) => format!("P:{}", p),
+            Arg::Short(s) => format!("S:{}", s),
+            Arg::Long(l) => format!("L:{}", l),
+        })
+        .collect()
+}
+
+proptest! {
+    #[test]
+    fn long_names_are_never_empty(raw in prop::collection::vec(raw_arg(), 0..16)) {
+        for arg in lex(raw) {
+            if let Arg::Long(name) = arg {
+                prop_assert!(!name.is_empty());
+            }
+        }
+    }
+
+    #[test]
+    fn short_names_are_one_char(raw in prop::collection::vec(raw_arg(), 0..16)) {
+        for arg in lex(raw) {
+            if let Arg::Short(name) = arg {
+                prop_assert_eq!(name.chars().count(), 1);
+            }
+        }
+    }
+
+    #[test]
+    fn everything_after_delimiter_is_positional(
+        before in prop::collection::vec(raw_arg(), 0..8),
+        after in prop::collection::vec(raw_arg(), 0..8),
+    ) {
+        let prefix = before
+            .iter()
+            .position(|arg| arg == "--")
+            .map_or(&before[..], |i| &before[..i]);
+        let mut raw = prefix.to_vec();
+        raw.push("--".to_owned());
+        raw.extend(after.iter().cloned());
+
+        let lexed = lex(raw);
+        let split = lex(prefix.to_vec()).len();
+        let mut expected = vec!["--".to_owned()];
+        expected.extend(after.iter().cloned());
+        prop_assert_eq!(rendered(&lexed[split..]), rendered(
+            &expected.into_iter().map(Arg::Positional).collect::<Vec<_>>()
+        ));
+    }
+}
+
+#[test]
+fn short_option_with_multibyte_name() {
+    assert_eq!(rendered(&args(&["-é"])), ["S:é"]);
+    assert_eq!(rendered(&args(&["-éa"])), ["S:é", "P:a"]);
+}
+
+#[test]
+fn equals_without_a_name_is_not_split() {
+    assert_eq!(rendered(&args(&["--=value"])), ["L:=value"]);
+    assert_eq!(rendered(&args(&["--date=now"])), ["L:date", "P:now"]);
+}
+
+#[test]
+fn negative_numbers_are_short_options_by_default() {
+    assert_eq!(rendered(&args(&["-5"])), ["S:5"]);
+}
+
+#[test]
+fn negative_numbers_can_be_positional() {
+    let lexed = lex_with_negatives(
+        ["-n", "-5", "-2.5", "-x1", "-", "--", "-3"]
+            .iter()
+            .map(|s| s.to_string())
+            .collect(),
+    );
+    assert_eq!(
+        rendered(&lexed),
+        ["S:n", "P:-5", "P:-2.5", "S:x", "P:1", "P:-", "P:--", "P:-3"]
+    );
+}
+
+fn clustered(raw: &[&str], with_values: &str) -> Vec<String> {
+    rendered(&lex_clustered(
+        raw.iter().map(|s| s.to_string()).collect(),
+        with_values,
+    ))
+}
+
+#[test]
+fn clusters_split_into_short_options() {
+    assert_eq!(clustered(&["-rf", "dir"], ""), ["S:r", "S:f", "P:dir"]);
+    assert_eq!(clustered(&["-é-a"], ""), ["S:é", "S:-", "S:a"]);
+    assert_eq!(clustered(&["-", "--", "-rf"], ""), ["P:-", "P:--", "P:-rf"]);
+    assert_eq!(clustered(&["--all=x", "-x"], ""), ["L:all", "P:x", "S:x"]);
+}
+
+#[test]
+fn options_with_values_end_a_cluster() {
+    assert_eq!(clustered(&["-qn5"], "n"), ["S:q", "S:n", "P:5"]);
+    assert_eq!(clustered(&["-n5q"], "n"), ["S:n", "P:5q"]);
+    assert_eq!(clustered(&["-qn", "5"], "n"), ["S:q", "S:n", "P:5"]);
+    assert_eq!(clustered(&["-rk2n"], "kt"), ["S:r", "S:k", "P:2n"]);
+    let lexed = lex_clustered_with_negatives(vec!["-qn".to_owned(), "-5".to_owned()], "n");
+    assert_eq!(rendered(&lexed), ["S:q", "S:n", "P:-5"]);
+}
+
+#[test]
+fn one_argument_with_its_attached_value() {
+    let (lexed, attached) = lex_one("-lw80".to_owned(), "w");
+    assert_eq!(rendered(&lexed), ["S:l", "S:w"]);
+    assert_eq!(attached.as_deref(), Some("80"));
+    let (lexed, attached) = lex_one("--color=never".to_owned(), "");
+    assert_eq!(rendered(&lexed), ["L:color"]);
+    assert_eq!(attached.as_deref(), Some("never"));
+    let (lexed, attached) = lex_one("file".to_owned(), "");
+    assert_eq!(rendered(&lexed), ["P:file"]);
+    assert_eq!(attached, None);
+}
+
+/// The original, allocate-per-name lexer, kept to check the in-place one against.
+fn reference_lex(raw_args: Vec<String>) -> Vec<String> {
+    let mut args = vec![];
+    let mut delimited = false;
+    for raw_arg in raw_args {
+        if delimited {
+            args.push(format!("P:{}", raw_arg));
+        } else if let Some(long) = raw_arg.strip_prefix("--") {
+            if long.is_empty() {
+                delimited = true;
+                args.push(format!("P:{}", raw_arg));
+            } else if let Some(i) = long.find('=').filter(|&i| i > 0) {
+                args.push(format!("L:{}", &long[..i]));
+                args.push(format!("P:{}", &long[i + 1..]));
+            } else {
+                args.push(format!("L:{}", long));
+            }
+        } else if let Some(short) = raw_arg.strip_prefix('-') {
+            let mut chars = short.chars();
+            if let Some(name) = chars.next() {
+                args.push(format!("S:{}", name));
+                if !chars.as_str().is_empty() {
+                    args.push(format!("P:{}", chars.as_str()));
+                }
+            } else {
+                args.push(format!("P:{}", raw_arg));
+            }
+        } else {
+            args.push(format!("P:{}", raw_arg));
+        }
+    }
+    args
+}
+
+proptest! {
+    #[test]
+    fn clusters_keep_every_letter(raw in prop::collection::vec(raw_arg(), 0..16)) {
+        for arg in lex_clustered(raw, "") {
+            if let Arg::Short(name) = arg {
+                prop_assert_eq!(name.chars().count(), 1);
+            }
+        }
+    }
+
+    #[test]
+    fn output_matches_reference(raw in prop::collection::vec(raw_arg(), 0..16)) {
+        prop_assert_eq!(rendered(&lex(raw.clone())), reference_lex(raw));
+    }
+}
+
+#[test]
+fn attached_values_match_reference() {
+    let raw: Vec<String> = ["--a=b=c", "--x=", "-é5", "-", "--", "--y=z", "-q"]
+        .iter()
+        .map(|s| s.to_string())
+        .collect();
+    assert_eq!(rendered(&lex(raw.clone())), reference_lex(raw));
+}
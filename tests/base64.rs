@@ -0,0 +1,68 @@
+mod common;
+
+use common::run_with_stdin;
+use common::TempDir;
+use std::fs;
+use std::process::Output;
+
+fn base64(dir: &TempDir, args: &[&str], stdin: &[u8]) -> Output {
+    run_with_stdin(env!("CARGO_BIN_EXE_base64"), dir.path(), args, stdin)
+}
+
+#[test]
+fn encode() {
+    let dir = TempDir::new();
+    let output = base64(&dir, &[], b"hello world\n");
+    assert!(output.status.success());
+    assert_eq!(
+        String::from_utf8_lossy(&output.stdout),
+        "aGVsbG8gd29ybGQK\n"
+    );
+}
+
+#[test]
+fn encode_file_with_default_wrap() {
+    let dir = TempDir::new();
+    fs::write(dir.join("data"), vec![0u8; 60]).unwrap();
+    let output = base64(&dir, &["data"], b"");
+    assert_eq!(
+        String::from_utf8_lossy(&output.stdout),
+        format!("{}\n{}\n", "A".repeat(76), "A".repeat(4))
+    );
+}
+
+#[test]
+fn custom_wrap() {
+    let dir = TempDir::new();
+    let output = base64(&dir, &["-w", "6"], b"hello world\n");
+    assert_eq!(
+        String::from_utf8_lossy(&output.stdout),
+        "aGVsbG\n8gd29y\nbGQK\n"
+    );
+    let output = base64(&dir, &["-w", "0"], b"hi");
+    assert_eq!(String::from_utf8_lossy(&output.stdout), "aGk=");
+}
+
+#[test]
+fn decode() {
+    let dir = TempDir::new();
+    let output = base64(&dir, &["-d"], b"aGVsbG\n8gd29y\nbGQK\n");
+    assert!(output.status.success());
+    assert_eq!(String::from_utf8_lossy(&output.stdout), "hello world\n");
+}
+
+#[test]
+fn decode_garbage() {
+    let dir = TempDir::new();
+    let output = base64(&dir, &["-d"], b"aGk=!\n");
+    assert_eq!(output.status.code(), Some(1));
+    assert_eq!(String::from_utf8_lossy(&output.stdout), "hi");
+    assert_eq!(
+        String::from_utf8_lossy(&output.stderr),
+        "base64: invalid input\n"
+    );
+
+    let output = base64(&dir, &["-d", "-i"], b"a!G k=\n");
+    assert!(output.status.success());
+    assert_eq!(String::from_utf8_lossy(&output.stdout), "hi");
+}
@@ -0,0 +1,66 @@
+use std::process::Command;
+use std::process::Output;
+
+fn basename(args: &[&str]) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_basename"))
+        .args(args)
+        .output()
+        .unwrap()
+}
+
+fn stdout(args: &[&str]) -> String {
+    let output = basename(args);
+    assert!(output.status.success(), "{:?}", output);
+    String::from_utf8(output.stdout).unwrap()
+}
+
+#[test]
+fn single_name() {
+    assert_eq!(stdout(&["/usr/bin/sort"]), "sort\n");
+    assert_eq!(stdout(&["/usr/bin/"]), "bin\n");
+}
+
+#[test]
+fn suffix_operand() {
+    assert_eq!(stdout(&["include/stdio.h", ".h"]), "stdio\n");
+    assert_eq!(stdout(&[".h", ".h"]), ".h\n");
+}
+
+#[test]
+fn multiple_names() {
+    assert_eq!(stdout(&["-a", "/a/b", "c/d/"]), "b\nd\n");
+    assert_eq!(
+        stdout(&["-s", ".rs", "src/main.rs", "lib.rs"]),
+        "main\nlib\n"
+    );
+}
+
+#[test]
+fn nul_terminated() {
+    assert_eq!(stdout(&["-z", "-a", "/a/b", "c"]), "b\0c\0");
+}
+
+#[test]
+fn names_after_delimiter() {
+    assert_eq!(stdout(&["--", "-z"]), "-z\n");
+}
+
+#[test]
+fn operand_errors() {
+    let output = basename(&[]);
+    assert_eq!(output.status.code(), Some(1));
+    assert_eq!(
+        String::from_utf8_lossy(&output.stderr),
+        "basename: missing operand\n"
+    );
+    let output = basename(&["a", "b", "c"]);
+    assert_eq!(
+        String::from_utf8_lossy(&output.stderr),
+        "basename: extra operand c\n"
+    );
+}
+
+#[test]
+fn zero_with_a_newline_in_a_name() {
+    assert_eq!(stdout(&["--zero", "a/b\nc"]), "b\nc\0");
+}
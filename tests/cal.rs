@@ -0,0 +1,69 @@
+mod common;
+
+use common::stdout;
+use std::process::Command;
+use std::process::Output;
+
+fn cal(args: &[&str]) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_cal"))
+        .args(args)
+        .output()
+        .unwrap()
+}
+
+#[test]
+fn sunday_first_month() {
+    let output = cal(&["2", "2000"]);
+    assert!(output.status.success());
+    assert_eq!(
+        stdout(&output),
+        "   February 2000\n\
+         Su Mo Tu We Th Fr Sa\n       \
+         1  2  3  4  5\n \
+         6  7  8  9 10 11 12\n\
+         13 14 15 16 17 18 19\n\
+         20 21 22 23 24 25 26\n\
+         27 28 29\n"
+    );
+}
+
+#[test]
+fn monday_first_month() {
+    let output = cal(&["-m", "2", "2000"]);
+    assert!(output.status.success());
+    assert_eq!(
+        stdout(&output),
+        "   February 2000\n\
+         Mo Tu We Th Fr Sa Su\n    \
+         1  2  3  4  5  6\n \
+         7  8  9 10 11 12 13\n\
+         14 15 16 17 18 19 20\n\
+         21 22 23 24 25 26 27\n\
+         28 29\n"
+    );
+}
+
+#[test]
+fn whole_year() {
+    let output = cal(&["2000"]);
+    assert!(output.status.success());
+    let printed = stdout(&output);
+    let lines: Vec<&str> = printed.lines().collect();
+    assert_eq!(lines.len(), 1 + 4 * 9);
+    assert_eq!(lines[0], format!("{:30}2000", ""));
+    assert_eq!(
+        lines[2],
+        "      January               February               March"
+    );
+    assert_eq!(
+        lines[4],
+        "                   1         1  2  3  4  5            1  2  3  4"
+    );
+    assert_eq!(cal(&["-y", "2000"]).stdout, output.stdout);
+}
+
+#[test]
+fn rejects_bad_operands() {
+    assert!(!cal(&["13", "2000"]).status.success());
+    assert!(!cal(&["1", "10000"]).status.success());
+}
@@ -0,0 +1,105 @@
+mod common;
+
+use common::run_with_stdin;
+use common::stdout;
+use common::TempDir;
+use std::fs;
+use std::process::Output;
+
+fn cat(dir: &TempDir, args: &[&str], stdin: &str) -> Output {
+    run_with_stdin(
+        env!("CARGO_BIN_EXE_cat"),
+        dir.path(),
+        args,
+        stdin.as_bytes(),
+    )
+}
+
+#[test]
+fn concatenates_files() {
+    let dir = TempDir::new();
+    fs::write(dir.join("a"), "one\n").unwrap();
+    fs::write(dir.join("b"), "two\n").unwrap();
+    let output = cat(&dir, &["a", "b"], "");
+    assert!(output.status.success());
+    assert_eq!(stdout(&output), "one\ntwo\n");
+}
+
+#[test]
+fn reads_stdin_without_files() {
+    let dir = TempDir::new();
+    let output = cat(&dir, &[], "piped\n");
+    assert_eq!(stdout(&output), "piped\n");
+}
+
+#[test]
+fn dash_reads_stdin_between_files() {
+    let dir = TempDir::new();
+    fs::write(dir.join("a"), "a\n").unwrap();
+    let output = cat(&dir, &["a", "-", "a"], "stdin\n");
+    assert_eq!(stdout(&output), "a\nstdin\na\n");
+}
+
+#[test]
+fn number_all_lines() {
+    let dir = TempDir::new();
+    let output = cat(&dir, &["-n"], "a\n\nb\n");
+    assert_eq!(stdout(&output), "     1\ta\n     2\t\n     3\tb\n");
+}
+
+#[test]
+fn number_continues_across_files() {
+    let dir = TempDir::new();
+    fs::write(dir.join("a"), "a\n").unwrap();
+    let output = cat(&dir, &["--number", "a", "a"], "");
+    assert_eq!(stdout(&output), "     1\ta\n     2\ta\n");
+}
+
+#[test]
+fn number_nonblank_overrides_number() {
+    let dir = TempDir::new();
+    let output = cat(&dir, &["-n", "-b"], "a\n\nb");
+    assert_eq!(stdout(&output), "     1\ta\n\n     2\tb");
+}
+
+#[test]
+fn squeeze_blank_and_show_ends() {
+    let dir = TempDir::new();
+    let output = cat(&dir, &["-s", "-E"], "a\n\n\n\nb\n");
+    assert_eq!(stdout(&output), "a$\n$\nb$\n");
+}
+
+#[test]
+fn missing_file_is_reported_and_skipped() {
+    let dir = TempDir::new();
+    fs::write(dir.join("a"), "a\n").unwrap();
+    let output = cat(&dir, &["missing", "a"], "");
+    assert_eq!(output.status.code(), Some(1));
+    assert_eq!(stdout(&output), "a\n");
+    assert!(String::from_utf8_lossy(&output.stderr).starts_with("cat: missing: "));
+}
+
+#[test]
+fn unknown_flag_is_an_error() {
+    let dir = TempDir::new();
+    let output = cat(&dir, &["--bogus"], "");
+    assert_eq!(output.status.code(), Some(1));
+    assert!(String::from_utf8_lossy(&output.stderr).contains("unknown argument --bogus"));
+}
+
+#[test]
+fn show_nonprinting_uses_caret_and_meta_notation() {
+    let dir = TempDir::new();
+    let input = b"a\x01\x1b\x7f\tb\n\x80\xff\xe9\n";
+    let output = run_with_stdin(env!("CARGO_BIN_EXE_cat"), dir.path(), &["-v"], input);
+    assert_eq!(stdout(&output), "a^A^[^?\tb\nM-^@M-^?M-i\n");
+}
+
+#[test]
+fn show_tabs_and_show_all() {
+    let dir = TempDir::new();
+    assert_eq!(stdout(&cat(&dir, &["-T"], "a\tb\x01\n")), "a^Ib\x01\n");
+    assert_eq!(stdout(&cat(&dir, &["-A"], "a\tb\x01\n")), "a^Ib^A$\n");
+    assert_eq!(stdout(&cat(&dir, &["-e"], "a\tb\x01\n")), "a\tb^A$\n");
+    assert_eq!(stdout(&cat(&dir, &["-t"], "a\tb\x01\n")), "a^Ib^A\n");
+}
@@ -0,0 +1,85 @@
+mod common;
+
+use common::run_with_stdin;
+use common::TempDir;
+use std::fs;
+use std::os::unix::fs::PermissionsExt;
+use std::path::Path;
+use std::process::Output;
+
+fn chmod(dir: &TempDir, args: &[&str]) -> Output {
+    run_with_stdin(env!("CARGO_BIN_EXE_chmod"), dir.path(), args, b"")
+}
+
+fn mode<P: AsRef<Path>>(path: P) -> u32 {
+    fs::metadata(path).unwrap().permissions().mode() & 0o7777
+}
+
+fn file_with_mode(dir: &TempDir, name: &str, mode: u32) {
+    fs::write(dir.join(name), "").unwrap();
+    fs::set_permissions(dir.join(name), fs::Permissions::from_mode(mode)).unwrap();
+}
+
+#[test]
+fn octal_mode() {
+    let dir = TempDir::new();
+    file_with_mode(&dir, "f", 0o600);
+    let output = chmod(&dir, &["751", "f"]);
+    assert!(output.status.success());
+    assert_eq!(mode(dir.join("f")), 0o751);
+}
+
+#[test]
+fn symbolic_modes() {
+    let dir = TempDir::new();
+    file_with_mode(&dir, "f", 0o666);
+    assert!(chmod(&dir, &["u+x,go-w", "f"]).status.success());
+    assert_eq!(mode(dir.join("f")), 0o744);
+    assert!(chmod(&dir, &["a=r", "f"]).status.success());
+    assert_eq!(mode(dir.join("f")), 0o444);
+    assert!(chmod(&dir, &["-r", "f"]).status.success());
+    assert_eq!(mode(dir.join("f")), 0o000);
+}
+
+#[test]
+fn recursive() {
+    let dir = TempDir::new();
+    fs::create_dir_all(dir.join("d/sub")).unwrap();
+    file_with_mode(&dir, "d/a", 0o600);
+    file_with_mode(&dir, "d/sub/b", 0o600);
+    let output = chmod(&dir, &["-R", "go+rX", "d"]);
+    assert!(output.status.success());
+    assert_eq!(mode(dir.join("d/a")), 0o644);
+    assert_eq!(mode(dir.join("d/sub/b")), 0o644);
+    assert_eq!(mode(dir.join("d/sub")) & 0o055, 0o055);
+}
+
+#[test]
+fn verbose() {
+    let dir = TempDir::new();
+    file_with_mode(&dir, "f", 0o644);
+    let output = chmod(&dir, &["-v", "755", "f"]);
+    assert_eq!(
+        String::from_utf8_lossy(&output.stdout),
+        "mode of 'f' changed from 0644 (rw-r--r--) to 0755 (rwxr-xr-x)\n"
+    );
+    let output = chmod(&dir, &["--verbose", "755", "f"]);
+    assert_eq!(
+        String::from_utf8_lossy(&output.stdout),
+        "mode of 'f' retained as 0755 (rwxr-xr-x)\n"
+    );
+}
+
+#[test]
+fn errors() {
+    let dir = TempDir::new();
+    let output = chmod(&dir, &["u+q", "f"]);
+    assert_eq!(output.status.code(), Some(1));
+    assert_eq!(
+        String::from_utf8_lossy(&output.stderr),
+        "chmod: invalid mode: 'u+q'\n"
+    );
+    let output = chmod(&dir, &["644", "missing"]);
+    assert_eq!(output.status.code(), Some(1));
+    assert!(String::from_utf8_lossy(&output.stderr).starts_with("chmod: cannot access 'missing': "));
+}
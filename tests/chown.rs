@@ -0,0 +1,102 @@
+mod common;
+
+use common::run_with_stdin;
+use common::TempDir;
+use std::fs;
+use std::os::unix::fs::symlink;
+use std::os::unix::fs::MetadataExt;
+use std::path::Path;
+use std::process::Output;
+
+fn chown(dir: &TempDir, args: &[&str]) -> Output {
+    run_with_stdin(env!("CARGO_BIN_EXE_chown"), dir.path(), args, b"")
+}
+
+/// Changing ownership needs root; everywhere else these tests pass vacuously.
+fn is_root() -> bool {
+    unsafe { libc::geteuid() == 0 }
+}
+
+fn owner<P: AsRef<Path>>(path: P) -> (u32, u32) {
+    let metadata = fs::symlink_metadata(path).unwrap();
+    (metadata.uid(), metadata.gid())
+}
+
+#[test]
+fn owner_and_group() {
+    if !is_root() {
+        return;
+    }
+    let dir = TempDir::new();
+    fs::write(dir.join("f"), "").unwrap();
+    let output = chown(&dir, &["1234:5678", "f"]);
+    assert!(output.status.success());
+    assert_eq!(owner(dir.join("f")), (1234, 5678));
+
+    assert!(chown(&dir, &["4321", "f"]).status.success());
+    assert_eq!(owner(dir.join("f")), (4321, 5678));
+    assert!(chown(&dir, &[":8765", "f"]).status.success());
+    assert_eq!(owner(dir.join("f")), (4321, 8765));
+    assert!(chown(&dir, &["root:", "f"]).status.success());
+    assert_eq!(owner(dir.join("f")), (0, 0));
+}
+
+#[test]
+fn recursive() {
+    if !is_root() {
+        return;
+    }
+    let dir = TempDir::new();
+    fs::create_dir_all(dir.join("d/sub")).unwrap();
+    fs::write(dir.join("d/sub/f"), "").unwrap();
+    assert!(chown(&dir, &["-R", "1234:1234", "d"]).status.success());
+    assert_eq!(owner(dir.join("d")), (1234, 1234));
+    assert_eq!(owner(dir.join("d/sub")), (1234, 1234));
+    assert_eq!(owner(dir.join("d/sub/f")), (1234, 1234));
+}
+
+#[test]
+fn symlinks() {
+    if !is_root() {
+        return;
+    }
+    let dir = TempDir::new();
+    fs::write(dir.join("target"), "").unwrap();
+    symlink("target", dir.join("link")).unwrap();
+    assert!(chown(&dir, &["-h", "1234", "link"]).status.success());
+    assert_eq!(owner(dir.join("link")).0, 1234);
+    assert_eq!(owner(dir.join("target")).0, 0);
+    assert!(chown(&dir, &["4321", "link"]).status.success());
+    assert_eq!(owner(dir.join("link")).0, 1234);
+    assert_eq!(owner(dir.join("target")).0, 4321);
+}
+
+#[test]
+fn invalid_user() {
+    let dir = TempDir::new();
+    fs::write(dir.join("f"), "").unwrap();
+    let output = chown(&dir, &["no-such-user-here", "f"]);
+    assert_eq!(output.status.code(), Some(1));
+    assert_eq!(
+        String::from_utf8_lossy(&output.stderr),
+        "chown: invalid user: 'no-such-user-here'\n"
+    );
+}
+
+#[test]
+fn recursive_by_name_applies_to_every_file() {
+    if !is_root() {
+        return;
+    }
+    let dir = TempDir::new();
+    fs::create_dir_all(dir.join("d/a/b")).unwrap();
+    let files = ["d/one", "d/a/two", "d/a/b/three", "d/a/b/four"];
+    for file in &files {
+        fs::write(dir.join(file), "").unwrap();
+    }
+    assert!(chown(&dir, &["-R", "1234:1234", "d"]).status.success());
+    assert!(chown(&dir, &["-R", "root:", "d"]).status.success());
+    for path in files.iter().chain(&["d", "d/a", "d/a/b"]) {
+        assert_eq!(owner(dir.join(path)), (0, 0), "{}", path);
+    }
+}
@@ -0,0 +1,44 @@
+mod common;
+
+use common::run_with_stdin;
+use common::TempDir;
+use std::fs;
+use std::process::Output;
+
+fn cksum(dir: &TempDir, args: &[&str], stdin: &[u8]) -> Output {
+    run_with_stdin(env!("CARGO_BIN_EXE_cksum"), dir.path(), args, stdin)
+}
+
+#[test]
+fn stdin_has_no_name() {
+    let dir = TempDir::new();
+    let output = cksum(&dir, &[], b"hello\n");
+    assert!(output.status.success());
+    assert_eq!(String::from_utf8_lossy(&output.stdout), "3015617425 6\n");
+}
+
+#[test]
+fn files() {
+    let dir = TempDir::new();
+    fs::write(dir.join("digits"), "123456789").unwrap();
+    fs::write(dir.join("empty"), "").unwrap();
+    let output = cksum(&dir, &["digits", "empty", "-"], b"hello\n");
+    assert!(output.status.success());
+    assert_eq!(
+        String::from_utf8_lossy(&output.stdout),
+        "930766865 9 digits\n4294967295 0 empty\n3015617425 6 -\n"
+    );
+}
+
+#[test]
+fn missing_file() {
+    let dir = TempDir::new();
+    fs::write(dir.join("empty"), "").unwrap();
+    let output = cksum(&dir, &["missing", "empty"], b"");
+    assert_eq!(output.status.code(), Some(1));
+    assert_eq!(
+        String::from_utf8_lossy(&output.stdout),
+        "4294967295 0 empty\n"
+    );
+    assert!(String::from_utf8_lossy(&output.stderr).starts_with("cksum: missing: "));
+}
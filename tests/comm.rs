@@ -0,0 +1,72 @@
+mod common;
+
+use common::run_with_stdin;
+use common::stdout;
+use common::TempDir;
+use std::fs;
+use std::process::Output;
+
+fn comm(dir: &TempDir, args: &[&str], stdin: &str) -> Output {
+    run_with_stdin(
+        env!("CARGO_BIN_EXE_comm"),
+        dir.path(),
+        args,
+        stdin.as_bytes(),
+    )
+}
+
+fn files() -> TempDir {
+    let dir = TempDir::new();
+    fs::write(dir.join("a"), "apple\nbanana\ncherry\n").unwrap();
+    fs::write(dir.join("b"), "banana\ncherry\ndate\n").unwrap();
+    dir
+}
+
+#[test]
+fn overlapping() {
+    let dir = files();
+    let output = comm(&dir, &["a", "b"], "");
+    assert!(output.status.success());
+    assert_eq!(stdout(&output), "apple\n\t\tbanana\n\t\tcherry\n\tdate\n");
+}
+
+#[test]
+fn disjoint() {
+    let dir = TempDir::new();
+    fs::write(dir.join("a"), "a\nc\n").unwrap();
+    fs::write(dir.join("b"), "b\nd").unwrap();
+    let output = comm(&dir, &["a", "b"], "");
+    assert_eq!(stdout(&output), "a\n\tb\nc\n\td\n");
+}
+
+#[test]
+fn suppressed_columns() {
+    let dir = files();
+    assert_eq!(
+        stdout(&comm(&dir, &["-12", "a", "b"], "")),
+        "banana\ncherry\n"
+    );
+    assert_eq!(
+        stdout(&comm(&dir, &["-3", "a", "b"], "")),
+        "apple\n\tdate\n"
+    );
+    assert_eq!(
+        stdout(&comm(&dir, &["-1", "a", "b"], "")),
+        "\tbanana\n\tcherry\ndate\n"
+    );
+}
+
+#[test]
+fn stdin_as_one_file() {
+    let dir = files();
+    let output = comm(&dir, &["-2", "-", "b"], "banana\nelderberry\n");
+    assert_eq!(stdout(&output), "\tbanana\nelderberry\n");
+}
+
+#[test]
+fn missing_file() {
+    let dir = files();
+    let output = comm(&dir, &["a", "missing"], "");
+    assert_eq!(output.status.code(), Some(1));
+    assert!(String::from_utf8_lossy(&output.stderr).starts_with("comm: missing: "));
+}
@@ -0,0 +1,433 @@
+mod common;
+
+use common::mtime;
+use common::run_with_stdin;
+use common::stderr;
+use common::TempDir;
+use std::ffi::CString;
+use std::fs;
+use std::fs::OpenOptions;
+use std::io::Write;
+use std::os::unix::ffi::OsStrExt;
+use std::os::unix::fs::chown;
+use std::os::unix::fs::symlink;
+use std::os::unix::fs::PermissionsExt;
+use std::os::unix::process::CommandExt;
+use std::os::unix::process::ExitStatusExt;
+use std::path::Path;
+use std::process::Command;
+use std::process::Output;
+use std::thread;
+use std::time::Duration;
+
+fn cp(dir: &TempDir, args: &[&str]) -> Output {
+    run_with_stdin(env!("CARGO_BIN_EXE_cp"), dir.path(), args, b"")
+}
+
+fn mode(path: impl AsRef<Path>) -> u32 {
+    fs::metadata(path).unwrap().permissions().mode() & 0o7777
+}
+
+/// Backdate `path` so a copy made now would get a visibly different mtime.
+fn backdate(path: impl AsRef<Path>) {
+    let status = Command::new(env!("CARGO_BIN_EXE_touch"))
+        .arg("-t")
+        .arg("200001010000")
+        .arg(path.as_ref())
+        .status()
+        .unwrap();
+    assert!(status.success());
+}
+
+#[test]
+fn copies_a_file() {
+    let dir = TempDir::new();
+    fs::write(dir.join("a"), "contents\n").unwrap();
+    let output = cp(&dir, &["a", "b"]);
+    assert!(output.status.success(), "{}", stderr(&output));
+    assert_eq!(fs::read_to_string(dir.join("b")).unwrap(), "contents\n");
+}
+
+#[test]
+fn overwrites_an_existing_file() {
+    let dir = TempDir::new();
+    fs::write(dir.join("a"), "new\n").unwrap();
+    fs::write(dir.join("b"), "old contents\n").unwrap();
+    cp(&dir, &["a", "b"]);
+    assert_eq!(fs::read_to_string(dir.join("b")).unwrap(), "new\n");
+}
+
+#[test]
+fn no_clobber_keeps_the_destination() {
+    let dir = TempDir::new();
+    fs::write(dir.join("a"), "new\n").unwrap();
+    fs::write(dir.join("b"), "old\n").unwrap();
+    let output = cp(&dir, &["-n", "a", "b"]);
+    assert!(output.status.success());
+    assert_eq!(fs::read_to_string(dir.join("b")).unwrap(), "old\n");
+}
+
+#[test]
+fn force_replaces_an_unwritable_destination() {
+    let dir = TempDir::new();
+    fs::write(dir.join("a"), "new\n").unwrap();
+    fs::write(dir.join("b"), "old\n").unwrap();
+    fs::set_permissions(dir.join("b"), fs::Permissions::from_mode(0o444)).unwrap();
+    let output = cp(&dir, &["-f", "a", "b"]);
+    assert!(output.status.success(), "{}", stderr(&output));
+    assert_eq!(fs::read_to_string(dir.join("b")).unwrap(), "new\n");
+}
+
+#[test]
+fn copies_into_a_directory() {
+    let dir = TempDir::new();
+    fs::write(dir.join("a"), "1").unwrap();
+    fs::write(dir.join("b"), "2").unwrap();
+    fs::create_dir(dir.join("d")).unwrap();
+    let output = cp(&dir, &["-v", "a", "b", "d"]);
+    assert!(output.status.success());
+    assert_eq!(fs::read_to_string(dir.join("d/a")).unwrap(), "1");
+    assert_eq!(fs::read_to_string(dir.join("d/b")).unwrap(), "2");
+    assert_eq!(
+        String::from_utf8_lossy(&output.stdout),
+        "'a' -> 'd/a'\n'b' -> 'd/b'\n"
+    );
+}
+
+#[test]
+fn multiple_sources_need_a_directory() {
+    let dir = TempDir::new();
+    fs::write(dir.join("a"), "").unwrap();
+    let output = cp(&dir, &["a", "a", "missing"]);
+    assert_eq!(output.status.code(), Some(1));
+    assert_eq!(stderr(&output), "cp: target 'missing' is not a directory\n");
+}
+
+#[test]
+fn directory_needs_recursive() {
+    let dir = TempDir::new();
+    fs::create_dir(dir.join("d")).unwrap();
+    let output = cp(&dir, &["d", "e"]);
+    assert_eq!(output.status.code(), Some(1));
+    assert_eq!(
+        stderr(&output),
+        "cp: -r not specified; omitting directory 'd'\n"
+    );
+}
+
+#[test]
+fn recursive_copy_with_preserve() {
+    let dir = TempDir::new();
+    fs::create_dir_all(dir.join("src/sub")).unwrap();
+    fs::write(dir.join("src/a"), "a").unwrap();
+    fs::write(dir.join("src/sub/b"), "b").unwrap();
+    fs::set_permissions(dir.join("src/a"), fs::Permissions::from_mode(0o640)).unwrap();
+    symlink("a", dir.join("src/link")).unwrap();
+    backdate(dir.join("src/a"));
+    backdate(dir.join("src/sub"));
+
+    let output = cp(&dir, &["-r", "-p", "src", "dest"]);
+    assert!(output.status.success(), "{}", stderr(&output));
+    assert_eq!(fs::read_to_string(dir.join("dest/a")).unwrap(), "a");
+    assert_eq!(fs::read_to_string(dir.join("dest/sub/b")).unwrap(), "b");
+    assert_eq!(
+        fs::read_link(dir.join("dest/link")).unwrap(),
+        Path::new("a")
+    );
+    assert_eq!(mode(dir.join("dest/a")), 0o640);
+    assert_eq!(mtime(dir.join("dest/a")), mtime(dir.join("src/a")));
+    assert_eq!(mtime(dir.join("dest/sub")), mtime(dir.join("src/sub")));
+}
+
+#[test]
+fn recursive_copy_of_a_read_only_directory() {
+    let dir = TempDir::new();
+    fs::create_dir_all(dir.join("src/sub")).unwrap();
+    fs::write(dir.join("src/sub/f"), "f").unwrap();
+    fs::set_permissions(dir.join("src/sub"), fs::Permissions::from_mode(0o555)).unwrap();
+    let mut command = Command::new(env!("CARGO_BIN_EXE_cp"));
+    command.current_dir(dir.path()).args(["-r", "src", "dest"]);
+    // root could write into the read-only copy anyway, so copy as somebody else.
+    if unsafe { libc::geteuid() } == 0 {
+        for path in &["", "src", "src/sub", "src/sub/f"] {
+            chown(dir.join(path), Some(65534), Some(65534)).unwrap();
+        }
+        command.uid(65534).gid(65534);
+    }
+    let output = command.output().unwrap();
+    assert!(output.status.success(), "{}", stderr(&output));
+    assert_eq!(fs::read_to_string(dir.join("dest/sub/f")).unwrap(), "f");
+    assert_eq!(mode(dir.join("dest/sub")), 0o555);
+    for path in &["src/sub", "dest/sub"] {
+        fs::set_permissions(dir.join(path), fs::Permissions::from_mode(0o755)).unwrap();
+    }
+}
+
+#[test]
+fn without_preserve_times_are_new() {
+    let dir = TempDir::new();
+    fs::write(dir.join("a"), "a").unwrap();
+    backdate(dir.join("a"));
+    cp(&dir, &["a", "b"]);
+    assert!(mtime(dir.join("b")) > mtime(dir.join("a")));
+}
+
+#[test]
+fn preserve_only_timestamps() {
+    let dir = TempDir::new();
+    fs::write(dir.join("a"), "a").unwrap();
+    fs::set_permissions(dir.join("a"), fs::Permissions::from_mode(0o644)).unwrap();
+    backdate(dir.join("a"));
+    fs::write(dir.join("b"), "").unwrap();
+    fs::set_permissions(dir.join("b"), fs::Permissions::from_mode(0o600)).unwrap();
+    let output = cp(&dir, &["--preserve=timestamps", "a", "b"]);
+    assert!(output.status.success(), "{}", stderr(&output));
+    assert_eq!(mtime(dir.join("b")), mtime(dir.join("a")));
+    assert_eq!(mode(dir.join("b")), 0o600);
+}
+
+#[test]
+fn preserve_only_mode() {
+    let dir = TempDir::new();
+    fs::write(dir.join("a"), "a").unwrap();
+    fs::set_permissions(dir.join("a"), fs::Permissions::from_mode(0o644)).unwrap();
+    backdate(dir.join("a"));
+    fs::write(dir.join("b"), "").unwrap();
+    fs::set_permissions(dir.join("b"), fs::Permissions::from_mode(0o600)).unwrap();
+    let output = cp(&dir, &["--preserve=mode", "a", "b"]);
+    assert!(output.status.success(), "{}", stderr(&output));
+    assert!(mtime(dir.join("b")) > mtime(dir.join("a")));
+    assert_eq!(mode(dir.join("b")), 0o644);
+}
+
+#[test]
+fn refuses_to_copy_a_directory_into_itself() {
+    let dir = TempDir::new();
+    fs::create_dir(dir.join("d")).unwrap();
+    let output = cp(&dir, &["-r", "d", "d/inner"]);
+    assert_eq!(output.status.code(), Some(1));
+    assert_eq!(
+        stderr(&output),
+        "cp: cannot copy a directory, 'd', into itself, 'd/inner'\n"
+    );
+    assert!(!dir.join("d/inner").exists());
+}
+
+#[test]
+fn same_file_is_an_error() {
+    let dir = TempDir::new();
+    fs::write(dir.join("a"), "keep").unwrap();
+    let output = cp(&dir, &["a", "./a"]);
+    assert_eq!(output.status.code(), Some(1));
+    assert_eq!(fs::read_to_string(dir.join("a")).unwrap(), "keep");
+}
+
+#[test]
+fn interrupted_copy_leaves_no_partial_file() {
+    let dir = TempDir::new();
+    // Reading from a FIFO blocks mid-copy for as long as the test keeps the write end open.
+    let fifo = CString::new(dir.join("fifo").as_os_str().as_bytes()).unwrap();
+    assert_eq!(unsafe { libc::mkfifo(fifo.as_ptr(), 0o600) }, 0);
+    let child = Command::new(env!("CARGO_BIN_EXE_cp"))
+        .current_dir(dir.path())
+        .args(["fifo", "dest"])
+        .spawn()
+        .unwrap();
+    let mut writer = OpenOptions::new()
+        .write(true)
+        .open(dir.join("fifo"))
+        .unwrap();
+    writer.write_all(&[b'x'; 100_000]).unwrap();
+    for _ in 0..100 {
+        if dir.join("dest").exists() {
+            break;
+        }
+        thread::sleep(Duration::from_millis(50));
+    }
+    assert!(dir.join("dest").exists());
+
+    unsafe { libc::kill(child.id() as libc::pid_t, libc::SIGINT) };
+    let status = child.wait_with_output().unwrap().status;
+    drop(writer);
+    assert_eq!(status.signal(), Some(libc::SIGINT));
+    assert!(!dir.join("dest").exists());
+}
+
+#[test]
+fn simple_backup() {
+    let dir = TempDir::new();
+    fs::write(dir.join("a"), "new").unwrap();
+    fs::write(dir.join("b"), "old").unwrap();
+    let output = cp(&dir, &["--backup=simple", "-v", "a", "b"]);
+    assert!(output.status.success(), "{}", stderr(&output));
+    assert_eq!(fs::read_to_string(dir.join("b")).unwrap(), "new");
+    assert_eq!(fs::read_to_string(dir.join("b~")).unwrap(), "old");
+    assert_eq!(
+        String::from_utf8_lossy(&output.stdout),
+        "'a' -> 'b' (backup: 'b~')\n"
+    );
+}
+
+#[test]
+fn numbered_backups() {
+    let dir = TempDir::new();
+    fs::write(dir.join("b"), "0").unwrap();
+    for (i, contents) in ["1", "2"].iter().enumerate() {
+        fs::write(dir.join("a"), contents).unwrap();
+        let output = cp(&dir, &["--backup=numbered", "a", "b"]);
+        assert!(output.status.success(), "{}", stderr(&output));
+        let backup = dir.join(format!("b.~{}~", i + 1));
+        assert_eq!(fs::read_to_string(backup).unwrap(), i.to_string());
+    }
+    // Existing numbered backups make `existing` number the next one too.
+    fs::write(dir.join("a"), "3").unwrap();
+    let output = cp(&dir, &["--backup=existing", "a", "b"]);
+    assert!(output.status.success(), "{}", stderr(&output));
+    assert_eq!(fs::read_to_string(dir.join("b.~3~")).unwrap(), "2");
+    assert!(!dir.join("b~").exists());
+}
+
+#[test]
+fn backup_needs_a_valid_control() {
+    let dir = TempDir::new();
+    let output = cp(&dir, &["--backup=sometimes", "a", "b"]);
+    assert!(!output.status.success());
+    assert!(stderr(&output).contains("invalid backup type 'sometimes'"));
+}
+
+#[test]
+fn interactive_asks_before_overwriting() {
+    let dir = TempDir::new();
+    fs::write(dir.join("a"), "a").unwrap();
+    fs::write(dir.join("b"), "b").unwrap();
+    let output = run_with_stdin(
+        env!("CARGO_BIN_EXE_cp"),
+        dir.path(),
+        &["-i", "a", "b"],
+        b"n\n",
+    );
+    assert!(output.status.success());
+    assert_eq!(stderr(&output), "cp: overwrite 'b'? ");
+    assert_eq!(fs::read_to_string(dir.join("b")).unwrap(), "b");
+    let output = run_with_stdin(
+        env!("CARGO_BIN_EXE_cp"),
+        dir.path(),
+        &["--interactive", "a", "b"],
+        b"yes\n",
+    );
+    assert!(output.status.success(), "{}", stderr(&output));
+    assert_eq!(fs::read_to_string(dir.join("b")).unwrap(), "a");
+}
+
+#[test]
+fn parents_recreates_the_source_path() {
+    let dir = TempDir::new();
+    fs::create_dir_all(dir.join("src/a")).unwrap();
+    fs::write(dir.join("src/a/b.txt"), "b").unwrap();
+    fs::create_dir(dir.join("dest")).unwrap();
+    let output = cp(&dir, &["--parents", "src/a/b.txt", "dest"]);
+    assert!(output.status.success(), "{}", stderr(&output));
+    assert_eq!(
+        fs::read_to_string(dir.join("dest/src/a/b.txt")).unwrap(),
+        "b"
+    );
+}
+
+#[test]
+fn parents_with_recursive() {
+    let dir = TempDir::new();
+    fs::create_dir_all(dir.join("src/a/c")).unwrap();
+    fs::write(dir.join("src/a/c/d"), "d").unwrap();
+    fs::create_dir(dir.join("dest")).unwrap();
+    let output = cp(&dir, &["--parents", "-r", "src/a", "dest"]);
+    assert!(output.status.success(), "{}", stderr(&output));
+    assert_eq!(fs::read_to_string(dir.join("dest/src/a/c/d")).unwrap(), "d");
+    let output = cp(&dir, &["--parents", "src/a/c/d", "dest/src/a/c/d"]);
+    assert!(stderr(&output).contains("must be a directory"));
+}
+
+#[test]
+fn trailing_slash_on_a_directory() {
+    let dir = TempDir::new();
+    fs::create_dir(dir.join("dir")).unwrap();
+    fs::write(dir.join("dir/file"), "f").unwrap();
+    for (i, flags) in [&[][..], &["--strip-trailing-slashes"][..]]
+        .iter()
+        .enumerate()
+    {
+        let dest = format!("dest{}", i);
+        let mut args = vec!["-r"];
+        args.extend_from_slice(flags);
+        args.extend_from_slice(&["dir/", &dest]);
+        let output = cp(&dir, &args);
+        assert!(output.status.success(), "{}", stderr(&output));
+        assert_eq!(
+            fs::read_to_string(dir.join(&dest).join("file")).unwrap(),
+            "f"
+        );
+    }
+}
+
+#[test]
+fn strip_trailing_slashes_copies_the_symlink() {
+    let dir = TempDir::new();
+    fs::create_dir(dir.join("dir")).unwrap();
+    fs::write(dir.join("dir/file"), "f").unwrap();
+    symlink("dir", dir.join("link")).unwrap();
+    let output = cp(&dir, &["-r", "link/", "followed"]);
+    assert!(output.status.success(), "{}", stderr(&output));
+    assert!(fs::symlink_metadata(dir.join("followed")).unwrap().is_dir());
+    let output = cp(&dir, &["-r", "--strip-trailing-slashes", "link/", "kept"]);
+    assert!(output.status.success(), "{}", stderr(&output));
+    assert_eq!(fs::read_link(dir.join("kept")).unwrap(), Path::new("dir"));
+}
+
+#[test]
+fn recursive_copy_of_a_symlink_cycle_terminates() {
+    let dir = TempDir::new();
+    fs::create_dir_all(dir.join("top/sub")).unwrap();
+    symlink("..", dir.join("top/sub/up")).unwrap();
+    let output = cp(&dir, &["-r", "top", "copy"]);
+    assert!(output.status.success(), "{}", stderr(&output));
+    assert_eq!(
+        fs::read_link(dir.join("copy/sub/up")).unwrap(),
+        Path::new("..")
+    );
+}
+
+#[test]
+fn install_into_missing_directories_with_a_mode() {
+    let dir = TempDir::new();
+    fs::write(dir.join("a"), "a").unwrap();
+    fs::set_permissions(dir.join("a"), fs::Permissions::from_mode(0o644)).unwrap();
+    let output = cp(&dir, &["-D", "-m", "600", "a", "x/y/z/b"]);
+    assert!(output.status.success(), "{}", stderr(&output));
+    assert_eq!(fs::read_to_string(dir.join("x/y/z/b")).unwrap(), "a");
+    assert_eq!(mode(dir.join("x/y/z/b")), 0o600);
+    assert_eq!(mode(dir.join("a")), 0o644);
+}
+
+#[test]
+fn reflink_copies_match_the_source() {
+    let dir = TempDir::new();
+    let contents: Vec<u8> = (0..100_000u32).map(|i| (i % 251) as u8).collect();
+    fs::write(dir.join("a"), &contents).unwrap();
+    // never always takes the byte copy, and auto falls back to it wherever the file system
+    // can't share blocks, so both work everywhere.
+    for (when, dest) in &[("--reflink=never", "never"), ("--reflink=auto", "auto")] {
+        let output = cp(&dir, &[when, "a", dest]);
+        assert!(output.status.success(), "{}", stderr(&output));
+        assert_eq!(fs::read(dir.join(dest)).unwrap(), contents);
+    }
+    fs::write(dir.join("never"), "changed").unwrap();
+    assert_eq!(fs::read(dir.join("a")).unwrap(), contents);
+    let output = cp(&dir, &["--reflink", "a", "always"]);
+    if output.status.success() {
+        assert_eq!(fs::read(dir.join("always")).unwrap(), contents);
+    } else {
+        assert!(stderr(&output).contains("failed to clone"));
+    }
+    assert!(!cp(&dir, &["--reflink=sometimes", "a", "b"])
+        .status
+        .success());
+}
@@ -0,0 +1,131 @@
+mod common;
+
+use common::run_with_stdin;
+use common::stdout;
+use common::TempDir;
+use std::fs;
+use std::process::Output;
+
+fn cut(dir: &TempDir, args: &[&str], stdin: &str) -> Output {
+    run_with_stdin(
+        env!("CARGO_BIN_EXE_cut"),
+        dir.path(),
+        args,
+        stdin.as_bytes(),
+    )
+}
+
+const TABLE: &str = "a:b:c:d:e\n1:2:3:4:5\n";
+
+#[test]
+fn selects_fields() {
+    let dir = TempDir::new();
+    assert_eq!(stdout(&cut(&dir, &["-d:", "-f2"], TABLE)), "b\n2\n");
+    assert_eq!(
+        stdout(&cut(&dir, &["-d", ":", "-f", "1,3-4"], TABLE)),
+        "a:c:d\n1:3:4\n"
+    );
+    assert_eq!(stdout(&cut(&dir, &["-d:", "-f4-"], TABLE)), "d:e\n4:5\n");
+    assert_eq!(stdout(&cut(&dir, &["-d:", "-f-2"], TABLE)), "a:b\n1:2\n");
+}
+
+#[test]
+fn output_delimiter_rejoins_fields() {
+    let dir = TempDir::new();
+    assert_eq!(
+        stdout(&cut(
+            &dir,
+            &["-d:", "-f1,3-4", "--output-delimiter=|"],
+            TABLE
+        )),
+        "a|c|d\n1|3|4\n"
+    );
+    assert_eq!(
+        stdout(&cut(
+            &dir,
+            &["-d:", "-f2,5", "--output-delimiter", " -> "],
+            TABLE
+        )),
+        "b -> e\n2 -> 5\n"
+    );
+}
+
+#[test]
+fn output_delimiter_separates_character_ranges() {
+    let dir = TempDir::new();
+    assert_eq!(
+        stdout(&cut(
+            &dir,
+            &["-c1-2,4,5", "--output-delimiter=, "],
+            "abcdef\n"
+        )),
+        "ab, de\n"
+    );
+}
+
+#[test]
+fn default_delimiter_is_tab() {
+    let dir = TempDir::new();
+    assert_eq!(stdout(&cut(&dir, &["-f2"], "x\ty\tz\n")), "y\n");
+}
+
+#[test]
+fn lines_without_delimiter() {
+    let dir = TempDir::new();
+    let input = "a:b\nplain\n";
+    assert_eq!(stdout(&cut(&dir, &["-d:", "-f2"], input)), "b\nplain\n");
+    assert_eq!(stdout(&cut(&dir, &["-d:", "-f2", "-s"], input)), "b\n");
+}
+
+#[test]
+fn selects_characters() {
+    let dir = TempDir::new();
+    assert_eq!(
+        stdout(&cut(&dir, &["-c", "1,3-5,7-"], "abcdefghi\n")),
+        "acdeghi\n"
+    );
+    assert_eq!(stdout(&cut(&dir, &["-c2-3"], "héllo\n")), "él\n");
+}
+
+#[test]
+fn selects_bytes() {
+    let dir = TempDir::new();
+    assert_eq!(stdout(&cut(&dir, &["-b", "2-3"], "abcd\n")), "bc\n");
+}
+
+#[test]
+fn complement() {
+    let dir = TempDir::new();
+    assert_eq!(
+        stdout(&cut(&dir, &["-d:", "-f2,4", "--complement"], TABLE)),
+        "a:c:e\n1:3:5\n"
+    );
+    assert_eq!(
+        stdout(&cut(&dir, &["-c1-2", "--complement"], "abcd\n")),
+        "cd\n"
+    );
+}
+
+#[test]
+fn missing_final_newline_gets_one() {
+    let dir = TempDir::new();
+    assert_eq!(stdout(&cut(&dir, &["-c1"], "ab")), "a\n");
+}
+
+#[test]
+fn reads_files() {
+    let dir = TempDir::new();
+    fs::write(dir.join("table"), TABLE).unwrap();
+    assert_eq!(stdout(&cut(&dir, &["-d:", "-f5", "table"], "")), "e\n5\n");
+}
+
+#[test]
+fn invalid_list() {
+    let dir = TempDir::new();
+    let output = cut(&dir, &["-f", "3-1"], "");
+    assert_eq!(output.status.code(), Some(1));
+    assert_eq!(
+        String::from_utf8_lossy(&output.stderr),
+        "cut: invalid decreasing range\n"
+    );
+}
@@ -0,0 +1,57 @@
+mod common;
+
+use chrono::Utc;
+use common::stdout;
+use common::TempDir;
+use std::fs::File;
+use std::process::Command;
+use std::process::Output;
+use std::time::Duration;
+use std::time::SystemTime;
+
+fn date(args: &[&str]) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_date"))
+        .args(args)
+        .output()
+        .unwrap()
+}
+
+#[test]
+fn formats_today() {
+    let before = Utc::today().format("%Y-%m-%d\n").to_string();
+    let output = date(&["-u", "+%Y-%m-%d"]);
+    let after = Utc::today().format("%Y-%m-%d\n").to_string();
+    assert!(output.status.success());
+    let printed = stdout(&output);
+    assert!(printed == before || printed == after);
+}
+
+#[test]
+fn given_date() {
+    let output = date(&["-u", "-d", "2000-01-02T03:04:05+01:00", "+%F %T"]);
+    assert!(output.status.success());
+    assert_eq!(stdout(&output), "2000-01-02 02:04:05\n");
+    let output = date(&["-u", "--date=@946782245"]);
+    assert_eq!(stdout(&output), "Sun Jan  2 03:04:05 UTC 2000\n");
+    assert!(!date(&["-d", "tomorrow"]).status.success());
+}
+
+#[test]
+fn reference_file() {
+    let dir = TempDir::new();
+    let path = dir.join("file");
+    let file = File::create(&path).unwrap();
+    file.set_modified(SystemTime::UNIX_EPOCH + Duration::from_secs(1_000_000_000))
+        .unwrap();
+    let output = date(&["-u", "-r", path.to_str().unwrap(), "+%s %F"]);
+    assert!(output.status.success());
+    assert_eq!(stdout(&output), "1000000000 2001-09-09\n");
+    assert!(!date(&["-r", dir.join("missing").to_str().unwrap()])
+        .status
+        .success());
+}
+
+#[test]
+fn rejects_bad_formats() {
+    assert!(!date(&["+%Q"]).status.success());
+}
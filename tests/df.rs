@@ -0,0 +1,105 @@
+mod common;
+
+use common::stdout;
+use std::process::Command;
+use std::process::Output;
+
+fn df(args: &[&str]) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_df"))
+        .args(args)
+        .output()
+        .unwrap()
+}
+
+/// The columns of the single filesystem row `df` printed.
+fn only_row(output: &Output) -> Vec<String> {
+    let printed = stdout(output);
+    let lines: Vec<&str> = printed.lines().collect();
+    assert_eq!(lines.len(), 2, "{}", printed);
+    lines[1].split_whitespace().map(str::to_owned).collect()
+}
+
+#[test]
+fn current_filesystem() {
+    let output = df(&["."]);
+    assert!(output.status.success());
+    assert!(stdout(&output).starts_with("Filesystem"));
+    let row = only_row(&output);
+    assert!(row[1].parse::<u64>().unwrap() > 0);
+    assert!(row[4].ends_with('%'));
+}
+
+#[test]
+fn human_readable_and_inodes() {
+    let output = df(&["-h", "."]);
+    assert!(output.status.success());
+    assert!(stdout(&output).contains(" Size "));
+    let output = df(&["-i", "."]);
+    assert!(output.status.success());
+    assert!(stdout(&output).contains(" Inodes "));
+}
+
+#[test]
+fn missing_file() {
+    let output = df(&["/does/not/exist"]);
+    assert!(!output.status.success());
+}
+
+/// The mount points of each file system of type `fs_type`, from the kernel's mount table.
+#[cfg(target_os = "linux")]
+fn mount_points(fs_type: &str) -> Vec<String> {
+    std::fs::read_to_string("/proc/self/mounts")
+        .unwrap()
+        .lines()
+        .filter_map(|line| {
+            let fields: Vec<&str> = line.split(' ').collect();
+            match fields.get(2) {
+                Some(&found) if found == fs_type => Some(fields[1].replace("\\040", " ")),
+                _ => None,
+            }
+        })
+        .collect()
+}
+
+#[cfg(target_os = "linux")]
+#[test]
+fn total_adds_a_row() {
+    let plain = stdout(&df(&["."]));
+    let output = df(&["--total", "."]);
+    assert!(output.status.success());
+    let printed = stdout(&output);
+    let lines: Vec<&str> = printed.lines().collect();
+    assert_eq!(lines.len(), plain.lines().count() + 1);
+    let total: Vec<&str> = lines.last().unwrap().split_whitespace().collect();
+    assert_eq!(total[0], "total");
+    assert_eq!(total[5], "-");
+    assert_eq!(total[1], only_row(&df(&["."]))[1]);
+}
+
+#[cfg(target_os = "linux")]
+#[test]
+fn exclude_type_removes_its_file_systems() {
+    let tmpfs = mount_points("tmpfs");
+    let targets = |output: &Output| -> Vec<String> {
+        stdout(output)
+            .lines()
+            .skip(1)
+            .map(|line| line.split_whitespace().last().unwrap().to_owned())
+            .collect()
+    };
+    let excluded = df(&["-x", "tmpfs"]);
+    assert!(excluded.status.success());
+    assert!(targets(&excluded)
+        .iter()
+        .all(|target| !tmpfs.contains(target)));
+    if !tmpfs.is_empty() {
+        let only = df(&["-t", "tmpfs"]);
+        assert!(only.status.success());
+        let listed = targets(&only);
+        assert!(
+            listed.iter().all(|target| tmpfs.contains(target)),
+            "{:?}",
+            listed
+        );
+    }
+}
@@ -0,0 +1,48 @@
+use std::process::Command;
+use std::process::Output;
+
+fn dirname(args: &[&str]) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_dirname"))
+        .args(args)
+        .output()
+        .unwrap()
+}
+
+fn stdout(args: &[&str]) -> String {
+    let output = dirname(args);
+    assert!(output.status.success(), "{:?}", output);
+    String::from_utf8(output.stdout).unwrap()
+}
+
+#[test]
+fn single_names() {
+    assert_eq!(stdout(&["/usr/bin/sort"]), "/usr/bin\n");
+    assert_eq!(stdout(&["sort"]), ".\n");
+    assert_eq!(stdout(&["/"]), "/\n");
+    assert_eq!(stdout(&["usr/"]), ".\n");
+}
+
+#[test]
+fn multiple_names() {
+    assert_eq!(stdout(&["/a/b", "c/d/", "e"]), "/a\nc\n.\n");
+}
+
+#[test]
+fn nul_terminated() {
+    assert_eq!(stdout(&["-z", "/a/b", "c"]), "/a\0.\0");
+}
+
+#[test]
+fn missing_operand() {
+    let output = dirname(&[]);
+    assert_eq!(output.status.code(), Some(1));
+    assert_eq!(
+        String::from_utf8_lossy(&output.stderr),
+        "dirname: missing operand\n"
+    );
+}
+
+#[test]
+fn zero_with_a_newline_in_a_name() {
+    assert_eq!(stdout(&["--zero", "a\nb/c"]), "a\nb\0");
+}
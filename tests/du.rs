@@ -0,0 +1,158 @@
+mod common;
+
+use common::run_with_stdin;
+use common::stdout;
+use common::TempDir;
+use std::fs;
+use std::os::unix::fs::symlink;
+use std::os::unix::fs::MetadataExt;
+use std::path::Path;
+use std::process::Command;
+use std::process::Output;
+use std::time::Duration;
+use std::time::SystemTime;
+
+fn du(dir: &Path, args: &[&str]) -> Output {
+    run_with_stdin(env!("CARGO_BIN_EXE_du"), dir, args, b"")
+}
+
+/// Builds `tree/{one, sub/{two, deeper/three}}` and returns its size in 1K blocks.
+fn make_tree(dir: &TempDir) -> u64 {
+    fs::create_dir_all(dir.join("tree/sub/deeper")).unwrap();
+    fs::write(dir.join("tree/one"), vec![b'1'; 5000]).unwrap();
+    fs::write(dir.join("tree/sub/two"), vec![b'2'; 20000]).unwrap();
+    fs::write(dir.join("tree/sub/deeper/three"), vec![b'3'; 100]).unwrap();
+    [
+        "tree",
+        "tree/one",
+        "tree/sub",
+        "tree/sub/two",
+        "tree/sub/deeper",
+        "tree/sub/deeper/three",
+    ]
+    .iter()
+    .map(|path| fs::symlink_metadata(dir.join(path)).unwrap().blocks() * 512)
+    .sum::<u64>()
+    .div_ceil(1024)
+}
+
+#[test]
+fn summary_total() {
+    let dir = TempDir::new();
+    let total = make_tree(&dir);
+    let output = du(dir.path(), &["-s", "tree"]);
+    assert!(output.status.success());
+    assert_eq!(stdout(&output), format!("{}\ttree\n", total));
+    let full = stdout(&du(dir.path(), &["tree"]));
+    assert_eq!(full.lines().last().unwrap(), format!("{}\ttree", total));
+}
+
+#[test]
+fn max_depth() {
+    let dir = TempDir::new();
+    make_tree(&dir);
+    let names = |output: Output| -> Vec<String> {
+        stdout(&output)
+            .lines()
+            .map(|line| line.split('\t').nth(1).unwrap().to_owned())
+            .collect()
+    };
+    assert_eq!(
+        names(du(dir.path(), &["tree"])),
+        ["tree/sub/deeper", "tree/sub", "tree"]
+    );
+    assert_eq!(
+        names(du(dir.path(), &["--max-depth=1", "tree"])),
+        ["tree/sub", "tree"]
+    );
+    assert_eq!(
+        names(du(dir.path(), &["-a", "-d", "1", "tree"])),
+        ["tree/one", "tree/sub", "tree"]
+    );
+}
+
+#[test]
+fn hard_links_count_once() {
+    let dir = TempDir::new();
+    fs::create_dir(dir.join("links")).unwrap();
+    fs::write(dir.join("links/a"), vec![b'a'; 50000]).unwrap();
+    let alone = stdout(&du(dir.path(), &["-s", "links"]));
+    fs::hard_link(dir.join("links/a"), dir.join("links/b")).unwrap();
+    assert_eq!(stdout(&du(dir.path(), &["-s", "links"])), alone);
+}
+
+#[test]
+fn missing_file() {
+    let dir = TempDir::new();
+    let output = du(dir.path(), &["missing"]);
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("cannot access 'missing'"));
+}
+
+#[test]
+fn human_readable_units() {
+    let dir = TempDir::new();
+    make_tree(&dir);
+    assert!(stdout(&du(dir.path(), &["-s", "-h", "tree"])).ends_with("K\ttree\n"));
+    assert!(stdout(&du(dir.path(), &["-s", "--si", "tree"])).ends_with("k\ttree\n"));
+}
+
+#[test]
+fn one_file_system_skips_other_mounts() {
+    // Best effort: /dev/pts is normally its own file system inside /dev.
+    match (fs::metadata("/dev"), fs::metadata("/dev/pts")) {
+        (Ok(dev), Ok(pts)) if dev.dev() != pts.dev() => {}
+        _ => return,
+    }
+    let dir = TempDir::new();
+    let output = du(dir.path(), &["-d", "1", "/dev"]);
+    assert!(stdout(&output).contains("\t/dev/pts\n"));
+    let output = du(dir.path(), &["-x", "-d", "1", "/dev"]);
+    assert!(!stdout(&output).contains("\t/dev/pts\n"));
+    assert!(stdout(&output).ends_with("\t/dev\n"));
+}
+
+#[test]
+fn modification_times() {
+    let dir = TempDir::new();
+    let file = fs::File::create(dir.join("old")).unwrap();
+    file.set_modified(SystemTime::UNIX_EPOCH + Duration::from_secs(946_782_245))
+        .unwrap();
+    let du_utc = |args: &[&str]| {
+        let output = Command::new(env!("CARGO_BIN_EXE_du"))
+            .current_dir(dir.path())
+            .args(args)
+            .env("TZ", "UTC")
+            .output()
+            .unwrap();
+        assert!(output.status.success());
+        stdout(&output)
+    };
+    assert_eq!(du_utc(&["--time", "old"]), "0\t2000-01-02 03:04\told\n");
+    assert_eq!(
+        du_utc(&["--time", "--time-style=+%Y", "old"]),
+        "0\t2000\told\n"
+    );
+    assert_eq!(du_utc(&["--time-style=+%Y", "old"]), "0\told\n");
+}
+
+#[test]
+fn dereference_or_not() {
+    let dir = TempDir::new();
+    fs::write(dir.join("target"), vec![b'x'; 20000]).unwrap();
+    symlink("target", dir.join("link")).unwrap();
+    let target = fs::metadata(dir.join("target")).unwrap().blocks() * 512;
+    let link = fs::symlink_metadata(dir.join("link")).unwrap().blocks() * 512;
+    assert_ne!(target, link);
+    let output = du(dir.path(), &["link"]);
+    assert_eq!(stdout(&output), format!("{}\tlink\n", link.div_ceil(1024)));
+    let output = du(dir.path(), &["-P", "link"]);
+    assert_eq!(stdout(&output), format!("{}\tlink\n", link.div_ceil(1024)));
+    let output = du(dir.path(), &["-L", "link"]);
+    assert_eq!(
+        stdout(&output),
+        format!("{}\tlink\n", target.div_ceil(1024))
+    );
+    let output = du(dir.path(), &["--dereference", "--no-dereference", "link"]);
+    assert_eq!(stdout(&output), format!("{}\tlink\n", link.div_ceil(1024)));
+}
@@ -0,0 +1,58 @@
+use std::process::Command;
+
+fn echo(args: &[&str]) -> String {
+    let output = Command::new(env!("CARGO_BIN_EXE_echo"))
+        .args(args)
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    String::from_utf8(output.stdout).unwrap()
+}
+
+#[test]
+fn joins_arguments_with_spaces() {
+    assert_eq!(echo(&["hello", "world"]), "hello world\n");
+    assert_eq!(echo(&[]), "\n");
+}
+
+#[test]
+fn no_trailing_newline() {
+    assert_eq!(echo(&["-n", "hello"]), "hello");
+}
+
+#[test]
+fn escapes_only_with_e() {
+    assert_eq!(echo(&["a\\tb\\n"]), "a\\tb\\n\n");
+    assert_eq!(echo(&["-e", "a\\tb\\\\c"]), "a\tb\\c\n");
+    assert_eq!(echo(&["-e", "\\x41\\0102"]), "AB\n");
+    assert_eq!(echo(&["-eE", "a\\tb"]), "a\\tb\n");
+}
+
+#[test]
+fn backslash_c_stops_output() {
+    assert_eq!(echo(&["-e", "one\\ctwo", "three"]), "one");
+}
+
+#[test]
+fn combined_flags() {
+    assert_eq!(echo(&["-ne", "a\\nb"]), "a\nb");
+}
+
+#[test]
+fn unknown_flags_are_passed_through() {
+    assert_eq!(echo(&["-x"]), "-x\n");
+    assert_eq!(echo(&["-nx", "y"]), "-nx y\n");
+    assert_eq!(echo(&["--", "a"]), "-- a\n");
+    assert_eq!(echo(&["-"]), "-\n");
+}
+
+#[test]
+fn flag_parsing_stops_at_first_string() {
+    assert_eq!(echo(&["a", "-n"]), "a -n\n");
+}
+
+#[test]
+fn help_only_when_alone() {
+    assert!(echo(&["--help"]).contains("Usage: echo"));
+    assert_eq!(echo(&["--help", "x"]), "--help x\n");
+}
@@ -0,0 +1,65 @@
+use std::process::Command;
+use std::process::Output;
+
+fn env(args: &[&str]) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_env"))
+        .env("RUST_COREUTILS_TEST", "inherited")
+        .args(args)
+        .output()
+        .unwrap()
+}
+
+fn stdout(args: &[&str]) -> String {
+    let output = env(args);
+    assert!(output.status.success(), "{:?}", output);
+    String::from_utf8(output.stdout).unwrap()
+}
+
+#[test]
+fn prints_the_environment() {
+    let output = stdout(&[]);
+    assert!(output
+        .lines()
+        .any(|line| line == "RUST_COREUTILS_TEST=inherited"));
+}
+
+#[test]
+fn ignore_environment() {
+    assert_eq!(stdout(&["-i"]), "");
+    assert_eq!(stdout(&["-i", "A=1", "B=2"]), "A=1\nB=2\n");
+    assert_eq!(stdout(&["-", "-0", "A=1", "B=2"]), "A=1\0B=2\0");
+}
+
+#[test]
+fn unset() {
+    let output = stdout(&["-u", "RUST_COREUTILS_TEST"]);
+    assert!(!output.contains("RUST_COREUTILS_TEST="));
+}
+
+#[test]
+fn assignments_override() {
+    let output = stdout(&["RUST_COREUTILS_TEST=changed"]);
+    assert!(output
+        .lines()
+        .any(|line| line == "RUST_COREUTILS_TEST=changed"));
+    assert!(!output.contains("inherited"));
+}
+
+#[test]
+fn runs_a_command() {
+    assert_eq!(stdout(&["FOO=bar", "printenv", "FOO"]), "bar\n");
+    assert_eq!(stdout(&["printenv", "RUST_COREUTILS_TEST"]), "inherited\n");
+}
+
+#[test]
+fn command_arguments_pass_through() {
+    assert_eq!(stdout(&["echo", "-i", "X=1"]), "-i X=1\n");
+}
+
+#[test]
+fn missing_command() {
+    let output = env(&["rust-coreutils-no-such-command"]);
+    assert_eq!(output.status.code(), Some(127));
+    let output = env(&["-0", "true"]);
+    assert_eq!(output.status.code(), Some(125));
+}
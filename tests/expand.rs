@@ -0,0 +1,67 @@
+mod common;
+
+use common::run_with_stdin;
+use common::stdout;
+use common::TempDir;
+use std::process::Output;
+
+fn expand(args: &[&str], stdin: &str) -> Output {
+    let dir = TempDir::new();
+    run_with_stdin(
+        env!("CARGO_BIN_EXE_expand"),
+        dir.path(),
+        args,
+        stdin.as_bytes(),
+    )
+}
+
+#[test]
+fn default_stops() {
+    let output = expand(&[], "\ta\tb\nabcdefgh\tc\n");
+    assert!(output.status.success());
+    assert_eq!(
+        stdout(&output),
+        format!(
+            "{}a{}b\nabcdefgh{}c\n",
+            " ".repeat(8),
+            " ".repeat(7),
+            " ".repeat(8)
+        )
+    );
+}
+
+#[test]
+fn tab_size() {
+    let output = expand(&["-t", "4"], "ab\tc\td\n");
+    assert_eq!(stdout(&output), "ab  c   d\n");
+}
+
+#[test]
+fn tab_list() {
+    let output = expand(&["-t", "2,5"], "\ta\tb\tc\n");
+    assert_eq!(stdout(&output), "  a  b c\n");
+    let output = expand(&["--tabs=3 6"], "x\ty\tz\n");
+    assert_eq!(stdout(&output), "x  y  z\n");
+}
+
+#[test]
+fn initial_only() {
+    let output = expand(&["-i", "-t", "4"], " \ta\tb\n");
+    assert_eq!(stdout(&output), "    a\tb\n");
+}
+
+#[test]
+fn multibyte_characters_take_one_column() {
+    let output = expand(&["-t", "4"], "é\tx\n");
+    assert_eq!(stdout(&output), "é   x\n");
+}
+
+#[test]
+fn invalid_tabs() {
+    let output = expand(&["-t", "4,2"], "");
+    assert_eq!(output.status.code(), Some(1));
+    assert_eq!(
+        String::from_utf8_lossy(&output.stderr),
+        "expand: tab sizes must be ascending\n"
+    );
+}
@@ -0,0 +1,64 @@
+mod common;
+
+use common::run_with_stdin;
+use common::stdout;
+use common::TempDir;
+use std::fs;
+use std::process::Output;
+
+fn fold(args: &[&str], stdin: &str) -> Output {
+    let dir = TempDir::new();
+    run_with_stdin(
+        env!("CARGO_BIN_EXE_fold"),
+        dir.path(),
+        args,
+        stdin.as_bytes(),
+    )
+}
+
+#[test]
+fn hard_wrap() {
+    let output = fold(&["-w", "4"], "abcdefghij\nabc\n");
+    assert!(output.status.success());
+    assert_eq!(stdout(&output), "abcd\nefgh\nij\nabc\n");
+}
+
+#[test]
+fn default_width() {
+    let line = "x".repeat(100);
+    let output = fold(&[], &line);
+    assert_eq!(stdout(&output), format!("{}\n{}", &line[..80], &line[80..]));
+}
+
+#[test]
+fn break_at_spaces() {
+    let output = fold(&["-s", "-w", "10"], "the quick brown fox jumps\n");
+    assert_eq!(stdout(&output), "the quick \nbrown fox \njumps\n");
+}
+
+#[test]
+fn tabs_take_columns() {
+    let output = fold(&["-w", "10"], "ab\tcdefgh\n");
+    assert_eq!(stdout(&output), "ab\tcd\nefgh\n");
+}
+
+#[test]
+fn tabs_are_one_byte_with_bytes() {
+    let output = fold(&["-b", "-w", "4"], "ab\tcdefgh\n");
+    assert_eq!(stdout(&output), "ab\tc\ndefg\nh\n");
+}
+
+#[test]
+fn files() {
+    let dir = TempDir::new();
+    fs::write(dir.join("a"), "123456\n").unwrap();
+    let output = run_with_stdin(
+        env!("CARGO_BIN_EXE_fold"),
+        dir.path(),
+        &["-w", "3", "a", "missing"],
+        b"",
+    );
+    assert_eq!(output.status.code(), Some(1));
+    assert_eq!(stdout(&output), "123\n456\n");
+    assert!(String::from_utf8_lossy(&output.stderr).starts_with("fold: missing: "));
+}
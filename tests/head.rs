@@ -0,0 +1,135 @@
+mod common;
+
+use common::run_with_stdin;
+use common::stdout;
+use common::TempDir;
+use std::fs;
+use std::process::Output;
+
+fn numbers(range: std::ops::RangeInclusive<u32>) -> String {
+    range.map(|n| format!("{}\n", n)).collect()
+}
+
+fn head(dir: &TempDir, args: &[&str], stdin: &str) -> Output {
+    run_with_stdin(
+        env!("CARGO_BIN_EXE_head"),
+        dir.path(),
+        args,
+        stdin.as_bytes(),
+    )
+}
+
+#[test]
+fn first_ten_lines_by_default() {
+    let dir = TempDir::new();
+    assert_eq!(stdout(&head(&dir, &[], &numbers(1..=20))), numbers(1..=10));
+}
+
+#[test]
+fn first_n_lines() {
+    let dir = TempDir::new();
+    assert_eq!(
+        stdout(&head(&dir, &["-n", "3"], &numbers(1..=20))),
+        numbers(1..=3)
+    );
+    assert_eq!(
+        stdout(&head(&dir, &["-2"], &numbers(1..=20))),
+        numbers(1..=2)
+    );
+    assert_eq!(
+        stdout(&head(&dir, &["-n", "50"], &numbers(1..=5))),
+        numbers(1..=5)
+    );
+}
+
+#[test]
+fn all_but_last_lines() {
+    let dir = TempDir::new();
+    assert_eq!(
+        stdout(&head(&dir, &["-n", "-3"], &numbers(1..=10))),
+        numbers(1..=7)
+    );
+    assert_eq!(stdout(&head(&dir, &["-n", "-30"], &numbers(1..=10))), "");
+    assert_eq!(
+        stdout(&head(&dir, &["--lines=-0"], &numbers(1..=4))),
+        numbers(1..=4)
+    );
+}
+
+#[test]
+fn first_bytes() {
+    let dir = TempDir::new();
+    assert_eq!(stdout(&head(&dir, &["-c", "5"], "hello world")), "hello");
+}
+
+#[test]
+fn all_but_last_bytes() {
+    let dir = TempDir::new();
+    assert_eq!(stdout(&head(&dir, &["-c", "-6"], "hello world")), "hello");
+    let big = "x".repeat(100_000) + "tail";
+    assert_eq!(
+        stdout(&head(&dir, &["--bytes", "-4"], &big)),
+        "x".repeat(100_000)
+    );
+}
+
+#[test]
+fn headers_for_multiple_files() {
+    let dir = TempDir::new();
+    fs::write(dir.join("a"), numbers(1..=3)).unwrap();
+    fs::write(dir.join("b"), numbers(4..=6)).unwrap();
+    let output = head(&dir, &["-n", "1", "a", "b"], "");
+    assert_eq!(stdout(&output), "==> a <==\n1\n\n==> b <==\n4\n");
+    let output = head(&dir, &["-q", "-n", "1", "a", "b"], "");
+    assert_eq!(stdout(&output), "1\n4\n");
+    let output = head(&dir, &["-v", "-n", "1", "-"], "x\n");
+    assert_eq!(stdout(&output), "==> standard input <==\nx\n");
+}
+
+#[test]
+fn clustered_short_options() {
+    let dir = TempDir::new();
+    fs::write(dir.join("a"), numbers(1..=3)).unwrap();
+    fs::write(dir.join("b"), numbers(4..=6)).unwrap();
+    let output = head(&dir, &["-qn1", "a", "b"], "");
+    assert!(output.status.success());
+    assert_eq!(stdout(&output), "1\n4\n");
+    let output = head(&dir, &["-vn", "-1", "a"], "");
+    assert_eq!(stdout(&output), "==> a <==\n1\n2\n");
+}
+
+#[test]
+fn missing_file_is_reported() {
+    let dir = TempDir::new();
+    let output = head(&dir, &["missing"], "");
+    assert_eq!(output.status.code(), Some(1));
+    assert!(String::from_utf8_lossy(&output.stderr).starts_with("head: cannot open missing"));
+}
+
+#[test]
+fn no_separator_before_the_first_header_printed() {
+    let dir = TempDir::new();
+    fs::write(dir.join("good"), numbers(1..=2)).unwrap();
+    let output = head(&dir, &["-n", "1", "missing", "good", "missing"], "");
+    assert_eq!(output.status.code(), Some(1));
+    assert_eq!(stdout(&output), "==> good <==\n1\n");
+}
+
+#[test]
+fn invalid_count() {
+    let dir = TempDir::new();
+    let output = head(&dir, &["-n", "many"], "");
+    assert_eq!(output.status.code(), Some(1));
+    assert_eq!(
+        String::from_utf8_lossy(&output.stderr),
+        "head: invalid number: 'many'\n"
+    );
+}
+
+#[test]
+fn counts_take_size_suffixes() {
+    let dir = TempDir::new();
+    let input = "x".repeat(3000);
+    assert_eq!(stdout(&head(&dir, &["-c", "1K"], &input)).len(), 1024);
+    assert_eq!(stdout(&head(&dir, &["-c", "2KB"], &input)).len(), 2000);
+}
@@ -0,0 +1,25 @@
+use std::process::Command;
+
+fn hostname(args: &[&str]) -> String {
+    let output = Command::new(env!("CARGO_BIN_EXE_hostname"))
+        .args(args)
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    String::from_utf8(output.stdout).unwrap()
+}
+
+#[test]
+fn prints_a_name() {
+    let name = hostname(&[]);
+    assert!(name.ends_with('\n'));
+    assert!(!name.trim_end().is_empty());
+}
+
+#[test]
+fn short_name_strips_the_domain() {
+    let name = hostname(&[]);
+    let short = hostname(&["-s"]);
+    assert!(!short.contains('.'));
+    assert_eq!(short.trim_end(), name.trim_end().split('.').next().unwrap());
+}
@@ -0,0 +1,72 @@
+mod common;
+
+use common::stdout;
+use coreutils_common::users::group_name;
+use coreutils_common::users::user_name;
+use std::process::Command;
+use std::process::Output;
+
+fn id(args: &[&str]) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_id"))
+        .args(args)
+        .output()
+        .unwrap()
+}
+
+#[test]
+fn user_id() {
+    let output = id(&["-u"]);
+    assert!(output.status.success());
+    assert_eq!(stdout(&output), format!("{}\n", unsafe { libc::geteuid() }));
+    let output = id(&["-u", "-r"]);
+    assert_eq!(stdout(&output), format!("{}\n", unsafe { libc::getuid() }));
+}
+
+#[test]
+fn group_id() {
+    let output = id(&["-g"]);
+    assert!(output.status.success());
+    assert_eq!(stdout(&output), format!("{}\n", unsafe { libc::getegid() }));
+}
+
+#[test]
+fn names() {
+    let euid = unsafe { libc::geteuid() };
+    let egid = unsafe { libc::getegid() };
+    assert_eq!(
+        stdout(&id(&["-u", "-n"])),
+        format!("{}\n", user_name(euid).unwrap())
+    );
+    assert_eq!(
+        stdout(&id(&["-g", "--name"])),
+        format!("{}\n", group_name(egid).unwrap())
+    );
+}
+
+#[test]
+fn all_groups_start_with_effective_group() {
+    let output = id(&["-G"]);
+    let groups = stdout(&output);
+    let first = groups.split_whitespace().next().unwrap();
+    assert_eq!(first, unsafe { libc::getegid() }.to_string());
+}
+
+#[test]
+fn default_format() {
+    let uid = unsafe { libc::getuid() };
+    let output = id(&[]);
+    let line = stdout(&output);
+    assert!(line.starts_with(&format!("uid={}(", uid)));
+    assert!(line.contains(" gid="));
+    assert!(line.contains(" groups="));
+}
+
+#[test]
+fn conflicting_choices() {
+    let output = id(&["-u", "-G"]);
+    assert_eq!(output.status.code(), Some(1));
+    assert_eq!(
+        String::from_utf8_lossy(&output.stderr),
+        "id: cannot print \"only\" of more than one choice\n"
+    );
+}
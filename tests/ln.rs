@@ -0,0 +1,162 @@
+mod common;
+
+use common::run_with_stdin;
+use common::stderr;
+use common::TempDir;
+use std::fs;
+use std::os::unix::fs::symlink;
+use std::os::unix::fs::MetadataExt;
+use std::path::Path;
+use std::process::Output;
+
+fn ln(dir: &TempDir, args: &[&str]) -> Output {
+    run_with_stdin(env!("CARGO_BIN_EXE_ln"), dir.path(), args, b"")
+}
+
+#[test]
+fn hard_link() {
+    let dir = TempDir::new();
+    fs::write(dir.join("a"), "a").unwrap();
+    let output = ln(&dir, &["a", "b"]);
+    assert!(output.status.success(), "{}", stderr(&output));
+    let a = fs::metadata(dir.join("a")).unwrap();
+    let b = fs::symlink_metadata(dir.join("b")).unwrap();
+    assert_eq!(a.ino(), b.ino());
+    assert_eq!(a.nlink(), 2);
+}
+
+#[test]
+fn symbolic_link() {
+    let dir = TempDir::new();
+    fs::write(dir.join("a"), "a").unwrap();
+    let output = ln(&dir, &["-s", "a", "b"]);
+    assert!(output.status.success(), "{}", stderr(&output));
+    assert_eq!(fs::read_link(dir.join("b")).unwrap(), Path::new("a"));
+    assert_eq!(fs::read_to_string(dir.join("b")).unwrap(), "a");
+}
+
+#[test]
+fn relative_symbolic_link() {
+    let dir = TempDir::new();
+    fs::create_dir_all(dir.join("data/files")).unwrap();
+    fs::create_dir_all(dir.join("links/deep")).unwrap();
+    fs::write(dir.join("data/files/a"), "a").unwrap();
+    let output = ln(&dir, &["-s", "-r", "data/files/a", "links/deep/a"]);
+    assert!(output.status.success(), "{}", stderr(&output));
+    assert_eq!(
+        fs::read_link(dir.join("links/deep/a")).unwrap(),
+        Path::new("../../data/files/a")
+    );
+    assert_eq!(fs::read_to_string(dir.join("links/deep/a")).unwrap(), "a");
+}
+
+#[test]
+fn relative_link_replaces_a_symlink() {
+    let dir = TempDir::new();
+    fs::create_dir_all(dir.join("elsewhere/deep")).unwrap();
+    fs::write(dir.join("a"), "a").unwrap();
+    fs::write(dir.join("elsewhere/deep/old"), "old").unwrap();
+    symlink("elsewhere/deep/old", dir.join("link")).unwrap();
+    let output = ln(&dir, &["-srf", "a", "link"]);
+    assert!(output.status.success(), "{}", stderr(&output));
+    assert_eq!(fs::read_link(dir.join("link")).unwrap(), Path::new("a"));
+    assert_eq!(fs::read_to_string(dir.join("link")).unwrap(), "a");
+}
+
+#[test]
+fn relative_link_into_a_directory() {
+    let dir = TempDir::new();
+    fs::create_dir(dir.join("d")).unwrap();
+    fs::write(dir.join("a"), "a").unwrap();
+    let output = ln(&dir, &["-s", "--relative", "a", "d"]);
+    assert!(output.status.success(), "{}", stderr(&output));
+    assert_eq!(fs::read_link(dir.join("d/a")).unwrap(), Path::new("../a"));
+}
+
+#[test]
+fn existing_destination() {
+    let dir = TempDir::new();
+    fs::write(dir.join("a"), "a").unwrap();
+    fs::write(dir.join("b"), "b").unwrap();
+    let output = ln(&dir, &["-s", "a", "b"]);
+    assert_eq!(output.status.code(), Some(1));
+    assert!(stderr(&output).starts_with("ln: failed to create symbolic link 'b': "));
+    let output = ln(&dir, &["-s", "-f", "a", "b"]);
+    assert!(output.status.success());
+    assert_eq!(fs::read_link(dir.join("b")).unwrap(), Path::new("a"));
+}
+
+#[test]
+fn clustered_short_options() {
+    let dir = TempDir::new();
+    fs::write(dir.join("a"), "a").unwrap();
+    fs::write(dir.join("b"), "b").unwrap();
+    let output = ln(&dir, &["-sf", "a", "b"]);
+    assert!(output.status.success(), "{}", stderr(&output));
+    assert_eq!(fs::read_link(dir.join("b")).unwrap(), Path::new("a"));
+    assert!(!dir.join("f").exists());
+}
+
+#[test]
+fn single_operand_links_into_current_directory() {
+    let dir = TempDir::new();
+    fs::create_dir(dir.join("d")).unwrap();
+    fs::write(dir.join("d/a"), "a").unwrap();
+    let output = ln(&dir, &["-s", "-v", "d/a"]);
+    assert!(output.status.success(), "{}", stderr(&output));
+    assert_eq!(fs::read_link(dir.join("a")).unwrap(), Path::new("d/a"));
+    assert_eq!(String::from_utf8_lossy(&output.stdout), "'./a' -> 'd/a'\n");
+}
+
+#[test]
+fn relative_needs_symbolic() {
+    let dir = TempDir::new();
+    let output = ln(&dir, &["-r", "a", "b"]);
+    assert_eq!(output.status.code(), Some(1));
+    assert_eq!(
+        stderr(&output),
+        "ln: cannot do --relative without --symbolic\n"
+    );
+}
+
+#[test]
+fn backup_replaces_without_force() {
+    let dir = TempDir::new();
+    fs::write(dir.join("a"), "a").unwrap();
+    fs::write(dir.join("b"), "b").unwrap();
+    let output = ln(&dir, &["-s", "--backup=simple", "a", "b"]);
+    assert!(output.status.success(), "{}", stderr(&output));
+    assert_eq!(fs::read_link(dir.join("b")).unwrap(), Path::new("a"));
+    assert_eq!(fs::read_to_string(dir.join("b~")).unwrap(), "b");
+}
+
+#[test]
+fn force_refuses_to_replace_the_target_itself() {
+    let dir = TempDir::new();
+    fs::write(dir.join("a"), "a").unwrap();
+    let output = ln(&dir, &["-f", "a", "a"]);
+    assert_eq!(output.status.code(), Some(1));
+    assert_eq!(stderr(&output), "ln: 'a' and 'a' are the same file\n");
+    assert_eq!(fs::read_to_string(dir.join("a")).unwrap(), "a");
+
+    fs::create_dir(dir.join("d")).unwrap();
+    fs::write(dir.join("d/x"), "x").unwrap();
+    let output = ln(&dir, &["-s", "-f", "x", "d/x"]);
+    assert_eq!(output.status.code(), Some(1));
+    assert_eq!(fs::read_to_string(dir.join("d/x")).unwrap(), "x");
+    assert!(!fs::symlink_metadata(dir.join("d/x"))
+        .unwrap()
+        .file_type()
+        .is_symlink());
+}
+
+#[test]
+fn backup_refuses_to_back_up_the_target_itself() {
+    let dir = TempDir::new();
+    fs::write(dir.join("a"), "a").unwrap();
+    let output = ln(&dir, &["--backup", "-f", "a", "a"]);
+    assert_eq!(output.status.code(), Some(1));
+    assert_eq!(stderr(&output), "ln: 'a' and 'a' are the same file\n");
+    assert_eq!(fs::read_to_string(dir.join("a")).unwrap(), "a");
+    assert!(!dir.join("a~").exists());
+}
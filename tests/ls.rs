@@ -0,0 +1,230 @@
+mod common;
+
+use common::run_with_stdin;
+use common::stdout;
+use common::TempDir;
+use std::fs;
+use std::os::unix::fs::PermissionsExt;
+use std::path::Path;
+use std::process::Command;
+use std::process::Output;
+use std::time::Duration;
+use std::time::SystemTime;
+
+fn ls(dir: &Path, args: &[&str]) -> Output {
+    run_with_stdin(env!("CARGO_BIN_EXE_ls"), dir, args, b"")
+}
+
+fn make_files(dir: &TempDir) {
+    fs::write(dir.join("small"), b"1").unwrap();
+    fs::write(dir.join("big"), vec![b'2'; 3000]).unwrap();
+    fs::write(dir.join(".hidden"), b"").unwrap();
+    fs::create_dir(dir.join("sub")).unwrap();
+    fs::write(dir.join("sub/inner"), b"").unwrap();
+    for file in &["small", "big", "sub/inner"] {
+        fs::set_permissions(dir.join(file), fs::Permissions::from_mode(0o644)).unwrap();
+    }
+}
+
+#[test]
+fn dotfiles_need_all() {
+    let dir = TempDir::new();
+    make_files(&dir);
+    let output = ls(dir.path(), &[]);
+    assert!(output.status.success());
+    assert_eq!(stdout(&output), "big\nsmall\nsub\n");
+    let output = ls(dir.path(), &["-a"]);
+    assert_eq!(stdout(&output), ".\n..\n.hidden\nbig\nsmall\nsub\n");
+    let output = ls(dir.path(), &["-A"]);
+    assert_eq!(stdout(&output), ".hidden\nbig\nsmall\nsub\n");
+}
+
+#[test]
+fn long_format_fields() {
+    let dir = TempDir::new();
+    make_files(&dir);
+    let output = ls(dir.path(), &["-l", "big", "sub"]);
+    assert!(output.status.success());
+    let stdout = stdout(&output);
+    let lines: Vec<&str> = stdout.lines().collect();
+    let fields: Vec<&str> = lines[0].split_whitespace().collect();
+    assert_eq!(fields[0], "-rw-r--r--");
+    assert_eq!(fields[1], "1");
+    assert_eq!(fields[4], "3000");
+    assert_eq!(fields[8], "big");
+    assert_eq!(&lines[1..], ["", "sub:", "total 0", lines[4]]);
+    assert!(lines[4].starts_with("-rw-r--r-- 1 "));
+    assert!(lines[4].ends_with(" inner"));
+}
+
+#[test]
+fn clustered_short_options() {
+    let dir = TempDir::new();
+    make_files(&dir);
+    let output = ls(dir.path(), &["-la"]);
+    assert!(output.status.success());
+    let listing = stdout(&output);
+    let names: Vec<&str> = listing
+        .lines()
+        .skip(1)
+        .map(|line| line.rsplit(' ').next().unwrap())
+        .collect();
+    assert_eq!(names, [".", "..", ".hidden", "big", "small", "sub"]);
+    let output = ls(dir.path(), &["-rS1", "big", "small"]);
+    assert_eq!(stdout(&output), "small\nbig\n");
+}
+
+#[test]
+fn sort_by_size_and_reverse() {
+    let dir = TempDir::new();
+    make_files(&dir);
+    let output = ls(dir.path(), &["-S", "big", "small"]);
+    assert_eq!(stdout(&output), "big\nsmall\n");
+    let output = ls(dir.path(), &["-r"]);
+    assert_eq!(stdout(&output), "sub\nsmall\nbig\n");
+}
+
+#[test]
+fn recursive() {
+    let dir = TempDir::new();
+    make_files(&dir);
+    let output = ls(dir.path(), &["-R", "-1"]);
+    assert_eq!(stdout(&output), ".:\nbig\nsmall\nsub\n\n./sub:\ninner\n");
+}
+
+#[test]
+fn missing_operand() {
+    let dir = TempDir::new();
+    let output = ls(dir.path(), &["missing"]);
+    assert_eq!(output.status.code(), Some(2));
+    assert!(String::from_utf8_lossy(&output.stderr).contains("cannot access 'missing'"));
+}
+
+#[test]
+fn columns_down_then_across() {
+    let dir = TempDir::new();
+    for name in &["a", "bbbbb", "c", "d", "ee"] {
+        fs::write(dir.join(name), b"").unwrap();
+    }
+    let output = ls(dir.path(), &["-C", "-w", "13"]);
+    assert_eq!(stdout(&output), "a      c  ee\nbbbbb  d\n");
+    let output = ls(dir.path(), &["-C", "--width=10"]);
+    assert_eq!(stdout(&output), "a      d\nbbbbb  ee\nc\n");
+    let output = ls(dir.path(), &["-C", "-w", "0"]);
+    assert_eq!(stdout(&output), "a  bbbbb  c  d  ee\n");
+}
+
+#[test]
+fn directory_lists_itself() {
+    let dir = TempDir::new();
+    make_files(&dir);
+    let output = ls(dir.path(), &["-d"]);
+    assert_eq!(stdout(&output), ".\n");
+    let output = ls(dir.path(), &["-d", "-R", "sub"]);
+    assert_eq!(stdout(&output), "sub\n");
+    let output = ls(dir.path(), &["-l", "-d", "sub"]);
+    assert!(stdout(&output).starts_with("d"));
+}
+
+#[test]
+fn directory_with_several_paths() {
+    let dir = TempDir::new();
+    make_files(&dir);
+    fs::create_dir(dir.join("other")).unwrap();
+    // What a shell expands `ls -d */ big` to.
+    let output = ls(dir.path(), &["-d", "sub/", "other/", "big"]);
+    assert_eq!(stdout(&output), "big\nother/\nsub/\n");
+}
+
+#[test]
+fn color_never_and_always() {
+    let dir = TempDir::new();
+    make_files(&dir);
+    let output = ls(dir.path(), &["--color=never"]);
+    assert!(!output.stdout.contains(&b'\x1b'));
+    assert_eq!(stdout(&output), "big\nsmall\nsub\n");
+    let output = Command::new(env!("CARGO_BIN_EXE_ls"))
+        .current_dir(dir.path())
+        .args(["--color=always"])
+        .env_remove("LS_COLORS")
+        .output()
+        .unwrap();
+    assert_eq!(stdout(&output), "big\nsmall\n\x1b[01;34msub\x1b[0m\n");
+    let output = Command::new(env!("CARGO_BIN_EXE_ls"))
+        .current_dir(dir.path())
+        .args(["--color", "sub"])
+        .env("LS_COLORS", "fi=33")
+        .output()
+        .unwrap();
+    assert_eq!(stdout(&output), "\x1b[33minner\x1b[0m\n");
+    // Not a terminal, so auto is the same as never.
+    let output = ls(dir.path(), &["--color=auto"]);
+    assert_eq!(stdout(&output), "big\nsmall\nsub\n");
+}
+
+#[test]
+fn glob_expands_only_when_asked() {
+    let dir = TempDir::new();
+    for name in &["b.txt", "a.txt", "c.log", ".hidden.txt"] {
+        fs::write(dir.join(name), b"").unwrap();
+    }
+    let output = ls(dir.path(), &["--glob", "*.txt"]);
+    assert!(output.status.success());
+    assert_eq!(stdout(&output), "a.txt\nb.txt\n");
+    let output = ls(dir.path(), &["*.txt"]);
+    assert_eq!(output.status.code(), Some(2));
+    let output = ls(dir.path(), &["--glob", "\\*.txt", "*.none"]);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("cannot access '*.txt'"));
+    assert!(stderr.contains("cannot access '*.none'"));
+}
+
+#[test]
+fn dereference_shows_the_target() {
+    let dir = TempDir::new();
+    make_files(&dir);
+    std::os::unix::fs::symlink("big", dir.join("link")).unwrap();
+    let output = ls(dir.path(), &["-l", "link"]);
+    assert!(stdout(&output).ends_with(" link -> big\n"));
+    let output = ls(dir.path(), &["-l", "-L", "link"]);
+    let stdout = stdout(&output);
+    assert!(stdout.starts_with("-rw-r--r--"));
+    assert!(stdout.contains(" 3000 "));
+    assert!(stdout.ends_with(" link\n"));
+}
+
+#[test]
+fn recursive_dereference_stops_at_cycles() {
+    let dir = TempDir::new();
+    fs::create_dir_all(dir.join("top/sub")).unwrap();
+    std::os::unix::fs::symlink("..", dir.join("top/sub/up")).unwrap();
+    let output = ls(dir.path(), &["-R", "-L", "top"]);
+    assert_eq!(output.status.code(), Some(1));
+    assert_eq!(stdout(&output), "top:\nsub\n\ntop/sub:\nup\n");
+    assert_eq!(
+        String::from_utf8_lossy(&output.stderr),
+        "ls: warning: not following 'top/sub/up': directory cycle\n"
+    );
+}
+
+#[test]
+fn time_styles() {
+    let dir = TempDir::new();
+    let file = fs::File::create(dir.join("old")).unwrap();
+    file.set_modified(SystemTime::UNIX_EPOCH + Duration::from_secs(946_782_245))
+        .unwrap();
+    let ls_utc = |style| {
+        let output = Command::new(env!("CARGO_BIN_EXE_ls"))
+            .current_dir(dir.path())
+            .args(["-l", style, "old"])
+            .env("TZ", "UTC")
+            .output()
+            .unwrap();
+        assert!(output.status.success());
+        stdout(&output)
+    };
+    assert!(ls_utc("--time-style=long-iso").ends_with(" 0 2000-01-02 03:04 old\n"));
+    assert!(ls_utc("--time-style=+%Y").ends_with(" 0 2000 old\n"));
+    assert!(ls_utc("--time-style=locale").ends_with(" 0 Jan  2  2000 old\n"));
+    assert!(!ls(dir.path(), &["--time-style=short"]).status.success());
+}
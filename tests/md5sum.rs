@@ -0,0 +1,87 @@
+mod common;
+
+use common::run_with_stdin;
+use common::TempDir;
+use std::fs;
+use std::process::Output;
+
+fn md5sum(dir: &TempDir, args: &[&str], stdin: &[u8]) -> Output {
+    run_with_stdin(env!("CARGO_BIN_EXE_md5sum"), dir.path(), args, stdin)
+}
+
+const HELLO: &str = "b1946ac92492d2347c6235b4d2611184";
+const EMPTY: &str = "d41d8cd98f00b204e9800998ecf8427e";
+
+#[test]
+fn known_hashes() {
+    let dir = TempDir::new();
+    fs::write(dir.join("hello"), "hello\n").unwrap();
+    fs::write(dir.join("empty"), "").unwrap();
+    let output = md5sum(&dir, &["hello", "empty"], b"");
+    assert!(output.status.success());
+    assert_eq!(
+        String::from_utf8_lossy(&output.stdout),
+        format!("{}  hello\n{}  empty\n", HELLO, EMPTY)
+    );
+}
+
+#[test]
+fn stdin_and_tag() {
+    let dir = TempDir::new();
+    let output = md5sum(&dir, &[], b"hello\n");
+    assert_eq!(
+        String::from_utf8_lossy(&output.stdout),
+        format!("{}  -\n", HELLO)
+    );
+    let output = md5sum(&dir, &["--tag", "-"], b"");
+    assert_eq!(
+        String::from_utf8_lossy(&output.stdout),
+        format!("MD5 (-) = {}\n", EMPTY)
+    );
+}
+
+#[test]
+fn check_file() {
+    let dir = TempDir::new();
+    fs::write(dir.join("hello"), "hello\n").unwrap();
+    fs::write(dir.join("empty"), "").unwrap();
+    fs::write(
+        dir.join("sums"),
+        format!("{}  hello\nMD5 (empty) = {}\n", HELLO, EMPTY),
+    )
+    .unwrap();
+    let output = md5sum(&dir, &["-c", "sums"], b"");
+    assert!(output.status.success());
+    assert_eq!(
+        String::from_utf8_lossy(&output.stdout),
+        "hello: OK\nempty: OK\n"
+    );
+}
+
+#[test]
+fn check_failures() {
+    let dir = TempDir::new();
+    fs::write(dir.join("hello"), "changed\n").unwrap();
+    let sums = format!("{}  hello\ngarbage\n{}  missing\n", HELLO, EMPTY);
+    let output = md5sum(&dir, &["--check"], sums.as_bytes());
+    assert_eq!(output.status.code(), Some(1));
+    assert_eq!(
+        String::from_utf8_lossy(&output.stdout),
+        "hello: FAILED\nmissing: FAILED open or read\n"
+    );
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("md5sum: WARNING: 1 line is improperly formatted\n"));
+    assert!(stderr.contains("md5sum: WARNING: 1 listed file could not be read\n"));
+    assert!(stderr.ends_with("md5sum: WARNING: 1 computed checksum did NOT match\n"));
+}
+
+#[test]
+fn check_without_sums() {
+    let dir = TempDir::new();
+    let output = md5sum(&dir, &["-c"], b"nothing here\n");
+    assert_eq!(output.status.code(), Some(1));
+    assert_eq!(
+        String::from_utf8_lossy(&output.stderr),
+        "md5sum: -: no properly formatted MD5 checksum lines found\n"
+    );
+}
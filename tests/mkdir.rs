@@ -0,0 +1,87 @@
+mod common;
+
+use common::run_with_stdin;
+use common::TempDir;
+use std::fs;
+use std::os::unix::fs::PermissionsExt;
+use std::path::Path;
+use std::process::Output;
+
+fn mkdir(dir: &TempDir, args: &[&str]) -> Output {
+    run_with_stdin(env!("CARGO_BIN_EXE_mkdir"), dir.path(), args, b"")
+}
+
+fn mode(path: impl AsRef<Path>) -> u32 {
+    fs::metadata(path).unwrap().permissions().mode() & 0o7777
+}
+
+#[test]
+fn creates_a_directory() {
+    let dir = TempDir::new();
+    let output = mkdir(&dir, &["new"]);
+    assert!(output.status.success());
+    assert!(dir.join("new").is_dir());
+}
+
+#[test]
+fn existing_directory_is_an_error_without_p() {
+    let dir = TempDir::new();
+    fs::create_dir(dir.join("new")).unwrap();
+    let output = mkdir(&dir, &["new"]);
+    assert_eq!(output.status.code(), Some(1));
+    assert!(String::from_utf8_lossy(&output.stderr)
+        .starts_with("mkdir: cannot create directory 'new': "));
+}
+
+#[test]
+fn missing_parent_is_an_error_without_p() {
+    let dir = TempDir::new();
+    let output = mkdir(&dir, &["a/b", "c"]);
+    assert_eq!(output.status.code(), Some(1));
+    assert!(!dir.join("a").exists());
+    assert!(dir.join("c").is_dir());
+}
+
+#[test]
+fn parents_creates_nested_paths() {
+    let dir = TempDir::new();
+    let output = mkdir(&dir, &["-p", "a/b/c"]);
+    assert!(output.status.success());
+    assert!(dir.join("a/b/c").is_dir());
+    let output = mkdir(&dir, &["-p", "a/b/c", "a/b/"]);
+    assert!(output.status.success());
+}
+
+#[test]
+fn verbose_reports_every_created_directory() {
+    let dir = TempDir::new();
+    fs::create_dir(dir.join("a")).unwrap();
+    let output = mkdir(&dir, &["-p", "-v", "a/b/c"]);
+    assert_eq!(
+        String::from_utf8_lossy(&output.stdout),
+        "mkdir: created directory 'a/b'\nmkdir: created directory 'a/b/c'\n"
+    );
+}
+
+#[test]
+fn mode_is_applied() {
+    let dir = TempDir::new();
+    let output = mkdir(&dir, &["-m", "700", "private"]);
+    assert!(output.status.success());
+    assert_eq!(mode(dir.join("private")), 0o700);
+    let output = mkdir(&dir, &["-p", "--mode=751", "x/y"]);
+    assert!(output.status.success());
+    assert_eq!(mode(dir.join("x/y")), 0o751);
+}
+
+#[test]
+fn invalid_mode() {
+    let dir = TempDir::new();
+    let output = mkdir(&dir, &["-m", "99", "new"]);
+    assert_eq!(output.status.code(), Some(1));
+    assert_eq!(
+        String::from_utf8_lossy(&output.stderr),
+        "mkdir: invalid mode '99'\n"
+    );
+    assert!(!dir.join("new").exists());
+}
@@ -0,0 +1,68 @@
+mod common;
+
+use common::run_with_stdin;
+use common::TempDir;
+use std::collections::HashSet;
+use std::fs;
+use std::os::unix::fs::PermissionsExt;
+use std::path::Path;
+use std::process::Output;
+
+fn mktemp(dir: &Path, args: &[&str]) -> Output {
+    run_with_stdin(env!("CARGO_BIN_EXE_mktemp"), dir, args, b"")
+}
+
+/// The single path `mktemp` printed.
+fn created(output: &Output) -> String {
+    assert!(output.status.success(), "{:?}", output);
+    String::from_utf8(output.stdout.clone())
+        .unwrap()
+        .trim_end()
+        .to_owned()
+}
+
+#[test]
+fn creates_a_file() {
+    let dir = TempDir::new();
+    let name = created(&mktemp(dir.path(), &["file.XXXXXX"]));
+    assert!(name.starts_with("file.") && name.len() == 11);
+    let metadata = fs::metadata(dir.join(&name)).unwrap();
+    assert!(metadata.is_file());
+    assert_eq!(metadata.len(), 0);
+    assert_eq!(metadata.permissions().mode() & 0o777, 0o600);
+}
+
+#[test]
+fn creates_a_directory() {
+    let dir = TempDir::new();
+    let dir_arg = dir.path().to_str().unwrap();
+    let path = created(&mktemp(dir.path(), &["-d", "-p", dir_arg, "d.XXX"]));
+    assert!(path.starts_with(dir_arg));
+    let metadata = fs::metadata(&path).unwrap();
+    assert!(metadata.is_dir());
+    assert_eq!(metadata.permissions().mode() & 0o777, 0o700);
+}
+
+#[test]
+fn names_are_unique() {
+    let dir = TempDir::new();
+    let names: HashSet<String> = (0..20)
+        .map(|_| created(&mktemp(dir.path(), &["u.XXX"])))
+        .collect();
+    assert_eq!(names.len(), 20);
+    assert_eq!(fs::read_dir(dir.path()).unwrap().count(), 20);
+}
+
+#[test]
+fn dry_run_creates_nothing() {
+    let dir = TempDir::new();
+    let name = created(&mktemp(dir.path(), &["-u", "dry.XXXX"]));
+    assert!(name.starts_with("dry."));
+    assert!(!dir.join(name).exists());
+}
+
+#[test]
+fn too_few_xs() {
+    let dir = TempDir::new();
+    assert!(!mktemp(dir.path(), &["a.XX"]).status.success());
+}
@@ -0,0 +1,156 @@
+mod common;
+
+use common::mtime;
+use common::run_with_stdin;
+use common::stderr;
+use common::TempDir;
+use std::fs;
+use std::os::unix::fs::MetadataExt;
+use std::path::Path;
+use std::process::Command;
+use std::process::Output;
+
+fn mv(dir: &TempDir, args: &[&str], stdin: &str) -> Output {
+    run_with_stdin(env!("CARGO_BIN_EXE_mv"), dir.path(), args, stdin.as_bytes())
+}
+
+#[test]
+fn renames_a_file() {
+    let dir = TempDir::new();
+    fs::write(dir.join("a"), "contents").unwrap();
+    let output = mv(&dir, &["a", "b"], "");
+    assert!(output.status.success(), "{}", stderr(&output));
+    assert!(!dir.join("a").exists());
+    assert_eq!(fs::read_to_string(dir.join("b")).unwrap(), "contents");
+}
+
+#[test]
+fn moves_into_an_existing_directory() {
+    let dir = TempDir::new();
+    fs::create_dir_all(dir.join("from")).unwrap();
+    fs::create_dir_all(dir.join("to")).unwrap();
+    fs::write(dir.join("from/a"), "a").unwrap();
+    fs::create_dir(dir.join("from/sub")).unwrap();
+    let output = mv(&dir, &["-v", "from/a", "from/sub", "to"], "");
+    assert!(output.status.success(), "{}", stderr(&output));
+    assert_eq!(fs::read_to_string(dir.join("to/a")).unwrap(), "a");
+    assert!(dir.join("to/sub").is_dir());
+    assert!(!dir.join("from/a").exists());
+    assert_eq!(
+        String::from_utf8_lossy(&output.stdout),
+        "renamed 'from/a' -> 'to/a'\nrenamed 'from/sub' -> 'to/sub'\n"
+    );
+}
+
+#[test]
+fn no_clobber() {
+    let dir = TempDir::new();
+    fs::write(dir.join("a"), "new").unwrap();
+    fs::write(dir.join("b"), "old").unwrap();
+    let output = mv(&dir, &["-n", "a", "b"], "");
+    assert!(output.status.success());
+    assert_eq!(fs::read_to_string(dir.join("b")).unwrap(), "old");
+    assert!(dir.join("a").exists());
+}
+
+#[test]
+fn interactive() {
+    let dir = TempDir::new();
+    fs::write(dir.join("a"), "a").unwrap();
+    fs::write(dir.join("b"), "b").unwrap();
+    fs::write(dir.join("c"), "c").unwrap();
+    fs::write(dir.join("d"), "d").unwrap();
+    let output = mv(&dir, &["-i", "a", "b"], "n\n");
+    assert_eq!(stderr(&output), "mv: overwrite 'b'? ");
+    assert_eq!(fs::read_to_string(dir.join("b")).unwrap(), "b");
+    mv(&dir, &["-i", "c", "d"], "y\n");
+    assert_eq!(fs::read_to_string(dir.join("d")).unwrap(), "c");
+}
+
+#[test]
+fn refuses_to_move_into_itself() {
+    let dir = TempDir::new();
+    fs::create_dir(dir.join("d")).unwrap();
+    let output = mv(&dir, &["d", "d/inner"], "");
+    assert_eq!(output.status.code(), Some(1));
+    assert_eq!(
+        stderr(&output),
+        "mv: cannot move 'd' to a subdirectory of itself, 'd/inner'\n"
+    );
+}
+
+#[test]
+fn missing_source() {
+    let dir = TempDir::new();
+    let output = mv(&dir, &["missing", "b"], "");
+    assert_eq!(output.status.code(), Some(1));
+    assert!(stderr(&output).starts_with("mv: cannot stat 'missing': "));
+}
+
+#[test]
+fn moves_across_file_systems() {
+    // Best effort: this needs a second file system, and /dev/shm usually is one.
+    let shm = Path::new("/dev/shm");
+    let dir = TempDir::new();
+    let same_device = fs::metadata(shm).map_or(true, |shm| {
+        shm.dev() == fs::metadata(dir.path()).unwrap().dev()
+    });
+    if same_device {
+        return;
+    }
+    let dest = shm.join(format!("rust-coreutils-mv-{}", std::process::id()));
+    fs::create_dir_all(dir.join("tree/sub")).unwrap();
+    fs::write(dir.join("tree/sub/f"), "f").unwrap();
+    let status = Command::new(env!("CARGO_BIN_EXE_touch"))
+        .arg("-t")
+        .arg("200001010000")
+        .arg(dir.join("tree/sub/f"))
+        .status()
+        .unwrap();
+    assert!(status.success());
+
+    let original_mtime = mtime(dir.join("tree/sub/f"));
+
+    let output = mv(&dir, &["tree", dest.to_str().unwrap()], "");
+    let moved = fs::read_to_string(dest.join("sub/f"));
+    let moved_mtime = moved.as_ref().ok().map(|_| mtime(dest.join("sub/f")));
+    let _ = fs::remove_dir_all(&dest);
+    assert!(output.status.success(), "{}", stderr(&output));
+    assert_eq!(moved.unwrap(), "f");
+    assert_eq!(moved_mtime, Some(original_mtime));
+    assert!(!dir.join("tree").exists());
+}
+
+#[test]
+fn backup_before_replacing() {
+    let dir = TempDir::new();
+    fs::write(dir.join("a"), "new").unwrap();
+    fs::write(dir.join("b"), "old").unwrap();
+    let output = mv(&dir, &["--backup=numbered", "a", "b"], "");
+    assert!(output.status.success(), "{}", stderr(&output));
+    assert_eq!(fs::read_to_string(dir.join("b")).unwrap(), "new");
+    assert_eq!(fs::read_to_string(dir.join("b.~1~")).unwrap(), "old");
+    assert!(!dir.join("a").exists());
+}
+
+#[test]
+fn strip_trailing_slashes_moves_the_symlink() {
+    let dir = TempDir::new();
+    fs::create_dir(dir.join("dir")).unwrap();
+    std::os::unix::fs::symlink("dir", dir.join("link")).unwrap();
+    let output = mv(&dir, &["--strip-trailing-slashes", "link/", "moved"], "");
+    assert!(output.status.success(), "{}", stderr(&output));
+    assert_eq!(fs::read_link(dir.join("moved")).unwrap(), Path::new("dir"));
+    assert!(dir.join("dir").is_dir());
+}
+
+#[test]
+fn refuses_to_move_a_file_onto_itself() {
+    let dir = TempDir::new();
+    fs::write(dir.join("a"), "a").unwrap();
+    let output = mv(&dir, &["-b", "a", "a"], "");
+    assert_eq!(output.status.code(), Some(1));
+    assert_eq!(stderr(&output), "mv: 'a' and 'a' are the same file\n");
+    assert_eq!(fs::read_to_string(dir.join("a")).unwrap(), "a");
+    assert!(!dir.join("a~").exists());
+}
@@ -0,0 +1,77 @@
+mod common;
+
+use common::run_with_stdin;
+use common::stdout;
+use common::TempDir;
+use std::fs;
+use std::process::Output;
+
+fn nl(dir: &TempDir, args: &[&str], stdin: &str) -> Output {
+    run_with_stdin(env!("CARGO_BIN_EXE_nl"), dir.path(), args, stdin.as_bytes())
+}
+
+#[test]
+fn numbers_nonempty_lines_by_default() {
+    let dir = TempDir::new();
+    let output = nl(&dir, &[], "a\n\nb\n");
+    assert!(output.status.success());
+    assert_eq!(stdout(&output), "     1\ta\n       \n     2\tb\n");
+}
+
+#[test]
+fn body_style_t() {
+    let dir = TempDir::new();
+    let output = nl(&dir, &["-b", "t"], "a\n\nb\n");
+    assert_eq!(stdout(&output), "     1\ta\n       \n     2\tb\n");
+}
+
+#[test]
+fn body_style_a() {
+    let dir = TempDir::new();
+    let output = nl(&dir, &["-ba"], "a\n\nb\n");
+    assert_eq!(stdout(&output), "     1\ta\n     2\t\n     3\tb\n");
+}
+
+#[test]
+fn body_style_n() {
+    let dir = TempDir::new();
+    let output = nl(&dir, &["-bn", "-s", ": "], "a\n");
+    assert_eq!(stdout(&output), "        a\n");
+}
+
+#[test]
+fn width_separator_and_increment() {
+    let dir = TempDir::new();
+    let output = nl(&dir, &["-w", "2", "-s", ") ", "-i", "5"], "a\nb\nc");
+    assert_eq!(stdout(&output), " 1) a\n 6) b\n11) c");
+}
+
+#[test]
+fn numbering_continues_across_files() {
+    let dir = TempDir::new();
+    fs::write(dir.join("a"), "one\n").unwrap();
+    fs::write(dir.join("b"), "two\n").unwrap();
+    let output = nl(&dir, &["a", "-", "b"], "piped\n");
+    assert_eq!(stdout(&output), "     1\tone\n     2\tpiped\n     3\ttwo\n");
+}
+
+#[test]
+fn missing_file_is_reported_and_skipped() {
+    let dir = TempDir::new();
+    fs::write(dir.join("a"), "one\n").unwrap();
+    let output = nl(&dir, &["missing", "a"], "");
+    assert_eq!(output.status.code(), Some(1));
+    assert_eq!(stdout(&output), "     1\tone\n");
+    assert!(String::from_utf8_lossy(&output.stderr).starts_with("nl: missing: "));
+}
+
+#[test]
+fn invalid_style() {
+    let dir = TempDir::new();
+    let output = nl(&dir, &["-b", "x"], "");
+    assert_eq!(output.status.code(), Some(1));
+    assert_eq!(
+        String::from_utf8_lossy(&output.stderr),
+        "nl: invalid body numbering style: 'x'\n"
+    );
+}
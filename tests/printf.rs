@@ -0,0 +1,78 @@
+use std::process::Command;
+use std::process::Output;
+
+fn printf(args: &[&str]) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_printf"))
+        .args(args)
+        .output()
+        .unwrap()
+}
+
+fn stdout(args: &[&str]) -> String {
+    let output = printf(args);
+    assert!(output.status.success(), "{:?}", output);
+    String::from_utf8(output.stdout).unwrap()
+}
+
+#[test]
+fn integers() {
+    assert_eq!(stdout(&["%d\\n", "42"]), "42\n");
+    assert_eq!(
+        stdout(&["[%5d|%-3d|%03d]", "1", "2", "3"]),
+        "[    1|2  |003]"
+    );
+    assert_eq!(stdout(&["%x %o %X", "255", "8", "0xab"]), "ff 10 AB");
+    assert_eq!(stdout(&["%d", "-5"]), "-5");
+}
+
+#[test]
+fn strings() {
+    assert_eq!(stdout(&["%s, %s!\\n", "Hello", "world"]), "Hello, world!\n");
+    assert_eq!(stdout(&["[%6.2s]", "abcdef"]), "[    ab]");
+    assert_eq!(stdout(&["%c%c", "xyz", "a"]), "xa");
+}
+
+#[test]
+fn arguments_are_recycled() {
+    assert_eq!(stdout(&["%s=%d\\n", "a", "1", "b", "2"]), "a=1\nb=2\n");
+    assert_eq!(stdout(&["<%s>", "a", "b", "c"]), "<a><b><c>");
+    assert_eq!(stdout(&["%s-%s\\n", "a", "b", "c"]), "a-b\nc-\n");
+}
+
+#[test]
+fn format_without_directives_prints_once() {
+    assert_eq!(stdout(&["plain\\n", "ignored", "args"]), "plain\n");
+    assert_eq!(stdout(&["100%%\\n"]), "100%\n");
+}
+
+#[test]
+fn backslash_c_stops_output() {
+    assert_eq!(stdout(&["%s\\c%s", "a", "b"]), "a");
+}
+
+#[test]
+fn arguments_are_not_options() {
+    assert_eq!(stdout(&["%s %s", "--help", "-x"]), "--help -x");
+    assert_eq!(stdout(&["--", "%s", "-n"]), "-n");
+}
+
+#[test]
+fn invalid_numbers_still_print() {
+    let output = printf(&["%d|", "abc", "7"]);
+    assert_eq!(output.status.code(), Some(1));
+    assert_eq!(String::from_utf8_lossy(&output.stdout), "0|7|");
+    assert_eq!(
+        String::from_utf8_lossy(&output.stderr),
+        "printf: 'abc': expected a numeric value\n"
+    );
+}
+
+#[test]
+fn invalid_conversion() {
+    let output = printf(&["%z"]);
+    assert_eq!(output.status.code(), Some(1));
+    assert_eq!(
+        String::from_utf8_lossy(&output.stderr),
+        "printf: %z: invalid conversion specification\n"
+    );
+}
@@ -0,0 +1,49 @@
+mod common;
+
+use common::TempDir;
+use std::fs;
+use std::os::unix::fs::symlink;
+use std::path::Path;
+use std::process::Command;
+
+fn pwd(dir: &Path, pwd_env: &Path, args: &[&str]) -> String {
+    let output = Command::new(env!("CARGO_BIN_EXE_pwd"))
+        .current_dir(dir)
+        .env("PWD", pwd_env)
+        .args(args)
+        .output()
+        .unwrap();
+    assert!(output.status.success(), "{:?}", output);
+    String::from_utf8(output.stdout).unwrap()
+}
+
+#[test]
+fn logical_and_physical_through_symlink() {
+    let dir = TempDir::new();
+    let real = dir.join("real");
+    let link = dir.join("link");
+    fs::create_dir(&real).unwrap();
+    symlink(&real, &link).unwrap();
+    let real = real.canonicalize().unwrap();
+
+    let logical = format!("{}\n", link.display());
+    let physical = format!("{}\n", real.display());
+    assert_eq!(pwd(&link, &link, &[]), logical);
+    assert_eq!(pwd(&link, &link, &["-L"]), logical);
+    assert_eq!(pwd(&link, &link, &["-P"]), physical);
+    assert_eq!(pwd(&link, &link, &["-P", "-L"]), logical);
+}
+
+#[test]
+fn stale_pwd_falls_back_to_physical() {
+    let dir = TempDir::new();
+    let real = dir.join("real");
+    let other = dir.join("other");
+    fs::create_dir(&real).unwrap();
+    fs::create_dir(&other).unwrap();
+    let physical = format!("{}\n", real.canonicalize().unwrap().display());
+
+    assert_eq!(pwd(&real, &other, &["-L"]), physical);
+    assert_eq!(pwd(&real, &real.join("."), &["-L"]), physical);
+    assert_eq!(pwd(&real, Path::new("relative"), &["-L"]), physical);
+}
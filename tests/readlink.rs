@@ -0,0 +1,94 @@
+mod common;
+
+use common::run_with_stdin;
+use common::TempDir;
+use std::fs;
+use std::os::unix::fs::symlink;
+use std::process::Output;
+
+fn readlink(dir: &TempDir, args: &[&str]) -> Output {
+    run_with_stdin(env!("CARGO_BIN_EXE_readlink"), dir.path(), args, b"")
+}
+
+fn canonical(dir: &TempDir) -> String {
+    dir.path().canonicalize().unwrap().display().to_string()
+}
+
+#[test]
+fn reads_symlink() {
+    let dir = TempDir::new();
+    symlink("some/target", dir.join("link")).unwrap();
+    let output = readlink(&dir, &["link"]);
+    assert!(output.status.success());
+    assert_eq!(String::from_utf8_lossy(&output.stdout), "some/target\n");
+}
+
+#[test]
+fn no_newline() {
+    let dir = TempDir::new();
+    symlink("target", dir.join("link")).unwrap();
+    let output = readlink(&dir, &["-n", "link"]);
+    assert_eq!(String::from_utf8_lossy(&output.stdout), "target");
+}
+
+#[test]
+fn not_a_symlink_fails_quietly() {
+    let dir = TempDir::new();
+    fs::write(dir.join("file"), "").unwrap();
+    let output = readlink(&dir, &["file"]);
+    assert_eq!(output.status.code(), Some(1));
+    assert!(output.stdout.is_empty());
+    assert!(output.stderr.is_empty());
+}
+
+#[test]
+fn canonicalizes_dot_dot() {
+    let dir = TempDir::new();
+    fs::create_dir_all(dir.join("a/b")).unwrap();
+    fs::write(dir.join("a/file"), "").unwrap();
+    symlink("a/b", dir.join("link")).unwrap();
+    let output = readlink(&dir, &["-f", "link/../file"]);
+    assert!(output.status.success());
+    assert_eq!(
+        String::from_utf8_lossy(&output.stdout),
+        format!("{}/a/file\n", canonical(&dir))
+    );
+}
+
+#[test]
+fn canonicalize_allows_missing_last_component() {
+    let dir = TempDir::new();
+    fs::create_dir(dir.join("a")).unwrap();
+    let output = readlink(&dir, &["-f", "a/../missing"]);
+    assert!(output.status.success());
+    assert_eq!(
+        String::from_utf8_lossy(&output.stdout),
+        format!("{}/missing\n", canonical(&dir))
+    );
+    let output = readlink(&dir, &["-f", "nope/missing"]);
+    assert_eq!(output.status.code(), Some(1));
+}
+
+#[test]
+fn canonicalize_existing_requires_every_component() {
+    let dir = TempDir::new();
+    fs::create_dir(dir.join("a")).unwrap();
+    let output = readlink(&dir, &["-e", "a/../missing"]);
+    assert_eq!(output.status.code(), Some(1));
+    let output = readlink(&dir, &["-e", "a/.."]);
+    assert!(output.status.success());
+    assert_eq!(
+        String::from_utf8_lossy(&output.stdout),
+        format!("{}\n", canonical(&dir))
+    );
+}
+
+#[test]
+fn zero_terminates_with_nul() {
+    let dir = TempDir::new();
+    symlink("one", dir.join("a")).unwrap();
+    symlink("two\nlines", dir.join("b")).unwrap();
+    let output = readlink(&dir, &["-z", "a", "b"]);
+    assert!(output.status.success());
+    assert_eq!(output.stdout, b"one\0two\nlines\0");
+}
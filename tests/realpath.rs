@@ -0,0 +1,67 @@
+mod common;
+
+use common::run_with_stdin;
+use common::stdout;
+use common::TempDir;
+use std::fs;
+use std::os::unix::fs::symlink;
+use std::process::Output;
+
+fn realpath(dir: &TempDir, args: &[&str]) -> Output {
+    run_with_stdin(env!("CARGO_BIN_EXE_realpath"), dir.path(), args, b"")
+}
+
+fn canonical(dir: &TempDir) -> String {
+    dir.path().canonicalize().unwrap().display().to_string()
+}
+
+#[test]
+fn existing_path() {
+    let dir = TempDir::new();
+    fs::create_dir_all(dir.join("a/b")).unwrap();
+    symlink("a/b", dir.join("link")).unwrap();
+    let output = realpath(&dir, &["link", "./a//b/.."]);
+    assert!(output.status.success());
+    assert_eq!(
+        stdout(&output),
+        format!("{0}/a/b\n{0}/a\n", canonical(&dir))
+    );
+}
+
+#[test]
+fn missing_last_component_by_default() {
+    let dir = TempDir::new();
+    let output = realpath(&dir, &["new"]);
+    assert!(output.status.success());
+    assert_eq!(stdout(&output), format!("{}/new\n", canonical(&dir)));
+
+    let output = realpath(&dir, &["missing/new"]);
+    assert_eq!(output.status.code(), Some(1));
+    assert!(String::from_utf8_lossy(&output.stderr).starts_with("realpath: missing/new: "));
+}
+
+#[test]
+fn canonicalize_existing() {
+    let dir = TempDir::new();
+    let output = realpath(&dir, &["-e", "new"]);
+    assert_eq!(output.status.code(), Some(1));
+    assert!(output.stdout.is_empty());
+}
+
+#[test]
+fn canonicalize_missing() {
+    let dir = TempDir::new();
+    let output = realpath(&dir, &["-m", "missing/x/../y"]);
+    assert!(output.status.success());
+    assert_eq!(stdout(&output), format!("{}/missing/y\n", canonical(&dir)));
+}
+
+#[test]
+fn relative_to() {
+    let dir = TempDir::new();
+    fs::create_dir_all(dir.join("a/b")).unwrap();
+    fs::create_dir_all(dir.join("c")).unwrap();
+    let output = realpath(&dir, &["--relative-to=a/b", "c", "a/b", "a/b/x"]);
+    assert!(output.status.success());
+    assert_eq!(stdout(&output), "../../c\n.\nx\n");
+}
@@ -0,0 +1,124 @@
+mod common;
+
+use common::run_with_stdin;
+use common::stderr;
+use common::TempDir;
+use std::fs;
+use std::os::unix::fs::symlink;
+use std::process::Output;
+
+fn rm(dir: &TempDir, args: &[&str], stdin: &str) -> Output {
+    run_with_stdin(env!("CARGO_BIN_EXE_rm"), dir.path(), args, stdin.as_bytes())
+}
+
+#[test]
+fn removes_a_file() {
+    let dir = TempDir::new();
+    fs::write(dir.join("file"), "").unwrap();
+    let output = rm(&dir, &["file"], "");
+    assert!(output.status.success());
+    assert!(!dir.join("file").exists());
+}
+
+#[test]
+fn directory_needs_recursive() {
+    let dir = TempDir::new();
+    fs::create_dir(dir.join("d")).unwrap();
+    let output = rm(&dir, &["d"], "");
+    assert_eq!(output.status.code(), Some(1));
+    assert_eq!(stderr(&output), "rm: cannot remove 'd': Is a directory\n");
+    assert!(dir.join("d").exists());
+}
+
+#[test]
+fn recursive_removes_a_tree() {
+    let dir = TempDir::new();
+    fs::create_dir_all(dir.join("d/sub")).unwrap();
+    fs::write(dir.join("d/a"), "").unwrap();
+    fs::write(dir.join("d/sub/b"), "").unwrap();
+    let output = rm(&dir, &["-r", "d"], "");
+    assert!(output.status.success());
+    assert!(!dir.join("d").exists());
+}
+
+#[test]
+fn clustered_recursive_and_force() {
+    for flags in ["-rf", "-fr"] {
+        let dir = TempDir::new();
+        fs::create_dir_all(dir.join("d/sub")).unwrap();
+        fs::write(dir.join("f"), "").unwrap();
+        let output = rm(&dir, &[flags, "d"], "");
+        assert!(output.status.success(), "{}", flags);
+        assert!(!dir.join("d").exists(), "{}", flags);
+        assert!(dir.join("f").exists(), "{}", flags);
+    }
+}
+
+#[test]
+fn recursive_does_not_follow_symlinks() {
+    let dir = TempDir::new();
+    fs::create_dir(dir.join("target")).unwrap();
+    fs::write(dir.join("target/keep"), "").unwrap();
+    fs::create_dir(dir.join("d")).unwrap();
+    symlink(dir.join("target"), dir.join("d/link")).unwrap();
+    let output = rm(&dir, &["-R", "d"], "");
+    assert!(output.status.success());
+    assert!(!dir.join("d").exists());
+    assert!(dir.join("target/keep").exists());
+}
+
+#[test]
+fn force_ignores_missing_files() {
+    let dir = TempDir::new();
+    let output = rm(&dir, &["missing"], "");
+    assert_eq!(output.status.code(), Some(1));
+    assert!(stderr(&output).starts_with("rm: cannot remove 'missing': "));
+    let output = rm(&dir, &["-f", "missing"], "");
+    assert!(output.status.success());
+    assert_eq!(stderr(&output), "");
+}
+
+#[test]
+fn continues_after_errors() {
+    let dir = TempDir::new();
+    fs::write(dir.join("b"), "").unwrap();
+    let output = rm(&dir, &["missing", "b"], "");
+    assert_eq!(output.status.code(), Some(1));
+    assert!(!dir.join("b").exists());
+}
+
+#[test]
+fn interactive_asks_first() {
+    let dir = TempDir::new();
+    fs::write(dir.join("a"), "").unwrap();
+    fs::write(dir.join("b"), "").unwrap();
+    let output = rm(&dir, &["-i", "a", "b"], "y\nn\n");
+    assert!(output.status.success());
+    assert!(!dir.join("a").exists());
+    assert!(dir.join("b").exists());
+    assert_eq!(stderr(&output), "rm: remove 'a'? rm: remove 'b'? ");
+}
+
+#[test]
+fn verbose() {
+    let dir = TempDir::new();
+    fs::create_dir(dir.join("d")).unwrap();
+    fs::write(dir.join("d/f"), "").unwrap();
+    let output = rm(&dir, &["-r", "-v", "d"], "");
+    assert_eq!(
+        String::from_utf8_lossy(&output.stdout),
+        "removed 'd/f'\nremoved directory 'd'\n"
+    );
+}
+
+#[test]
+fn refuses_dot() {
+    let dir = TempDir::new();
+    let output = rm(&dir, &["-r", "."], "");
+    assert_eq!(output.status.code(), Some(1));
+    assert_eq!(
+        stderr(&output),
+        "rm: refusing to remove '.' or '..' directory: skipping '.'\n"
+    );
+    assert!(dir.path().exists());
+}
@@ -0,0 +1,76 @@
+mod common;
+
+use common::run_with_stdin;
+use common::TempDir;
+use std::fs;
+use std::process::Output;
+
+fn rmdir(dir: &TempDir, args: &[&str]) -> Output {
+    run_with_stdin(env!("CARGO_BIN_EXE_rmdir"), dir.path(), args, b"")
+}
+
+#[test]
+fn removes_an_empty_directory() {
+    let dir = TempDir::new();
+    fs::create_dir(dir.join("empty")).unwrap();
+    let output = rmdir(&dir, &["empty"]);
+    assert!(output.status.success());
+    assert!(!dir.join("empty").exists());
+}
+
+#[test]
+fn non_empty_directory_is_an_error() {
+    let dir = TempDir::new();
+    fs::create_dir(dir.join("full")).unwrap();
+    fs::write(dir.join("full/file"), "").unwrap();
+    let output = rmdir(&dir, &["full"]);
+    assert_eq!(output.status.code(), Some(1));
+    assert!(String::from_utf8_lossy(&output.stderr).starts_with("rmdir: failed to remove 'full': "));
+    assert!(dir.join("full/file").exists());
+}
+
+#[test]
+fn ignore_fail_on_non_empty() {
+    let dir = TempDir::new();
+    fs::create_dir(dir.join("full")).unwrap();
+    fs::write(dir.join("full/file"), "").unwrap();
+    let output = rmdir(&dir, &["--ignore-fail-on-non-empty", "full"]);
+    assert!(output.status.success());
+    assert_eq!(String::from_utf8_lossy(&output.stderr), "");
+}
+
+#[test]
+fn parents_removes_the_chain() {
+    let dir = TempDir::new();
+    fs::create_dir_all(dir.join("a/b/c")).unwrap();
+    let output = rmdir(&dir, &["-p", "a/b/c"]);
+    assert!(output.status.success());
+    assert!(!dir.join("a").exists());
+}
+
+#[test]
+fn parents_stops_at_a_non_empty_ancestor() {
+    let dir = TempDir::new();
+    fs::create_dir_all(dir.join("a/b/c")).unwrap();
+    fs::write(dir.join("a/keep"), "").unwrap();
+    let output = rmdir(&dir, &["-p", "a/b/c"]);
+    assert_eq!(output.status.code(), Some(1));
+    assert!(!dir.join("a/b").exists());
+    assert!(dir.join("a/keep").exists());
+
+    fs::create_dir_all(dir.join("a/b/c")).unwrap();
+    let output = rmdir(&dir, &["-p", "--ignore-fail-on-non-empty", "a/b/c"]);
+    assert!(output.status.success());
+    assert!(!dir.join("a/b").exists());
+}
+
+#[test]
+fn verbose() {
+    let dir = TempDir::new();
+    fs::create_dir_all(dir.join("a/b")).unwrap();
+    let output = rmdir(&dir, &["-p", "-v", "a/b"]);
+    assert_eq!(
+        String::from_utf8_lossy(&output.stdout),
+        "rmdir: removing directory, 'a/b'\nrmdir: removing directory, 'a'\n"
+    );
+}
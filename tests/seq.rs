@@ -0,0 +1,80 @@
+use std::process::Command;
+use std::process::Output;
+
+fn seq(args: &[&str]) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_seq"))
+        .args(args)
+        .output()
+        .unwrap()
+}
+
+fn stdout(args: &[&str]) -> String {
+    let output = seq(args);
+    assert!(output.status.success(), "{:?}", output);
+    String::from_utf8(output.stdout).unwrap()
+}
+
+#[test]
+fn integer_ranges() {
+    assert_eq!(stdout(&["3"]), "1\n2\n3\n");
+    assert_eq!(stdout(&["2", "4"]), "2\n3\n4\n");
+    assert_eq!(stdout(&["1", "3", "10"]), "1\n4\n7\n10\n");
+    assert_eq!(stdout(&["0"]), "");
+}
+
+#[test]
+fn reverse_ranges() {
+    assert_eq!(stdout(&["5", "-2", "0"]), "5\n3\n1\n");
+    assert_eq!(stdout(&["-1", "-1", "-3"]), "-1\n-2\n-3\n");
+    assert_eq!(stdout(&["1", "5"]), "1\n2\n3\n4\n5\n");
+    assert_eq!(stdout(&["5", "1"]), "");
+}
+
+#[test]
+fn float_ranges() {
+    assert_eq!(stdout(&["0.1", "0.1", "0.5"]), "0.1\n0.2\n0.3\n0.4\n0.5\n");
+    assert_eq!(stdout(&["1", "0.5", "2"]), "1.0\n1.5\n2.0\n");
+    assert_eq!(stdout(&["1", "-0.25", "0.5"]), "1.00\n0.75\n0.50\n");
+    assert_eq!(stdout(&["0", "0.3", "1"]), "0.0\n0.3\n0.6\n0.9\n");
+}
+
+#[test]
+fn exponent_operands() {
+    assert_eq!(stdout(&["1e3", "1e3"]), "1000\n");
+}
+
+#[test]
+fn separator() {
+    assert_eq!(stdout(&["-s", ", ", "3"]), "1, 2, 3\n");
+}
+
+#[test]
+fn equal_width() {
+    assert_eq!(stdout(&["-w", "8", "10"]), "08\n09\n10\n");
+    assert_eq!(stdout(&["-w", "-1", "1"]), "-1\n00\n01\n");
+}
+
+#[test]
+fn format() {
+    assert_eq!(
+        stdout(&["-f", "%.2f", "1", "0.5", "2"]),
+        "1.00\n1.50\n2.00\n"
+    );
+    assert_eq!(stdout(&["-f", "n%03g", "2"]), "n001\nn002\n");
+}
+
+#[test]
+fn errors() {
+    let output = seq(&["1", "0", "3"]);
+    assert_eq!(output.status.code(), Some(1));
+    assert_eq!(
+        String::from_utf8_lossy(&output.stderr),
+        "seq: invalid Zero increment value: 0\n"
+    );
+    let output = seq(&["x"]);
+    assert_eq!(
+        String::from_utf8_lossy(&output.stderr),
+        "seq: invalid floating point argument: x\n"
+    );
+    assert_eq!(seq(&[]).status.code(), Some(1));
+}
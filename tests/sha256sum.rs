@@ -0,0 +1,82 @@
+mod common;
+
+use common::run_with_stdin;
+use common::TempDir;
+use std::fs;
+use std::process::Output;
+
+fn sha256sum(dir: &TempDir, args: &[&str], stdin: &[u8]) -> Output {
+    run_with_stdin(env!("CARGO_BIN_EXE_sha256sum"), dir.path(), args, stdin)
+}
+
+const HELLO: &str = "5891b5b522d5df086d0ff0b110fbd9d21bb4fc7163af34d08286a2e846f6be03";
+const EMPTY: &str = "e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855";
+
+#[test]
+fn known_hash() {
+    let dir = TempDir::new();
+    fs::write(dir.join("hello"), "hello\n").unwrap();
+    let output = sha256sum(&dir, &["hello", "-"], b"");
+    assert!(output.status.success());
+    assert_eq!(
+        String::from_utf8_lossy(&output.stdout),
+        format!("{}  hello\n{}  -\n", HELLO, EMPTY)
+    );
+}
+
+#[test]
+fn tag() {
+    let dir = TempDir::new();
+    let output = sha256sum(&dir, &["--tag"], b"hello\n");
+    assert_eq!(
+        String::from_utf8_lossy(&output.stdout),
+        format!("SHA256 (-) = {}\n", HELLO)
+    );
+}
+
+#[test]
+fn check_reads_tagged_and_plain_lines() {
+    let dir = TempDir::new();
+    fs::write(dir.join("hello"), "hello\n").unwrap();
+    fs::write(dir.join("empty"), "").unwrap();
+    let sums = format!("SHA256 (hello) = {}\n{} *empty\n", HELLO, EMPTY);
+    let output = sha256sum(&dir, &["-c", "-"], sums.as_bytes());
+    assert!(output.status.success());
+    assert_eq!(
+        String::from_utf8_lossy(&output.stdout),
+        "hello: OK\nempty: OK\n"
+    );
+}
+
+#[test]
+fn failing_check_line() {
+    let dir = TempDir::new();
+    fs::write(dir.join("hello"), "hello\n").unwrap();
+    fs::write(dir.join("empty"), "").unwrap();
+    fs::write(
+        dir.join("sums"),
+        format!("{}  hello\n{}  empty\n", HELLO, HELLO),
+    )
+    .unwrap();
+    let output = sha256sum(&dir, &["-c", "sums"], b"");
+    assert_eq!(output.status.code(), Some(1));
+    assert_eq!(
+        String::from_utf8_lossy(&output.stdout),
+        "hello: OK\nempty: FAILED\n"
+    );
+    assert_eq!(
+        String::from_utf8_lossy(&output.stderr),
+        "sha256sum: WARNING: 1 computed checksum did NOT match\n"
+    );
+}
+
+#[test]
+fn md5_lines_are_not_sha256_lines() {
+    let dir = TempDir::new();
+    let output = sha256sum(&dir, &["-c"], b"d41d8cd98f00b204e9800998ecf8427e  x\n");
+    assert_eq!(output.status.code(), Some(1));
+    assert_eq!(
+        String::from_utf8_lossy(&output.stderr),
+        "sha256sum: -: no properly formatted SHA256 checksum lines found\n"
+    );
+}
@@ -0,0 +1,45 @@
+use std::process::Command;
+use std::process::Output;
+use std::time::Duration;
+use std::time::Instant;
+
+fn sleep(args: &[&str]) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_sleep"))
+        .args(args)
+        .output()
+        .unwrap()
+}
+
+#[test]
+fn sums_multiple_durations() {
+    let start = Instant::now();
+    let output = sleep(&["0.05", "0.05s", "0.001m"]);
+    assert!(output.status.success(), "{:?}", output);
+    assert!(start.elapsed() >= Duration::from_millis(160));
+}
+
+#[test]
+fn zero_returns_immediately() {
+    let output = sleep(&["0"]);
+    assert!(output.status.success());
+}
+
+#[test]
+fn invalid_interval() {
+    let output = sleep(&["1x"]);
+    assert_eq!(output.status.code(), Some(1));
+    assert_eq!(
+        String::from_utf8_lossy(&output.stderr),
+        "sleep: invalid time interval '1x'\n"
+    );
+}
+
+#[test]
+fn missing_operand() {
+    let output = sleep(&[]);
+    assert_eq!(output.status.code(), Some(1));
+    assert_eq!(
+        String::from_utf8_lossy(&output.stderr),
+        "sleep: missing operand\n"
+    );
+}
@@ -0,0 +1,199 @@
+mod common;
+
+use common::run_with_stdin;
+use common::stderr;
+use common::stdout;
+use common::TempDir;
+use std::fs;
+use std::process::Command;
+use std::process::Output;
+
+fn sort(dir: &TempDir, args: &[&str], stdin: &str) -> Output {
+    run_with_stdin(
+        env!("CARGO_BIN_EXE_sort"),
+        dir.path(),
+        args,
+        stdin.as_bytes(),
+    )
+}
+
+#[test]
+fn lexical_order() {
+    let dir = TempDir::new();
+    let output = sort(&dir, &[], "10\n9\n100\nb\na\n");
+    assert!(output.status.success());
+    assert_eq!(stdout(&output), "10\n100\n9\na\nb\n");
+}
+
+#[test]
+fn numeric_order() {
+    let dir = TempDir::new();
+    assert_eq!(
+        stdout(&sort(&dir, &["-n"], "10\n9\n100\n-1\n2.5\n")),
+        "-1\n2.5\n9\n10\n100\n"
+    );
+}
+
+#[test]
+fn clustered_reverse_numeric_order() {
+    let dir = TempDir::new();
+    fs::write(dir.join("file"), "2\n10\n1\n").unwrap();
+    let output = sort(&dir, &["-rn", "file"], "");
+    assert!(output.status.success());
+    assert_eq!(stdout(&output), "10\n2\n1\n");
+    let input = "b:2\na:10\nc:1\n";
+    assert_eq!(
+        stdout(&sort(&dir, &["-nt:", "-rk2"], input)),
+        "a:10\nb:2\nc:1\n"
+    );
+}
+
+#[test]
+fn reverse() {
+    let dir = TempDir::new();
+    assert_eq!(stdout(&sort(&dir, &["-r"], "a\nc\nb\n")), "c\nb\na\n");
+    assert_eq!(
+        stdout(&sort(&dir, &["-r", "-n"], "2\n10\n1\n")),
+        "10\n2\n1\n"
+    );
+}
+
+#[test]
+fn fold_case_is_stable() {
+    let dir = TempDir::new();
+    assert_eq!(stdout(&sort(&dir, &[], "b\nB\na\n")), "B\na\nb\n");
+    assert_eq!(stdout(&sort(&dir, &["-f"], "b\nB\na\n")), "a\nb\nB\n");
+}
+
+#[test]
+fn unique() {
+    let dir = TempDir::new();
+    assert_eq!(stdout(&sort(&dir, &["-u"], "b\na\nb\na\n")), "a\nb\n");
+    assert_eq!(stdout(&sort(&dir, &["-f", "-u"], "b\nB\na\n")), "a\nb\n");
+    assert_eq!(stdout(&sort(&dir, &["-n", "-u"], "1\n01\n2\n")), "1\n2\n");
+}
+
+#[test]
+fn key_fields() {
+    let dir = TempDir::new();
+    let input = "x 3\ny 1\nz 2\n";
+    assert_eq!(stdout(&sort(&dir, &["-k", "2"], input)), "y 1\nz 2\nx 3\n");
+    let input = "b:2:x\na:10:y\nc:1:z\n";
+    assert_eq!(
+        stdout(&sort(&dir, &["-t:", "-k2,2", "-n"], input)),
+        "c:1:z\nb:2:x\na:10:y\n"
+    );
+}
+
+#[test]
+fn numeric_key_on_the_second_field() {
+    let dir = TempDir::new();
+    let input = "apple,10\npear,9\nfig,100\nkiwi,9.5\n";
+    assert_eq!(
+        stdout(&sort(&dir, &["-t,", "-k2n"], input)),
+        "pear,9\nkiwi,9.5\napple,10\nfig,100\n"
+    );
+    // A key's own options replace the global ones for that key only.
+    let input = "b 1\na 2\nb 2\na 1\n";
+    assert_eq!(
+        stdout(&sort(&dir, &["-k1,1", "-k2,2nr"], input)),
+        "a 2\na 1\nb 2\nb 1\n"
+    );
+}
+
+#[test]
+fn key_at_a_character_offset() {
+    let dir = TempDir::new();
+    let input = "id-300 x\nid-020 y\nid-100 z\n";
+    assert_eq!(
+        stdout(&sort(&dir, &["-k1.4,1.6n"], input)),
+        "id-020 y\nid-100 z\nid-300 x\n"
+    );
+    let input = "x  b3\ny a2\nz   c1\n";
+    assert_eq!(
+        stdout(&sort(&dir, &["-k2.2b,2.2b"], input)),
+        "z   c1\ny a2\nx  b3\n"
+    );
+}
+
+#[test]
+fn merges_files() {
+    let dir = TempDir::new();
+    fs::write(dir.join("a"), "c\na\n").unwrap();
+    fs::write(dir.join("b"), "b").unwrap();
+    assert_eq!(stdout(&sort(&dir, &["a", "b"], "")), "a\nb\nc\n");
+}
+
+#[test]
+fn check_sorted_input() {
+    let dir = TempDir::new();
+    let output = sort(&dir, &["-c"], "a\nb\nb\nc\n");
+    assert!(output.status.success());
+    assert_eq!(stdout(&output), "");
+    assert!(sort(&dir, &["-c", "-n"], "9\n10\n").status.success());
+}
+
+#[test]
+fn check_reports_the_first_disorder() {
+    let dir = TempDir::new();
+    fs::write(dir.join("f"), "a\nc\nb\nd\na\n").unwrap();
+    let output = sort(&dir, &["--check", "f"], "");
+    assert_eq!(output.status.code(), Some(1));
+    assert_eq!(stdout(&output), "");
+    assert_eq!(stderr(&output), "sort: f:3: disorder: b\n");
+}
+
+#[test]
+fn check_unique_rejects_duplicates() {
+    let dir = TempDir::new();
+    let output = sort(&dir, &["-c", "-u"], "a\nb\nb\n");
+    assert_eq!(output.status.code(), Some(1));
+    assert_eq!(stderr(&output), "sort: -:3: disorder: b\n");
+    assert!(sort(&dir, &["-c", "-u"], "a\nb\n").status.success());
+}
+
+/// Sorts the file `input` with a tiny -S budget, so that it spills many runs to `dir/tmp`.
+fn sort_spilling(dir: &TempDir, args: &[&str]) -> Output {
+    let output = Command::new(env!("CARGO_BIN_EXE_sort"))
+        .current_dir(dir.path())
+        .args(["-S", "200b"])
+        .args(args)
+        .arg("input")
+        .env("TMPDIR", dir.join("tmp"))
+        .output()
+        .unwrap();
+    assert!(output.status.success(), "{}", stderr(&output));
+    assert_eq!(fs::read_dir(dir.join("tmp")).unwrap().count(), 0);
+    output
+}
+
+#[test]
+fn spills_and_merges_large_inputs() {
+    let dir = TempDir::new();
+    fs::create_dir(dir.join("tmp")).unwrap();
+    // Enough lines for far more runs than are merged at once.
+    let mut lines: Vec<String> = (0..2000)
+        .map(|i| {
+            format!(
+                "{}{:04}",
+                ["b", "A", "a", "B"][i % 4],
+                (i * 7919) % 2000 / 4
+            )
+        })
+        .collect();
+    fs::write(dir.join("input"), lines.join("\n") + "\n").unwrap();
+
+    let output = sort_spilling(&dir, &[]);
+    let mut expected = lines.clone();
+    expected.sort();
+    assert_eq!(stdout(&output), expected.join("\n") + "\n");
+
+    // Equal lines from different runs still come out in input order.
+    let output = sort_spilling(&dir, &["-f"]);
+    lines.sort_by_key(|line| line.to_ascii_uppercase());
+    assert_eq!(stdout(&output), lines.join("\n") + "\n");
+
+    let output = sort_spilling(&dir, &["-f", "-u"]);
+    lines.dedup_by_key(|line| line.to_ascii_uppercase());
+    assert_eq!(stdout(&output), lines.join("\n") + "\n");
+}
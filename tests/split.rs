@@ -0,0 +1,83 @@
+mod common;
+
+use common::run_with_stdin;
+use common::TempDir;
+use std::fs;
+use std::process::Output;
+
+fn split(dir: &TempDir, args: &[&str], stdin: &[u8]) -> Output {
+    run_with_stdin(env!("CARGO_BIN_EXE_split"), dir.path(), args, stdin)
+}
+
+/// The names of everything in `dir`, sorted.
+fn listing(dir: &TempDir) -> Vec<String> {
+    let mut names: Vec<String> = fs::read_dir(dir.path())
+        .unwrap()
+        .map(|entry| entry.unwrap().file_name().to_string_lossy().into_owned())
+        .collect();
+    names.sort();
+    names
+}
+
+fn read(dir: &TempDir, name: &str) -> String {
+    fs::read_to_string(dir.join(name)).unwrap()
+}
+
+#[test]
+fn by_lines() {
+    let dir = TempDir::new();
+    let output = split(&dir, &["-l", "2"], b"1\n2\n3\n4\n5\n");
+    assert!(output.status.success());
+    assert_eq!(listing(&dir), vec!["xaa", "xab", "xac"]);
+    assert_eq!(read(&dir, "xaa"), "1\n2\n");
+    assert_eq!(read(&dir, "xab"), "3\n4\n");
+    assert_eq!(read(&dir, "xac"), "5\n");
+}
+
+#[test]
+fn by_bytes_with_prefix() {
+    let dir = TempDir::new();
+    fs::write(dir.join("input"), "abcdefghij").unwrap();
+    let output = split(&dir, &["-b", "4", "input", "part."], b"");
+    assert!(output.status.success());
+    assert_eq!(
+        listing(&dir),
+        vec!["input", "part.aa", "part.ab", "part.ac"]
+    );
+    assert_eq!(read(&dir, "part.aa"), "abcd");
+    assert_eq!(read(&dir, "part.ab"), "efgh");
+    assert_eq!(read(&dir, "part.ac"), "ij");
+}
+
+#[test]
+fn byte_units() {
+    let dir = TempDir::new();
+    let data = vec![b'x'; 2500];
+    let output = split(&dir, &["-b", "1K"], &data);
+    assert!(output.status.success());
+    assert_eq!(listing(&dir), vec!["xaa", "xab", "xac"]);
+    assert_eq!(fs::metadata(dir.join("xaa")).unwrap().len(), 1024);
+    assert_eq!(fs::metadata(dir.join("xac")).unwrap().len(), 452);
+}
+
+#[test]
+fn exact_multiple_leaves_no_empty_piece() {
+    let dir = TempDir::new();
+    split(&dir, &["-l", "1"], b"a\nb\n");
+    assert_eq!(listing(&dir), vec!["xaa", "xab"]);
+    let dir = TempDir::new();
+    split(&dir, &[], b"");
+    assert!(listing(&dir).is_empty());
+}
+
+#[test]
+fn suffixes_run_out() {
+    let dir = TempDir::new();
+    let output = split(&dir, &["-a", "1", "-b", "1"], &[b'x'; 27]);
+    assert_eq!(output.status.code(), Some(1));
+    assert_eq!(
+        String::from_utf8_lossy(&output.stderr),
+        "split: output file suffixes exhausted\n"
+    );
+    assert_eq!(listing(&dir).len(), 26);
+}
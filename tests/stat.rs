@@ -0,0 +1,135 @@
+mod common;
+
+use common::run_with_stdin;
+use common::stdout;
+use common::TempDir;
+use std::fs;
+use std::os::unix::fs::symlink;
+use std::os::unix::fs::MetadataExt;
+use std::os::unix::fs::PermissionsExt;
+use std::process::Command;
+use std::process::Output;
+use std::time::Duration;
+use std::time::SystemTime;
+
+fn stat(dir: &TempDir, args: &[&str]) -> Output {
+    run_with_stdin(env!("CARGO_BIN_EXE_stat"), dir.path(), args, b"")
+}
+
+#[test]
+fn default_output() {
+    let dir = TempDir::new();
+    fs::write(dir.join("file"), "hello").unwrap();
+    fs::set_permissions(dir.join("file"), fs::Permissions::from_mode(0o640)).unwrap();
+    let metadata = fs::metadata(dir.join("file")).unwrap();
+    let output = stat(&dir, &["file"]);
+    assert!(output.status.success());
+    let lines: Vec<String> = stdout(&output).lines().map(String::from).collect();
+    assert_eq!(lines.len(), 7);
+    assert_eq!(lines[0], "  File: file");
+    assert!(lines[1].starts_with("  Size: 5         \tBlocks: "));
+    assert!(lines[1].ends_with(" regular file"));
+    assert!(lines[2].contains(&format!("Inode: {:<10}", metadata.ino())));
+    assert!(lines[3].starts_with(&format!(
+        "Access: (0640/-rw-r-----)  Uid: ({:>5}/",
+        metadata.uid()
+    )));
+    assert!(lines[4].starts_with("Access: "));
+    assert!(lines[5].starts_with("Modify: "));
+    assert!(lines[6].starts_with("Change: "));
+}
+
+#[test]
+fn custom_format() {
+    let dir = TempDir::new();
+    fs::write(dir.join("a"), "hello").unwrap();
+    fs::write(dir.join("b"), "").unwrap();
+    let output = stat(&dir, &["-c", "%s", "a", "b"]);
+    assert!(output.status.success());
+    assert_eq!(stdout(&output), "5\n0\n");
+}
+
+#[test]
+fn modification_time_format() {
+    let dir = TempDir::new();
+    fs::write(dir.join("a"), "").unwrap();
+    let mtime = fs::metadata(dir.join("a")).unwrap().mtime();
+    let output = stat(&dir, &["--format=%n %F %Y", "a", "."]);
+    assert_eq!(
+        stdout(&output).lines().next().unwrap(),
+        format!("a regular empty file {}", mtime)
+    );
+    assert!(stdout(&output)
+        .lines()
+        .nth(1)
+        .unwrap()
+        .starts_with(". directory "));
+}
+
+#[test]
+fn missing_file() {
+    let dir = TempDir::new();
+    fs::write(dir.join("a"), "").unwrap();
+    let output = stat(&dir, &["-c", "%n", "missing", "a"]);
+    assert_eq!(output.status.code(), Some(1));
+    assert_eq!(stdout(&output), "a\n");
+    assert!(String::from_utf8_lossy(&output.stderr).starts_with("stat: cannot stat 'missing': "));
+}
+
+#[test]
+fn time_styles() {
+    let dir = TempDir::new();
+    let file = fs::File::create(dir.join("old")).unwrap();
+    file.set_modified(SystemTime::UNIX_EPOCH + Duration::from_secs(946_782_245))
+        .unwrap();
+    let stat_utc = |style| {
+        let output = Command::new(env!("CARGO_BIN_EXE_stat"))
+            .current_dir(dir.path())
+            .args(["-c", "%y", style, "old"])
+            .env("TZ", "UTC")
+            .output()
+            .unwrap();
+        assert!(output.status.success());
+        stdout(&output)
+    };
+    assert_eq!(
+        stat_utc("--time-style=full-iso"),
+        "2000-01-02 03:04:05.000000000 +0000\n"
+    );
+    assert_eq!(stat_utc("--time-style=long-iso"), "2000-01-02 03:04\n");
+    assert_eq!(stat_utc("--time-style=+%Y"), "2000\n");
+}
+
+#[test]
+fn printf_interprets_escapes_without_a_newline() {
+    let dir = TempDir::new();
+    fs::write(dir.join("a"), "hello").unwrap();
+    fs::write(dir.join("b"), "").unwrap();
+    let output = stat(&dir, &["-c", "%s", "a", "b"]);
+    assert_eq!(stdout(&output), "5\n0\n");
+    let output = stat(&dir, &["--printf", "%s", "a", "b"]);
+    assert!(output.status.success());
+    assert_eq!(stdout(&output), "50");
+    let output = stat(&dir, &["--printf=%n\\t%s\\n", "a", "b"]);
+    assert_eq!(stdout(&output), "a\t5\nb\t0\n");
+    let output = stat(&dir, &["-c", "%n\\t%s", "a"]);
+    assert_eq!(stdout(&output), "a\\t5\n");
+}
+
+#[test]
+fn dereference_or_not() {
+    let dir = TempDir::new();
+    fs::write(dir.join("target"), "hello").unwrap();
+    symlink("target", dir.join("link")).unwrap();
+    let output = stat(&dir, &["-c", "%s %F", "link"]);
+    assert_eq!(stdout(&output), "5 regular file\n");
+    let output = stat(&dir, &["-L", "-c", "%s %F", "link"]);
+    assert_eq!(stdout(&output), "5 regular file\n");
+    let output = stat(&dir, &["-P", "-c", "%s %F", "link"]);
+    assert_eq!(stdout(&output), "6 symbolic link\n");
+    let output = stat(
+        &dir,
+        &["--no-dereference", "--dereference", "-c", "%s", "link"],
+    );
+    assert_eq!(stdout(&output), "5\n");
+}
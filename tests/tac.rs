@@ -0,0 +1,64 @@
+mod common;
+
+use common::run_with_stdin;
+use common::stdout;
+use common::TempDir;
+use std::fs;
+use std::process::Output;
+
+fn tac(dir: &TempDir, args: &[&str], stdin: &str) -> Output {
+    run_with_stdin(
+        env!("CARGO_BIN_EXE_tac"),
+        dir.path(),
+        args,
+        stdin.as_bytes(),
+    )
+}
+
+#[test]
+fn reverses_lines_of_a_file() {
+    let dir = TempDir::new();
+    fs::write(dir.join("lines"), "one\ntwo\nthree\n").unwrap();
+    let output = tac(&dir, &["lines"], "");
+    assert!(output.status.success());
+    assert_eq!(stdout(&output), "three\ntwo\none\n");
+}
+
+#[test]
+fn reads_stdin() {
+    let dir = TempDir::new();
+    assert_eq!(stdout(&tac(&dir, &["-"], "a\nb\n")), "b\na\n");
+    assert_eq!(stdout(&tac(&dir, &[], "a\nb")), "ba\n");
+}
+
+#[test]
+fn custom_separator() {
+    let dir = TempDir::new();
+    assert_eq!(stdout(&tac(&dir, &["-s", ","], "a,b,c,")), "c,b,a,");
+    assert_eq!(stdout(&tac(&dir, &["--separator=::"], "a::b::")), "b::a::");
+}
+
+#[test]
+fn separator_before() {
+    let dir = TempDir::new();
+    assert_eq!(stdout(&tac(&dir, &["-b", "-s", ","], ",a,b")), ",b,a");
+}
+
+#[test]
+fn files_are_reversed_separately() {
+    let dir = TempDir::new();
+    fs::write(dir.join("a"), "1\n2\n").unwrap();
+    fs::write(dir.join("b"), "3\n4\n").unwrap();
+    assert_eq!(stdout(&tac(&dir, &["a", "b"], "")), "2\n1\n4\n3\n");
+}
+
+#[test]
+fn empty_separator_is_rejected() {
+    let dir = TempDir::new();
+    let output = tac(&dir, &["-s", ""], "");
+    assert_eq!(output.status.code(), Some(1));
+    assert_eq!(
+        String::from_utf8_lossy(&output.stderr),
+        "tac: separator cannot be empty\n"
+    );
+}
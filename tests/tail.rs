@@ -0,0 +1,143 @@
+mod common;
+
+use common::run_with_stdin;
+use common::stdout;
+use common::TempDir;
+use std::fs;
+use std::fs::OpenOptions;
+use std::io::Read;
+use std::io::Write;
+use std::process::Command;
+use std::process::Output;
+use std::process::Stdio;
+use std::sync::mpsc;
+use std::thread;
+use std::time::Duration;
+use std::time::Instant;
+
+fn numbers(range: std::ops::RangeInclusive<u32>) -> String {
+    range.map(|n| format!("{}\n", n)).collect()
+}
+
+fn tail(dir: &TempDir, args: &[&str], stdin: &str) -> Output {
+    run_with_stdin(
+        env!("CARGO_BIN_EXE_tail"),
+        dir.path(),
+        args,
+        stdin.as_bytes(),
+    )
+}
+
+#[test]
+fn last_ten_lines_by_default() {
+    let dir = TempDir::new();
+    assert_eq!(stdout(&tail(&dir, &[], &numbers(1..=20))), numbers(11..=20));
+}
+
+#[test]
+fn last_n_lines() {
+    let dir = TempDir::new();
+    fs::write(dir.join("file"), numbers(1..=20)).unwrap();
+    assert_eq!(
+        stdout(&tail(&dir, &["-n", "3", "file"], "")),
+        numbers(18..=20)
+    );
+    assert_eq!(stdout(&tail(&dir, &["-2", "file"], "")), numbers(19..=20));
+    assert_eq!(stdout(&tail(&dir, &["-n", "0", "file"], "")), "");
+    assert_eq!(
+        stdout(&tail(&dir, &["-n", "50", "file"], "")),
+        numbers(1..=20)
+    );
+}
+
+#[test]
+fn from_line_offset() {
+    let dir = TempDir::new();
+    assert_eq!(
+        stdout(&tail(&dir, &["-n", "+18"], &numbers(1..=20))),
+        numbers(18..=20)
+    );
+    assert_eq!(
+        stdout(&tail(&dir, &["-n", "+1"], &numbers(1..=3))),
+        numbers(1..=3)
+    );
+    assert_eq!(stdout(&tail(&dir, &["-n", "+9"], &numbers(1..=3))), "");
+}
+
+#[test]
+fn bytes() {
+    let dir = TempDir::new();
+    assert_eq!(stdout(&tail(&dir, &["-c", "5"], "hello world")), "world");
+    assert_eq!(stdout(&tail(&dir, &["-c", "+7"], "hello world")), "world");
+}
+
+#[test]
+fn headers_for_multiple_files() {
+    let dir = TempDir::new();
+    fs::write(dir.join("a"), numbers(1..=3)).unwrap();
+    fs::write(dir.join("b"), numbers(4..=6)).unwrap();
+    let output = tail(&dir, &["-n", "1", "a", "b"], "");
+    assert_eq!(stdout(&output), "==> a <==\n3\n\n==> b <==\n6\n");
+    let output = tail(&dir, &["-q", "-n", "1", "a", "b"], "");
+    assert_eq!(stdout(&output), "3\n6\n");
+}
+
+/// Spawn `tail -f`, collecting its stdout on a channel until the returned child is killed.
+fn spawn_follow(dir: &TempDir, args: &[&str]) -> (std::process::Child, mpsc::Receiver<String>) {
+    let mut child = Command::new(env!("CARGO_BIN_EXE_tail"))
+        .current_dir(dir.path())
+        .args(args)
+        .stdout(Stdio::piped())
+        .spawn()
+        .unwrap();
+    let mut stdout = child.stdout.take().unwrap();
+    let (sender, receiver) = mpsc::channel();
+    thread::spawn(move || {
+        let mut buf = [0; 1024];
+        while let Ok(n) = stdout.read(&mut buf) {
+            if n == 0
+                || sender
+                    .send(String::from_utf8_lossy(&buf[..n]).into_owned())
+                    .is_err()
+            {
+                break;
+            }
+        }
+    });
+    (child, receiver)
+}
+
+fn wait_for(receiver: &mpsc::Receiver<String>, seen: &mut String, expected: &str) {
+    let deadline = Instant::now() + Duration::from_secs(10);
+    while !seen.contains(expected) {
+        let left = deadline.saturating_duration_since(Instant::now());
+        match receiver.recv_timeout(left) {
+            Ok(chunk) => seen.push_str(&chunk),
+            Err(_) => panic!("timed out waiting for {:?}, saw {:?}", expected, seen),
+        }
+    }
+}
+
+#[test]
+fn follow_prints_appended_data() {
+    let dir = TempDir::new();
+    fs::write(dir.join("log"), "first\n").unwrap();
+    let (mut child, receiver) = spawn_follow(&dir, &["-f", "-s", "0.05", "log"]);
+    let mut seen = String::new();
+    wait_for(&receiver, &mut seen, "first\n");
+
+    let mut log = OpenOptions::new()
+        .append(true)
+        .open(dir.join("log"))
+        .unwrap();
+    log.write_all(b"second\n").unwrap();
+    wait_for(&receiver, &mut seen, "second\n");
+
+    // Truncate and start over; tail should pick the new contents up from the beginning
+    fs::write(dir.join("log"), "new\n").unwrap();
+    wait_for(&receiver, &mut seen, "new\n");
+
+    child.kill().unwrap();
+    child.wait().unwrap();
+    assert_eq!(seen, "first\nsecond\nnew\n");
+}
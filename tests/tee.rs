@@ -0,0 +1,61 @@
+mod common;
+
+use common::run_with_stdin;
+use common::stdout;
+use common::TempDir;
+use std::fs;
+use std::process::Output;
+
+fn tee(dir: &TempDir, args: &[&str], stdin: &str) -> Output {
+    run_with_stdin(
+        env!("CARGO_BIN_EXE_tee"),
+        dir.path(),
+        args,
+        stdin.as_bytes(),
+    )
+}
+
+#[test]
+fn copies_to_stdout_and_files() {
+    let dir = TempDir::new();
+    let output = tee(&dir, &["a", "b"], "piped\ndata\n");
+    assert!(output.status.success());
+    assert_eq!(stdout(&output), "piped\ndata\n");
+    assert_eq!(fs::read_to_string(dir.join("a")).unwrap(), "piped\ndata\n");
+    assert_eq!(fs::read_to_string(dir.join("b")).unwrap(), "piped\ndata\n");
+}
+
+#[test]
+fn truncates_by_default() {
+    let dir = TempDir::new();
+    fs::write(dir.join("a"), "old contents\n").unwrap();
+    tee(&dir, &["a"], "new\n");
+    assert_eq!(fs::read_to_string(dir.join("a")).unwrap(), "new\n");
+}
+
+#[test]
+fn appends_with_a() {
+    let dir = TempDir::new();
+    fs::write(dir.join("a"), "old\n").unwrap();
+    let output = tee(&dir, &["-a", "a"], "new\n");
+    assert!(output.status.success());
+    assert_eq!(fs::read_to_string(dir.join("a")).unwrap(), "old\nnew\n");
+}
+
+#[test]
+fn keeps_writing_after_an_open_error() {
+    let dir = TempDir::new();
+    let output = tee(&dir, &["missing/a", "b"], "data\n");
+    assert_eq!(output.status.code(), Some(1));
+    assert_eq!(stdout(&output), "data\n");
+    assert_eq!(fs::read_to_string(dir.join("b")).unwrap(), "data\n");
+    assert!(String::from_utf8_lossy(&output.stderr).starts_with("tee: missing/a: "));
+}
+
+#[test]
+fn ignore_interrupts_still_copies() {
+    let dir = TempDir::new();
+    let output = tee(&dir, &["-i", "a"], "data\n");
+    assert!(output.status.success());
+    assert_eq!(fs::read_to_string(dir.join("a")).unwrap(), "data\n");
+}
@@ -0,0 +1,304 @@
+mod common;
+
+use common::atime;
+use common::mtime;
+use common::stdout;
+use common::TempDir;
+use std::ffi::OsStr;
+use std::fs;
+use std::fs::File;
+use std::io::Write;
+use std::os::unix::ffi::OsStrExt;
+use std::os::unix::fs::symlink;
+use std::process::Command;
+use std::process::Output;
+use std::process::Stdio;
+use std::time::UNIX_EPOCH;
+
+fn touch(dir: &TempDir, args: &[&str]) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_touch"))
+        .current_dir(dir.path())
+        .args(args)
+        .output()
+        .unwrap()
+}
+
+#[test]
+fn creates_missing_file() {
+    let dir = TempDir::new();
+    let output = touch(&dir, &["new"]);
+    assert!(output.status.success());
+    assert!(dir.join("new").is_file());
+}
+
+#[test]
+fn no_create_skips_missing_file() {
+    let dir = TempDir::new();
+    let output = touch(&dir, &["-c", "missing"]);
+    assert!(output.status.success());
+    assert!(String::from_utf8_lossy(&output.stdout).contains("Skipping missing"));
+    assert!(!dir.join("missing").exists());
+}
+
+#[test]
+fn date_sets_both_times() {
+    let dir = TempDir::new();
+    let output = touch(&dir, &["-d", "2000-01-02T03:04:05Z", "dated"]);
+    assert!(output.status.success());
+    assert_eq!(mtime(dir.join("dated")), 946_782_245);
+    assert_eq!(atime(dir.join("dated")), 946_782_245);
+}
+
+#[test]
+fn reference_copies_times() {
+    let dir = TempDir::new();
+    assert!(touch(&dir, &["-d", "2010-06-07T08:09:10Z", "reference"])
+        .status
+        .success());
+    File::create(dir.join("target")).unwrap();
+    let output = touch(&dir, &["-r", "reference", "target"]);
+    assert!(output.status.success());
+    assert_eq!(mtime(dir.join("target")), mtime(dir.join("reference")));
+}
+
+#[test]
+fn missing_reference_is_an_error() {
+    let dir = TempDir::new();
+    let output = touch(&dir, &["-r", "nope", "target"]);
+    assert_eq!(output.status.code(), Some(1));
+    assert!(String::from_utf8_lossy(&output.stderr).contains("referenced file nope does not exist"));
+}
+
+#[test]
+fn unknown_flag_is_an_error() {
+    let dir = TempDir::new();
+    let output = touch(&dir, &["-Q", "file"]);
+    assert_eq!(output.status.code(), Some(1));
+    assert!(output.stdout.is_empty());
+    assert_eq!(
+        String::from_utf8_lossy(&output.stderr),
+        "touch: unknown argument -Q\n"
+    );
+    assert!(!dir.join("file").exists());
+}
+
+#[test]
+fn no_files_is_an_error() {
+    let dir = TempDir::new();
+    let output = touch(&dir, &[]);
+    assert_eq!(output.status.code(), Some(1));
+    assert!(String::from_utf8_lossy(&output.stderr).contains("must specify at least one file"));
+}
+
+fn touch_with_stdin(dir: &TempDir, args: &[&str], stdin: &[u8]) -> Output {
+    let mut child = Command::new(env!("CARGO_BIN_EXE_touch"))
+        .current_dir(dir.path())
+        .args(args)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .unwrap();
+    child.stdin.take().unwrap().write_all(stdin).unwrap();
+    child.wait_with_output().unwrap()
+}
+
+#[test]
+fn files_from_stdin() {
+    let dir = TempDir::new();
+    let output = touch_with_stdin(&dir, &["--files-from=-"], b"one\ntwo\nthree\n");
+    assert!(output.status.success());
+    for name in &["one", "two", "three"] {
+        assert!(dir.join(name).is_file(), "{} was not created", name);
+    }
+}
+
+#[test]
+fn files_from_null_separated() {
+    let dir = TempDir::new();
+    let output = touch_with_stdin(&dir, &["--files-from=-", "-z"], b"with\nnewline\0plain\0");
+    assert!(output.status.success());
+    assert!(dir.join("with\nnewline").is_file());
+    assert!(dir.join("plain").is_file());
+    assert!(!dir.join("with").exists());
+}
+
+#[test]
+fn files_from_keeps_non_utf8_names() {
+    let dir = TempDir::new();
+    let output = touch_with_stdin(&dir, &["--files-from", "-", "--null"], b"caf\xe9\0");
+    assert!(output.status.success());
+    assert!(dir.join(OsStr::from_bytes(b"caf\xe9")).is_file());
+}
+
+#[test]
+fn interior_nul_is_an_error() {
+    let dir = TempDir::new();
+    let output = touch_with_stdin(&dir, &["--files-from=-"], b"bad\0name\n");
+    assert_eq!(output.status.code(), Some(1));
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(
+        stderr.contains("invalid file name 'bad\\0name': contains NUL"),
+        "{}",
+        stderr
+    );
+    assert!(!stderr.contains('\0'), "{}", stderr);
+    assert!(!stderr.contains("panicked"), "{}", stderr);
+}
+
+#[test]
+fn files_from_list_file_and_argv() {
+    let dir = TempDir::new();
+    fs::write(dir.join("list"), "listed\n").unwrap();
+    let output = touch(&dir, &["--files-from", "list", "argv"]);
+    assert!(output.status.success());
+    assert!(dir.join("listed").is_file());
+    assert!(dir.join("argv").is_file());
+}
+
+#[test]
+fn no_dereference_creates_regular_file() {
+    let dir = TempDir::new();
+    let output = touch(&dir, &["-h", "-d", "2000-01-02T03:04:05Z", "newfile"]);
+    assert!(output.status.success(), "{:?}", output);
+    let metadata = fs::symlink_metadata(dir.join("newfile")).unwrap();
+    assert!(metadata.file_type().is_file());
+    assert_eq!(mtime(dir.join("newfile")), 946_782_245);
+}
+
+#[test]
+fn symlinks_are_followed_unless_no_dereference() {
+    let dir = TempDir::new();
+    assert!(touch(&dir, &["-d", "2000-01-01T00:00:00Z", "target"])
+        .status
+        .success());
+    symlink("target", dir.join("link")).unwrap();
+
+    assert!(touch(&dir, &["-d", "2001-01-01T00:00:00Z", "link"])
+        .status
+        .success());
+    assert_eq!(mtime(dir.join("target")), 978_307_200);
+
+    assert!(touch(&dir, &["-h", "-d", "2002-01-01T00:00:00Z", "link"])
+        .status
+        .success());
+    assert_eq!(mtime(dir.join("target")), 978_307_200);
+    let link_mtime = fs::symlink_metadata(dir.join("link"))
+        .unwrap()
+        .modified()
+        .unwrap()
+        .duration_since(UNIX_EPOCH)
+        .unwrap()
+        .as_secs();
+    assert_eq!(link_mtime, 1_009_843_200);
+}
+
+#[test]
+fn no_dereference_does_not_follow_dangling_link() {
+    let dir = TempDir::new();
+    symlink("missing", dir.join("dangling")).unwrap();
+    let output = touch(&dir, &["-h", "dangling"]);
+    assert!(output.status.success(), "{:?}", output);
+    assert!(!dir.join("missing").exists());
+}
+
+#[test]
+fn summary_counts_outcomes() {
+    let dir = TempDir::new();
+    File::create(dir.join("a")).unwrap();
+    File::create(dir.join("b")).unwrap();
+    let output = touch(&dir, &["--summary", "a", "b", "c", "nodir/d"]);
+    assert_eq!(output.status.code(), Some(1));
+    assert!(dir.join("c").is_file());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(
+        stderr.ends_with("touch: 2 files updated, 1 created, 1 failed\n"),
+        "{}",
+        stderr
+    );
+}
+
+#[test]
+fn failures_do_not_stop_later_files() {
+    let dir = TempDir::new();
+    let output = touch(&dir, &["nodir/a", "b"]);
+    assert_eq!(output.status.code(), Some(1));
+    assert!(dir.join("b").is_file());
+    assert!(!String::from_utf8_lossy(&output.stderr).contains("updated"));
+}
+
+#[test]
+fn utc_stamp_differs_from_local_stamp() {
+    let dir = TempDir::new();
+    let run = |args: &[&str]| {
+        Command::new(env!("CARGO_BIN_EXE_touch"))
+            .current_dir(dir.path())
+            .env("TZ", "EST5")
+            .args(args)
+            .status()
+            .unwrap()
+    };
+    assert!(run(&["-t", "200001020304", "local"]).success());
+    assert!(run(&["-u", "-t", "200001020304", "utc"]).success());
+    assert_eq!(mtime(dir.join("utc")), 946_782_240);
+    assert_eq!(mtime(dir.join("local")), 946_782_240 + 5 * 60 * 60);
+}
+
+#[test]
+fn stamps_existing_and_new_files() {
+    let dir = TempDir::new();
+    fs::write(dir.join("existing"), "kept").unwrap();
+    let output = touch(
+        &dir,
+        &["--summary", "-d", "2000-01-02T03:04:05Z", "existing", "new"],
+    );
+    assert!(output.status.success(), "{:?}", output);
+    assert!(
+        String::from_utf8_lossy(&output.stderr).ends_with("1 file updated, 1 created, 0 failed\n")
+    );
+    for name in &["existing", "new"] {
+        assert_eq!(mtime(dir.join(name)), 946_782_245);
+        assert_eq!(atime(dir.join(name)), 946_782_245);
+    }
+    assert_eq!(fs::read_to_string(dir.join("existing")).unwrap(), "kept");
+    assert_eq!(fs::read_to_string(dir.join("new")).unwrap(), "");
+}
+
+#[test]
+fn version_is_the_package_version() {
+    let dir = TempDir::new();
+    let output = touch(&dir, &["--version"]);
+    assert!(output.status.success());
+    // Read the root manifest, rather than trusting any crate's CARGO_PKG_VERSION.
+    let manifest = fs::read_to_string(concat!(env!("CARGO_MANIFEST_DIR"), "/Cargo.toml")).unwrap();
+    let version = manifest
+        .lines()
+        .find_map(|line| line.strip_prefix("version = "))
+        .unwrap()
+        .trim_matches('"');
+    assert_eq!(stdout(&output), format!("touch version {}\n", version));
+}
+
+#[test]
+fn diagnostics_are_uncolored_when_piped() {
+    let dir = TempDir::new();
+    let output = Command::new(env!("CARGO_BIN_EXE_touch"))
+        .current_dir(dir.path())
+        .env_remove("NO_COLOR")
+        .args(["-Q", "file"])
+        .stderr(Stdio::piped())
+        .output()
+        .unwrap();
+    assert_eq!(output.status.code(), Some(1));
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(
+        stderr.starts_with("touch: unknown argument -Q"),
+        "{}",
+        stderr
+    );
+    assert!(!stderr.contains('\x1b'));
+    let output = touch(&dir, &["nodir/file"]);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.starts_with("touch: "), "{}", stderr);
+    assert!(!stderr.contains('\x1b'));
+}
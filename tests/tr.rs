@@ -0,0 +1,138 @@
+mod common;
+
+use common::run_with_stdin;
+use common::TempDir;
+use std::io;
+use std::io::Read;
+use std::io::Write;
+use std::process::Command;
+use std::process::Output;
+use std::process::Stdio;
+use std::sync::mpsc;
+use std::thread;
+use std::time::Duration;
+
+fn tr(args: &[&str], stdin: &str) -> Output {
+    let dir = TempDir::new();
+    run_with_stdin(env!("CARGO_BIN_EXE_tr"), dir.path(), args, stdin.as_bytes())
+}
+
+fn stdout(args: &[&str], stdin: &str) -> String {
+    let output = tr(args, stdin);
+    assert!(output.status.success(), "{:?}", output);
+    String::from_utf8(output.stdout).unwrap()
+}
+
+#[test]
+fn translates_case() {
+    assert_eq!(stdout(&["a-z", "A-Z"], "Hello, world\n"), "HELLO, WORLD\n");
+    assert_eq!(
+        stdout(&["[:upper:]", "[:lower:]"], "Hello, World\n"),
+        "hello, world\n"
+    );
+}
+
+#[test]
+fn short_set2_repeats_its_last_character() {
+    assert_eq!(stdout(&["abcd", "xy"], "abcde"), "xyyye");
+}
+
+#[test]
+fn truncate_set1_leaves_the_rest_alone() {
+    assert_eq!(stdout(&["-t", "abcd", "xy"], "abcde"), "xycde");
+    assert_eq!(stdout(&["--truncate-set1", "a-z", "AB"], "abc\n"), "ABc\n");
+    assert_eq!(stdout(&["-t", "abc", ""], "abc"), "abc");
+    assert!(!tr(&["abc", ""], "").status.success());
+}
+
+#[test]
+fn repeated_set1_characters_use_the_last_mapping() {
+    assert_eq!(stdout(&["aa", "xy"], "a"), "y");
+}
+
+#[test]
+fn deletes() {
+    assert_eq!(stdout(&["-d", "[:digit:]"], "a1b22c333\n"), "abc\n");
+    assert_eq!(stdout(&["-d", "-c", "a-z\\n"], "a1B-c\n"), "ac\n");
+}
+
+#[test]
+fn squeezes() {
+    assert_eq!(stdout(&["-s", " "], "a   b  c\n"), "a b c\n");
+    assert_eq!(stdout(&["-s", "a-z", "A-Z"], "aabbcc\n"), "ABC\n");
+    assert_eq!(stdout(&["-d", "-s", "0-9", " "], "a1  2 b\n"), "a b\n");
+}
+
+#[test]
+fn complement_translates_everything_else() {
+    assert_eq!(stdout(&["-c", "a-z\\n", "_"], "ab-cd e\n"), "ab_cd_e\n");
+}
+
+#[test]
+fn complement_maps_every_other_byte_to_the_last_of_set2() {
+    // The complement starts at NUL, so NUL takes SET2's first byte and every later one its last.
+    assert_eq!(stdout(&["-c", "a", "xy"], "abc\u{0}"), "ayyx");
+    assert_eq!(stdout(&["-c", "-t", "a", "xy"], "ab\u{0}\u{1}"), "abxy");
+    assert_eq!(
+        stdout(&["-c", "-s", "a-z", "\n"], "one  two,three\n"),
+        "one\ntwo\nthree\n"
+    );
+}
+
+#[test]
+fn escapes() {
+    assert_eq!(stdout(&["\\n", " "], "a\nb\n"), "a b ");
+}
+
+#[test]
+fn operand_errors() {
+    let output = tr(&["a"], "");
+    assert_eq!(output.status.code(), Some(1));
+    assert!(String::from_utf8_lossy(&output.stderr)
+        .starts_with("tr: missing operand after 'a'\nTwo strings must be given when translating."));
+    let output = tr(&["-d", "a", "b"], "");
+    assert_eq!(
+        String::from_utf8_lossy(&output.stderr),
+        "tr: extra operand 'b'\n"
+    );
+    let output = tr(&["z-a", "x"], "");
+    assert_eq!(
+        String::from_utf8_lossy(&output.stderr),
+        "tr: range-endpoints of 'z-a' are in reverse collating sequence order\n"
+    );
+}
+
+#[test]
+fn streams_without_waiting_for_end_of_input() {
+    const MEGABYTE: usize = 1 << 20;
+    let mut child = Command::new(env!("CARGO_BIN_EXE_tr"))
+        .args(["a", "B"])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .spawn()
+        .unwrap();
+    let mut stdin = child.stdin.take().unwrap();
+    let mut stdout = child.stdout.take().unwrap();
+    let (sender, receiver) = mpsc::channel();
+    thread::spawn(move || {
+        // tr holds back at most a buffer or two, so nearly everything written so far should
+        // arrive before stdin is closed.
+        let mut seen = 0;
+        let mut buffer = [0; 64 * 1024];
+        while seen < MEGABYTE - 64 * 1024 {
+            let n = stdout.read(&mut buffer).unwrap();
+            assert!(n > 0 && buffer[..n].iter().all(|&b| b == b'B'));
+            seen += n;
+        }
+        sender.send(seen).unwrap();
+        io::copy(&mut stdout, &mut io::sink()).unwrap();
+    });
+    stdin.write_all(&vec![b'a'; MEGABYTE]).unwrap();
+    let seen = receiver.recv_timeout(Duration::from_secs(30));
+    drop(stdin);
+    assert!(
+        seen.is_ok(),
+        "tr did not write output before its input ended"
+    );
+    assert!(child.wait().unwrap().success());
+}
@@ -0,0 +1,46 @@
+use std::process::Command;
+use std::process::Output;
+
+fn run(bin: &str, args: &[&str]) -> Output {
+    Command::new(bin).args(args).output().unwrap()
+}
+
+#[test]
+fn exit_codes() {
+    let t = env!("CARGO_BIN_EXE_true");
+    let f = env!("CARGO_BIN_EXE_false");
+    assert_eq!(run(t, &[]).status.code(), Some(0));
+    assert_eq!(run(f, &[]).status.code(), Some(1));
+    assert_eq!(
+        run(t, &["-x", "--bogus", "anything"]).status.code(),
+        Some(0)
+    );
+    assert_eq!(
+        run(f, &["-x", "--bogus", "anything"]).status.code(),
+        Some(1)
+    );
+}
+
+#[test]
+fn help_and_version() {
+    let output = run(env!("CARGO_BIN_EXE_true"), &["--help"]);
+    assert!(output.status.success());
+    assert!(String::from_utf8_lossy(&output.stdout).contains("Usage: true"));
+    let output = run(env!("CARGO_BIN_EXE_true"), &["--version"]);
+    assert!(output.status.success());
+    assert_eq!(
+        String::from_utf8_lossy(&output.stdout),
+        format!("true version {}\n", env!("CARGO_PKG_VERSION"))
+    );
+    // false prints its help too, but still fails.
+    let output = run(env!("CARGO_BIN_EXE_false"), &["--help"]);
+    assert_eq!(output.status.code(), Some(1));
+    assert!(String::from_utf8_lossy(&output.stdout).contains("Usage: false"));
+}
+
+#[test]
+fn options_among_other_arguments_are_ignored() {
+    let output = run(env!("CARGO_BIN_EXE_true"), &["--help", "x"]);
+    assert!(output.status.success());
+    assert!(output.stdout.is_empty());
+}
@@ -0,0 +1,62 @@
+mod common;
+
+use common::run_with_stdin;
+use common::stdout;
+use common::TempDir;
+use std::process::Output;
+
+fn unexpand(args: &[&str], stdin: &str) -> Output {
+    let dir = TempDir::new();
+    run_with_stdin(
+        env!("CARGO_BIN_EXE_unexpand"),
+        dir.path(),
+        args,
+        stdin.as_bytes(),
+    )
+}
+
+#[test]
+fn leading_only() {
+    let output = unexpand(&[], "        x       y\n                z\n");
+    assert!(output.status.success());
+    assert_eq!(stdout(&output), "\tx       y\n\t\tz\n");
+}
+
+#[test]
+fn all_blanks() {
+    let output = unexpand(&["-a"], "        x       y\n");
+    assert_eq!(stdout(&output), "\tx\ty\n");
+}
+
+#[test]
+fn tab_size_implies_all() {
+    let output = unexpand(&["-t", "4"], "    a   b\n");
+    assert_eq!(stdout(&output), "\ta\tb\n");
+    let output = unexpand(&["--first-only", "-t", "4"], "    a   b\n");
+    assert_eq!(stdout(&output), "\ta   b\n");
+}
+
+#[test]
+fn short_runs_are_kept() {
+    let output = unexpand(&["-a"], "ab      c  d\n");
+    assert_eq!(stdout(&output), "ab\tc  d\n");
+}
+
+#[test]
+fn round_trips_with_expand() {
+    let dir = TempDir::new();
+    let text = "\tone\ttwo\n  \tthree\n";
+    let expanded = run_with_stdin(
+        env!("CARGO_BIN_EXE_expand"),
+        dir.path(),
+        &[],
+        text.as_bytes(),
+    );
+    let output = run_with_stdin(
+        env!("CARGO_BIN_EXE_unexpand"),
+        dir.path(),
+        &["-a"],
+        &expanded.stdout,
+    );
+    assert_eq!(stdout(&output), "\tone\ttwo\n\tthree\n");
+}
@@ -0,0 +1,116 @@
+mod common;
+
+use common::run_with_stdin;
+use common::stdout;
+use common::TempDir;
+use std::fs;
+use std::process::Output;
+
+fn uniq(dir: &TempDir, args: &[&str], stdin: &str) -> Output {
+    run_with_stdin(
+        env!("CARGO_BIN_EXE_uniq"),
+        dir.path(),
+        args,
+        stdin.as_bytes(),
+    )
+}
+
+const INPUT: &str = "a\na\nb\nc\nc\nc\na\n";
+
+#[test]
+fn merges_adjacent_lines() {
+    let dir = TempDir::new();
+    let output = uniq(&dir, &[], INPUT);
+    assert!(output.status.success());
+    assert_eq!(stdout(&output), "a\nb\nc\na\n");
+}
+
+#[test]
+fn counts() {
+    let dir = TempDir::new();
+    assert_eq!(
+        stdout(&uniq(&dir, &["-c"], INPUT)),
+        "      2 a\n      1 b\n      3 c\n      1 a\n"
+    );
+}
+
+#[test]
+fn only_duplicates_or_unique() {
+    let dir = TempDir::new();
+    assert_eq!(stdout(&uniq(&dir, &["-d"], INPUT)), "a\nc\n");
+    assert_eq!(stdout(&uniq(&dir, &["-u"], INPUT)), "b\na\n");
+    assert_eq!(stdout(&uniq(&dir, &["-d", "-u"], INPUT)), "");
+}
+
+#[test]
+fn all_repeated() {
+    let dir = TempDir::new();
+    let input = "a\na\nb\nc\nc\nc\nd\nd\n";
+    assert_eq!(stdout(&uniq(&dir, &["-D"], input)), "a\na\nc\nc\nc\nd\nd\n");
+    assert_eq!(
+        stdout(&uniq(&dir, &["--all-repeated=separate"], input)),
+        "a\na\n\nc\nc\nc\n\nd\nd\n"
+    );
+    assert_eq!(
+        stdout(&uniq(&dir, &["--all-repeated=prepend"], input)),
+        "\na\na\n\nc\nc\nc\n\nd\nd\n"
+    );
+}
+
+#[test]
+fn group() {
+    let dir = TempDir::new();
+    assert_eq!(
+        stdout(&uniq(&dir, &["--group=prepend"], INPUT)),
+        "\na\na\n\nb\n\nc\nc\nc\n\na\n"
+    );
+    assert_eq!(
+        stdout(&uniq(&dir, &["--group"], INPUT)),
+        "a\na\n\nb\n\nc\nc\nc\n\na\n"
+    );
+    assert_eq!(
+        stdout(&uniq(&dir, &["--group=both"], "x\nx\ny\n")),
+        "\nx\nx\n\ny\n\n"
+    );
+    assert_eq!(stdout(&uniq(&dir, &["--group=append"], "")), "");
+}
+
+#[test]
+fn ignore_case() {
+    let dir = TempDir::new();
+    let input = "Apple\napple\nAPPLE\nbanana\n";
+    assert_eq!(stdout(&uniq(&dir, &[], input)), input);
+    assert_eq!(
+        stdout(&uniq(&dir, &["-i", "-c"], input)),
+        "      3 Apple\n      1 banana\n"
+    );
+}
+
+#[test]
+fn skip_fields_and_chars() {
+    let dir = TempDir::new();
+    let input = "1 same\n2 same\n3 other\n";
+    assert_eq!(
+        stdout(&uniq(&dir, &["-f", "1"], input)),
+        "1 same\n3 other\n"
+    );
+    assert_eq!(stdout(&uniq(&dir, &["-s2"], input)), "1 same\n3 other\n");
+}
+
+#[test]
+fn input_and_output_files() {
+    let dir = TempDir::new();
+    fs::write(dir.join("in"), INPUT).unwrap();
+    let output = uniq(&dir, &["in", "out"], "");
+    assert!(output.status.success());
+    assert_eq!(stdout(&output), "");
+    assert_eq!(fs::read_to_string(dir.join("out")).unwrap(), "a\nb\nc\na\n");
+}
+
+#[test]
+fn missing_input() {
+    let dir = TempDir::new();
+    let output = uniq(&dir, &["missing"], "");
+    assert_eq!(output.status.code(), Some(1));
+    assert!(String::from_utf8_lossy(&output.stderr).starts_with("uniq: missing: "));
+}
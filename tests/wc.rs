@@ -0,0 +1,123 @@
+mod common;
+
+use common::run_with_stdin;
+use common::stderr;
+use common::stdout;
+use common::TempDir;
+use std::ffi::OsStr;
+use std::fs;
+use std::os::unix::ffi::OsStrExt;
+use std::process::Output;
+
+const FIXTURE: &str = "the quick brown\nfox  jumps\n\nover the lazy dög\n";
+
+fn wc(dir: &TempDir, args: &[&str], stdin: &str) -> Output {
+    run_with_stdin(env!("CARGO_BIN_EXE_wc"), dir.path(), args, stdin.as_bytes())
+}
+
+fn fixture_dir() -> TempDir {
+    let dir = TempDir::new();
+    fs::write(dir.join("fixture"), FIXTURE).unwrap();
+    dir
+}
+
+#[test]
+fn default_counts() {
+    let dir = fixture_dir();
+    let output = wc(&dir, &["fixture"], "");
+    assert!(output.status.success());
+    assert_eq!(stdout(&output), " 4  9 47 fixture\n");
+}
+
+#[test]
+fn single_count() {
+    let dir = fixture_dir();
+    assert_eq!(stdout(&wc(&dir, &["-l", "fixture"], "")), "4 fixture\n");
+    assert_eq!(stdout(&wc(&dir, &["-w", "fixture"], "")), "9 fixture\n");
+    assert_eq!(stdout(&wc(&dir, &["-c", "fixture"], "")), "47 fixture\n");
+    assert_eq!(
+        stdout(&wc(&dir, &["--chars", "fixture"], "")),
+        "46 fixture\n"
+    );
+}
+
+#[test]
+fn counts_are_printed_in_fixed_order() {
+    let dir = fixture_dir();
+    let output = wc(&dir, &["-c", "-l", "-m", "fixture"], "");
+    assert_eq!(stdout(&output), " 4 46 47 fixture\n");
+}
+
+#[test]
+fn total_for_multiple_files() {
+    let dir = fixture_dir();
+    fs::write(dir.join("other"), "one two\n").unwrap();
+    let output = wc(&dir, &["fixture", "other"], "");
+    assert_eq!(
+        stdout(&output),
+        " 4  9 47 fixture\n 1  2  8 other\n 5 11 55 total\n"
+    );
+}
+
+#[test]
+fn stdin_without_name() {
+    let dir = TempDir::new();
+    let output = wc(&dir, &[], FIXTURE);
+    assert_eq!(stdout(&output), "      4       9      47\n");
+    assert_eq!(stdout(&wc(&dir, &["-l"], FIXTURE)), "4\n");
+    assert_eq!(stdout(&wc(&dir, &["-w", "-"], FIXTURE)), "9 -\n");
+}
+
+#[test]
+fn missing_file_is_reported() {
+    let dir = fixture_dir();
+    let output = wc(&dir, &["-l", "missing", "fixture"], "");
+    assert_eq!(output.status.code(), Some(1));
+    assert_eq!(stdout(&output), " 4 fixture\n 4 total\n");
+    assert!(String::from_utf8_lossy(&output.stderr).starts_with("wc: missing: "));
+}
+
+#[test]
+fn files0_from() {
+    let dir = fixture_dir();
+    fs::write(dir.join("other"), "one two\n").unwrap();
+    fs::write(dir.join("list"), "fixture\0other\0").unwrap();
+    let output = wc(&dir, &["--files0-from=list"], "");
+    assert!(output.status.success());
+    assert_eq!(
+        stdout(&output),
+        " 4  9 47 fixture\n 1  2  8 other\n 5 11 55 total\n"
+    );
+    let output = wc(&dir, &["-l", "--files0-from", "-"], "other\0fixture");
+    assert_eq!(stdout(&output), " 1 other\n 4 fixture\n 5 total\n");
+}
+
+#[test]
+fn files0_from_keeps_non_utf8_names() {
+    let dir = fixture_dir();
+    fs::write(dir.join(OsStr::from_bytes(b"caf\xe9")), "one two\n").unwrap();
+    fs::write(dir.join("list"), b"caf\xe9\0").unwrap();
+    let output = wc(&dir, &["-w", "--files0-from=list"], "");
+    assert!(output.status.success(), "{}", stderr(&output));
+    assert_eq!(stdout(&output), "2 caf\u{fffd}\n");
+}
+
+#[test]
+fn files0_from_rejects_empty_names() {
+    let dir = fixture_dir();
+    fs::write(dir.join("other"), "one two\n").unwrap();
+    let output = wc(&dir, &["-l", "--files0-from=-"], "fixture\0\0other\0");
+    assert_eq!(output.status.code(), Some(1));
+    assert_eq!(stderr(&output), "wc: -:2: invalid zero-length file name\n");
+    assert_eq!(stdout(&output), " 4 fixture\n 1 other\n 5 total\n");
+}
+
+#[test]
+fn files0_from_refuses_operands() {
+    let dir = fixture_dir();
+    fs::write(dir.join("list"), "fixture\0").unwrap();
+    let output = wc(&dir, &["--files0-from=list", "fixture"], "");
+    assert!(!output.status.success());
+    let output = wc(&dir, &["--files0-from=-"], "-\0");
+    assert!(String::from_utf8_lossy(&output.stderr).contains("no file name of '-' allowed"));
+}
@@ -0,0 +1,31 @@
+use coreutils_common::users::user_name;
+use std::env;
+use std::process::Command;
+
+#[test]
+fn prints_effective_user() {
+    let output = Command::new(env!("CARGO_BIN_EXE_whoami")).output().unwrap();
+    assert!(output.status.success());
+    let name = String::from_utf8(output.stdout).unwrap();
+    let expected = user_name(unsafe { libc::geteuid() }).unwrap();
+    assert_eq!(name, format!("{}\n", expected));
+    // In a login shell this is $USER too, unless something like sudo switched users.
+    if let Ok(user) = env::var("USER") {
+        if user == expected {
+            assert_eq!(name.trim_end(), user);
+        }
+    }
+}
+
+#[test]
+fn rejects_operands() {
+    let output = Command::new(env!("CARGO_BIN_EXE_whoami"))
+        .arg("someone")
+        .output()
+        .unwrap();
+    assert_eq!(output.status.code(), Some(1));
+    assert_eq!(
+        String::from_utf8_lossy(&output.stderr),
+        "whoami: extra operand 'someone'\n"
+    );
+}
@@ -0,0 +1,63 @@
+use std::io::BufRead;
+use std::io::BufReader;
+use std::process::Command;
+use std::process::Stdio;
+
+/// Read the first `count` lines `yes` prints with `args`, then stop it.
+fn first_lines(args: &[&str], count: usize) -> Vec<String> {
+    let mut child = Command::new(env!("CARGO_BIN_EXE_yes"))
+        .args(args)
+        .stdout(Stdio::piped())
+        .spawn()
+        .unwrap();
+    let lines = BufReader::new(child.stdout.take().unwrap())
+        .lines()
+        .take(count)
+        .map(|line| line.unwrap())
+        .collect();
+    child.kill().unwrap();
+    child.wait().unwrap();
+    lines
+}
+
+#[test]
+fn repeats_y_by_default() {
+    let lines = first_lines(&[], 5000);
+    assert_eq!(lines.len(), 5000);
+    assert!(lines.iter().all(|line| line == "y"));
+}
+
+#[test]
+fn repeats_the_given_strings() {
+    let lines = first_lines(&["hello", "world"], 3000);
+    assert!(lines.iter().all(|line| line == "hello world"));
+}
+
+#[test]
+fn dashes_are_part_of_the_string() {
+    assert_eq!(first_lines(&["--", "-n"], 2), ["-n", "-n"]);
+    assert_eq!(first_lines(&["-x"], 2), ["-x", "-x"]);
+}
+
+#[test]
+fn help_exits() {
+    let output = Command::new(env!("CARGO_BIN_EXE_yes"))
+        .arg("--help")
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    assert!(String::from_utf8_lossy(&output.stdout).contains("Usage: yes"));
+}
+
+#[test]
+fn stops_when_the_reader_goes_away() {
+    let mut child = Command::new(env!("CARGO_BIN_EXE_yes"))
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .unwrap();
+    drop(child.stdout.take());
+    let output = child.wait_with_output().unwrap();
+    assert!(!output.status.success());
+    assert!(output.stderr.is_empty());
+}